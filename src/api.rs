@@ -5,7 +5,8 @@
 //! `default-features = false`). It composes the existing seeded
 //! `System::generate_from_constraints_seeded` + `sysmap::render_png` and
 //! `worldmap::generate` + `worldmap::render_png` pipelines into two
-//! "give me a PNG" calls and surfaces a single unified error type.
+//! "give me a PNG" calls (plus their SVG / JSON siblings) and surfaces a
+//! single unified error type.
 //!
 //! The Leptos UI in this crate still uses the lower-level pieces
 //! directly; this module exists only for the library shape.
@@ -36,6 +37,10 @@ pub enum WorldgenError {
     /// produced — typically a tiny-skia error mapped to text.
     #[error("png render failed: {0}")]
     Render(String),
+
+    /// Encoding the structured (JSON) form of a system or planet failed.
+    #[error("json encode failed: {0}")]
+    Encode(String),
 }
 
 impl From<Vec<ConstraintError>> for WorldgenError {
@@ -50,6 +55,11 @@ impl From<MapError> for WorldgenError {
     }
 }
 
+/// The scale system-map PNGs render at when the caller doesn't pick one:
+/// 3200×1800. Shared by `/api/system` and `worldgen system` so the same
+/// query gives the same bytes from either.
+pub const DEFAULT_SYSTEM_SCALE: f32 = 2.0;
+
 /// Generate a Traveller solar system from `constraints`, render it to a
 /// system-map PNG, and return the bytes.
 ///
@@ -112,6 +122,29 @@ pub fn generate_system_svg(
     Ok(crate::sysmap::render_svg(&system))
 }
 
+/// Generate a Traveller solar system and return it as a versioned JSON
+/// document (the [`crate::systems::persist`] format).
+///
+/// The structured parallel to [`generate_system_png`]: same `(seed,
+/// constraints)` drives the same `System`, so a client can load the
+/// document with [`System::from_json`] and render its own view of the
+/// exact system the PNG shows. Stars, orbits, UWPs, trade classes and
+/// astro data for every body (companions and satellites included) are
+/// all in the document.
+///
+/// **Determinism contract:** for a fixed `(seed, constraints)` pair the
+/// returned string is identical across runs (same caveat as the PNG
+/// path: pinned worldgen dep version).
+pub fn generate_system_json(
+    seed: u64,
+    constraints: SystemConstraints,
+) -> Result<String, WorldgenError> {
+    let system = System::generate_from_constraints_seeded(seed, constraints)?;
+    system
+        .to_json()
+        .map_err(|e| WorldgenError::Encode(e.to_string()))
+}
+
 /// Generate a planet surface map for the given UWP, render it to PNG,
 /// and return the bytes.
 ///
@@ -152,6 +185,22 @@ pub fn generate_planet_png_scaled(
     crate::worldmap::render_png_scaled(&map, scale).map_err(WorldgenError::Render)
 }
 
+/// Generate a planet surface map and return its per-hex summary as JSON.
+///
+/// The structured parallel to [`generate_planet_png`]: same `(seed, uwp,
+/// name)` produces the same map, summarized via
+/// [`WorldMap::summary`](crate::worldmap::WorldMap::summary) — biome,
+/// elevation, climate and features per hex. Skips rasterization, so it's
+/// much cheaper than the PNG path.
+pub fn generate_planet_json(
+    seed: u64,
+    uwp: &str,
+    name: Option<&str>,
+) -> Result<String, WorldgenError> {
+    let map: WorldMap = crate::worldmap::generate(uwp, seed, name)?;
+    serde_json::to_string(&map.summary()).map_err(|e| WorldgenError::Encode(e.to_string()))
+}
+
 /// One star's classification, as the convenience builder expects it.
 ///
 /// Mirrors a single `Constraint::Star` row but with the fields the
@@ -4,9 +4,10 @@
 //!
 //! 1. Receive exactly one [`ClientMessage::RunSummary`] frame. Malformed
 //!    JSON is rejected with `internal_error`.
-//! 2. Run two pre-checks fail-fast (global rate limit, prompt size cap)
-//!    — each rejects with the appropriate [`ServerMessage::Error`] and
-//!    a clean Close. There is no per-connection rate limit: each
+//! 2. Run two pre-checks fail-fast (request size cap — the prompt, and
+//!    the voyage's steps and ship name — then global rate limit) — each
+//!    rejects with the appropriate [`ServerMessage::Error`] and a clean
+//!    Close. There is no per-connection rate limit: each
 //!    connection is one-shot (one `RunSummary`, then close), so a
 //!    per-connection gate would never have a prior request to gate
 //!    against. The global 1/sec limiter is the real defense.
@@ -20,6 +21,12 @@
 //!    send a [`ServerMessage::Error`] with `code: "vertex_error"`,
 //!    then close cleanly.
 //!
+//! When the client sent its [`LogVoyage`], the global rate limit, a
+//! missing GCP project and a Vertex outage that struck before any text
+//! streamed (auth, network, 429 or 5xx) don't fail the request: the log
+//! is written from templates by [`write_log`] instead and sent as one
+//! `Delta`, then a `Done` carrying [`TEMPLATE_FINISH_REASON`].
+//!
 //! Authorization headers and the prompt are never logged.
 
 use std::net::SocketAddr;
@@ -34,7 +41,10 @@ use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::tungstenite::Message;
 
 use crate::backend::vertex_client::{self, VertexError};
-use crate::comms::captains_log::{ClientMessage, MAX_PROMPT_BYTES, ServerMessage};
+use crate::comms::captains_log::{
+    ClientMessage, LogVoyage, MAX_PROMPT_BYTES, ServerMessage, TEMPLATE_FINISH_REASON,
+};
+use crate::simulator::captains_log_template::write_log;
 
 /// Minimum gap between accepted requests across the entire process.
 const GLOBAL_RATE_GAP: Duration = Duration::from_secs(1);
@@ -108,8 +118,8 @@ pub async fn handle_captains_log_ws(
     // `internal_error` since the schema is fixed and the client
     // controls it.
     let parsed: Result<ClientMessage, _> = serde_json::from_str(&first);
-    let prompt = match parsed {
-        Ok(ClientMessage::RunSummary { prompt }) => prompt,
+    let (prompt, voyage) = match parsed {
+        Ok(ClientMessage::RunSummary { prompt, voyage }) => (prompt, voyage),
         Err(e) => {
             send_error(
                 &tx,
@@ -123,7 +133,31 @@ pub async fn handle_captains_log_ws(
         }
     };
 
-    // ---- Check 1: global rate limit ----
+    // ---- Check 1: request size ----
+    // Before anything else, so no branch below (the template fallback
+    // included) ever renders an oversized request.
+    if prompt.len() > MAX_PROMPT_BYTES {
+        send_error(
+            &tx,
+            "prompt_too_large",
+            &format!(
+                "prompt exceeds max size ({} > {} bytes)",
+                prompt.len(),
+                MAX_PROMPT_BYTES
+            ),
+            None,
+            None,
+        );
+        close_clean(tx, send_task, &mut ws_receiver).await;
+        return;
+    }
+    if let Some(Err(message)) = voyage.as_ref().map(LogVoyage::check_size) {
+        send_error(&tx, "voyage_too_large", &message, None, None);
+        close_clean(tx, send_task, &mut ws_receiver).await;
+        return;
+    }
+
+    // ---- Check 2: global rate limit ----
     {
         let mut guard = global_rate_limiter.lock().await;
         if let Some(prev) = *guard {
@@ -131,6 +165,15 @@ pub async fn handle_captains_log_ws(
             if elapsed < GLOBAL_RATE_GAP {
                 let retry_ms = (GLOBAL_RATE_GAP - elapsed).as_millis() as u32;
                 drop(guard);
+                if let Some(voyage) = &voyage {
+                    log::info!(
+                        "captains_log: rate limited, writing template log for {}",
+                        peer_addr
+                    );
+                    send_template(&tx, voyage);
+                    close_clean(tx, send_task, &mut ws_receiver).await;
+                    return;
+                }
                 send_error(
                     &tx,
                     "rate_limit_global",
@@ -142,40 +185,38 @@ pub async fn handle_captains_log_ws(
                 return;
             }
         }
-        // Tentatively claim the slot. We update before the size
-        // check would also be reasonable, but the spec says "in
-        // order"; we only stamp here once we've decided to accept.
+        // Claim the slot: the request has passed every check.
         *guard = Some(Instant::now());
     }
 
-    // ---- Check 2: prompt size ----
-    if prompt.len() > MAX_PROMPT_BYTES {
-        send_error(
-            &tx,
-            "prompt_too_large",
-            &format!(
-                "prompt exceeds max size ({} > {} bytes)",
-                prompt.len(),
-                MAX_PROMPT_BYTES
-            ),
-            None,
-            None,
-        );
-        close_clean(tx, send_task, &mut ws_receiver).await;
-        return;
-    }
-
     log::info!(
         "captains_log: accepted request from {} ({} bytes)",
         peer_addr,
         prompt.len()
     );
 
+    // Without a project there is no Vertex to ask.
+    if project.is_empty()
+        && let Some(voyage) = &voyage
+    {
+        log::info!(
+            "captains_log: no GCP project, writing template log for {}",
+            peer_addr
+        );
+        send_template(&tx, voyage);
+        close_clean(tx, send_task, &mut ws_receiver).await;
+        return;
+    }
+
     // ---- Stream from Vertex ----
     // The closure clones `tx` so it can outlive any single call;
-    // each delta is queued to the WS via the mpsc bridge.
+    // each delta is queued to the WS via the mpsc bridge. `streamed`
+    // records whether the client has seen any text, after which a
+    // template log can no longer stand in.
     let tx_for_deltas = tx.clone();
+    let mut streamed = false;
     let result = vertex_client::stream_generate(project.as_ref(), &prompt, |text: &str| {
+        streamed = true;
         let msg = ServerMessage::Delta {
             text: text.to_string(),
         };
@@ -239,13 +280,61 @@ pub async fn handle_captains_log_ws(
                 url,
                 body
             );
-            send_error(&tx, "vertex_error", &short, status, None);
+            match &voyage {
+                Some(voyage) if !streamed && vertex_unavailable(&e) => {
+                    log::info!(
+                        "captains_log: vertex unavailable, writing template log for {}",
+                        peer_addr
+                    );
+                    send_template(&tx, voyage);
+                }
+                _ => send_error(&tx, "vertex_error", &short, status, None),
+            }
         }
     }
 
     close_clean(tx, send_task, &mut ws_receiver).await;
 }
 
+/// Whether a Vertex error means the service is out of reach or over
+/// quota, rather than that it rejected this request.
+fn vertex_unavailable(e: &VertexError) -> bool {
+    match e {
+        VertexError::Auth(_) | VertexError::Network(_) => true,
+        VertexError::Status { status, .. } => *status == 429 || *status >= 500,
+        VertexError::Sse(_) => false,
+    }
+}
+
+/// Queue the template-written log for `voyage` as one `Delta`, then the
+/// `Done` marking it as such.
+fn send_template(tx: &mpsc::UnboundedSender<Message>, voyage: &LogVoyage) {
+    let text = write_log(
+        &voyage.ship_name,
+        &voyage.params,
+        &voyage.steps,
+        &voyage.result,
+    );
+    let frames = [
+        ServerMessage::Delta { text },
+        ServerMessage::Done {
+            prompt_tokens: 0,
+            output_tokens: 0,
+            finish_reason: Some(TEMPLATE_FINISH_REASON.to_string()),
+        },
+    ];
+    for frame in frames {
+        match serde_json::to_string(&frame) {
+            Ok(json) => {
+                let _ = tx.send(Message::Text(json.into()));
+            }
+            Err(e) => {
+                log::error!("captains_log: failed to serialize template log: {}", e);
+            }
+        }
+    }
+}
+
 /// Queue a `ServerMessage::Error` frame on the bridge.
 fn send_error(
     tx: &mpsc::UnboundedSender<Message>,
@@ -393,6 +482,20 @@ mod tests {
         assert!(json.contains("\"text\":\"hello\""));
     }
 
+    #[test]
+    fn only_outages_fall_back_to_the_template() {
+        let status = |status| VertexError::Status {
+            status,
+            body: String::new(),
+        };
+        assert!(vertex_unavailable(&VertexError::Auth("no creds".into())));
+        assert!(vertex_unavailable(&VertexError::Network("reset".into())));
+        assert!(vertex_unavailable(&status(429)));
+        assert!(vertex_unavailable(&status(503)));
+        assert!(!vertex_unavailable(&status(400)));
+        assert!(!vertex_unavailable(&VertexError::Sse("bad frame".into())));
+    }
+
     #[test]
     fn rate_limit_constants() {
         assert_eq!(GLOBAL_RATE_GAP, Duration::from_secs(1));
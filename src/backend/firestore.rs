@@ -35,7 +35,14 @@
 //!       ├── available_passengers: AvailablePassengers?
 //!       ├── ship_manifest: ShipManifest
 //!       ├── system_broker_skill: i16  # Planet-side counterparty broker
-//!       └── illegal_goods: bool
+//!       ├── illegal_goods: bool
+//!       ├── itinerary: Itinerary # Multi-leg trade loop
+//!       ├── itinerary_plan: ItineraryPlan?
+//!       └── ledger: Ledger       # Every credit earned or spent
+//!
+//! markets/                       # Collection shared by every ship
+//!   └── ledger/                  # Document containing the MarketLedger
+//!       └── markets: {key: WorldMarket}  # Per-world memory of trade
 //! ```
 //!
 //! ## Error Handling
@@ -50,6 +57,7 @@ use log::{debug, error, warn};
 use thiserror::Error;
 
 use crate::comms::TradeState;
+use crate::trade::market::MarketLedger;
 
 /// Document name for the trade state within each session collection
 const STATE_DOCUMENT_NAME: &str = "state";
@@ -57,6 +65,12 @@ const STATE_DOCUMENT_NAME: &str = "state";
 /// Default session ID for shared state (all users see the same state)
 pub const DEFAULT_SESSION_ID: &str = "default";
 
+/// Collection holding the market memory shared by every ship
+const MARKETS_COLLECTION: &str = "markets";
+
+/// Document name for the market ledger within the markets collection
+const MARKETS_DOCUMENT_NAME: &str = "ledger";
+
 /// Name for special case database that indicates no Firestore connection (for local debugging)
 const NULL_DATABASE_NAME: &str = "debug";
 
@@ -357,3 +371,73 @@ pub async fn trade_state_exists(
         }
     }
 }
+
+/// Retrieves the market ledger shared by every ship from Firestore
+///
+/// An empty ledger is returned if none has been saved yet.
+///
+/// # Errors
+///
+/// Returns `FirestoreError` if the database operation fails
+pub async fn get_markets(db_option: &Option<FirestoreDb>) -> Result<MarketLedger, FirestoreError> {
+    match db_option {
+        None => {
+            warn!("🔥 Running without Firestore connection; markets start empty.");
+            Ok(MarketLedger::new())
+        }
+        Some(db) => {
+            // Structure: {MARKETS_COLLECTION}/{MARKETS_DOCUMENT_NAME}
+            let result: Option<MarketLedger> = db
+                .fluent()
+                .select()
+                .by_id_in(MARKETS_COLLECTION)
+                .obj()
+                .one(MARKETS_DOCUMENT_NAME)
+                .await
+                .map_err(|e| {
+                    error!("❌ Firestore: Failed to read market ledger: {}", e);
+                    FirestoreError::ReadError(e.to_string())
+                })?;
+            debug!(
+                "✅ Firestore: Loaded market ledger ({} markets)",
+                result.as_ref().map_or(0, MarketLedger::len)
+            );
+            Ok(result.unwrap_or_default())
+        }
+    }
+}
+
+/// Saves the market ledger shared by every ship to Firestore
+///
+/// This performs an upsert operation, like [`save_trade_state`].
+///
+/// # Errors
+///
+/// Returns `FirestoreError` if the database operation fails
+pub async fn save_markets(
+    db_option: &Option<FirestoreDb>,
+    markets: &MarketLedger,
+) -> Result<(), FirestoreError> {
+    match db_option {
+        None => {
+            debug!("🔥 Saving markets without Firestore connection.");
+            Ok(())
+        }
+        Some(db) => {
+            // Structure: {MARKETS_COLLECTION}/{MARKETS_DOCUMENT_NAME}
+            db.fluent()
+                .update()
+                .in_col(MARKETS_COLLECTION)
+                .document_id(MARKETS_DOCUMENT_NAME)
+                .object(markets)
+                .execute::<()>()
+                .await
+                .map_err(|e| {
+                    error!("❌ Firestore: Failed to save market ledger: {}", e);
+                    FirestoreError::WriteError(e.to_string())
+                })?;
+            debug!("✅ Firestore: Saved market ledger ({} markets)", markets.len());
+            Ok(())
+        }
+    }
+}
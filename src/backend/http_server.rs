@@ -16,7 +16,15 @@
 //!   clickable. `scale` is accepted but ignored (SVG is
 //!   resolution-independent). See [`handle_system_svg`]. Both share
 //!   [`parse_system_request`] for parsing/validation.
+//! - `GET /api/system_json?…` (same query params) → `200 application/json`
+//!   carrying the generated `System` itself — stars, orbits, UWPs, trade
+//!   classes and astro data — in the versioned `systems::persist` format.
+//!   Same seed derivation, so it is exactly the system `/api/system` drew.
+//!   See [`handle_system_json`].
 //! - `GET /api/world?…` → `200 image/png` of a planet surface (GCS-cached).
+//! - `GET /api/world_json?…` (same query params minus `scale`) →
+//!   `200 application/json` per-hex summary of the same planet map
+//!   (biome, elevation, climate, features). See [`handle_world_json`].
 //!
 //! All responses include permissive CORS headers (`*` origin, GET + OPTIONS
 //! allowed) so a browser client served from a different origin (e.g. the
@@ -26,7 +34,8 @@
 //! hand-rolls an HTTP/1.1 request line and header parser plus minimal
 //! response writers. Everything beyond that funnels through the existing
 //! public library API (`system_seed`, `parse_stellar`, `build_constraints`,
-//! `generate_system_png_scaled`, `generate_system_svg`).
+//! `generate_system_png_scaled`, `generate_system_svg`, `generate_system_json`,
+//! `generate_planet_json`).
 
 use std::collections::HashMap;
 use std::net::SocketAddr;
@@ -38,8 +47,8 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
 use tokio::net::TcpStream;
 
 use crate::api::{
-    build_constraints, generate_planet_png_scaled, generate_system_png_scaled, generate_system_svg,
-    parse_stellar,
+    DEFAULT_SYSTEM_SCALE, build_constraints, generate_planet_json, generate_planet_png_scaled,
+    generate_system_json, generate_system_png_scaled, generate_system_svg, parse_stellar,
 };
 use crate::backend::gcs::GcsClient;
 use crate::seed::{planet_seed, system_seed};
@@ -145,20 +154,23 @@ pub async fn handle_http(
     match path {
         "/api/system" => handle_system(reader.get_mut(), query, head_only).await,
         "/api/system_svg" => handle_system_svg(reader.get_mut(), query, head_only).await,
+        "/api/system_json" => handle_system_json(reader.get_mut(), query, head_only).await,
         "/api/world" => handle_world(reader.get_mut(), query, head_only, gcs).await,
+        "/api/world_json" => handle_world_json(reader.get_mut(), query, head_only).await,
         _ => write_simple(reader.get_mut(), 404, "Not Found", "Unknown endpoint").await,
     }
 }
 
-/// The parsed, validated inputs shared by `/api/system` (PNG) and
-/// `/api/system_svg` (SVG). Both endpoints take the identical query string
-/// and derive the same `(seed, constraints)`; only the render target and the
-/// `scale` use differ, so the parsing lives in one place.
+/// The parsed, validated inputs shared by `/api/system` (PNG),
+/// `/api/system_svg` (SVG) and `/api/system_json` (JSON). All three take the
+/// identical query string and derive the same `(seed, constraints)`; only
+/// the output format and the `scale` use differ, so the parsing lives in
+/// one place.
 struct SystemRequest {
     seed: u64,
     constraints: SystemConstraints,
-    /// Requested pixel scale. Used by the PNG path; the SVG path ignores it
-    /// (vector output is resolution-independent).
+    /// Requested pixel scale. Used by the PNG path; the SVG and JSON paths
+    /// ignore it.
     scale: f32,
 }
 
@@ -223,7 +235,7 @@ fn parse_system_request(query: &str) -> Result<SystemRequest, HttpError> {
     let scale = params
         .get("scale")
         .and_then(|s| s.trim().parse::<f32>().ok())
-        .unwrap_or(2.0);
+        .unwrap_or(DEFAULT_SYSTEM_SCALE);
 
     let seed = system_seed(sector, hex_x, hex_y);
     let constraints = build_constraints(name, uwp, &stars, giants, belts, planets)
@@ -286,6 +298,82 @@ async fn handle_system_svg(
     write_svg(stream, svg.as_bytes(), head_only).await
 }
 
+/// Handler for `GET /api/system_json`. The structured parallel to
+/// `/api/system`: identical query params and seed derivation, but the
+/// response is the generated `System` as an `application/json` document
+/// (see [`crate::systems::persist`]). A client can load it and render its
+/// own view of exactly the system the PNG endpoint drew. `scale` is
+/// accepted but ignored.
+async fn handle_system_json(
+    stream: &mut TcpStream,
+    query: &str,
+    head_only: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let req = match parse_system_request(query) {
+        Ok(r) => r,
+        Err((code, reason, body)) => return write_simple(stream, code, reason, &body).await,
+    };
+
+    let json = match generate_system_json(req.seed, req.constraints) {
+        Ok(s) => s,
+        Err(e) => {
+            return write_simple(stream, 500, "Internal Server Error", &format!("{e}")).await;
+        }
+    };
+
+    write_json(stream, json.as_bytes(), head_only).await
+}
+
+/// The parsed, validated inputs shared by `/api/world` (PNG) and
+/// `/api/world_json` (JSON): the planet seed derived from the world's
+/// identity, plus the UWP and name the generator consumes.
+struct WorldRequest {
+    seed: u64,
+    uwp: String,
+    name: String,
+}
+
+/// Parse + validate the shared `/api/world*` query params (`sector`, `hex`,
+/// `name`, `uwp`, optional `orbit`) and derive the planet seed. Missing or
+/// malformed params → `400`. `scale` is left to the PNG handler.
+fn parse_world_request(params: &HashMap<String, String>) -> Result<WorldRequest, HttpError> {
+    let missing = |p: &str| (400, "Bad Request", format!("missing required param: {p}"));
+    let sector = params
+        .get("sector")
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| missing("sector"))?;
+    let hex = params
+        .get("hex")
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| missing("hex"))?;
+    let name = params
+        .get("name")
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| missing("name"))?;
+    let uwp = params
+        .get("uwp")
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| missing("uwp"))?;
+
+    let (hex_x, hex_y) = parse_hex_quad(hex).ok_or((
+        400,
+        "Bad Request",
+        "hex must be a 4-digit string like \"2018\"".to_string(),
+    ))?;
+
+    let orbit = params
+        .get("orbit")
+        .and_then(|s| s.trim().parse::<i32>().ok())
+        .unwrap_or(3);
+
+    let sys_seed = system_seed(sector, hex_x, hex_y);
+    Ok(WorldRequest {
+        seed: planet_seed(sys_seed, orbit, name),
+        uwp: uwp.clone(),
+        name: name.clone(),
+    })
+}
+
 /// Handler for `GET /api/world`. Renders a planet surface PNG, caching the
 /// canonical-scale render in GCS. Subsequent requests for the same
 /// `(sector, hex, name, uwp, orbit)` are served from the cache and
@@ -316,44 +404,11 @@ async fn handle_world(
     gcs: Arc<GcsClient>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let params = parse_query(query);
-
-    let sector = match params.get("sector") {
-        Some(s) if !s.is_empty() => s.as_str(),
-        _ => {
-            return write_simple(stream, 400, "Bad Request", "missing required param: sector")
-                .await;
-        }
-    };
-    let hex = match params.get("hex") {
-        Some(h) if !h.is_empty() => h.as_str(),
-        _ => return write_simple(stream, 400, "Bad Request", "missing required param: hex").await,
-    };
-    let name = match params.get("name") {
-        Some(n) if !n.is_empty() => n.as_str(),
-        _ => return write_simple(stream, 400, "Bad Request", "missing required param: name").await,
-    };
-    let uwp = match params.get("uwp") {
-        Some(u) if !u.is_empty() => u.as_str(),
-        _ => return write_simple(stream, 400, "Bad Request", "missing required param: uwp").await,
-    };
-
-    let (hex_x, hex_y) = match parse_hex_quad(hex) {
-        Some(h) => h,
-        None => {
-            return write_simple(
-                stream,
-                400,
-                "Bad Request",
-                "hex must be a 4-digit string like \"2018\"",
-            )
-            .await;
-        }
+    let req = match parse_world_request(&params) {
+        Ok(r) => r,
+        Err((code, reason, body)) => return write_simple(stream, code, reason, &body).await,
     };
-
-    let orbit = params
-        .get("orbit")
-        .and_then(|s| s.trim().parse::<i32>().ok())
-        .unwrap_or(3);
+    let (seed, uwp, name) = (req.seed, req.uwp.as_str(), req.name.as_str());
 
     // Requested scale: defaults to 1.0 to match `generate_planet_png`'s
     // legacy native resolution. Values > CANONICAL_SCALE are clamped
@@ -374,8 +429,6 @@ async fn handle_world(
     }
     let output_scale = requested_scale.min(PLANET_CANONICAL_SCALE);
 
-    let sys_seed = system_seed(sector, hex_x, hex_y);
-    let seed = planet_seed(sys_seed, orbit, name);
     let cache_key = planet_cache_key(seed, uwp, name);
     let cache_object = format!("{PLANET_CACHE_PREFIX}/{cache_key:016x}.png");
 
@@ -446,11 +499,36 @@ async fn handle_world(
     write_png(stream, &response_bytes, head_only, Some(cache_status)).await
 }
 
+/// Handler for `GET /api/world_json`. The structured parallel to
+/// `/api/world`: identical identity params and seed chain, but the
+/// response is the map's per-hex summary (`worldmap::MapSummary`) as
+/// `application/json`. No rasterization happens, so this skips the GCS
+/// cache entirely — generating the grid is cheap next to the PNG render.
+/// Error mapping matches `/api/world`.
+async fn handle_world_json(
+    stream: &mut TcpStream,
+    query: &str,
+    head_only: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let params = parse_query(query);
+    let req = match parse_world_request(&params) {
+        Ok(r) => r,
+        Err((code, reason, body)) => return write_simple(stream, code, reason, &body).await,
+    };
+
+    let json = match generate_planet_json(req.seed, &req.uwp, Some(&req.name)) {
+        Ok(s) => s,
+        Err(e) => return classify_render_error(stream, e).await,
+    };
+
+    write_json(stream, json.as_bytes(), head_only).await
+}
+
 /// Map a `WorldgenError` from the planet generator into the right HTTP
-/// status. The library has three error variants but only two of them
-/// are reachable from this code path — we don't pass constraints, so
-/// `Constraints` is impossible; `Map(MapError)` is the bad-UWP case
-/// (→ 422); `Render(_)` is everything else (→ 500).
+/// status. Not every variant is reachable from this code path — we don't
+/// pass constraints, so `Constraints` is impossible; `Map(MapError)` is
+/// the bad-UWP case (→ 422); `Render(_)` / `Encode(_)` are everything
+/// else (→ 500).
 async fn classify_render_error(
     stream: &mut TcpStream,
     e: crate::api::WorldgenError,
@@ -458,7 +536,7 @@ async fn classify_render_error(
     use crate::api::WorldgenError::*;
     match e {
         Map(m) => write_simple(stream, 422, "Unprocessable Entity", &format!("{m:?}")).await,
-        Constraints(_) | Render(_) => {
+        Constraints(_) | Render(_) | Encode(_) => {
             write_simple(stream, 500, "Internal Server Error", &format!("{e}")).await
         }
     }
@@ -723,6 +801,33 @@ async fn write_svg(
     Ok(())
 }
 
+/// Write an `application/json` 200 response. Same long immutable cache
+/// headers as the image writers — the body is a deterministic function of
+/// the query string.
+async fn write_json(
+    stream: &mut TcpStream,
+    bytes: &[u8],
+    head_only: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let headers = format!(
+        "HTTP/1.1 200 OK\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {len}\r\n\
+         Cache-Control: public, max-age=31536000, immutable\r\n\
+         Connection: close\r\n\
+         {cors}\
+         \r\n",
+        len = bytes.len(),
+        cors = CORS_HEADERS,
+    );
+    stream.write_all(headers.as_bytes()).await?;
+    if !head_only {
+        stream.write_all(bytes).await?;
+    }
+    stream.shutdown().await.ok();
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Tests (the request parsing — endpoint flow is exercised end-to-end
 // from `tests/http_server_smoke.rs`).
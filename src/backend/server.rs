@@ -20,11 +20,16 @@ use tokio_tungstenite::tungstenite::Message;
 
 use crate::backend::TradeState;
 use crate::backend::firestore::{
-    FirestoreError, get_trade_state, initialize_firestore, save_trade_state,
+    FirestoreError, get_markets, get_trade_state, initialize_firestore, save_markets,
+    save_trade_state,
 };
 use crate::comms::{ServerCommand, ServerMessage};
+use crate::simulator::economy::{DAYS_IN_PORT, DAYS_PER_JUMP};
+use crate::simulator::itinerary::{ItineraryPlan, plan_itinerary};
 use crate::systems::world::World;
 use crate::trade::available_goods::AvailableGoodsTable;
+use crate::trade::market::{MarketKey, MarketLedger};
+use crate::trade::port_fees::PortCharges;
 use crate::trade::table::TradeTable;
 use crate::util::calculate_hex_distance;
 
@@ -54,6 +59,12 @@ type SharedDb = Arc<Option<FirestoreDb>>;
 /// value to detect what changed without round-tripping to Firestore.
 type SharedStates = Arc<RwLock<HashMap<String, TradeState>>>;
 
+/// Every world's market memory, shared by all ships on this server so
+/// one ship's trades move the prices the next one sees. Loaded from
+/// Firestore at startup and saved back whenever a ship executes trades.
+/// The simulator starts its voyages from a copy and never writes back.
+pub type SharedMarkets = Arc<RwLock<MarketLedger>>;
+
 /// The trade state server that manages WebSocket connections and state broadcasting
 pub struct TradeServer {
     /// Address the server listens on
@@ -67,13 +78,17 @@ pub struct TradeServer {
     /// Per-ship cached trade state (used to detect changes and recalculate).
     /// Each entry corresponds to a `ship_name` selected by some client.
     states: SharedStates,
+    /// Market memory shared across every ship.
+    markets: SharedMarkets,
 }
 
 impl TradeServer {
     /// Creates a new TradeServer bound to the specified address
     ///
-    /// Initializes the Firestore database connection using environment variables.
-    /// If the database ID is set to "debug", the server runs without a Firestore connection.
+    /// Initializes the Firestore database connection using environment variables
+    /// and loads the shared market ledger from it. If the database ID is set to
+    /// "debug", the server runs without a Firestore connection and the markets
+    /// start empty.
     ///
     /// # Arguments
     ///
@@ -81,9 +96,13 @@ impl TradeServer {
     ///
     /// # Errors
     ///
-    /// Returns `FirestoreError` if the Firestore initialization fails
+    /// Returns `FirestoreError` if the Firestore initialization fails or the
+    /// saved market ledger can't be read. Starting empty instead would
+    /// overwrite it on the first trade.
     pub async fn new(addr: SocketAddr) -> Result<Self, FirestoreError> {
         let db = initialize_firestore().await?;
+        let markets = get_markets(&db).await?;
+        log::info!("Loaded market memory for {} worlds", markets.len());
 
         Ok(Self {
             addr,
@@ -91,6 +110,7 @@ impl TradeServer {
             next_client_id: Arc::new(RwLock::new(0)),
             db: Arc::new(db),
             states: Arc::new(RwLock::new(HashMap::new())),
+            markets: Arc::new(RwLock::new(markets)),
         })
     }
 
@@ -99,6 +119,11 @@ impl TradeServer {
         &self.db
     }
 
+    /// Returns a handle on the market memory shared across every ship
+    pub fn markets(&self) -> SharedMarkets {
+        self.markets.clone()
+    }
+
     /// Starts the WebSocket server and begins accepting connections
     ///
     /// This method runs indefinitely, accepting new connections and spawning
@@ -112,9 +137,11 @@ impl TradeServer {
             let next_id = self.next_client_id.clone();
             let db = self.db.clone();
             let states = self.states.clone();
+            let markets = self.markets.clone();
 
             tokio::spawn(async move {
-                if let Err(e) = handle_connection(stream, addr, clients, next_id, db, states).await
+                if let Err(e) =
+                    handle_connection(stream, addr, clients, next_id, db, states, markets).await
                 {
                     log::error!("Error handling connection from {}: {}", addr, e);
                 }
@@ -158,6 +185,7 @@ impl TradeServer {
             self.next_client_id.clone(),
             self.db.clone(),
             self.states.clone(),
+            self.markets.clone(),
         )
         .await
     }
@@ -171,10 +199,11 @@ async fn handle_connection(
     next_id: Arc<RwLock<ClientId>>,
     db: SharedDb,
     states: SharedStates,
+    markets: SharedMarkets,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let ws_stream = tokio_tungstenite::accept_async(stream).await?;
     log::info!("WebSocket connection established: {}", addr);
-    handle_post_handshake(ws_stream, addr, clients, next_id, db, states).await
+    handle_post_handshake(ws_stream, addr, clients, next_id, db, states, markets).await
 }
 
 /// Handles a single WebSocket connection whose handshake is already done.
@@ -191,6 +220,7 @@ async fn handle_post_handshake(
     next_id: Arc<RwLock<ClientId>>,
     db: SharedDb,
     states: SharedStates,
+    markets: SharedMarkets,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
@@ -235,11 +265,19 @@ async fn handle_post_handshake(
                 // Try to parse as a ServerMessage (which can be either a state update or a command)
                 match serde_json::from_str::<ServerMessage>(&text) {
                     Ok(ServerMessage::StateUpdate(trade_state)) => {
-                        handle_trade_state_update(client_id, trade_state, &db, &clients, &states)
-                            .await;
+                        handle_trade_state_update(
+                            client_id,
+                            trade_state,
+                            &db,
+                            &clients,
+                            &states,
+                            &markets,
+                        )
+                        .await;
                     }
                     Ok(ServerMessage::Command(ServerCommand::Regenerate)) => {
-                        handle_regenerate_command(client_id, &db, &clients, &states).await;
+                        handle_regenerate_command(client_id, &db, &clients, &states, &markets)
+                            .await;
                     }
                     Ok(ServerMessage::Command(ServerCommand::SelectShip { ship_name })) => {
                         handle_select_ship(client_id, ship_name, &db, &clients, &states).await;
@@ -247,6 +285,9 @@ async fn handle_post_handshake(
                     Ok(ServerMessage::Command(ServerCommand::ApplyMonthlyExpenses)) => {
                         handle_apply_monthly_expenses(client_id, &db, &clients, &states).await;
                     }
+                    Ok(ServerMessage::Command(ServerCommand::ExecuteTrades)) => {
+                        handle_execute_trades(client_id, &db, &clients, &states, &markets).await;
+                    }
                     Err(e) => {
                         log::warn!(
                             "Failed to deserialize message from client {}: {}",
@@ -443,6 +484,7 @@ async fn handle_select_ship(
 /// - Buy/sell prices when skills or worlds change
 /// - Ship manifest prices when destination or skills change
 /// - Available passengers when worlds, distance, or skills change
+/// - The itinerary plan when the itinerary, ship, or skills change
 ///
 /// After recalculation, the updated state is broadcast to all clients
 /// viewing the same ship.
@@ -452,6 +494,7 @@ async fn handle_trade_state_update(
     db: &SharedDb,
     clients: &Clients,
     states: &SharedStates,
+    markets: &SharedMarkets,
 ) {
     let ship_name = match current_ship_of(clients, client_id).await {
         Some(s) => s,
@@ -470,8 +513,19 @@ async fn handle_trade_state_update(
         states_guard.get(&ship_name).cloned()
     };
 
+    // The trading date and the ledger are the server's: they only move
+    // when trades are executed or expenses applied, so keep ours over
+    // whatever the client echoed back.
+    if let Some(prev) = &prev_state {
+        state.date = prev.date;
+        state.ledger = prev.ledger.clone();
+    }
+
     // Detect what changed and recalculate as needed
-    let recalculated = recalculate_trade_state(&mut state, prev_state.as_ref());
+    let recalculated = {
+        let markets = markets.read().await;
+        recalculate_trade_state(&mut state, prev_state.as_ref(), &markets)
+    };
 
     if recalculated {
         log::info!(
@@ -509,9 +563,20 @@ async fn handle_trade_state_update(
 
 /// Recalculates trade state when world names/UWPs or skills change
 ///
+/// A regenerated origin market, and every price, reflects `markets`'
+/// memory of recent trade at the worlds involved.
+///
 /// Returns true if any recalculation was performed.
-fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeState>) -> bool {
+fn recalculate_trade_state(
+    state: &mut TradeState,
+    prev_state: Option<&TradeState>,
+    markets: &MarketLedger,
+) -> bool {
     let mut recalculated = false;
+    let origin_market = state
+        .origin_coords
+        .map(|(x, y)| MarketKey::at_galactic(x, y));
+    let day = state.date.day_number();
 
     // Parse origin world from name/UWP, setting coordinates and zone
     let origin_world = if !state.origin_world_name.is_empty() && state.origin_uwp.len() == 9 {
@@ -521,6 +586,7 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
                 // Set coordinates and zone from state
                 world.coordinates = state.origin_coords;
                 world.travel_zone = state.origin_zone;
+                world.set_facilities(state.origin_bases.clone());
                 Some(world)
             }
             Err(e) => {
@@ -540,6 +606,7 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
                 // Set coordinates and zone from state
                 world.coordinates = state.dest_coords;
                 world.travel_zone = state.dest_zone;
+                world.set_facilities(state.dest_bases.clone());
                 Some(world)
             }
             Err(e) => {
@@ -564,6 +631,7 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
                 || prev.origin_uwp != state.origin_uwp
                 || prev.origin_coords != state.origin_coords
                 || prev.origin_zone != state.origin_zone
+                || prev.origin_bases != state.origin_bases
                 || prev.illegal_goods != state.illegal_goods
         });
 
@@ -574,6 +642,7 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
                 || prev.dest_uwp != state.dest_uwp
                 || prev.dest_coords != state.dest_coords
                 || prev.dest_zone != state.dest_zone
+                || prev.dest_bases != state.dest_bases
         });
 
     // Check if skills changed.
@@ -596,7 +665,10 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
             world.get_population(),
             state.illegal_goods,
         ) {
-            Ok(new_table) => {
+            Ok(mut new_table) => {
+                if let Some(key) = &origin_market {
+                    markets.adjust_supply(key, day, world.get_population(), &mut new_table);
+                }
                 state.available_goods = new_table;
                 recalculated = true;
                 log::info!(
@@ -625,10 +697,16 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
     if (origin_changed || dest_changed || skills_changed)
         && let Some(world) = origin_world.as_ref()
     {
+        if let Some(key) = &origin_market {
+            let population = world.get_population();
+            markets.adjust_demand(key, day, population, &mut state.available_goods);
+            markets.adjust_demand(key, day, population, &mut state.ship_manifest.trade_goods);
+        }
+
         // Price goods to buy at origin (player buying from system).
         state.available_goods.price_goods_to_buy(
             &world.get_trade_classes(),
-            state.ship.broker_skill,
+            state.ship.effective_broker_skill(),
             state.system_broker_skill,
         );
 
@@ -637,7 +715,7 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
         state.available_goods.price_goods_to_sell(
             Some(world.get_trade_classes()),
             state.system_broker_skill,
-            state.ship.broker_skill,
+            state.ship.effective_broker_skill(),
         );
 
         state.available_goods.sort_by_discount();
@@ -647,7 +725,7 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
         // player-as-buyer, system-as-supplier).
         state.ship_manifest.price_goods(
             &origin_world,
-            state.ship.broker_skill,
+            state.ship.effective_broker_skill(),
             state.system_broker_skill,
         );
         recalculated = true;
@@ -669,9 +747,10 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
                 dest.travel_zone,
                 dest.tech_level,
                 distance,
-                state.ship.steward_skill as i32,
-                state.ship.broker_skill as i32,
+                state.ship.effective_steward_skill() as i32,
+                state.ship.effective_broker_skill() as i32,
             );
+            passengers.generate_mail(world, dest, distance, state.ship.weapons);
 
             state.available_passengers = Some(passengers);
             recalculated = true;
@@ -691,6 +770,23 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
         }
     }
 
+    // Replan the itinerary if it or anything it's planned on changed;
+    // otherwise keep the plan already sent rather than the client's echo.
+    let itinerary_changed = prev_state.is_none()
+        || prev_state.is_some_and(|prev| {
+            prev.itinerary != state.itinerary || prev.illegal_goods != state.illegal_goods
+        });
+    if itinerary_changed || skills_changed {
+        state.itinerary_plan = plan_itinerary_for(state, markets);
+        recalculated = true;
+        log::info!(
+            "Replanned itinerary of {} stops",
+            state.itinerary.stops.len()
+        );
+    } else if let Some(prev) = prev_state {
+        state.itinerary_plan = prev.itinerary_plan.clone();
+    }
+
     // Store the generated world objects in the state so they're sent back to clients
     // This ensures the client and server always have the same World objects
     state.origin_world = origin_world;
@@ -699,6 +795,30 @@ fn recalculate_trade_state(state: &mut TradeState, prev_state: Option<&TradeStat
     recalculated
 }
 
+/// Plans the state's itinerary from its trading date.
+///
+/// Returns `None` for an itinerary of fewer than two stops, or one that
+/// can't be planned (logged).
+fn plan_itinerary_for(state: &TradeState, markets: &MarketLedger) -> Option<ItineraryPlan> {
+    if state.itinerary.stops.len() < 2 {
+        return None;
+    }
+    match plan_itinerary(
+        &state.itinerary,
+        &state.ship,
+        state.system_broker_skill,
+        state.illegal_goods,
+        markets,
+        state.date,
+    ) {
+        Ok(plan) => Some(plan),
+        Err(e) => {
+            log::error!("Failed to plan itinerary: {}", e);
+            None
+        }
+    }
+}
+
 /// Handles a regenerate command from a client
 ///
 /// This re-rolls all random values (prices, passengers) without changing the state.
@@ -709,6 +829,7 @@ async fn handle_regenerate_command(
     db: &SharedDb,
     clients: &Clients,
     states: &SharedStates,
+    markets: &SharedMarkets,
 ) {
     let ship_name = match current_ship_of(clients, client_id).await {
         Some(s) => s,
@@ -740,6 +861,12 @@ async fn handle_regenerate_command(
         _ => 0,
     };
 
+    let markets = markets.read().await;
+    let day = state.date.day_number();
+    let origin_market = state
+        .origin_coords
+        .map(|(x, y)| MarketKey::at_galactic(x, y));
+
     // Regenerate trade table with fresh die rolls
     if let Some(ref world) = origin_world {
         match AvailableGoodsTable::for_world(
@@ -751,16 +878,23 @@ async fn handle_regenerate_command(
             Ok(mut new_table) => {
                 // Reset die rolls to get fresh random values
                 new_table.reset_die_rolls();
+                if let Some(key) = &origin_market {
+                    markets.adjust_supply(key, day, world.get_population(), &mut new_table);
+                }
+                if let (Some((x, y)), Some(dest)) = (state.dest_coords, dest_world.as_ref()) {
+                    let key = MarketKey::at_galactic(x, y);
+                    markets.adjust_demand(&key, day, dest.get_population(), &mut new_table);
+                }
                 new_table.price_goods_to_buy(
                     &world.get_trade_classes(),
-                    state.ship.broker_skill,
+                    state.ship.effective_broker_skill(),
                     state.system_broker_skill,
                 );
                 let dest_trade_classes = dest_world.as_ref().map(|w| w.get_trade_classes());
                 new_table.price_goods_to_sell(
                     dest_trade_classes,
                     state.system_broker_skill,
-                    state.ship.broker_skill,
+                    state.ship.effective_broker_skill(),
                 );
                 new_table.sort_by_discount();
                 state.available_goods = new_table;
@@ -773,11 +907,19 @@ async fn handle_regenerate_command(
     }
 
     // Regenerate manifest with fresh die rolls
-    if origin_world.is_some() {
+    if let Some(world) = origin_world.as_ref() {
+        if let Some(key) = &origin_market {
+            markets.adjust_demand(
+                key,
+                day,
+                world.get_population(),
+                &mut state.ship_manifest.trade_goods,
+            );
+        }
         state.ship_manifest.reset_die_rolls();
         state.ship_manifest.price_goods(
             &origin_world,
-            state.ship.broker_skill,
+            state.ship.effective_broker_skill(),
             state.system_broker_skill,
         );
         log::info!("Regenerated manifest prices");
@@ -801,14 +943,19 @@ async fn handle_regenerate_command(
             dest.travel_zone,
             dest.tech_level,
             distance,
-            state.ship.steward_skill as i32,
-            state.ship.broker_skill as i32,
+            state.ship.effective_steward_skill() as i32,
+            state.ship.effective_broker_skill() as i32,
         );
+        passengers.generate_mail(origin, dest, distance, state.ship.weapons);
 
         state.available_passengers = Some(passengers);
         log::info!("Regenerated passengers with fresh die rolls");
     }
 
+    // Replan the itinerary with fresh die rolls
+    state.itinerary_plan = plan_itinerary_for(&state, &markets);
+    drop(markets);
+
     // Save updated state to Firestore under this ship's session
     if let Err(e) = save_trade_state(db, &ship_name, &state).await {
         log::error!(
@@ -830,8 +977,9 @@ async fn handle_regenerate_command(
 ///
 /// Subtracts one 28-day period of fixed expenses (mortgage +
 /// maintenance + salary, computed by [`crate::trade::Ship::monthly_expenses`])
-/// from the current ship's manifest profit, persists the updated state,
-/// and broadcasts it to every client viewing this ship. Mirrors the
+/// from the current ship's manifest profit, posts them to its ledger,
+/// persists the updated state, and broadcasts it to every client
+/// viewing this ship. Mirrors the
 /// structure of `handle_regenerate_command` so the cache / Firestore /
 /// broadcast invariants stay aligned across both commands.
 ///
@@ -870,6 +1018,9 @@ async fn handle_apply_monthly_expenses(
 
     let expenses = state.ship.monthly_expenses();
     state.ship_manifest.profit -= expenses;
+    state
+        .ledger
+        .record_periodic(state.date, &state.origin_world_name, &state.ship);
     log::info!(
         "Applied monthly expenses for ship {}: -{} credits (new profit: {})",
         ship_name,
@@ -893,3 +1044,126 @@ async fn handle_apply_monthly_expenses(
         .insert(ship_name.clone(), state.clone());
     broadcast_to_ship(clients, &ship_name, &state).await;
 }
+
+/// Handles an `ExecuteTrades` command from a client.
+///
+/// Records the trades planned on the ship's cached state — goods marked
+/// to buy from the origin's market and cargo marked to sell to it — in
+/// the shared market memory, posts the leg's settlement to the ship's
+/// ledger, then moves the ship's trading date on by a port stay and a
+/// jump. The client settles its manifest and makes the
+/// destination its new origin itself; the state update that follows is
+/// priced against the updated markets and broadcast as usual, so
+/// nothing is broadcast here.
+async fn handle_execute_trades(
+    client_id: ClientId,
+    db: &SharedDb,
+    clients: &Clients,
+    states: &SharedStates,
+    markets: &SharedMarkets,
+) {
+    let ship_name = match current_ship_of(clients, client_id).await {
+        Some(s) => s,
+        None => {
+            log::warn!(
+                "Client {} sent ExecuteTrades before SelectShip — dropping",
+                client_id
+            );
+            return;
+        }
+    };
+
+    let (state, traded) = {
+        let mut states_guard = states.write().await;
+        let Some(state) = states_guard.get_mut(&ship_name) else {
+            log::warn!(
+                "Received ExecuteTrades for ship {} but no state cached",
+                ship_name
+            );
+            return;
+        };
+
+        let traded = if let Some((x, y)) = state.origin_coords {
+            let key = MarketKey::at_galactic(x, y);
+            let day = state.date.day_number();
+            let mut markets = markets.write().await;
+            for good in state
+                .available_goods
+                .goods
+                .iter()
+                .filter(|g| g.transacted > 0)
+            {
+                markets.record_purchase(&key, day, good.source_index, good.transacted);
+            }
+            for good in state
+                .ship_manifest
+                .trade_goods
+                .goods
+                .iter()
+                .filter(|g| g.transacted > 0)
+            {
+                markets.record_sale(&key, day, good.source_index, good.transacted);
+            }
+            Some((*markets).clone())
+        } else {
+            log::warn!(
+                "Ship {} executed trades at an origin without coordinates; market not updated",
+                ship_name
+            );
+            None
+        };
+
+        // Post the leg to the ledger as the client settles it.
+        let distance = match (state.origin_coords, state.dest_coords) {
+            (Some((ox, oy)), Some((dx, dy))) => calculate_hex_distance(ox, oy, dx, dy),
+            _ => 0,
+        };
+        let charges = state
+            .origin_world
+            .as_ref()
+            .map_or_else(PortCharges::default, |world| {
+                PortCharges::assess(
+                    world,
+                    &state.ship,
+                    state.ship_manifest.trade_goods_proceeds(),
+                    state.available_goods.total_buy_cost() as i64,
+                )
+            });
+        state.ledger.record_trades(
+            state.date,
+            &state.origin_world_name,
+            distance,
+            &state.ship_manifest,
+            &state.available_goods.goods,
+            &state.available_passengers,
+            &charges,
+        );
+
+        state.date = state.date.add_days(DAYS_IN_PORT + DAYS_PER_JUMP);
+        // The loop now starts later, against markets that just moved.
+        state.itinerary_plan = plan_itinerary_for(state, &*markets.read().await);
+        log::info!(
+            "Executed trades for ship {}; trading date now {}",
+            ship_name,
+            state.date.format()
+        );
+        (state.clone(), traded)
+    };
+
+    if let Err(e) = save_trade_state(db, &ship_name, &state).await {
+        log::error!(
+            "Failed to save state for ship {} after executing trades: {}",
+            ship_name,
+            e
+        );
+    }
+    if let Some(markets) = traded
+        && let Err(e) = save_markets(db, &markets).await
+    {
+        log::error!(
+            "Failed to save markets after ship {} executed trades: {}",
+            ship_name,
+            e
+        );
+    }
+}
@@ -7,31 +7,79 @@
 //! 3. Server streams `ServerMessage::Step` frames until the run finishes.
 //! 4. Server sends exactly one `ServerMessage::Done` or `ServerMessage::Error`.
 //! 5. Server closes the connection.
+//!
+//! While a voyage streams, `Pause`, `Resume`, `StepOnce` and `Cancel`
+//! messages steer it between port calls. A paused voyage sends
+//! `ServerMessage::Paused` with its checkpoint and waits for the next
+//! control message; a cancelled one finishes with
+//! `ServerMessage::Cancelled` instead of `Done`. A voyage can also start
+//! from a stored checkpoint with `ClientMessage::RunFromCheckpoint`.
+//!
+//! A `ClientMessage::RunBatch(batch)` runs the voyage many times instead,
+//! streaming `ServerMessage::BatchProgress` and finishing with
+//! `ServerMessage::BatchDone` or `ServerMessage::Error`. `Cancel` stops a
+//! batch too, as does the client going away.
+//!
+//! A voyage or batch whose params carry no market memory of their own
+//! starts from a snapshot of the trade server's shared markets, so the
+//! simulator sees the same gluts and shortages the crews have left. The
+//! simulator is read-only against the shared markets: a voyage's trades
+//! stay in its own copy and never reach the crews' markets, since a
+//! what-if voyage (let alone a batch of hundreds) didn't really happen.
 
 use std::time::Duration;
 
-use futures_util::stream::SplitStream;
+use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
 use tokio::net::TcpStream;
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TryRecvError;
 use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::tungstenite::Message;
 
-use crate::simulator::executor::run_simulation;
+use crate::backend::server::SharedMarkets;
+use crate::simulator::batch::{BatchParams, BatchSummary, MAX_BATCH_RUNS, run_batch};
+use crate::simulator::executor::Voyage;
 use crate::simulator::protocol::{ClientMessage, ServerMessage};
-use crate::simulator::types::{SimulationResult, SimulationStep};
+use crate::simulator::types::SimulationParams;
 use crate::simulator::world_fetch::WorldCache;
 
+type WsSender = SplitSink<WebSocketStream<TcpStream>, Message>;
+type WsReceiver = SplitStream<WebSocketStream<TcpStream>>;
+
+/// A client request to steer a running voyage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Pause,
+    Resume,
+    StepOnce,
+    Cancel,
+}
+
+impl Control {
+    fn from_message(msg: &ClientMessage) -> Option<Control> {
+        match msg {
+            ClientMessage::Pause => Some(Control::Pause),
+            ClientMessage::Resume => Some(Control::Resume),
+            ClientMessage::StepOnce => Some(Control::StepOnce),
+            ClientMessage::Cancel => Some(Control::Cancel),
+            _ => None,
+        }
+    }
+}
+
 /// Handle a single simulator WebSocket connection from start to finish.
 ///
 /// This is independent of the trade-tool [`crate::backend::server::TradeServer`] —
-/// it doesn't share clients, state, or the broadcast machinery.
+/// it doesn't share clients, state, or the broadcast machinery, only a
+/// read of its market memory.
 pub async fn handle_simulator_connection(
     stream: TcpStream,
+    markets: SharedMarkets,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let ws_stream = tokio_tungstenite::accept_async(stream).await?;
     log::info!("simulator: WebSocket connection established");
-    handle_ws(ws_stream).await
+    handle_ws(ws_stream, markets).await
 }
 
 /// Handle a simulator WebSocket once the handshake is already done.
@@ -39,16 +87,26 @@ pub async fn handle_simulator_connection(
 /// HTTP path before deciding which handler to call.
 pub async fn handle_simulator_ws(
     ws_stream: WebSocketStream<TcpStream>,
+    markets: SharedMarkets,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    handle_ws(ws_stream).await
+    handle_ws(ws_stream, markets).await
+}
+
+/// Start `params` from a snapshot of the shared market memory unless it
+/// brings its own. Nothing the voyage trades is written back.
+async fn seed_markets(params: &mut SimulationParams, markets: &SharedMarkets) {
+    if params.markets.is_empty() {
+        params.markets = markets.read().await.clone();
+    }
 }
 
 async fn handle_ws(
     ws_stream: WebSocketStream<TcpStream>,
+    markets: SharedMarkets,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let (mut ws_sender, mut ws_receiver) = ws_stream.split();
 
-    // Read the first message — must be a RunSimulation.
+    // Read the first message — must start a voyage or a batch.
     let first = match ws_receiver.next().await {
         Some(Ok(Message::Text(t))) => t,
         Some(Ok(Message::Close(_))) => {
@@ -72,102 +130,283 @@ async fn handle_ws(
     let client_msg: ClientMessage = match serde_json::from_str(&first) {
         Ok(m) => m,
         Err(e) => {
-            send_error(
-                &mut ws_sender,
-                &format!("could not parse RunSimulation: {}", e),
-            )
-            .await;
+            send_error(&mut ws_sender, &format!("could not parse request: {}", e)).await;
             return Ok(());
         }
     };
 
-    let ClientMessage::RunSimulation(params) = client_msg;
+    let outcome = match client_msg {
+        ClientMessage::RunSimulation(mut params) => {
+            seed_markets(&mut params, &markets).await;
+            log::info!(
+                "simulator: starting run for {} ({}-{}) jump={} cargo={}",
+                params.home_world.name,
+                params.home_world.sector,
+                params.home_world.uwp,
+                params.ship.jump_rating,
+                params.ship.cargo_capacity
+            );
+            match Voyage::new(params) {
+                Ok(voyage) => stream_voyage(&mut ws_sender, &mut ws_receiver, voyage).await,
+                Err(e) => Some(Err(e.to_string())),
+            }
+        }
+        ClientMessage::RunFromCheckpoint(checkpoint) => {
+            log::info!(
+                "simulator: resuming run for {} at {} after {} port calls",
+                checkpoint.params.home_world.name,
+                checkpoint.current_ref.name,
+                checkpoint.port_calls
+            );
+            let voyage = Voyage::from_checkpoint(*checkpoint);
+            stream_voyage(&mut ws_sender, &mut ws_receiver, voyage).await
+        }
+        ClientMessage::RunBatch(mut batch) => {
+            seed_markets(&mut batch.params, &markets).await;
+            stream_batch(&mut ws_sender, &mut ws_receiver, batch).await
+        }
+        ClientMessage::Pause
+        | ClientMessage::Resume
+        | ClientMessage::StepOnce
+        | ClientMessage::Cancel => Some(Err("no voyage is running".to_string())),
+    };
+    let Some(outcome) = outcome else {
+        return Ok(());
+    };
 
-    log::info!(
-        "simulator: starting run for {} ({}-{}) jump={} cargo={}",
-        params.home_world.name,
-        params.home_world.sector,
-        params.home_world.uwp,
-        params.ship.jump_rating,
-        params.ship.cargo_capacity
-    );
+    match outcome {
+        Ok(done) => {
+            send_frame(&mut ws_sender, &done).await;
+        }
+        Err(message) => {
+            send_error(&mut ws_sender, &message).await;
+        }
+    }
+
+    // Drive a clean WebSocket close: send Close, then read until the client
+    // sends its Close back (or we time out). If we drop the receiver with
+    // unread data still in the kernel buffer, Linux turns the socket close
+    // into a TCP RST — which the browser reports as code 1006 even though
+    // the run finished cleanly.
+    let _ = ws_sender.send(Message::Close(None)).await;
+    drain_until_close(&mut ws_receiver, Duration::from_secs(2)).await;
+    log::info!("simulator: connection closed");
+    Ok(())
+}
 
-    // Bridge: executor task pushes steps over an mpsc channel; this
-    // task reads them and pipes them out as text frames. When the
-    // executor finishes, it returns its result over a oneshot.
-    let (step_tx, mut step_rx) = mpsc::unbounded_channel::<SimulationStep>();
-    let (result_tx, result_rx) =
-        tokio::sync::oneshot::channel::<Result<SimulationResult, String>>();
+/// Run a voyage, piping its steps to the client and its control messages
+/// to the voyage. Returns the terminal `Done` or `Cancelled` frame or an
+/// error message, or `None` if the client went away.
+async fn stream_voyage(
+    ws_sender: &mut WsSender,
+    ws_receiver: &mut WsReceiver,
+    voyage: Voyage,
+) -> Option<Result<ServerMessage, String>> {
+    // Bridge: the voyage task pushes Step and Paused frames over an mpsc
+    // channel; this task pipes them out as text frames and forwards
+    // control messages the other way. When the voyage ends, it returns
+    // its terminal frame over a oneshot.
+    let (frame_tx, mut frame_rx) = mpsc::unbounded_channel::<ServerMessage>();
+    let (control_tx, control_rx) = mpsc::unbounded_channel::<Control>();
+    let (result_tx, result_rx) = tokio::sync::oneshot::channel::<Result<ServerMessage, String>>();
 
     tokio::spawn(async move {
-        let mut cache = WorldCache::new();
-        let res = run_simulation(params, &mut cache, |step| {
-            // Drop steps if the writer is gone; the executor keeps going.
-            let _ = step_tx.send(step);
-        })
-        .await;
-        let res = res.map_err(|e| e.to_string());
+        let mut cache = world_cache().await;
+        let res = drive_voyage(voyage, &mut cache, control_rx, frame_tx).await;
         let _ = result_tx.send(res);
     });
 
-    // Pipe steps as they arrive.
-    while let Some(step) = step_rx.recv().await {
-        let msg = ServerMessage::Step(step);
-        match serde_json::to_string(&msg) {
-            Ok(json) => {
-                if ws_sender.send(Message::Text(json.into())).await.is_err() {
+    loop {
+        tokio::select! {
+            frame = frame_rx.recv() => {
+                // `None` once the voyage task has finished.
+                let Some(frame) = frame else { break };
+                if !send_frame(ws_sender, &frame).await {
                     log::warn!("simulator: client closed mid-run");
-                    return Ok(());
+                    return None;
                 }
             }
-            Err(e) => {
-                log::error!("simulator: failed to serialize step: {}", e);
-            }
+            incoming = ws_receiver.next() => match incoming {
+                Some(Ok(Message::Text(t))) => {
+                    match serde_json::from_str::<ClientMessage>(&t) {
+                        Ok(msg) => match Control::from_message(&msg) {
+                            Some(control) => {
+                                let _ = control_tx.send(control);
+                            }
+                            None => log::warn!("simulator: ignoring new run request mid-run"),
+                        },
+                        Err(e) => log::warn!("simulator: could not parse control message: {}", e),
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                    // Dropping `control_tx` on return cancels the voyage.
+                    log::warn!("simulator: client closed mid-run");
+                    return None;
+                }
+                Some(Ok(_)) => {}
+            },
         }
     }
 
-    // Executor has finished; pull its result.
-    let result = match result_rx.await {
-        Ok(r) => r,
-        Err(_) => {
-            send_error(&mut ws_sender, "executor task dropped").await;
-            drain_until_close(&mut ws_receiver, Duration::from_secs(2)).await;
-            return Ok(());
-        }
-    };
+    match result_rx.await {
+        Ok(r) => Some(r),
+        Err(_) => Some(Err("executor task dropped".to_string())),
+    }
+}
 
-    match result {
-        Ok(r) => {
-            let done = ServerMessage::Done(r);
-            match serde_json::to_string(&done) {
-                Ok(json) => {
-                    let _ = ws_sender.send(Message::Text(json.into())).await;
+/// Step `voyage` to its end, obeying control messages between port
+/// calls. Steps and `Paused` frames go out on `frames`; the terminal
+/// `Done` or `Cancelled` frame is returned. A closed control channel
+/// means the client has gone, and cancels the voyage.
+async fn drive_voyage(
+    mut voyage: Voyage,
+    source: &mut WorldCache,
+    mut controls: mpsc::UnboundedReceiver<Control>,
+    frames: mpsc::UnboundedSender<ServerMessage>,
+) -> Result<ServerMessage, String> {
+    let cancelled = |voyage: &Voyage| Ok(ServerMessage::Cancelled(Box::new(voyage.checkpoint())));
+    let mut paused = false;
+    // Port calls still to make before pausing again, while paused.
+    let mut steps_allowed = 0u32;
+    loop {
+        // Pick up whatever arrived during the last port call.
+        loop {
+            match controls.try_recv() {
+                Ok(Control::Pause) => paused = true,
+                Ok(Control::Resume) => paused = false,
+                Ok(Control::StepOnce) => {
+                    paused = true;
+                    steps_allowed += 1;
                 }
-                Err(e) => {
-                    log::error!("simulator: failed to serialize Done: {}", e);
+                Ok(Control::Cancel) | Err(TryRecvError::Disconnected) => {
+                    return cancelled(&voyage);
                 }
+                Err(TryRecvError::Empty) => break,
             }
         }
-        Err(message) => {
-            send_error(&mut ws_sender, &message).await;
+        if voyage.is_finished() {
+            return Ok(ServerMessage::Done(voyage.result()));
         }
+        if paused && steps_allowed == 0 {
+            let _ = frames.send(ServerMessage::Paused(Box::new(voyage.checkpoint())));
+            loop {
+                match controls.recv().await {
+                    Some(Control::Pause) => continue,
+                    Some(Control::Resume) => paused = false,
+                    Some(Control::StepOnce) => steps_allowed = 1,
+                    Some(Control::Cancel) | None => return cancelled(&voyage),
+                }
+                break;
+            }
+        }
+        voyage
+            .step(source, &mut |step| {
+                let _ = frames.send(ServerMessage::Step(step));
+            })
+            .await
+            .map_err(|e| e.to_string())?;
+        steps_allowed = steps_allowed.saturating_sub(1);
     }
+}
 
-    // Drive a clean WebSocket close: send Close, then read until the client
-    // sends its Close back (or we time out). If we drop the receiver with
-    // unread data still in the kernel buffer, Linux turns the socket close
-    // into a TCP RST — which the browser reports as code 1006 even though
-    // the run finished cleanly.
-    let _ = ws_sender.send(Message::Close(None)).await;
-    drain_until_close(&mut ws_receiver, Duration::from_secs(2)).await;
-    log::info!("simulator: connection closed");
-    Ok(())
+/// Run a Monte Carlo batch, reporting progress after each voyage. Returns
+/// the terminal `BatchDone` frame or an error message, or `None` if the
+/// client went away. A `Cancel` message or a closed connection aborts the
+/// batch task rather than leaving it to run to the end.
+async fn stream_batch(
+    ws_sender: &mut WsSender,
+    ws_receiver: &mut WsReceiver,
+    batch: BatchParams,
+) -> Option<Result<ServerMessage, String>> {
+    if !(1..=MAX_BATCH_RUNS).contains(&batch.runs) {
+        return Some(Err(format!(
+            "batch runs must be between 1 and {}, got {}",
+            MAX_BATCH_RUNS, batch.runs
+        )));
+    }
+    log::info!(
+        "simulator: starting {}-run batch for {} ({}-{})",
+        batch.runs,
+        batch.params.home_world.name,
+        batch.params.home_world.sector,
+        batch.params.home_world.uwp
+    );
+
+    let runs = batch.runs;
+    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<u32>();
+    let (result_tx, result_rx) = tokio::sync::oneshot::channel::<Result<BatchSummary, String>>();
+
+    let task = tokio::spawn(async move {
+        let mut cache = world_cache().await;
+        let res = run_batch(batch, &mut cache, |completed| {
+            let _ = progress_tx.send(completed);
+        })
+        .await;
+        let _ = result_tx.send(res.map_err(|e| e.to_string()));
+    });
+
+    loop {
+        tokio::select! {
+            completed = progress_rx.recv() => {
+                // `None` once the batch task has finished.
+                let Some(completed) = completed else { break };
+                let msg = ServerMessage::BatchProgress { completed, runs };
+                if !send_frame(ws_sender, &msg).await {
+                    log::warn!("simulator: client closed mid-batch");
+                    task.abort();
+                    return None;
+                }
+            }
+            incoming = ws_receiver.next() => match incoming {
+                Some(Ok(Message::Text(t))) => match serde_json::from_str::<ClientMessage>(&t) {
+                    Ok(ClientMessage::Cancel) => {
+                        log::info!("simulator: batch cancelled");
+                        task.abort();
+                        return Some(Err("batch cancelled".to_string()));
+                    }
+                    Ok(_) => log::warn!("simulator: ignoring message mid-batch"),
+                    Err(e) => log::warn!("simulator: could not parse control message: {}", e),
+                },
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                    log::warn!("simulator: client closed mid-batch");
+                    task.abort();
+                    return None;
+                }
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    match result_rx.await {
+        Ok(r) => Some(r.map(ServerMessage::BatchDone)),
+        Err(_) => Some(Err("batch task dropped".to_string())),
+    }
+}
+
+/// A TravellerMap-backed world cache with the full sector-origin table
+/// loaded. The full table lets routes cross into any neighbouring sector;
+/// without it we fall back to the built-in table of common sectors.
+async fn world_cache() -> WorldCache {
+    let mut cache = WorldCache::new();
+    if let Err(e) = cache.load_sector_origins().await {
+        log::warn!("simulator: using built-in sector origins ({})", e);
+    }
+    cache
+}
+
+/// Send one frame as JSON text. Returns `false` if the client is gone;
+/// a frame that fails to serialize is logged and skipped.
+async fn send_frame(ws_sender: &mut WsSender, msg: &ServerMessage) -> bool {
+    match serde_json::to_string(msg) {
+        Ok(json) => ws_sender.send(Message::Text(json.into())).await.is_ok(),
+        Err(e) => {
+            log::error!("simulator: failed to serialize frame: {}", e);
+            true
+        }
+    }
 }
 
-async fn drain_until_close(
-    ws_receiver: &mut SplitStream<WebSocketStream<TcpStream>>,
-    timeout: Duration,
-) {
+async fn drain_until_close(ws_receiver: &mut WsReceiver, timeout: Duration) {
     let _ = tokio::time::timeout(timeout, async {
         while let Some(msg) = ws_receiver.next().await {
             match msg {
@@ -188,7 +188,7 @@ async fn dispatch(
         log::info!("WS connection from {} requested path {}", peer_addr, path);
 
         if path.starts_with("/ws/simulator") {
-            simulator_server::handle_simulator_ws(ws_stream).await?;
+            simulator_server::handle_simulator_ws(ws_stream, trade_server.markets()).await?;
         } else if path.starts_with("/ws/captains-log") {
             captains_log_server::handle_captains_log_ws(
                 ws_stream,
@@ -0,0 +1,925 @@
+//! # Worldgen Command-Line Tool
+//!
+//! A native, headless front end to the library API for scripting referee
+//! handouts without running the HTTP server or the Leptos app. It needs no
+//! crate features beyond the always-compiled library surface:
+//!
+//! ```text
+//! cargo run --no-default-features --bin worldgen -- \
+//!     system --sector "Spinward Marches" --hex 1910 --uwp A788899-A \
+//!            --stellar "G2 V" --out regina.png
+//! ```
+//!
+//! ## Subcommands
+//!
+//! - `system` — generate a star system and write it as PNG, SVG or JSON
+//!   (the `systems::persist` format).
+//! - `planet` — generate a planet surface map and write it as PNG, SVG or
+//!   the per-hex JSON summary.
+//! - `trade` — roll the market of goods available on a world and print it
+//!   as a table or JSON.
+//! - `sector` — roll a whole sector (or one subsector) map and list its
+//!   occupied hexes as text, JSON, a T5 Second Survey tab-delimited file
+//!   or a legacy `.sec` file. Each listed hex feeds straight back into
+//!   `system` to expand it. With `--in` the map is read from an existing
+//!   T5 or `.sec` file instead of rolled, so published sectors can be
+//!   converted or listed.
+//! - `batch` — run a ship-simulator voyage (a `SimulationParams` JSON
+//!   file) many times under different seeds and print the statistical
+//!   summary. Worlds come from TravellerMap, or from a T5 / `.sec` file
+//!   with `--in` for offline runs, and `--incidents` swaps in a referee's
+//!   own incident table (the `simulator::incident_table` JSON). Needs the
+//!   `backend` feature:
+//!   `cargo run --features backend --bin worldgen -- batch ...`.
+//! - `fleet` — run several voyages (a `simulator::fleet::FleetParams`
+//!   JSON file) on one calendar, trading in shared markets, and print each
+//!   ship's profit beside what it makes sailing alone on the same seed —
+//!   the difference is what the ships take from one another. `--seed N`
+//!   gives ship `i` seed `N + i`. Same world sources and feature as
+//!   `batch`.
+//! - `log` — run one voyage (a `SimulationParams` JSON file) and write its
+//!   captain's log from phrase tables, with no model behind it: the
+//!   template log the server falls back on when Vertex AI is unavailable.
+//!   Same world sources and feature as `batch`.
+//!
+//! `system` and `planet` take the same identity arguments as the
+//! `/api/system` and `/api/world` endpoints and derive their seeds the same
+//! way, so a file written here is byte-identical to what the server returns
+//! for the same query. The output format comes from `--format`, else from
+//! the `--out` extension, else defaults to PNG. Without `--out` (or with
+//! `--out -`) the output goes to stdout.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::ExitCode;
+
+use worldgen::sector::{Density, Sector};
+use worldgen::seed::{planet_seed, system_seed};
+use worldgen::systems::world::World;
+use worldgen::trade::available_goods::AvailableGoodsTable;
+use worldgen::trade::table::TradeTable;
+use worldgen::{
+    DEFAULT_SYSTEM_SCALE, INITIAL_NAME, WorldgenError, build_constraints, generate_planet_json,
+    generate_planet_png_scaled, generate_system_json, generate_system_png_scaled,
+    generate_system_svg, parse_stellar,
+};
+
+const USAGE: &str = "\
+usage: worldgen <command> [options]
+
+commands:
+  system   --sector NAME --hex CCRR --uwp UWP [--name NAME] [--stellar STARS]
+           [--pbg PBG] [--worlds N] [--scale S] [--format png|svg|json] [--out FILE]
+  planet   --sector NAME --hex CCRR --uwp UWP [--name NAME] [--orbit N]
+           [--scale S] [--format png|svg|json] [--out FILE]
+  trade    --uwp UWP [--name NAME] [--broker N] [--system-broker N]
+           [--illegal] [--format text|json] [--out FILE]
+  sector   --sector NAME [--subsector A-P] [--density sparse|scattered|standard|dense]
+           [--format text|json|t5|sec] [--out FILE]
+  sector   --in FILE [--sector NAME] [--format text|json|t5|sec] [--out FILE]
+  batch    --params FILE [--runs N] [--seed N] [--in FILE] [--incidents FILE]
+           [--format text|json] [--out FILE]   (backend feature only)
+  fleet    --params FILE [--seed N] [--in FILE]
+           [--format text|json] [--out FILE]   (backend feature only)
+  log      --params FILE [--seed N] [--in FILE] [--out FILE]
+                                               (backend feature only)
+  help     show this message
+";
+
+/// Options that take no value.
+const FLAGS: &[&str] = &["illegal"];
+
+/// Everything that can stop a command from producing output.
+#[derive(Debug, thiserror::Error)]
+enum CliError {
+    /// Bad or missing command-line arguments; reported with the usage text.
+    #[error("{0}")]
+    Usage(String),
+
+    /// The library rejected the inputs or failed to render.
+    #[error(transparent)]
+    Worldgen(#[from] WorldgenError),
+
+    /// Writing the output failed.
+    #[error("write failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A simulated voyage failed.
+    #[cfg(feature = "backend")]
+    #[error(transparent)]
+    Simulation(#[from] worldgen::simulator::executor::ExecutorError),
+}
+
+/// Output encodings a command can be asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Png,
+    Svg,
+    Json,
+    Text,
+    /// T5 Second Survey tab-delimited sector listing.
+    T5,
+    /// Legacy fixed-column `.sec` sector listing.
+    Sec,
+}
+
+impl Format {
+    fn parse(s: &str) -> Option<Format> {
+        match s.to_ascii_lowercase().as_str() {
+            "png" => Some(Format::Png),
+            "svg" => Some(Format::Svg),
+            "json" => Some(Format::Json),
+            "text" | "txt" => Some(Format::Text),
+            "t5" | "tab" | "tsv" => Some(Format::T5),
+            "sec" => Some(Format::Sec),
+            _ => None,
+        }
+    }
+}
+
+/// Parsed `--key value` / `--key=value` options plus bare flags.
+#[derive(Debug, Default)]
+struct Options {
+    values: HashMap<String, String>,
+    flags: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Options, CliError> {
+        let mut opts = Options::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let Some(key) = arg.strip_prefix("--") else {
+                return Err(CliError::Usage(format!("unexpected argument: {arg}")));
+            };
+            if let Some((k, v)) = key.split_once('=') {
+                opts.values.insert(k.to_string(), v.to_string());
+            } else if FLAGS.contains(&key) {
+                opts.flags.push(key.to_string());
+            } else {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| CliError::Usage(format!("--{key} needs a value")))?;
+                opts.values.insert(key.to_string(), value.clone());
+            }
+        }
+        Ok(opts)
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(key)
+            .map(|s| s.as_str())
+            .filter(|s| !s.is_empty())
+    }
+
+    fn require(&self, key: &str) -> Result<&str, CliError> {
+        self.get(key)
+            .ok_or_else(|| CliError::Usage(format!("missing required option --{key}")))
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        self.flags.iter().any(|f| f == key)
+    }
+
+    /// Parse an optional numeric option, erroring (rather than silently
+    /// defaulting) when it's present but malformed.
+    fn number<T: std::str::FromStr>(&self, key: &str, default: T) -> Result<T, CliError> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(s) => s
+                .trim()
+                .parse()
+                .map_err(|_| CliError::Usage(format!("--{key} must be a number, got {s:?}"))),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.get("name").unwrap_or(INITIAL_NAME)
+    }
+
+    /// `--out`, with `-` meaning stdout like a missing `--out`.
+    fn out(&self) -> Option<&str> {
+        self.get("out").filter(|p| *p != "-")
+    }
+
+    /// The requested output format: `--format`, else the `--out` file
+    /// extension, else `default`.
+    fn format(&self, default: Format) -> Result<Format, CliError> {
+        if let Some(f) = self.get("format") {
+            return Format::parse(f)
+                .ok_or_else(|| CliError::Usage(format!("unknown format {f:?}")));
+        }
+        Ok(self
+            .out()
+            .and_then(|p| std::path::Path::new(p).extension())
+            .and_then(|e| e.to_str())
+            .and_then(Format::parse)
+            .unwrap_or(default))
+    }
+
+    /// `--sector` + `--hex` → the system seed `/api/system` would use.
+    fn system_seed(&self) -> Result<u64, CliError> {
+        let sector = self.require("sector")?;
+        let hex = self.require("hex")?;
+        let (x, y) = parse_hex_quad(hex).ok_or_else(|| {
+            CliError::Usage(format!(
+                "--hex must be a 4-digit string like \"1910\", got {hex:?}"
+            ))
+        })?;
+        Ok(system_seed(sector, x, y))
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(msg)) => {
+            eprintln!("worldgen: {msg}\n\n{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(e) => {
+            eprintln!("worldgen: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), CliError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(CliError::Usage("no command given".to_string()));
+    };
+    if matches!(command.as_str(), "help" | "--help" | "-h") {
+        print!("{USAGE}");
+        return Ok(());
+    }
+    let opts = Options::parse(rest)?;
+    let bytes = match command.as_str() {
+        "system" => system(&opts)?,
+        "planet" => planet(&opts)?,
+        "trade" => trade(&opts)?,
+        "sector" => sector(&opts)?,
+        #[cfg(feature = "backend")]
+        "batch" => batch(&opts)?,
+        #[cfg(feature = "backend")]
+        "fleet" => fleet(&opts)?,
+        #[cfg(feature = "backend")]
+        "log" => log(&opts)?,
+        other => return Err(CliError::Usage(format!("unknown command {other:?}"))),
+    };
+    write_output(opts.out(), &bytes)
+}
+
+/// `worldgen system`: same inputs and seed derivation as `/api/system`.
+fn system(opts: &Options) -> Result<Vec<u8>, CliError> {
+    let seed = opts.system_seed()?;
+    let uwp = opts.require("uwp")?;
+    let stars = parse_stellar(opts.get("stellar").unwrap_or(""));
+
+    // PBG's B and G digits are the belt and gas giant counts; `--worlds`
+    // is Traveller Map's total body count, from which the main world, the
+    // belts and the giants are backed out to leave the extra planets.
+    let pbg = opts.get("pbg").unwrap_or("");
+    let belts = digit_at(pbg, 1).unwrap_or(0) as usize;
+    let giants = digit_at(pbg, 2).unwrap_or(0) as usize;
+    let planets = match opts.get("worlds") {
+        Some(_) => {
+            (opts.number::<i32>("worlds", 0)? - 1 - belts as i32 - giants as i32).max(0) as usize
+        }
+        None => 0,
+    };
+
+    let constraints = build_constraints(opts.name(), uwp, &stars, giants, belts, planets)?;
+    Ok(match opts.format(Format::Png)? {
+        Format::Png => generate_system_png_scaled(
+            seed,
+            constraints,
+            opts.number("scale", DEFAULT_SYSTEM_SCALE)?,
+        )?,
+        Format::Svg => generate_system_svg(seed, constraints)?.into_bytes(),
+        Format::Json => generate_system_json(seed, constraints)?.into_bytes(),
+        Format::Text | Format::T5 | Format::Sec => {
+            return Err(CliError::Usage(
+                "system supports png, svg or json only".to_string(),
+            ));
+        }
+    })
+}
+
+/// `worldgen planet`: same inputs and seed chain as `/api/world`.
+fn planet(opts: &Options) -> Result<Vec<u8>, CliError> {
+    let uwp = opts.require("uwp")?;
+    let name = opts.name();
+    let seed = planet_seed(opts.system_seed()?, opts.number("orbit", 3)?, name);
+    Ok(match opts.format(Format::Png)? {
+        Format::Png => {
+            generate_planet_png_scaled(seed, uwp, Some(name), opts.number("scale", 1.0)?)?
+        }
+        Format::Svg => {
+            let map =
+                worldgen::worldmap::generate(uwp, seed, Some(name)).map_err(WorldgenError::from)?;
+            worldgen::worldmap::render_svg(&map).into_bytes()
+        }
+        Format::Json => generate_planet_json(seed, uwp, Some(name))?.into_bytes(),
+        Format::Text | Format::T5 | Format::Sec => {
+            return Err(CliError::Usage(
+                "planet supports png, svg or json only".to_string(),
+            ));
+        }
+    })
+}
+
+/// `worldgen trade`: the market a trader would find on the world, priced
+/// for buying with the given broker skills and sorted best deal first.
+/// Markets are rolled fresh on every run, just as in the trade computer.
+fn trade(opts: &Options) -> Result<Vec<u8>, CliError> {
+    let uwp = opts.require("uwp")?;
+    let name = opts.name();
+    let mut world = World::from_uwp(name, uwp, false, true)
+        .map_err(|e| CliError::Usage(format!("invalid --uwp {uwp:?}: {e}")))?;
+    world.gen_trade_classes();
+    let classes = world.get_trade_classes();
+
+    let mut market = AvailableGoodsTable::for_world(
+        TradeTable::global(),
+        &classes,
+        world.get_population(),
+        opts.flag("illegal"),
+    )
+    .map_err(|e| CliError::Usage(format!("could not generate market: {e}")))?;
+    market.price_goods_to_buy(
+        &classes,
+        opts.number("broker", 0)?,
+        opts.number("system-broker", 0)?,
+    );
+    market.sort_by_discount();
+
+    Ok(match opts.format(Format::Text)? {
+        Format::Text => {
+            format!("{name} {uwp} {}\n{market}", world.trade_classes_string()).into_bytes()
+        }
+        Format::Json => {
+            serde_json::to_vec(&market).map_err(|e| WorldgenError::Encode(e.to_string()))?
+        }
+        Format::Png | Format::Svg | Format::T5 | Format::Sec => {
+            return Err(CliError::Usage(
+                "trade supports text or json only".to_string(),
+            ));
+        }
+    })
+}
+
+/// `worldgen sector`: the occupied hexes of a sector or subsector map,
+/// either rolled or read from a T5 / `.sec` file with `--in`.
+fn sector(opts: &Options) -> Result<Vec<u8>, CliError> {
+    let sector = match opts.get("in") {
+        Some(path) => read_sector_file(opts, path)?,
+        None => generate_sector(opts)?,
+    };
+
+    Ok(match opts.format(Format::Text)? {
+        Format::Text => {
+            let mut text = String::new();
+            for hex in &sector.hexes {
+                let world = hex.world()?;
+                let bases: String = hex
+                    .bases
+                    .iter()
+                    .map(|b| b.to_string().chars().next().unwrap_or(' '))
+                    .collect();
+                text.push_str(&format!(
+                    "{} {:<20} {} {:<2} {:<6} {} {} {}\n",
+                    hex.hex(),
+                    hex.name,
+                    hex.uwp,
+                    bases,
+                    hex.zone,
+                    hex.pbg,
+                    hex.stellar,
+                    world.trade_classes_string(),
+                ));
+            }
+            text.into_bytes()
+        }
+        Format::Json => {
+            serde_json::to_vec(&sector).map_err(|e| WorldgenError::Encode(e.to_string()))?
+        }
+        Format::T5 => sector.to_t5_tab().into_bytes(),
+        Format::Sec => sector.to_sec().into_bytes(),
+        Format::Png | Format::Svg => {
+            return Err(CliError::Usage(
+                "sector supports text, json, t5 or sec only".to_string(),
+            ));
+        }
+    })
+}
+
+/// Roll the sector (or `--subsector`) named by `--sector`.
+fn generate_sector(opts: &Options) -> Result<Sector, CliError> {
+    let name = opts.require("sector")?;
+    let density = Density::from(opts.get("density").unwrap_or("standard"));
+    Ok(match opts.get("subsector") {
+        None => Sector::generate(name, density),
+        Some(s) => s
+            .chars()
+            .next()
+            .filter(|_| s.len() == 1)
+            .and_then(|c| Sector::generate_subsector(name, c, density))
+            .ok_or_else(|| CliError::Usage(format!("--subsector must be A-P, got {s:?}")))?,
+    })
+}
+
+/// Load a sector listing: `.sec` files by extension, anything else as T5
+/// tab-delimited. The sector name defaults to the file stem.
+fn read_sector_file(opts: &Options, path: &str) -> Result<Sector, CliError> {
+    if opts.get("subsector").is_some() {
+        return Err(CliError::Usage(
+            "--subsector only applies to generated sectors".to_string(),
+        ));
+    }
+    let path = std::path::Path::new(path);
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| CliError::Usage(format!("cannot read {}: {e}", path.display())))?;
+    let name = opts.get("sector").map(str::to_string).unwrap_or_else(|| {
+        path.file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or(INITIAL_NAME)
+            .to_string()
+    });
+    let is_sec = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("sec"));
+    let parsed = if is_sec {
+        Sector::from_sec(&name, &text)
+    } else {
+        Sector::from_t5_tab(&name, &text)
+    };
+    parsed.map_err(|e| CliError::Usage(format!("{}: {e}", path.display())))
+}
+
+/// `worldgen batch`: a Monte Carlo batch of one voyage, summarized.
+#[cfg(feature = "backend")]
+fn batch(opts: &Options) -> Result<Vec<u8>, CliError> {
+    use worldgen::simulator::batch::{BatchParams, MAX_BATCH_RUNS, run_batch};
+    use worldgen::simulator::incident_table::IncidentTable;
+    use worldgen::simulator::types::SimulationParams;
+    use worldgen::simulator::world_fetch::WorldCache;
+    use worldgen::simulator::world_source::SectorFiles;
+
+    let path = opts.require("params")?;
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| CliError::Usage(format!("cannot read {path}: {e}")))?;
+    let mut params: SimulationParams = serde_json::from_str(&json)
+        .map_err(|e| CliError::Usage(format!("{path}: not simulation params: {e}")))?;
+    if opts.get("seed").is_some() {
+        params.seed = Some(opts.number("seed", 0)?);
+    }
+    if let Some(file) = opts.get("incidents") {
+        let json = std::fs::read_to_string(file)
+            .map_err(|e| CliError::Usage(format!("cannot read {file}: {e}")))?;
+        let table =
+            IncidentTable::from_json(&json).map_err(|e| CliError::Usage(format!("{file}: {e}")))?;
+        params.incidents = Some(table);
+    }
+    let runs = opts.number("runs", 100u32)?;
+    if !(1..=MAX_BATCH_RUNS).contains(&runs) {
+        return Err(CliError::Usage(format!(
+            "--runs must be between 1 and {MAX_BATCH_RUNS}, got {runs}"
+        )));
+    }
+    let format = opts.format(Format::Text)?;
+    if !matches!(format, Format::Text | Format::Json) {
+        return Err(CliError::Usage(
+            "batch supports text or json only".to_string(),
+        ));
+    }
+
+    let home = params.home_world.clone();
+    let target = params.target_completion_date;
+    let request = BatchParams { params, runs };
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let summary = runtime.block_on(async {
+        let report = |done: u32| eprint!("\rworldgen: voyage {done}/{runs}");
+        let summary = match opts.get("in") {
+            // Offline: the file stands in for the home sector.
+            Some(file) => {
+                let mut source = SectorFiles::new();
+                source
+                    .load(&home.sector, file)
+                    .map_err(|e| CliError::Usage(format!("{file}: {e}")))?;
+                run_batch(request, &mut source, report).await?
+            }
+            None => {
+                let mut cache = WorldCache::new();
+                if let Err(e) = cache.load_sector_origins().await {
+                    eprintln!("worldgen: using built-in sector origins ({e})");
+                }
+                run_batch(request, &mut cache, report).await?
+            }
+        };
+        eprintln!();
+        Ok::<_, CliError>(summary)
+    })?;
+
+    Ok(match format {
+        Format::Json => {
+            serde_json::to_vec(&summary).map_err(|e| WorldgenError::Encode(e.to_string()))?
+        }
+        _ => {
+            let p = &summary.owner_profit;
+            let i = &summary.incidents;
+            let pct = |x: f64| format!("{:5.1}%", x * 100.0);
+            let rows = [
+                (
+                    "owner loses money".to_string(),
+                    pct(summary.loss_probability),
+                ),
+                (
+                    "budget goes negative".to_string(),
+                    pct(summary.went_negative_probability),
+                ),
+                ("marooned".to_string(), pct(summary.marooned_probability)),
+                (
+                    "returns home".to_string(),
+                    pct(summary.returned_home_probability),
+                ),
+                (
+                    format!("home by {}", target.format()),
+                    pct(summary.on_time_probability),
+                ),
+                (
+                    "jumps per voyage".to_string(),
+                    format!("{:5.1}", summary.mean_jumps),
+                ),
+            ];
+            let mut text = format!(
+                "{} voyages from {} ({} {:02}{:02}), base seed {}\n\n",
+                summary.runs, home.name, home.sector, home.hex_x, home.hex_y, summary.base_seed
+            );
+            text.push_str(&format!(
+                "owner profit  mean {:.0}  median {}\n\
+                 \x20             min {}  p5 {}  p25 {}  p75 {}  p95 {}  max {}\n\n",
+                p.mean, p.median, p.min, p.p5, p.p25, p.p75, p.p95, p.max
+            ));
+            for (label, value) in rows {
+                text.push_str(&format!("{label:<22} {value}\n"));
+            }
+            text.push_str(&format!(
+                "\nincidents per voyage   piracy {:.2}  scam {:.2}  crew loss {:.2}  \
+                 accident {:.2}  government {:.2}  other {:.2}  avoided {:.2}\n",
+                i.piracy, i.trade_scam, i.crew_loss, i.accident, i.government, i.other, i.avoided
+            ));
+            text.into_bytes()
+        }
+    })
+}
+
+/// `worldgen fleet`: several voyages on one calendar, sharing markets
+/// and traffic, each compared with the same ship sailing alone.
+#[cfg(feature = "backend")]
+fn fleet(opts: &Options) -> Result<Vec<u8>, CliError> {
+    use worldgen::simulator::executor::run_simulation;
+    use worldgen::simulator::fleet::{FleetParams, FleetResult, run_fleet};
+    use worldgen::simulator::types::{Action, SimulationResult};
+    use worldgen::simulator::world_fetch::WorldCache;
+    use worldgen::simulator::world_source::{SectorFiles, WorldSource};
+
+    /// The fleet's run, then each ship's on its own against the same
+    /// starting markets.
+    async fn sail(
+        request: FleetParams,
+        source: &mut impl WorldSource,
+    ) -> Result<(FleetResult, Vec<SimulationResult>), CliError> {
+        let mut ports = 0;
+        let fleet = run_fleet(request.clone(), source, |_, step| {
+            if matches!(step.action, Action::Arrive { .. }) {
+                ports += 1;
+                eprint!("\rworldgen: fleet port call {ports}");
+            }
+        })
+        .await?;
+        eprintln!();
+        let mut alone = Vec::new();
+        for mut params in request.ships {
+            params.markets = request.markets.clone();
+            alone.push(run_simulation(params, source, |_| {}).await?);
+        }
+        Ok((fleet, alone))
+    }
+
+    let path = opts.require("params")?;
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| CliError::Usage(format!("cannot read {path}: {e}")))?;
+    let mut request: FleetParams = serde_json::from_str(&json)
+        .map_err(|e| CliError::Usage(format!("{path}: not fleet params: {e}")))?;
+    let Some(home) = request.ships.first().map(|p| p.home_world.clone()) else {
+        return Err(CliError::Usage(format!("{path}: the fleet has no ships")));
+    };
+    // Every ship needs a fixed seed so its solo run replays its dice.
+    let base_seed = match opts.get("seed") {
+        Some(_) => Some(opts.number("seed", 0u64)?),
+        None => None,
+    };
+    for (i, ship) in request.ships.iter_mut().enumerate() {
+        ship.seed = base_seed
+            .map(|seed| seed.wrapping_add(i as u64))
+            .or(ship.seed)
+            .or_else(|| Some(rand::random()));
+    }
+    let format = opts.format(Format::Text)?;
+    if !matches!(format, Format::Text | Format::Json) {
+        return Err(CliError::Usage(
+            "fleet supports text or json only".to_string(),
+        ));
+    }
+
+    let names: Vec<String> = request.ships.iter().map(|p| p.ship.name.clone()).collect();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let (fleet, alone) = runtime.block_on(async {
+        match opts.get("in") {
+            // Offline: the file stands in for the first ship's home sector.
+            Some(file) => {
+                let mut source = SectorFiles::new();
+                source
+                    .load(&home.sector, file)
+                    .map_err(|e| CliError::Usage(format!("{file}: {e}")))?;
+                sail(request, &mut source).await
+            }
+            None => {
+                let mut cache = WorldCache::new();
+                if let Err(e) = cache.load_sector_origins().await {
+                    eprintln!("worldgen: using built-in sector origins ({e})");
+                }
+                sail(request, &mut cache).await
+            }
+        }
+    })?;
+
+    Ok(match format {
+        Format::Json => serde_json::to_vec(&serde_json::json!({
+            "ships": fleet.ships,
+            "fleet": fleet.fleet,
+            "alone": alone,
+        }))
+        .map_err(|e| WorldgenError::Encode(e.to_string()))?,
+        _ => {
+            let mut text = format!(
+                "{} ships from {} ({} {:02}{:02})\n\n",
+                names.len(),
+                home.name,
+                home.sector,
+                home.hex_x,
+                home.hex_y
+            );
+            text.push_str(&format!(
+                "{:<20} {:>20} {:>12} {:>12} {:>12} {:>6}  {}\n",
+                "ship", "seed", "in fleet", "alone", "difference", "jumps", "ended"
+            ));
+            let ended = |r: &SimulationResult| {
+                if r.marooned {
+                    "marooned".to_string()
+                } else if r.returned_home {
+                    format!("home {}", r.end_date.format())
+                } else {
+                    r.end_date.format()
+                }
+            };
+            for ((name, ship), solo) in names.iter().zip(&fleet.ships).zip(&alone) {
+                text.push_str(&format!(
+                    "{:<20} {:>20} {:>12} {:>12} {:>12} {:>6}  {}\n",
+                    name,
+                    ship.seed,
+                    ship.owner_profit,
+                    solo.owner_profit,
+                    ship.owner_profit - solo.owner_profit,
+                    ship.jumps,
+                    ended(ship)
+                ));
+            }
+            let alone_profit: i64 = alone.iter().map(|r| r.owner_profit).sum();
+            text.push_str(&format!(
+                "{:<20} {:>20} {:>12} {:>12} {:>12} {:>6}  {}\n",
+                "fleet",
+                "",
+                fleet.fleet.owner_profit,
+                alone_profit,
+                fleet.fleet.owner_profit - alone_profit,
+                fleet.fleet.jumps,
+                ended(&fleet.fleet)
+            ));
+            text.into_bytes()
+        }
+    })
+}
+
+/// `worldgen log`: one voyage, told by the template captain's log.
+#[cfg(feature = "backend")]
+fn log(opts: &Options) -> Result<Vec<u8>, CliError> {
+    use worldgen::simulator::captains_log_template::write_log;
+    use worldgen::simulator::executor::run_simulation;
+    use worldgen::simulator::types::SimulationParams;
+    use worldgen::simulator::world_fetch::WorldCache;
+    use worldgen::simulator::world_source::SectorFiles;
+
+    let path = opts.require("params")?;
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| CliError::Usage(format!("cannot read {path}: {e}")))?;
+    let mut params: SimulationParams = serde_json::from_str(&json)
+        .map_err(|e| CliError::Usage(format!("{path}: not simulation params: {e}")))?;
+    if opts.get("seed").is_some() {
+        params.seed = Some(opts.number("seed", 0)?);
+    }
+
+    let home = params.home_world.clone();
+    let mut steps = Vec::new();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    let result = runtime.block_on(async {
+        let on_step = |step| steps.push(step);
+        let result = match opts.get("in") {
+            // Offline: the file stands in for the home sector.
+            Some(file) => {
+                let mut source = SectorFiles::new();
+                source
+                    .load(&home.sector, file)
+                    .map_err(|e| CliError::Usage(format!("{file}: {e}")))?;
+                run_simulation(params.clone(), &mut source, on_step).await?
+            }
+            None => {
+                let mut cache = WorldCache::new();
+                if let Err(e) = cache.load_sector_origins().await {
+                    eprintln!("worldgen: using built-in sector origins ({e})");
+                }
+                run_simulation(params.clone(), &mut cache, on_step).await?
+            }
+        };
+        Ok::<_, CliError>(result)
+    })?;
+
+    Ok(write_log(&params.ship.name, &params, &steps, &result).into_bytes())
+}
+
+fn write_output(out: Option<&str>, bytes: &[u8]) -> Result<(), CliError> {
+    match out {
+        Some(path) => std::fs::write(path, bytes)?,
+        None => {
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(bytes)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// `"1910"` → `(19, 10)`. Same rules as the HTTP server's `hex` param.
+fn parse_hex_quad(s: &str) -> Option<(u8, u8)> {
+    if s.len() != 4 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((s[0..2].parse().ok()?, s[2..4].parse().ok()?))
+}
+
+fn digit_at(s: &str, idx: usize) -> Option<u32> {
+    s.chars().nth(idx).and_then(|c| c.to_digit(10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opts(args: &[&str]) -> Options {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        Options::parse(&args).unwrap()
+    }
+
+    #[test]
+    fn parses_spaced_and_equals_options_and_flags() {
+        let o = opts(&["--sector", "Spinward Marches", "--hex=1910", "--illegal"]);
+        assert_eq!(o.get("sector"), Some("Spinward Marches"));
+        assert_eq!(o.get("hex"), Some("1910"));
+        assert!(o.flag("illegal"));
+        assert!(!o.flag("other"));
+    }
+
+    #[test]
+    fn missing_value_and_stray_argument_are_usage_errors() {
+        let args = vec!["--uwp".to_string()];
+        assert!(matches!(Options::parse(&args), Err(CliError::Usage(_))));
+        let args = vec!["regina.png".to_string()];
+        assert!(matches!(Options::parse(&args), Err(CliError::Usage(_))));
+    }
+
+    #[test]
+    fn format_comes_from_flag_then_extension_then_default() {
+        assert_eq!(
+            opts(&["--out", "a.svg"]).format(Format::Png).unwrap(),
+            Format::Svg
+        );
+        assert_eq!(
+            opts(&["--out", "a.svg", "--format", "json"])
+                .format(Format::Png)
+                .unwrap(),
+            Format::Json
+        );
+        assert_eq!(
+            opts(&["--out", "a.bin"]).format(Format::Png).unwrap(),
+            Format::Png
+        );
+        assert_eq!(opts(&[]).format(Format::Text).unwrap(), Format::Text);
+        assert!(opts(&["--format", "gif"]).format(Format::Png).is_err());
+    }
+
+    #[test]
+    fn dash_out_means_stdout() {
+        assert_eq!(opts(&["--out", "-"]).out(), None);
+        assert_eq!(opts(&["--out", "x.png"]).out(), Some("x.png"));
+    }
+
+    #[test]
+    fn system_seed_matches_the_http_endpoint() {
+        let o = opts(&["--sector", "Spinward Marches", "--hex", "1910"]);
+        assert_eq!(
+            o.system_seed().unwrap(),
+            system_seed("Spinward Marches", 19, 10)
+        );
+        assert!(
+            opts(&["--sector", "X", "--hex", "19A0"])
+                .system_seed()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn malformed_number_is_an_error_not_a_default() {
+        assert!(opts(&["--scale", "big"]).number("scale", 1.0f32).is_err());
+        assert_eq!(opts(&[]).number("scale", 1.0f32).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn system_json_matches_library_call() {
+        let o = opts(&[
+            "--sector",
+            "Spinward Marches",
+            "--hex",
+            "1910",
+            "--uwp",
+            "A788899-A",
+            "--name",
+            "Regina",
+            "--stellar",
+            "G2 V",
+            "--pbg",
+            "703",
+            "--format",
+            "json",
+        ]);
+        let cs = build_constraints("Regina", "A788899-A", &parse_stellar("G2 V"), 3, 0, 0).unwrap();
+        let expected = generate_system_json(system_seed("Spinward Marches", 19, 10), cs).unwrap();
+        assert_eq!(system(&o).unwrap(), expected.into_bytes());
+    }
+
+    #[test]
+    fn trade_text_lists_goods() {
+        let o = opts(&["--uwp", "A788899-A", "--name", "Regina"]);
+        let text = String::from_utf8(trade(&o).unwrap()).unwrap();
+        assert!(text.starts_with("Regina A788899-A"));
+        assert!(text.lines().count() > 1);
+    }
+
+    #[test]
+    fn sector_file_round_trips_through_in() {
+        let generated = opts(&[
+            "--sector",
+            "Cli Test",
+            "--subsector",
+            "A",
+            "--format",
+            "sec",
+        ]);
+        let sec = sector(&generated).unwrap();
+        let path = std::env::temp_dir().join(format!("worldgen-cli-{}.sec", std::process::id()));
+        std::fs::write(&path, &sec).unwrap();
+
+        let loaded = opts(&[
+            "--in",
+            path.to_str().unwrap(),
+            "--sector",
+            "Cli Test",
+            "--format",
+            "sec",
+        ]);
+        let reread = sector(&loaded);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reread.unwrap(), sec);
+
+        let with_subsector = opts(&["--in", "x.sec", "--subsector", "A"]);
+        assert!(matches!(sector(&with_subsector), Err(CliError::Usage(_))));
+    }
+}
@@ -4,9 +4,14 @@
 //! 1. Client opens `/ws/captains-log`.
 //! 2. Client sends one [`ClientMessage::RunSummary`] with a fully-built
 //!    prompt string (assembled by
-//!    [`crate::components::captains_log_prompt::build_prompt`]).
+//!    [`crate::simulator::captains_log_prompt::build_prompt`]).
 //! 3. Server streams zero or more [`ServerMessage::Delta`] frames as
-//!    Vertex AI generates text.
+//!    Vertex AI generates text. When Vertex is unavailable or the global
+//!    rate limit is hit and the client sent its [`LogVoyage`], the server
+//!    instead writes the log from templates
+//!    ([`crate::simulator::captains_log_template::write_log`]) and sends
+//!    it as a single `Delta`, followed by a `Done` whose `finish_reason`
+//!    is [`TEMPLATE_FINISH_REASON`].
 //! 4. Server sends exactly one terminal frame — either
 //!    [`ServerMessage::Done`] on success or [`ServerMessage::Error`] on
 //!    any failure — and closes the connection.
@@ -16,6 +21,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::simulator::types::{SimulationParams, SimulationResult, SimulationStep};
+
 /// Messages the client sends to the captain's-log server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -25,7 +32,48 @@ pub enum ClientMessage {
     /// The prompt is fully-assembled on the frontend; the backend
     /// treats it as opaque text and forwards it to Vertex AI as the
     /// user message of `streamGenerateContent`.
-    RunSummary { prompt: String },
+    RunSummary {
+        prompt: String,
+        /// The voyage the prompt was built from, for the template
+        /// fallback. Without it the server can only report Vertex
+        /// failures as errors.
+        #[serde(default)]
+        voyage: Option<LogVoyage>,
+    },
+}
+
+/// A finished voyage, as the template fallback needs it: the same inputs
+/// [`build_prompt`](crate::simulator::captains_log_prompt::build_prompt)
+/// takes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogVoyage {
+    pub ship_name: String,
+    pub params: SimulationParams,
+    pub steps: Vec<SimulationStep>,
+    pub result: SimulationResult,
+}
+
+impl LogVoyage {
+    /// Check the voyage is small enough to write a log for: at most
+    /// [`MAX_LOG_STEPS`] steps and a ship name of at most
+    /// [`MAX_SHIP_NAME_BYTES`]. The error says which limit it broke.
+    pub fn check_size(&self) -> Result<(), String> {
+        if self.steps.len() > MAX_LOG_STEPS {
+            Err(format!(
+                "voyage has too many steps ({} > {})",
+                self.steps.len(),
+                MAX_LOG_STEPS
+            ))
+        } else if self.ship_name.len() > MAX_SHIP_NAME_BYTES {
+            Err(format!(
+                "ship name exceeds max size ({} > {} bytes)",
+                self.ship_name.len(),
+                MAX_SHIP_NAME_BYTES
+            ))
+        } else {
+            Ok(())
+        }
+    }
 }
 
 /// Messages the server sends back over the WebSocket.
@@ -41,7 +89,9 @@ pub enum ServerMessage {
     /// Generation finished. Carries usage counters and the Vertex
     /// `finishReason` so the UI can flag truncated / safety-blocked
     /// responses. `STOP` is the clean case; `MAX_TOKENS`, `SAFETY`,
-    /// `RECITATION` etc. mean the output was cut off.
+    /// `RECITATION` etc. mean the output was cut off;
+    /// [`TEMPLATE_FINISH_REASON`] means the log was written from
+    /// templates without Vertex.
     Done {
         /// Tokens in the assembled prompt (including the instruction
         /// header), as reported by Vertex.
@@ -56,7 +106,8 @@ pub enum ServerMessage {
     /// Terminal error. Closes the connection.
     Error {
         /// Stable machine-readable code. One of: `"rate_limit_global"`,
-        /// `"prompt_too_large"`, `"vertex_error"`, `"internal_error"`.
+        /// `"prompt_too_large"`, `"voyage_too_large"`, `"vertex_error"`,
+        /// `"internal_error"`.
         code: String,
         /// Short human-readable description, suitable for inline UI
         /// display. For `vertex_error` this includes the first ~200
@@ -72,8 +123,19 @@ pub enum ServerMessage {
     },
 }
 
+/// The `finish_reason` of a log written by the template fallback
+/// rather than Vertex. Its token counts are zero.
+pub const TEMPLATE_FINISH_REASON: &str = "TEMPLATE";
+
 /// Maximum size of the assembled prompt the server will accept, in
 /// bytes. Calibrated to a worst-case 5-year voyage with full per-port
 /// detail (~64 KB) plus headroom; anything larger is almost certainly
 /// malicious or a bug.
 pub const MAX_PROMPT_BYTES: usize = 256 * 1024;
+
+/// Most steps a [`LogVoyage`] may carry, so the server's template
+/// rendering stays bounded.
+pub const MAX_LOG_STEPS: usize = 20_000;
+
+/// Longest ship name, in bytes, a [`LogVoyage`] may carry.
+pub const MAX_SHIP_NAME_BYTES: usize = 256;
@@ -12,11 +12,14 @@ use wasm_bindgen::prelude::*;
 use web_sys::{CloseEvent, ErrorEvent, MessageEvent, WebSocket};
 
 use super::{ServerCommand, ServerMessage, TradeState};
-use crate::systems::world::World;
+use crate::simulator::itinerary::{Itinerary, ItineraryPlan};
+use crate::simulator::types::Date;
+use crate::systems::world::{Facility, World};
 use crate::trade::Ship;
 use crate::trade::ZoneClassification;
 use crate::trade::available_goods::AvailableGoodsTable;
 use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::ledger::Ledger;
 use crate::trade::ship_manifest::ShipManifest;
 
 /// Write-side handles for every reactive field the WebSocket client
@@ -36,8 +39,8 @@ use crate::trade::ship_manifest::ShipManifest;
 ///
 /// - World fields (`origin_*`, `dest_*`) split per attribute the client
 ///   may want to bind individually. The client sends `name`, `uwp`,
-///   `coords`, `zone` to the server; the server fills the `*_world`
-///   fields and sends the populated [`World`] back.
+///   `coords`, `zone`, `bases` to the server; the server fills the
+///   `*_world` fields and sends the populated [`World`] back.
 /// - `ship` is the unified [`Ship`] config — capacity, crew, hardware,
 ///   ship-broker / steward / leadership skills, periodic costs.
 ///   Carried as a single signal because every field is server-mirrored
@@ -51,11 +54,13 @@ pub struct TradeSignals {
     pub origin_uwp: WriteSignal<String>,
     pub origin_coords: WriteSignal<Option<(i32, i32)>>,
     pub origin_zone: WriteSignal<ZoneClassification>,
+    pub origin_bases: WriteSignal<Vec<Facility>>,
     pub origin_world: WriteSignal<Option<World>>,
     pub dest_world_name: WriteSignal<String>,
     pub dest_uwp: WriteSignal<String>,
     pub dest_coords: WriteSignal<Option<(i32, i32)>>,
     pub dest_zone: WriteSignal<ZoneClassification>,
+    pub dest_bases: WriteSignal<Vec<Facility>>,
     pub dest_world: WriteSignal<Option<World>>,
     pub available_goods: WriteSignal<AvailableGoodsTable>,
     pub available_passengers: WriteSignal<Option<AvailablePassengers>>,
@@ -68,6 +73,15 @@ pub struct TradeSignals {
     /// skill lives on `ship.broker_skill`, not here.
     pub system_broker_skill: WriteSignal<i16>,
     pub illegal_goods: WriteSignal<bool>,
+    /// The ship's trading date. Server-authoritative; the client only
+    /// displays it.
+    pub date: WriteSignal<Date>,
+    pub itinerary: WriteSignal<Itinerary>,
+    /// The server's plan for `itinerary`; the client only displays it.
+    pub itinerary_plan: WriteSignal<Option<ItineraryPlan>>,
+    /// The ship's ledger. Server-authoritative; the client only displays
+    /// and exports it.
+    pub ledger: WriteSignal<Ledger>,
 }
 
 /// WebSocket client for trade state synchronization
@@ -278,6 +292,27 @@ impl Client {
         }
     }
 
+    /// Tell the server the planned trades are being executed.
+    ///
+    /// Send this before settling the manifest and moving to the
+    /// destination, so the server records the trades against the
+    /// origin's market while its cached state still describes them.
+    pub fn send_execute_trades(&self) {
+        let msg = ServerMessage::Command(ServerCommand::ExecuteTrades);
+        match serde_json::to_string(&msg) {
+            Ok(json) => {
+                if let Err(e) = self.ws.send_with_str(&json) {
+                    error!("Failed to send execute_trades command: {:?}", e);
+                } else {
+                    debug!("Sent execute_trades command to server");
+                }
+            }
+            Err(e) => {
+                error!("Failed to serialize execute_trades command: {}", e);
+            }
+        }
+    }
+
     /// Check if the WebSocket connection is open
     pub fn is_connected(&self) -> bool {
         self.ws.ready_state() == WebSocket::OPEN
@@ -324,16 +359,19 @@ fn states_equal(a: &TradeState, b: &TradeState) -> bool {
         && a.origin_uwp == b.origin_uwp
         && a.origin_coords == b.origin_coords
         && a.origin_zone == b.origin_zone
+        && a.origin_bases == b.origin_bases
         && a.dest_world_name == b.dest_world_name
         && a.dest_uwp == b.dest_uwp
         && a.dest_coords == b.dest_coords
         && a.dest_zone == b.dest_zone
+        && a.dest_bases == b.dest_bases
         && a.available_goods == b.available_goods
         && a.available_passengers == b.available_passengers
         && a.ship_manifest == b.ship_manifest
         && a.ship == b.ship
         && a.system_broker_skill == b.system_broker_skill
         && a.illegal_goods == b.illegal_goods
+        && a.itinerary == b.itinerary
 }
 
 /// Handle incoming WebSocket messages
@@ -371,11 +409,13 @@ fn handle_message(
     signals.origin_uwp.set(state.origin_uwp);
     signals.origin_coords.set(state.origin_coords);
     signals.origin_zone.set(state.origin_zone);
+    signals.origin_bases.set(state.origin_bases);
     signals.origin_world.set(state.origin_world);
     signals.dest_world_name.set(state.dest_world_name);
     signals.dest_uwp.set(state.dest_uwp);
     signals.dest_coords.set(state.dest_coords);
     signals.dest_zone.set(state.dest_zone);
+    signals.dest_bases.set(state.dest_bases);
     signals.dest_world.set(state.dest_world);
     signals.available_goods.set(state.available_goods);
     signals.available_passengers.set(state.available_passengers);
@@ -383,6 +423,10 @@ fn handle_message(
     signals.ship.set(state.ship.clone());
     signals.system_broker_skill.set(state.system_broker_skill);
     signals.illegal_goods.set(state.illegal_goods);
+    signals.date.set(state.date);
+    signals.itinerary.set(state.itinerary);
+    signals.itinerary_plan.set(state.itinerary_plan);
+    signals.ledger.set(state.ledger);
 
     info!("Trade state updated from server");
 }
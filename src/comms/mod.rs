@@ -48,4 +48,11 @@ pub enum ServerCommand {
     /// broadcasts the updated state to all clients viewing that ship.
     #[serde(rename = "apply_monthly_expenses")]
     ApplyMonthlyExpenses,
+    /// Record the trades planned on the ship's state in the server's
+    /// market memory and advance the ship's trading date past the jump.
+    /// Sent just before the client settles its manifest and moves to the
+    /// destination; the state update that follows is priced against the
+    /// updated markets.
+    #[serde(rename = "execute_trades")]
+    ExecuteTrades,
 }
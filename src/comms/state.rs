@@ -4,11 +4,14 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::systems::world::World;
+use crate::simulator::itinerary::{Itinerary, ItineraryPlan};
+use crate::simulator::types::Date;
+use crate::systems::world::{Facility, World};
 use crate::trade::Ship;
 use crate::trade::ZoneClassification;
 use crate::trade::available_goods::AvailableGoodsTable;
 use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::ledger::Ledger;
 use crate::trade::ship_manifest::ShipManifest;
 
 /// The synchronized trade state shared between all connected clients
@@ -35,6 +38,10 @@ pub struct TradeState {
     pub origin_coords: Option<(i32, i32)>,
     /// Origin world travel zone classification (sent by client)
     pub origin_zone: ZoneClassification,
+    /// Origin world bases (from TravellerMap, sent by client). Naval and
+    /// scout bases make mail more likely.
+    #[serde(default)]
+    pub origin_bases: Vec<Facility>,
     /// Origin World object (generated by server, sent back to client)
     pub origin_world: Option<World>,
     /// Destination world name (empty if no destination, sent by client)
@@ -45,6 +52,9 @@ pub struct TradeState {
     pub dest_coords: Option<(i32, i32)>,
     /// Destination world travel zone classification (sent by client)
     pub dest_zone: ZoneClassification,
+    /// Destination world bases (from TravellerMap, sent by client)
+    #[serde(default)]
+    pub dest_bases: Vec<Facility>,
     /// Destination World object (generated by server, sent back to client)
     pub dest_world: Option<World>,
     /// Available goods at the origin
@@ -67,4 +77,22 @@ pub struct TradeState {
     pub system_broker_skill: i16,
     /// Whether illegal goods are allowed
     pub illegal_goods: bool,
+    /// The ship's trading date. Server-authoritative: it advances by a
+    /// port stay and a jump each time trades are executed, and dates the
+    /// ship's entries in the shared market memory.
+    #[serde(default)]
+    pub date: Date,
+    /// A multi-leg trade loop to plan (sent by client).
+    #[serde(default)]
+    pub itinerary: Itinerary,
+    /// The plan for [`Self::itinerary`]. Server-authoritative: replanned
+    /// whenever the itinerary, ship, skills or date change, and `None`
+    /// until the itinerary has two valid stops.
+    #[serde(default)]
+    pub itinerary_plan: Option<ItineraryPlan>,
+    /// Every credit the ship has earned or spent. Server-authoritative:
+    /// posted to when trades are executed and monthly expenses applied,
+    /// and persisted with the rest of the ship's state.
+    #[serde(default)]
+    pub ledger: Ledger,
 }
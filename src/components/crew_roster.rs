@@ -0,0 +1,192 @@
+//! Crew roster component — a table of the ship's individual crew, one
+//! row per crewmember, with their role, skills, salary and status.
+//!
+//! Used by the trade computer's ship section and the ship simulator's
+//! crew fieldset. While the roster lists anyone, the ship's crew size,
+//! salary and skills are derived from it rather than from the flat
+//! fields beside it.
+
+use leptos::prelude::*;
+
+use crate::components::help_tooltip::HelpTooltip;
+use crate::components::tooltip_docs as docs;
+use crate::trade::crew::{CrewMember, CrewRole, CrewStatus, parse_skills};
+
+/// Editable crew roster.
+///
+/// * `crew` - The roster to show
+/// * `on_change` - Called with the whole roster after every edit
+#[component]
+pub fn CrewRoster(
+    crew: Signal<Vec<CrewMember>>,
+    on_change: Callback<Vec<CrewMember>>,
+) -> impl IntoView {
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    let edit = move |index: usize, f: &dyn Fn(&mut CrewMember)| {
+        let mut roster = crew.get_untracked();
+        if let Some(member) = roster.get_mut(index) {
+            f(member);
+            on_change.run(roster);
+        }
+    };
+    let add = move |_| {
+        let mut roster = crew.get_untracked();
+        roster.push(CrewMember::new(
+            format!("Crewmember {}", roster.len() + 1),
+            CrewRole::Crew,
+            0,
+        ));
+        on_change.run(roster);
+    };
+
+    view! {
+        <div class="crew-roster">
+            <div class="trade-header-row">
+                <h3 class="trade-section">"Crew roster" <HelpTooltip text=docs::CREW_ROSTER /></h3>
+                <button class="blue-button no-print" on:click=add>
+                    "Add crewmember"
+                </button>
+            </div>
+            {move || {
+                error
+                    .get()
+                    .map(|msg| {
+                        view! {
+                            <div class="error d-print-none" style="color: #c44; margin-top: 0.5rem;">
+                                {msg}
+                            </div>
+                        }
+                    })
+            }}
+            <Show when=move || !crew.read().is_empty()>
+                <table class="trade-table">
+                    <thead>
+                        <tr>
+                            <th class="table-entry">"Name"</th>
+                            <th class="table-entry">"Role"</th>
+                            <th class="table-entry">"Skills"</th>
+                            <th class="table-entry">"Salary (Cr)"</th>
+                            <th class="table-entry">"Status"</th>
+                            <th class="table-entry"></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || {
+                            crew.get()
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, member)| {
+                                    let role = member.role;
+                                    let status = member.status;
+                                    view! {
+                                        <tr>
+                                            <td class="table-entry">
+                                                <input
+                                                    type="text"
+                                                    prop:value=member.name.clone()
+                                                    on:change=move |ev| {
+                                                        let name = event_target_value(&ev).trim().to_string();
+                                                        edit(index, &|m| m.name = name.clone());
+                                                    }
+                                                />
+                                            </td>
+                                            <td class="table-entry">
+                                                <select on:change=move |ev| {
+                                                    let value = event_target_value(&ev);
+                                                    if let Some(r) = CrewRole::ALL
+                                                        .into_iter()
+                                                        .find(|r| r.to_string() == value)
+                                                    {
+                                                        edit(index, &|m| m.role = r);
+                                                    }
+                                                }>
+                                                    {CrewRole::ALL
+                                                        .into_iter()
+                                                        .map(|r| {
+                                                            view! {
+                                                                <option value=r.to_string() selected=r == role>
+                                                                    {r.to_string()}
+                                                                </option>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </select>
+                                            </td>
+                                            <td class="table-entry">
+                                                <input
+                                                    type="text"
+                                                    placeholder="Broker 2, Pilot 1"
+                                                    prop:value=member.skills_text()
+                                                    on:change=move |ev| {
+                                                        match parse_skills(&event_target_value(&ev)) {
+                                                            Ok(skills) => {
+                                                                set_error.set(None);
+                                                                edit(index, &|m| m.skills = skills.clone());
+                                                            }
+                                                            Err(e) => set_error.set(Some(e)),
+                                                        }
+                                                    }
+                                                />
+                                            </td>
+                                            <td class="table-entry">
+                                                <input
+                                                    type="number"
+                                                    min="0"
+                                                    prop:value=member.salary
+                                                    on:change=move |ev| {
+                                                        let salary: i64 = event_target_value(&ev)
+                                                            .parse()
+                                                            .unwrap_or(0)
+                                                            .max(0);
+                                                        edit(index, &|m| m.salary = salary);
+                                                    }
+                                                />
+                                            </td>
+                                            <td class="table-entry">
+                                                <select on:change=move |ev| {
+                                                    let value = event_target_value(&ev);
+                                                    if let Some(st) = CrewStatus::ALL
+                                                        .into_iter()
+                                                        .find(|st| st.to_string() == value)
+                                                    {
+                                                        edit(index, &|m| m.status = st);
+                                                    }
+                                                }>
+                                                    {CrewStatus::ALL
+                                                        .into_iter()
+                                                        .map(|st| {
+                                                            view! {
+                                                                <option value=st.to_string() selected=st == status>
+                                                                    {st.to_string()}
+                                                                </option>
+                                                            }
+                                                        })
+                                                        .collect_view()}
+                                                </select>
+                                            </td>
+                                            <td class="table-entry">
+                                                <button
+                                                    class="blue-button no-print"
+                                                    on:click=move |_| {
+                                                        let mut roster = crew.get_untracked();
+                                                        if index < roster.len() {
+                                                            roster.remove(index);
+                                                            on_change.run(roster);
+                                                        }
+                                                    }
+                                                >
+                                                    "Remove"
+                                                </button>
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </tbody>
+                </table>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,168 @@
+//! Ledger component — a ship's income and expenses by account, its
+//! most recent entries, and buttons to download the whole ledger.
+//!
+//! Used by the trade computer, for the ship session's ledger kept by the
+//! server, and by the ship simulator, for a finished voyage's ledger.
+//!
+//! The downloads are built in the browser: **Export CSV** for a
+//! spreadsheet, **Export JSON** for anything that wants the raw entries.
+
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::JsValue;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+use crate::components::help_tooltip::HelpTooltip;
+use crate::components::tooltip_docs as docs;
+use crate::trade::ledger::{Account, Ledger};
+use crate::util::Credits;
+
+/// How many of the latest entries to list.
+const RECENT_ENTRIES: usize = 10;
+
+/// Ledger summary and export panel.
+///
+/// * `ledger` - The ledger to show
+/// * `file_stem` - Download file name without extension, e.g. the ship's name
+#[component]
+pub fn LedgerView(ledger: Signal<Ledger>, file_stem: Signal<String>) -> impl IntoView {
+    let (error, set_error) = signal::<Option<String>>(None);
+
+    let download = move |extension: &str, mime: &str, text: String| {
+        let stem = file_stem.get_untracked();
+        let stem = if stem.is_empty() {
+            "ship"
+        } else {
+            stem.as_str()
+        };
+        let filename = format!("{stem}-ledger.{extension}");
+        match trigger_text_download(&text, mime, &filename) {
+            Ok(()) => set_error.set(None),
+            Err(e) => set_error.set(Some(format!("Download failed: {e:?}"))),
+        }
+    };
+    let on_csv = move |_| download("csv", "text/csv", ledger.read().to_csv());
+    let on_json = move |_| match ledger.read().to_json() {
+        Ok(json) => download("json", "application/json", json),
+        Err(e) => set_error.set(Some(format!("Export failed: {e}"))),
+    };
+
+    view! {
+        <div class="ledger">
+            <div class="trade-header-row">
+                <h2>"Ledger" <HelpTooltip text=docs::LEDGER /></h2>
+                <button class="blue-button no-print" on:click=on_csv>
+                    "Export CSV"
+                </button>
+                <button class="blue-button no-print" on:click=on_json>
+                    "Export JSON"
+                </button>
+            </div>
+            {move || {
+                error
+                    .get()
+                    .map(|msg| {
+                        view! {
+                            <div class="error d-print-none" style="color: #c44; margin-top: 0.5rem;">
+                                {msg}
+                            </div>
+                        }
+                    })
+            }}
+            {move || {
+                let ledger = ledger.read();
+                let mut accounts: Vec<Account> = Vec::new();
+                for entry in &ledger.entries {
+                    if !accounts.contains(&entry.account) {
+                        accounts.push(entry.account);
+                    }
+                }
+                view! {
+                    <div class="manifest-grid">
+                        {accounts
+                            .into_iter()
+                            .map(|account| {
+                                view! {
+                                    <div class="manifest-item">
+                                        <span class="manifest-label">
+                                            {format!("{account}:")}
+                                        </span>
+                                        <span class="manifest-value">
+                                            {Credits::from(ledger.total(account)).as_string()}
+                                        </span>
+                                    </div>
+                                }
+                            })
+                            .collect_view()}
+                        <div class="manifest-item">
+                            <span class="manifest-label">"Balance:"</span>
+                            <span class="manifest-value">
+                                {Credits::from(ledger.balance()).as_string()}
+                            </span>
+                        </div>
+                    </div>
+                    <table class="trade-table">
+                        <thead>
+                            <tr>
+                                <th class="table-entry">"Date"</th>
+                                <th class="table-entry">"World"</th>
+                                <th class="table-entry">"Account"</th>
+                                <th class="table-entry">"Description"</th>
+                                <th class="table-entry">"Amount"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {ledger
+                                .entries
+                                .iter()
+                                .rev()
+                                .take(RECENT_ENTRIES)
+                                .map(|entry| {
+                                    view! {
+                                        <tr>
+                                            <td class="table-entry">{entry.date.format()}</td>
+                                            <td class="table-entry">{entry.world.clone()}</td>
+                                            <td class="table-entry">{entry.account.name()}</td>
+                                            <td class="table-entry">{entry.description.clone()}</td>
+                                            <td class="table-entry">
+                                                {Credits::from(entry.amount).as_string()}
+                                            </td>
+                                        </tr>
+                                    }
+                                })
+                                .collect_view()}
+                        </tbody>
+                    </table>
+                }
+            }}
+        </div>
+    }
+}
+
+/// Trigger a download of `text` as `filename`. Creates a temporary
+/// `<a download>` anchor, clicks it, then cleans it up along with the
+/// object URL.
+fn trigger_text_download(text: &str, mime: &str, filename: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::new();
+    parts.push(&JsValue::from_str(text));
+    let opts = BlobPropertyBag::new();
+    opts.set_type(mime);
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &opts)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("no window"))?
+        .document()
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let a: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    a.set_href(&url);
+    a.set_download(filename);
+    let body = document
+        .body()
+        .ok_or_else(|| JsValue::from_str("no body"))?;
+    body.append_child(&a)?;
+    a.click();
+    body.remove_child(&a)?;
+    Url::revoke_object_url(&url)?;
+    Ok(())
+}
@@ -117,9 +117,9 @@
 //! - Touch-friendly interactive elements
 //! - Scalable text and spacing
 
-pub mod captains_log_instructions;
-pub mod captains_log_prompt;
+pub mod crew_roster;
 pub mod help_tooltip;
+pub mod ledger;
 pub mod selector;
 pub mod ship_simulator;
 pub mod system_generator;
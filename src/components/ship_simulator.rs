@@ -2,7 +2,8 @@
 //!
 //! A self-contained Leptos page that:
 //! 1. Collects [`SimulationParams`] from a form.
-//! 2. Opens a WebSocket to `/ws/simulator` and streams the simulation.
+//! 2. Opens a WebSocket to `/ws/simulator` and streams the simulation,
+//!    which can be paused, stepped one port call at a time, or cancelled.
 //! 3. Renders each [`SimulationStep`] as it arrives.
 //! 4. Shows a final summary with a "Save as PDF" (browser print) button.
 
@@ -10,25 +11,38 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use leptos::prelude::*;
+use leptos::task::spawn_local;
 use log::{error, info};
 use wasm_bindgen::prelude::*;
-use web_sys::{CloseEvent, ErrorEvent, MessageEvent, WebSocket};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CloseEvent, ErrorEvent, HtmlInputElement, MessageEvent, WebSocket};
 
 use crate::comms::captains_log::{
-    ClientMessage as LogClientMessage, ServerMessage as LogServerMessage,
+    ClientMessage as LogClientMessage, LogVoyage, ServerMessage as LogServerMessage,
+    TEMPLATE_FINISH_REASON,
 };
-use crate::components::captains_log_prompt::build_prompt;
+use crate::components::crew_roster::CrewRoster;
 use crate::components::help_tooltip::HelpTooltip;
+use crate::components::ledger::LedgerView;
 use crate::components::tooltip_docs as docs;
 use crate::components::traveller_map::WorldSearch;
+use crate::simulator::captains_log_prompt::build_prompt;
+use crate::simulator::condition::{ConditionModel, describe_hits, tally_hits};
 use crate::simulator::economy::WEAPONS_MAX;
+use crate::simulator::fuel::FuelModel;
+use crate::simulator::incident_table::IncidentTable;
+use crate::simulator::ledger::voyage_ledger;
 use crate::simulator::map_render::{MapWaypoint, build_plain_link_url, build_route_map_data};
 use crate::simulator::protocol::{ClientMessage, ServerMessage};
+use crate::simulator::strategy::RouteStrategyConfig;
 use crate::simulator::types::{
     Action, Date, SimulationParams, SimulationResult, SimulationStep, WorldRef,
 };
 use crate::trade::Ship;
 use crate::trade::ZoneClassification;
+use crate::trade::cargo_plan::RiskTolerance;
+use crate::trade::crew::{CrewMember, CrewStatus};
+use crate::trade::ship_template::{SHIP_TEMPLATES, ShipTemplate};
 
 /// High-level run state of the simulator.
 #[derive(Debug, Clone)]
@@ -39,12 +53,29 @@ enum RunState {
     Connecting,
     /// Simulation is streaming. Tracks how many steps we've seen.
     Running { steps_seen: u32 },
+    /// Simulation is paused between port calls, waiting for Resume,
+    /// Step or Cancel.
+    Paused { steps_seen: u32 },
+    /// Simulation was cancelled before it finished.
+    Cancelled { steps_seen: u32 },
     /// Simulation finished cleanly with a result.
     Done(SimulationResult),
     /// Simulation errored or the socket closed without a `Done`.
     Errored(String),
 }
 
+impl RunState {
+    /// Steps received so far in the current run.
+    fn steps_seen(&self) -> u32 {
+        match self {
+            RunState::Running { steps_seen }
+            | RunState::Paused { steps_seen }
+            | RunState::Cancelled { steps_seen } => *steps_seen,
+            _ => 0,
+        }
+    }
+}
+
 /// Get the WebSocket URL for the simulator endpoint.
 ///
 /// Mirrors `bin/main.rs::get_ws_url` but for `/ws/simulator`.
@@ -165,11 +196,32 @@ impl SimClient {
                 Ok(ServerMessage::Step(step)) => {
                     steps.update(|v| v.push(step));
                     run_state.update(|s| {
-                        if let RunState::Running { steps_seen } = s {
-                            *steps_seen += 1;
-                        } else {
-                            *s = RunState::Running { steps_seen: 1 };
-                        }
+                        *s = RunState::Running {
+                            steps_seen: s.steps_seen() + 1,
+                        };
+                    });
+                }
+                Ok(ServerMessage::Paused(checkpoint)) => {
+                    info!(
+                        "Simulation paused after {} port calls",
+                        checkpoint.port_calls
+                    );
+                    run_state.update(|s| {
+                        *s = RunState::Paused {
+                            steps_seen: s.steps_seen(),
+                        };
+                    });
+                }
+                Ok(ServerMessage::Cancelled(checkpoint)) => {
+                    *got_terminal_for_msg.borrow_mut() = true;
+                    info!(
+                        "Simulation cancelled after {} port calls",
+                        checkpoint.port_calls
+                    );
+                    run_state.update(|s| {
+                        *s = RunState::Cancelled {
+                            steps_seen: s.steps_seen(),
+                        };
                     });
                 }
                 Ok(ServerMessage::Done(result)) => {
@@ -182,6 +234,10 @@ impl SimClient {
                     error!("Simulation error: {}", message);
                     run_state.set(RunState::Errored(message));
                 }
+                Ok(ServerMessage::BatchProgress { .. } | ServerMessage::BatchDone(_)) => {
+                    // Only sent in reply to `RunBatch`, which this page never asks for.
+                    error!("Unexpected batch frame on a single-run connection");
+                }
                 Err(err) => {
                     error!("Failed to parse ServerMessage: {} (text: {})", err, text);
                 }
@@ -205,7 +261,10 @@ impl SimClient {
                 };
                 run_state.update(|s| {
                     // Don't clobber a Done/Errored that already came in.
-                    if !matches!(s, RunState::Done(_) | RunState::Errored(_)) {
+                    if !matches!(
+                        s,
+                        RunState::Done(_) | RunState::Errored(_) | RunState::Cancelled { .. }
+                    ) {
                         *s = RunState::Errored(reason.clone());
                     }
                 });
@@ -227,6 +286,19 @@ impl SimClient {
             on_error,
         })
     }
+
+    /// Send a control message (`Pause`, `Resume`, `StepOnce`, `Cancel`)
+    /// to the running simulation.
+    fn send(&self, msg: &ClientMessage) {
+        match serde_json::to_string(msg) {
+            Ok(json) => {
+                if let Err(e) = self.ws.send_with_str(&json) {
+                    error!("Failed to send {:?}: {:?}", msg, e);
+                }
+            }
+            Err(e) => error!("Failed to serialize {:?}: {}", msg, e),
+        }
+    }
 }
 
 /// State of the captain's-log generation flow. Independent of `RunState` —
@@ -259,10 +331,12 @@ struct LogClient {
 }
 
 impl LogClient {
-    /// Open a WebSocket and send a single `RunSummary` once it opens.
-    /// Frames are dispatched into `log_text` / `log_state`.
+    /// Open a WebSocket and send a single `RunSummary` once it opens,
+    /// carrying the voyage too in case the server has to write the log
+    /// from templates. Frames are dispatched into `log_text` / `log_state`.
     fn start(
         prompt: String,
+        voyage: LogVoyage,
         log_text: RwSignal<String>,
         log_state: RwSignal<LogState>,
     ) -> Result<Self, String> {
@@ -276,10 +350,12 @@ impl LogClient {
         // ---- on_open: send the prompt ----
         let ws_for_open = ws.clone();
         let prompt_for_open = prompt;
+        let voyage_for_open = voyage;
         let log_state_for_open = log_state;
         let on_open = Closure::<dyn FnMut()>::new(move || {
             let msg = LogClientMessage::RunSummary {
                 prompt: prompt_for_open.clone(),
+                voyage: Some(voyage_for_open.clone()),
             };
             match serde_json::to_string(&msg) {
                 Ok(json) => match ws_for_open.send_with_str(&json) {
@@ -319,6 +395,9 @@ impl LogClient {
                     finish_reason,
                 }) => {
                     *got_terminal_for_msg.borrow_mut() = true;
+                    if finish_reason.as_deref() == Some(TEMPLATE_FINISH_REASON) {
+                        info!("Captain's log written from templates; Vertex was unavailable");
+                    }
                     info!(
                         "Captain's log done: {} prompt / {} output tokens (finish_reason={:?})",
                         prompt_tokens, output_tokens, finish_reason
@@ -406,6 +485,57 @@ fn parse_ddd_yyyy(s: &str) -> Option<Date> {
     Some(Date::new(day, year))
 }
 
+/// Parse the optional seed field. `Some(None)` for a blank field (let the
+/// server pick), `Some(Some(seed))` for a number, `None` if malformed.
+fn parse_seed(s: &str) -> Option<Option<u64>> {
+    let s = s.trim();
+    if s.is_empty() {
+        Some(None)
+    } else {
+        s.parse().ok().map(Some)
+    }
+}
+
+/// Route planners offered on the form, as `(value, label)`. Fixed
+/// itineraries need a list of stops and are only available through
+/// params JSON.
+const ROUTE_PLANS: &[(&str, &str)] = &[
+    ("greedy", "Greedy (best next jump)"),
+    ("lookahead2", "Look ahead 2 jumps"),
+    ("lookahead3", "Look ahead 3 jumps"),
+    ("main", "Stay on the main route"),
+];
+
+/// The route strategy for a `ROUTE_PLANS` value, with default settings.
+fn route_config(plan: &str) -> RouteStrategyConfig {
+    match plan {
+        "lookahead2" | "lookahead3" => RouteStrategyConfig::Lookahead {
+            legs: if plan == "lookahead3" { 3 } else { 2 },
+            discount: 0.8,
+        },
+        "main" => RouteStrategyConfig::MainRoute { min_population: 6 },
+        _ => RouteStrategyConfig::Greedy,
+    }
+}
+
+/// Buy planners offered on the form, as `(value, label)`.
+const BUY_PLANS: &[(&str, &str)] = &[
+    ("greedy", "Best prices"),
+    ("bold", "Planned, bold"),
+    ("balanced", "Planned, balanced"),
+    ("cautious", "Planned, cautious"),
+];
+
+/// The cargo-plan risk tolerance for a `BUY_PLANS` value.
+fn buy_config(plan: &str) -> Option<RiskTolerance> {
+    match plan {
+        "bold" => Some(RiskTolerance::Bold),
+        "balanced" => Some(RiskTolerance::Balanced),
+        "cautious" => Some(RiskTolerance::Cautious),
+        _ => None,
+    }
+}
+
 /// Top-level simulator page. Owns the form + log + summary state.
 #[component]
 pub fn ShipSimulator() -> impl IntoView {
@@ -413,11 +543,18 @@ pub fn ShipSimulator() -> impl IntoView {
     // Ship
     let ship_name = RwSignal::new(String::new());
     let cargo_capacity = RwSignal::new(80i32);
+    let hull_tons = RwSignal::new(200i32);
     let crew_staterooms = RwSignal::new(4i32);
     let passenger_staterooms = RwSignal::new(4i32);
     let low_berths = RwSignal::new(4i32);
     let jump_rating = RwSignal::new(2i16);
     let fuel_cost_per_parsec = RwSignal::new(500i64);
+    // Off = flat `fuel_cost_per_parsec`; on = tankage and refuelling.
+    let model_fuel = RwSignal::new(false);
+    let fuel_model = RwSignal::new(FuelModel::default());
+    // Off = incidents' repairs charged on the spot; on = damage carried.
+    let track_condition = RwSignal::new(false);
+    let condition_model = RwSignal::new(ConditionModel::default());
     let maintenance_per_period = RwSignal::new(5_000i64);
     let salary_per_period = RwSignal::new(12_000i64);
     let mortgage_per_period = RwSignal::new(0i64);
@@ -427,8 +564,13 @@ pub fn ShipSimulator() -> impl IntoView {
     let broker_skill = RwSignal::new(1i16);
     let steward_skill = RwSignal::new(1i16);
     let leadership_skill = RwSignal::new(1i16);
+    let deception_skill = RwSignal::new(0i16);
+    let admin_skill = RwSignal::new(0i16);
     let weapons = RwSignal::new(2i16);
     let crew_size = RwSignal::new(4i32);
+    // Individual crew; while it lists anyone it stands in for the crew
+    // size, salary and skills above.
+    let crew = RwSignal::new(Vec::<CrewMember>::new());
 
     // Voyage
     // Adversarial broker skill assumed for the planet's merchants on
@@ -439,6 +581,13 @@ pub fn ShipSimulator() -> impl IntoView {
     let start_date_text = RwSignal::new("001-1105".to_string());
     let target_date_text = RwSignal::new("090-1105".to_string());
     let illegal_goods = RwSignal::new(false);
+    let accept_charters = RwSignal::new(false);
+    // Blank = let the server pick a seed (reported back with the result).
+    let seed_text = RwSignal::new(String::new());
+    let route_plan = RwSignal::new("greedy".to_string());
+    let buy_plan = RwSignal::new("greedy".to_string());
+    // A referee's incident table; `None` rolls on the standard one.
+    let incidents = RwSignal::new(None::<IncidentTable>);
 
     // Home world. Populated by the TravellerMap autocomplete (WorldSearch).
     // We seed Regina/Spinward Marches as a sensible default so users can hit
@@ -463,12 +612,14 @@ pub fn ShipSimulator() -> impl IntoView {
     // ---- Validation ----
     let is_valid = Memo::new(move |_| {
         cargo_capacity.get() > 0
+            && hull_tons.get() >= 0
             && crew_staterooms.get() >= 0
             && passenger_staterooms.get() >= 0
             && low_berths.get() >= 0
             && crew_size.get() >= 0
             && jump_rating.get() > 0
             && fuel_cost_per_parsec.get() >= 0
+            && (!model_fuel.get() || fuel_model.with(|m| m.tons_per_parsec > 0))
             && maintenance_per_period.get() >= 0
             && salary_per_period.get() >= 0
             && mortgage_per_period.get() >= 0
@@ -476,8 +627,11 @@ pub fn ShipSimulator() -> impl IntoView {
             && (-3..=5).contains(&broker_skill.get())
             && (-3..=5).contains(&steward_skill.get())
             && (0..=5).contains(&leadership_skill.get())
+            && (0..=5).contains(&deception_skill.get())
+            && (0..=5).contains(&admin_skill.get())
             && (0..=24).contains(&weapons.get())
             && (-3..=5).contains(&planetary_broker_skill.get())
+            && parse_seed(&seed_text.read()).is_some()
             && {
                 match (
                     parse_ddd_yyyy(&start_date_text.read()),
@@ -512,18 +666,29 @@ pub fn ShipSimulator() -> impl IntoView {
                 broker_skill: broker_skill.get_untracked(),
                 steward_skill: steward_skill.get_untracked(),
                 leadership_skill: leadership_skill.get_untracked(),
+                deception_skill: deception_skill.get_untracked(),
+                admin_skill: admin_skill.get_untracked(),
                 weapons: weapons.get_untracked(),
                 cargo_capacity: cargo_capacity.get_untracked(),
+                hull_tons: hull_tons.get_untracked(),
                 passenger_staterooms: passenger_staterooms.get_untracked(),
                 low_berths: low_berths.get_untracked(),
                 crew_staterooms: crew_staterooms.get_untracked(),
                 crew_size: crew_size.get_untracked(),
                 jump_rating: jump_rating.get_untracked(),
+                fuel_tons: fuel_model.with_untracked(|m| m.tankage as i32),
                 mortgage_per_period: mortgage_per_period.get_untracked(),
                 maintenance_per_period: maintenance_per_period.get_untracked(),
                 salary_per_period: salary_per_period.get_untracked(),
+                crew: crew.get_untracked(),
             },
             fuel_cost_per_parsec: fuel_cost_per_parsec.get_untracked(),
+            fuel: model_fuel
+                .get_untracked()
+                .then(|| fuel_model.get_untracked()),
+            condition: track_condition
+                .get_untracked()
+                .then(|| condition_model.get_untracked()),
             crew_profit_share: crew_profit_share.get_untracked(),
             starting_budget: starting_budget.get_untracked(),
             home_world: {
@@ -543,6 +708,13 @@ pub fn ShipSimulator() -> impl IntoView {
                 .unwrap_or_else(|| Date::new(90, 1105)),
             illegal_goods: illegal_goods.get_untracked(),
             planetary_broker_skill: planetary_broker_skill.get_untracked(),
+            seed: parse_seed(&seed_text.get_untracked()).flatten(),
+            route: route_config(&route_plan.get_untracked()),
+            route_weights: Default::default(),
+            cargo_plan: buy_config(&buy_plan.get_untracked()),
+            accept_charters: accept_charters.get_untracked(),
+            markets: Default::default(),
+            incidents: incidents.get_untracked(),
         };
 
         last_params.set(Some(params.clone()));
@@ -558,6 +730,20 @@ pub fn ShipSimulator() -> impl IntoView {
         }
     };
 
+    // ---- Pause / Resume / Step / Cancel ----
+    // `Some(paused)` while a run is live, `None` otherwise; the buttons
+    // are hidden when there is no run to steer.
+    let live = Memo::new(move |_| match run_state.get() {
+        RunState::Running { .. } => Some(false),
+        RunState::Paused { .. } => Some(true),
+        _ => None,
+    });
+    let control = move |msg: ClientMessage| {
+        if let Some(client) = client_holder.borrow().as_ref() {
+            client.send(&msg);
+        }
+    };
+
     view! {
         <div class:App>
             <h1 class="no-print">"Ship Simulator"</h1>
@@ -565,11 +751,16 @@ pub fn ShipSimulator() -> impl IntoView {
             <SimForm
                 ship_name=ship_name
                 cargo_capacity=cargo_capacity
+                hull_tons=hull_tons
                 crew_staterooms=crew_staterooms
                 passenger_staterooms=passenger_staterooms
                 low_berths=low_berths
                 jump_rating=jump_rating
                 fuel_cost_per_parsec=fuel_cost_per_parsec
+                model_fuel=model_fuel
+                fuel_model=fuel_model
+                track_condition=track_condition
+                condition_model=condition_model
                 maintenance_per_period=maintenance_per_period
                 salary_per_period=salary_per_period
                 mortgage_per_period=mortgage_per_period
@@ -577,13 +768,21 @@ pub fn ShipSimulator() -> impl IntoView {
                 broker_skill=broker_skill
                 steward_skill=steward_skill
                 leadership_skill=leadership_skill
+                deception_skill=deception_skill
+                admin_skill=admin_skill
                 weapons=weapons
                 crew_size=crew_size
+                crew=crew
                 starting_budget=starting_budget
                 start_date_text=start_date_text
                 target_date_text=target_date_text
                 illegal_goods=illegal_goods
+                accept_charters=accept_charters
                 planetary_broker_skill=planetary_broker_skill
+                seed_text=seed_text
+                route_plan=route_plan
+                buy_plan=buy_plan
+                incidents=incidents
                 home_name=home_name
                 home_sector=home_sector
                 home_coords=home_coords
@@ -595,21 +794,57 @@ pub fn ShipSimulator() -> impl IntoView {
                 <button
                     class="blue-button"
                     prop:disabled=move || {
-                        !is_valid.get() || matches!(run_state.get(), RunState::Connecting | RunState::Running { .. })
+                        !is_valid.get()
+                            || matches!(
+                                run_state.get(),
+                                RunState::Connecting | RunState::Running { .. } | RunState::Paused { .. }
+                            )
                     }
                     on:click=run
                 >
                     {move || match run_state.get() {
                         RunState::Connecting => "Connecting...".to_string(),
                         RunState::Running { steps_seen } => format!("Running ({} steps)...", steps_seen),
+                        RunState::Paused { steps_seen } => format!("Paused ({} steps)", steps_seen),
                         _ => "Run Simulation".to_string(),
                     }}
                 </button>
+                <span style:display=move || if live.get().is_some() { "inline" } else { "none" }>
+                    <button
+                        class="blue-button"
+                        on:click={
+                            let control = control.clone();
+                            move |_| {
+                                control(if live.get_untracked() == Some(true) {
+                                    ClientMessage::Resume
+                                } else {
+                                    ClientMessage::Pause
+                                })
+                            }
+                        }
+                    >
+                        {move || if live.get() == Some(true) { "Resume" } else { "Pause" }}
+                    </button>
+                    <button
+                        class="blue-button"
+                        on:click={
+                            let control = control.clone();
+                            move |_| control(ClientMessage::StepOnce)
+                        }
+                    >
+                        "Step"
+                    </button>
+                    <button class="blue-button" on:click=move |_| control(ClientMessage::Cancel)>
+                        "Cancel"
+                    </button>
+                </span>
                 <span class="sim-status">
                     {move || match run_state.get() {
                         RunState::Idle => String::new(),
                         RunState::Connecting => "Connecting to simulator backend...".to_string(),
                         RunState::Running { steps_seen } => format!("Streaming — {} step(s) received", steps_seen),
+                        RunState::Paused { steps_seen } => format!("Paused after {} step(s)", steps_seen),
+                        RunState::Cancelled { steps_seen } => format!("Cancelled after {} step(s).", steps_seen),
                         RunState::Done(_) => "Simulation complete.".to_string(),
                         RunState::Errored(ref msg) => format!("Error: {}", msg),
                     }}
@@ -631,6 +866,20 @@ pub fn ShipSimulator() -> impl IntoView {
                 })}
             </div>
 
+            {move || matches!(run_state.get(), RunState::Done(_)).then(|| view! {
+                <LedgerView
+                    ledger=Signal::derive(move || {
+                        last_params
+                            .get()
+                            .map(|params| voyage_ledger(&params, &steps.read()))
+                            .unwrap_or_default()
+                    })
+                    file_stem=Signal::derive(move || {
+                        last_params.get().map(|params| params.ship.name).unwrap_or_default()
+                    })
+                />
+            })}
+
             <SimLog steps=steps home_name=home_name />
 
             <RouteMap run_state=run_state steps=steps />
@@ -644,11 +893,16 @@ pub fn ShipSimulator() -> impl IntoView {
 fn SimForm(
     ship_name: RwSignal<String>,
     cargo_capacity: RwSignal<i32>,
+    hull_tons: RwSignal<i32>,
     crew_staterooms: RwSignal<i32>,
     passenger_staterooms: RwSignal<i32>,
     low_berths: RwSignal<i32>,
     jump_rating: RwSignal<i16>,
     fuel_cost_per_parsec: RwSignal<i64>,
+    model_fuel: RwSignal<bool>,
+    fuel_model: RwSignal<FuelModel>,
+    track_condition: RwSignal<bool>,
+    condition_model: RwSignal<ConditionModel>,
     maintenance_per_period: RwSignal<i64>,
     salary_per_period: RwSignal<i64>,
     mortgage_per_period: RwSignal<i64>,
@@ -656,19 +910,49 @@ fn SimForm(
     broker_skill: RwSignal<i16>,
     steward_skill: RwSignal<i16>,
     leadership_skill: RwSignal<i16>,
+    deception_skill: RwSignal<i16>,
+    admin_skill: RwSignal<i16>,
     weapons: RwSignal<i16>,
     crew_size: RwSignal<i32>,
+    crew: RwSignal<Vec<CrewMember>>,
     starting_budget: RwSignal<i64>,
     start_date_text: RwSignal<String>,
     target_date_text: RwSignal<String>,
     illegal_goods: RwSignal<bool>,
+    accept_charters: RwSignal<bool>,
     planetary_broker_skill: RwSignal<i16>,
+    seed_text: RwSignal<String>,
+    route_plan: RwSignal<String>,
+    buy_plan: RwSignal<String>,
+    incidents: RwSignal<Option<IncidentTable>>,
     home_name: RwSignal<String>,
     home_sector: RwSignal<String>,
     home_coords: RwSignal<Option<(i32, i32)>>,
     home_uwp: RwSignal<String>,
     home_zone: RwSignal<ZoneClassification>,
 ) -> impl IntoView {
+    let (incidents_error, set_incidents_error) = signal::<Option<String>>(None);
+    let load_incidents = move |ev: leptos::ev::Event| {
+        let input: HtmlInputElement = event_target(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        spawn_local(async move {
+            let text = JsFuture::from(file.text())
+                .await
+                .ok()
+                .and_then(|t| t.as_string());
+            match text.map(|json| IncidentTable::from_json(&json)) {
+                Some(Ok(table)) => {
+                    set_incidents_error.set(None);
+                    incidents.set(Some(table));
+                }
+                Some(Err(e)) => set_incidents_error.set(Some(e.to_string())),
+                None => set_incidents_error.set(Some(format!("cannot read {}", file.name()))),
+            }
+        });
+    };
+
     view! {
         <div class="sim-form no-print">
             <fieldset class="sim-fieldset">
@@ -684,6 +968,41 @@ fn SimForm(
                             bind:value=ship_name
                         />
                     </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Template" <HelpTooltip text=docs::SHIP_TEMPLATE />
+                        </span>
+                        <select on:change=move |ev| {
+                            let Some(template) = ShipTemplate::find(&event_target_value(&ev)) else {
+                                return;
+                            };
+                            let ship = Ship::from_template(template);
+                            cargo_capacity.set(ship.cargo_capacity);
+                            hull_tons.set(ship.hull_tons);
+                            crew_staterooms.set(ship.crew_staterooms);
+                            passenger_staterooms.set(ship.passenger_staterooms);
+                            low_berths.set(ship.low_berths);
+                            jump_rating.set(ship.jump_rating);
+                            weapons.set(ship.weapons);
+                            crew_size.set(ship.crew_size);
+                            maintenance_per_period.set(ship.maintenance_per_period);
+                            salary_per_period.set(ship.salary_per_period);
+                            mortgage_per_period.set(ship.mortgage_per_period);
+                            fuel_model
+                                .update(|f| {
+                                    f.tankage = ship.fuel_tons as u32;
+                                    f.tons_per_parsec = template.fuel_per_parsec() as u32;
+                                });
+                        }>
+                            <option value="" selected>
+                                "Custom"
+                            </option>
+                            {SHIP_TEMPLATES
+                                .iter()
+                                .map(|t| view! { <option value=t.id>{t.name}</option> })
+                                .collect_view()}
+                        </select>
+                    </label>
                     <label>
                         <span class="sim-label-row">
                             "Cargo capacity (tons)"
@@ -700,6 +1019,22 @@ fn SimForm(
                             }
                         />
                     </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Hull (tons)"
+                            <HelpTooltip text=docs::HULL_TONS />
+                        </span>
+                        <input
+                            type="number"
+                            min="0"
+                            prop:value=move || hull_tons.get()
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<i32>() {
+                                    hull_tons.set(v);
+                                }
+                            }
+                        />
+                    </label>
                     <label>
                         <span class="sim-label-row">
                             "Crew staterooms"
@@ -780,6 +1115,65 @@ fn SimForm(
                             }
                         />
                     </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Model fuel tankage"
+                            <HelpTooltip text=docs::SIM_FUEL_MODEL />
+                        </span>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || model_fuel.get()
+                            on:change=move |ev| model_fuel.set(event_target_checked(&ev))
+                        />
+                    </label>
+                    <label style:display=move || if model_fuel.get() { "" } else { "none" }>
+                        <span class="sim-label-row">"Fuel tankage (t)"</span>
+                        <input
+                            type="number"
+                            min="0"
+                            prop:value=move || fuel_model.with(|m| m.tankage)
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<u32>() {
+                                    fuel_model.update(|m| m.tankage = v);
+                                }
+                            }
+                        />
+                    </label>
+                    <label style:display=move || if model_fuel.get() { "" } else { "none" }>
+                        <span class="sim-label-row">"Fuel per parsec (t)"</span>
+                        <input
+                            type="number"
+                            min="1"
+                            prop:value=move || fuel_model.with(|m| m.tons_per_parsec)
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<u32>() {
+                                    fuel_model.update(|m| m.tons_per_parsec = v);
+                                }
+                            }
+                        />
+                    </label>
+                    <label style:display=move || if model_fuel.get() { "" } else { "none" }>
+                        <span class="sim-label-row">"Fuel scoops"</span>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || fuel_model.with(|m| m.scoops)
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                fuel_model.update(|m| m.scoops = on);
+                            }
+                        />
+                    </label>
+                    <label style:display=move || if model_fuel.get() { "" } else { "none" }>
+                        <span class="sim-label-row">"Fuel processor"</span>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || fuel_model.with(|m| m.processor)
+                            on:change=move |ev| {
+                                let on = event_target_checked(&ev);
+                                fuel_model.update(|m| m.processor = on);
+                            }
+                        />
+                    </label>
                     <label>
                         <span class="sim-label-row">
                             "Maintenance / period (Cr)"
@@ -796,6 +1190,30 @@ fn SimForm(
                             }
                         />
                     </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Track ship condition"
+                            <HelpTooltip text=docs::SIM_CONDITION_MODEL />
+                        </span>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || track_condition.get()
+                            on:change=move |ev| track_condition.set(event_target_checked(&ev))
+                        />
+                    </label>
+                    <label style:display=move || if track_condition.get() { "" } else { "none" }>
+                        <span class="sim-label-row">"Days since overhaul"</span>
+                        <input
+                            type="number"
+                            min="0"
+                            prop:value=move || condition_model.with(|m| m.days_since_maintenance)
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<u32>() {
+                                    condition_model.update(|m| m.days_since_maintenance = v);
+                                }
+                            }
+                        />
+                    </label>
                     <label>
                         <span class="sim-label-row">
                             "Crew salary / period (Cr)"
@@ -904,6 +1322,40 @@ fn SimForm(
                             }
                         />
                     </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Deception"
+                            <HelpTooltip text=docs::DECEPTION_SKILL />
+                        </span>
+                        <input
+                            type="number"
+                            min="0"
+                            max="5"
+                            prop:value=move || deception_skill.get()
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<i16>() {
+                                    deception_skill.set(v);
+                                }
+                            }
+                        />
+                    </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Admin"
+                            <HelpTooltip text=docs::ADMIN_SKILL />
+                        </span>
+                        <input
+                            type="number"
+                            min="0"
+                            max="5"
+                            prop:value=move || admin_skill.get()
+                            on:input=move |ev| {
+                                if let Ok(v) = event_target_value(&ev).parse::<i16>() {
+                                    admin_skill.set(v);
+                                }
+                            }
+                        />
+                    </label>
                     <label>
                         <span class="sim-label-row">
                             "Weapons"
@@ -938,6 +1390,10 @@ fn SimForm(
                         />
                     </label>
                 </div>
+                <CrewRoster
+                    crew=crew.into()
+                    on_change=Callback::new(move |roster| crew.set(roster))
+                />
             </fieldset>
 
             <fieldset class="sim-fieldset">
@@ -994,6 +1450,19 @@ fn SimForm(
                             }
                         />
                     </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Accept charters"
+                            <HelpTooltip text=docs::ACCEPT_CHARTERS />
+                        </span>
+                        <input
+                            type="checkbox"
+                            prop:checked=move || accept_charters.get()
+                            on:change=move |ev| {
+                                accept_charters.set(event_target_checked(&ev));
+                            }
+                        />
+                    </label>
                     <label>
                         <span class="sim-label-row">
                             "System broker skill"
@@ -1011,6 +1480,92 @@ fn SimForm(
                             }
                         />
                     </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Seed"
+                            <HelpTooltip text=docs::SIM_SEED />
+                        </span>
+                        <input
+                            type="text"
+                            placeholder="random"
+                            class:sim-invalid=move || parse_seed(&seed_text.read()).is_none()
+                            bind:value=seed_text
+                        />
+                    </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Route planner"
+                            <HelpTooltip text=docs::SIM_ROUTE_PLANNER />
+                        </span>
+                        <select on:change=move |ev| route_plan.set(event_target_value(&ev))>
+                            {ROUTE_PLANS
+                                .iter()
+                                .map(|&(value, label)| {
+                                    view! {
+                                        <option
+                                            value=value
+                                            selected=move || route_plan.get() == value
+                                        >
+                                            {label}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+                    </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Buying"
+                            <HelpTooltip text=docs::SIM_BUY_PLANNER />
+                        </span>
+                        <select on:change=move |ev| buy_plan.set(event_target_value(&ev))>
+                            {BUY_PLANS
+                                .iter()
+                                .map(|&(value, label)| {
+                                    view! {
+                                        <option
+                                            value=value
+                                            selected=move || buy_plan.get() == value
+                                        >
+                                            {label}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+                    </label>
+                    <label>
+                        <span class="sim-label-row">
+                            "Incident table"
+                            <HelpTooltip text=docs::SIM_INCIDENT_TABLE />
+                        </span>
+                        <input type="file" accept=".json,application/json" on:change=load_incidents />
+                        <span class="sim-label-row">
+                            {move || {
+                                incidents
+                                    .with(|table| match table {
+                                        Some(table) => format!("{} incidents loaded", table.entries.len()),
+                                        None => "Standard".to_string(),
+                                    })
+                            }}
+                            <Show when=move || incidents.with(Option::is_some)>
+                                <button
+                                    class="blue-button"
+                                    on:click=move |ev| {
+                                        ev.prevent_default();
+                                        incidents.set(None);
+                                    }
+                                >
+                                    "Use standard"
+                                </button>
+                            </Show>
+                        </span>
+                        {move || {
+                            incidents_error
+                                .get()
+                                .map(|msg| view! { <span class="sim-invalid">{msg}</span> })
+                        }}
+                    </label>
                 </div>
             </fieldset>
 
@@ -1118,6 +1673,26 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
             format!("Loaded {tons}t freight in {lots} lots — pending revenue {revenue_pending} Cr"),
             "sim-action sim-action-freight",
         ),
+        Action::LoadMail {
+            containers,
+            tons,
+            revenue_pending,
+        } => (
+            format!(
+                "Loaded {containers} mail containers ({tons}t) — pending revenue {revenue_pending} Cr"
+            ),
+            "sim-action sim-action-freight",
+        ),
+        Action::Charter {
+            weeks,
+            weekly_rate,
+            revenue_pending,
+        } => (
+            format!(
+                "Chartered for {weeks} weeks at {weekly_rate} Cr/week — pending revenue {revenue_pending} Cr"
+            ),
+            "sim-action sim-action-freight",
+        ),
         Action::BoardPax {
             high,
             medium,
@@ -1149,6 +1724,160 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
             format!("Jumped {distance} pc to {} — fuel {fuel_cost} Cr", to.name),
             "sim-action sim-action-jump",
         ),
+        Action::PayBerthing { hull_tons, fee } => (
+            format!("Paid berthing: {fee} Cr ({hull_tons}t hull)"),
+            "sim-action sim-action-fees",
+        ),
+        Action::PayImportTariff {
+            good,
+            value,
+            rate_pct,
+            tariff,
+        } => (
+            format!("Import tariff on {good}: {rate_pct}% of {value} Cr = {tariff} Cr"),
+            "sim-action sim-action-fees",
+        ),
+        Action::PayExportTariff {
+            good,
+            value,
+            rate_pct,
+            tariff,
+        } => (
+            format!("Export tariff on {good}: {rate_pct}% of {value} Cr = {tariff} Cr"),
+            "sim-action sim-action-fees",
+        ),
+        // A ship customs didn't search is a non-event.
+        Action::CustomsInspection {
+            inspected: false, ..
+        } => return None,
+        Action::CustomsInspection {
+            inspection_roll,
+            inspection_dm,
+            concealment_roll,
+            concealment_dm,
+            found,
+            ..
+        } => (
+            format!(
+                "Customs search (inspect {}, conceal {}): {}",
+                inspection_roll + inspection_dm,
+                concealment_roll.unwrap_or(0) + concealment_dm.unwrap_or(0),
+                if *found {
+                    "contraband found"
+                } else {
+                    "contraband stayed hidden"
+                }
+            ),
+            "sim-action sim-action-customs",
+        ),
+        Action::ContrabandSeized {
+            seized,
+            buy_cost_sunk,
+            fine,
+        } => (
+            format!(
+                "Contraband seized: {} (−{buy_cost_sunk} Cr sunk), fined {fine} Cr",
+                seized
+                    .iter()
+                    .map(|(good, tons)| format!("{tons}t {good}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            "sim-action sim-action-incident sim-action-customs",
+        ),
+        Action::ShipImpounded {
+            weeks_lost,
+            release_fee,
+        } => (
+            format!("Ship impounded: +{weeks_lost} weeks, −{release_fee} Cr release fee"),
+            "sim-action sim-action-incident sim-action-customs",
+        ),
+        Action::BuyFuel {
+            tons,
+            refined,
+            price_per_ton,
+            cost,
+        } => (
+            format!(
+                "Bought {tons}t {} fuel @ {price_per_ton}/t = {cost} Cr",
+                if *refined { "refined" } else { "unrefined" }
+            ),
+            "sim-action sim-action-fuel",
+        ),
+        Action::SkimGasGiant {
+            tons,
+            refined,
+            gas_giants,
+        } => (
+            format!(
+                "Skimmed {tons}t {} fuel from a gas giant ({gas_giants} in system), +1 day",
+                if *refined { "refined" } else { "unrefined" }
+            ),
+            "sim-action sim-action-fuel",
+        ),
+        Action::ScoopWater { tons, refined } => (
+            format!(
+                "Scooped {tons}t {} fuel from surface water, +1 day",
+                if *refined { "refined" } else { "unrefined" }
+            ),
+            "sim-action sim-action-fuel",
+        ),
+        // A clean check on unrefined fuel is as much noise as an
+        // avoided incident.
+        Action::MisjumpCheck {
+            misjumped: false, ..
+        } => return None,
+        Action::MisjumpCheck {
+            roll,
+            dm,
+            weeks_lost,
+            repair_cost,
+            ..
+        } => (
+            if *dm > 0 {
+                format!(
+                    "Misjump on worn drives (roll {roll} DM +{dm}): +{weeks_lost} weeks adrift, −{repair_cost} Cr drive repairs"
+                )
+            } else {
+                format!(
+                    "Misjump on unrefined fuel (roll {roll}): +{weeks_lost} weeks adrift, −{repair_cost} Cr drive repairs"
+                )
+            },
+            "sim-action sim-action-incident sim-action-misjump",
+        ),
+        Action::ShipDamaged { cause, hits } => (
+            format!("{cause}: damage to {}", describe_hits(&tally_hits(hits))),
+            "sim-action sim-action-incident sim-action-damage",
+        ),
+        Action::AnnualMaintenance { weeks, missed } => (
+            match missed {
+                ..=1 => format!("Annual overhaul: +{weeks} weeks in the yard"),
+                n => format!(
+                    "Annual overhaul after missing {}: +{weeks} weeks in the yard",
+                    n - 1
+                ),
+            },
+            "sim-action sim-action-repair",
+        ),
+        Action::Repair {
+            repaired,
+            cost,
+            days,
+            deferred,
+        } => (
+            if *deferred > 0 {
+                format!(
+                    "Repaired {} for −{cost} Cr, +{days} days ({deferred} hit(s) deferred)",
+                    describe_hits(repaired)
+                )
+            } else {
+                format!(
+                    "Repaired {} for −{cost} Cr, +{days} days",
+                    describe_hits(repaired)
+                )
+            },
+            "sim-action sim-action-repair",
+        ),
         Action::PayPeriodic {
             maintenance,
             salary,
@@ -1180,6 +1909,7 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
             credits_lost,
             weeks_lost,
             weapons,
+            gunner,
             avoidance_total,
             table_total,
             ..
@@ -1187,7 +1917,7 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
             format!(
                 "Pirates! −{cargo_lost_tons}t cargo (−{buy_cost_sunk} Cr sunk), \
                  −{credits_lost} Cr in repairs, +{weeks_lost} weeks delay \
-                 (weapons {weapons}, avoid={avoidance_total}, table={table_total})"
+                 (weapons {weapons}, gunner {gunner}, avoid={avoidance_total}, table={table_total})"
             ),
             "sim-action sim-action-incident sim-action-piracy",
         ),
@@ -1210,11 +1940,16 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
             leadership,
             avoidance_total,
             table_total,
+            crewmember,
             ..
         } => (
             format!(
-                "Crew layover: +{weeks_lost} weeks \
-                 (leadership {leadership}, avoid={avoidance_total}, table={table_total})"
+                "{}Crew layover: +{weeks_lost} weeks \
+                 (leadership {leadership}, avoid={avoidance_total}, table={table_total})",
+                crewmember
+                    .as_ref()
+                    .map(|m| format!("{}. ", crewmember_lost(m)))
+                    .unwrap_or_default()
             ),
             "sim-action sim-action-incident sim-action-crew",
         ),
@@ -1224,10 +1959,15 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
             table_total,
             ..
         } => (
-            format!(
-                "Accident: −{repair_cost} Cr in repairs \
-                 (avoid={avoidance_total}, table={table_total})"
-            ),
+            // Under the condition model the damage is logged as hits.
+            if *repair_cost > 0 {
+                format!(
+                    "Accident: −{repair_cost} Cr in repairs \
+                     (avoid={avoidance_total}, table={table_total})"
+                )
+            } else {
+                format!("Accident (avoid={avoidance_total}, table={table_total})")
+            },
             "sim-action sim-action-incident sim-action-accident",
         ),
         Action::IncidentGovernment {
@@ -1243,6 +1983,51 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
             ),
             "sim-action sim-action-incident sim-action-government",
         ),
+        Action::Incident {
+            name,
+            text,
+            credits_lost,
+            repair_cost,
+            weeks_lost,
+            cargo_lost_tons,
+            buy_cost_sunk,
+            crewmember,
+            avoidance_total,
+            table_total,
+            ..
+        } => {
+            let mut effects = Vec::new();
+            if *credits_lost > 0 {
+                effects.push(format!("−{credits_lost} Cr"));
+            } else if *credits_lost < 0 {
+                effects.push(format!("+{} Cr", -credits_lost));
+            }
+            if *repair_cost != 0 {
+                effects.push(format!("−{repair_cost} Cr in repairs"));
+            }
+            if *cargo_lost_tons > 0 {
+                effects.push(format!(
+                    "−{cargo_lost_tons}t cargo (−{buy_cost_sunk} Cr sunk)"
+                ));
+            }
+            if *weeks_lost > 0 {
+                effects.push(format!("+{weeks_lost} weeks"));
+            }
+            if let Some(m) = crewmember {
+                effects.push(crewmember_lost(m));
+            }
+            let mut line = format!("{name}:");
+            for part in [text.clone(), effects.join(", ")] {
+                if !part.is_empty() {
+                    line.push(' ');
+                    line.push_str(&part);
+                }
+            }
+            (
+                format!("{line} (avoid={avoidance_total}, table={table_total})"),
+                "sim-action sim-action-incident",
+            )
+        }
         Action::Marooned {
             budget,
             total_parsecs_jumped,
@@ -1258,6 +2043,15 @@ fn describe_action(action: &Action, home_port: &str) -> Option<(String, &'static
     })
 }
 
+/// "Name (Role) died" or "... left the ship" for a crewmember an
+/// incident took off the roster.
+fn crewmember_lost(m: &CrewMember) -> String {
+    match m.status {
+        CrewStatus::Dead => format!("{} ({}) died", m.name, m.role),
+        _ => format!("{} ({}) left the ship", m.name, m.role),
+    }
+}
+
 /// Renders the streaming log of simulation steps.
 #[component]
 fn SimLog(steps: RwSignal<Vec<SimulationStep>>, home_name: RwSignal<String>) -> impl IntoView {
@@ -1519,6 +2313,10 @@ fn SimSummary(
                                     {if r.completed_normally { "Yes" } else { "No" }}
                                 </span>
                             </div>
+                            <div class="sim-summary-row">
+                                <span class="sim-summary-label">"Seed"</span>
+                                <span class="sim-summary-value">{r.seed}</span>
+                            </div>
                         </div>
                         <button class="blue-button no-print" on:click=print_handler>
                             "Save as PDF"
@@ -1591,12 +2389,19 @@ fn CaptainsLog(
         log_text.set(String::new());
         log_state.set(LogState::Streaming);
 
-        let prompt = {
+        let (prompt, voyage) = {
             let steps_ref = steps.read();
-            build_prompt(&params.ship.name, &params, &steps_ref, &result)
+            let prompt = build_prompt(&params.ship.name, &params, &steps_ref, &result);
+            let voyage = LogVoyage {
+                ship_name: params.ship.name.clone(),
+                params: params.clone(),
+                steps: steps_ref.to_vec(),
+                result: result.clone(),
+            };
+            (prompt, voyage)
         };
 
-        match LogClient::start(prompt, log_text, log_state) {
+        match LogClient::start(prompt, voyage, log_text, log_state) {
             Ok(client) => {
                 *client_holder_for_click.borrow_mut() = Some(client);
             }
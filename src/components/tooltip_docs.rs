@@ -20,6 +20,15 @@ pub const JUMP_RATING: &str = "Max jump of this ship. Jump rating is used when l
 pub const FUEL_COST_PER_PARSEC: &str = "Cost of fuel used per jump by the ship.  If the ship has no fuel processors enter Cr 1000/ton for refined fule. \
                                         If the ship has fuel processors, then enter Cr 500/ton for unrefined fuel. \
                                         If the ship also has fuel scoops, then wilderness refueling is possible so enter 0.";
+pub const HULL_TONS: &str = "Hull displacement of the ship in tons.  Starports charge berthing per 100 tons of hull (or part thereof); \
+                            a blank or zero hull is charged as 100 tons.";
+pub const DECEPTION_SKILL: &str =
+    "Best Deception skill aboard.  Helps keep contraband hidden when customs searches the ship.";
+pub const ADMIN_SKILL: &str =
+    "Best Admin skill aboard.  Papers in good order make customs less likely to search the ship.";
+pub const CUSTOMS_RISK: &str = "Odds of a customs search at the destination and of any contraband in the hold (or being bought) being found \
+                                and seized, from its law level and travel zone and the crew's Admin and Deception.  Expected loss counts \
+                                the seized cargo, the fine and any impound fee.";
 pub const MAINTENANCE_PER_PERIOD: &str =
     "Ship maintenance cost (per the design) per monthly maintenance period.";
 pub const SALARY_PER_PERIOD: &str =
@@ -36,9 +45,15 @@ pub const STEWARD_SKILL: &str =
 pub const LEADERSHIP: &str = "Leadership skill of captain. Higher leadership skill helps avoid \
      complications and mitigate their impact when they happen.";
 pub const WEAPONS: &str = "The total number of weapons summed across all turrets on the ship. \
-     The higher the number the lower the impact of pirate encounters.";
+     The higher the number the lower the impact of pirate encounters.  An armed ship is also \
+     more likely to be offered mail.";
 pub const CREW_SIZE: &str = "Total number of crew onboard. Used to calculate monthly life support \
      costs.";
+pub const CREW_ROSTER: &str = "The ship's individual crew: name, role, skills (e.g. \"Broker 2, Pilot 1\"), salary and status.  \
+                                While the roster lists anyone, the ship's crew size and salary are those of the crew still aboard, and \
+                                its Broker, Steward, Leadership, Gunner, Deception and Admin skills are the best among the active crew \
+                                — the crew size, salary and skill fields are ignored.  Injured crew draw pay but don't work; the dead \
+                                and those who've left do neither.  In the simulator a crew-loss incident takes one crewmember off the ship.";
 
 // ---- Voyage ----
 pub const STARTING_BUDGET: &str = "Capital provided by the owner to facilitate speculative trading.  This budget must be repaid before any crew \
@@ -48,13 +63,62 @@ pub const TARGET_COMPLETION: &str = "A rough target completion date for the crui
                                      heading for home: when the cruise is half way towards the target completion date, a strong preference is given \
                                      for next works that take the ship back towards its homeworld.";
 pub const ILLEGAL_GOODS: &str = "Is this ship willing to trade in illegal goods.";
+pub const ACCEPT_CHARTERS: &str = "Hire the whole ship out when a charter is offered at a port where the hold is otherwise empty.  \
+                                   A chartered leg is paid by the week and carries nothing of the ship's own.";
+pub const SIM_SEED: &str = "Seed for every roll the voyage makes.  Leave blank for a fresh run; enter the seed shown with a finished run \
+                            (with the same settings) to replay it exactly.";
+pub const SIM_ROUTE_PLANNER: &str = "How the captain picks each next world.  Greedy takes the best-looking world one jump away; look-ahead \
+                                     weighs two- or three-jump sequences; main route keeps to class A and B ports on populous worlds.";
+pub const SIM_BUY_PLANNER: &str = "How the captain picks speculative cargo.  Best prices buys the goods with the best rolled sale price \
+                                   first; the planned options weigh every possible sale roll, spread the money over several goods, and \
+                                   give up some expected profit for a steadier return the more cautious they are.";
+pub const SIM_INCIDENT_TABLE: &str = "The table of incidents rolled on when the ship fails to avoid trouble in port.  Load a JSON incident \
+                                      table to replace the standard piracy, trade scams, crew loss, accidents and government \
+                                      complications with a referee's own.";
+pub const SIM_FUEL_MODEL: &str = "Track the fuel in the ship's tanks instead of charging a flat cost per parsec.  The ship buys refined fuel at \
+                                  A and B starports and unrefined fuel at C and D; with fuel scoops it skims gas giants or scoops water \
+                                  for free at the cost of a day.  Jumping on unrefined fuel risks a misjump unless a fuel processor refines it.";
+pub const SIM_CONDITION_MODEL: &str = "Carry damage from port to port instead of paying an incident's repairs on the spot.  Piracy and accidents \
+                                       land hits on the hull, drives and power plant; a D starport patches only the hull, a C port everything \
+                                       but the J-drive, and A and B shipyards fix anything, budget allowing.  Annual overhauls need an A or B \
+                                       shipyard, and every year one is missed adds to the odds of breakdowns, misjumps and trouble in port.";
 
 // ---- Trade Computer only ----
+pub const SHIP_TEMPLATE: &str = "Start from a canonical ship design.  Picking one fills in the hull, hold, staterooms, low berths, crew, \
+                                  jump rating and turrets (and, in the simulator, fuel tankage), and sets the mortgage (1/240th of the purchase price per period), \
+                                  maintenance (0.1% of the price a year) and the standard crew's salaries.  The name and skills are kept.";
 pub const SHIP_NAME: &str = "Name of this ship.  Each unique ship is saved separately with all its current information, especially its ship \
                              stats and manifest.  By saving this information you can return to this information session after session.";
 pub const DISTANCE: &str = "Distance from current world to desination world in parsecs.";
 pub const SYSTEM_BROKER_SKILL: &str =
     "The (adversarial) broker skill of the current trading world.";
+pub const PORT_CHARGES: &str = "Berthing at the origin starport (more at better ports and for bigger hulls) plus customs: an import tariff \
+                               on goods sold here and an export tariff on goods bought here, both rising with law level and government.  \
+                               Deducted from profit when trades are executed.";
+pub const TRADING_DATE: &str = "The ship's date, kept by the server.  It moves on a week in port and a week in jump each time \
+                                trades are executed, and dates the ship's trades in the market memory: worlds remember \
+                                recent selling and buying, so a glutted good sells cheaper there and a stripped one is \
+                                scarce and dearer, recovering over a few weeks";
+pub const LEDGER: &str = "Every credit the ship has earned or spent, by account: goods, fares, freight, mail, charters, port charges, \
+                          life support, fuel, maintenance, salaries and mortgage.  Each entry moves credits between the ship's cash \
+                          and one account.  Export to CSV for a spreadsheet, or to JSON.";
+pub const SUGGEST_MANIFEST: &str = "Fill the free hold with the goods that pay best on average at the destination, within the budget.  \
+                                    Every possible sale roll is weighed, not just the one shown: a cautious plan spreads the money over \
+                                    several goods and gives up some expected profit for a steadier return.  Hold space taken by \
+                                    freight, passengers' baggage and goods kept aboard is left alone, and a ton only goes to goods if \
+                                    they beat the freight that could fill it.  Replaces any purchases already entered.";
+pub const ITINERARY: &str = "Plan a trade loop of several jumps.  At each stop the planner sells any cargo worth its cost, books the best \
+                             passengers, buys goods priced for the next stop and fills the rest of the hold with freight; cargo \
+                             not worth selling rides on to the next stop, and everything left aboard is sold at the last one.  \
+                             Profit is after life support and fuel.  Add the first world again at the end to close the loop.  \
+                             Everyone on this ship sees the same plan; Regenerate re-rolls it.";
+pub const ITINERARY_BUDGET: &str = "Cash available for speculative goods at the first stop.  Each later stop adds the profit made so far.";
+pub const ITINERARY_FUEL: &str = "Fuel cost per parsec jumped, charged on every leg.";
+pub const MAIL: &str = "Mail containers offered for this route.  Each takes 5 tons of hold and pays Cr 25,000 at any distance.  \
+                        Mail is more likely for an armed ship, from worlds with naval or scout bases, and between worlds of similar tech level.";
+pub const CHARTER: &str = "Hire the whole ship out for this leg.  The charter pays by the week for the ship's cargo capacity, staterooms \
+                           and low berths; the charterer's passengers and cargo replace the ship's own.";
+
 pub const EXECUTE_TRADES: &str = "Execute all trades at this world.  All purchased goods will have their cost deducted from profit and appear \
                                   in the manifest.  All sold goods in the manifest will add proceeeds to profit and be removed.  Passenger \
                                   fares will be added to the profit, as will profit for chartered freight cargo.  Monthly expenses are not \
@@ -13,6 +13,8 @@
 //! - **TradeView**: Market display showing available goods and pricing
 //! - **ShipManifestView**: Cargo and passenger manifest with revenue calculations
 //! - **PassengerView**: Available passenger and freight opportunities
+//! - **ItineraryView**: Multi-leg trade loop planner
+//! - **LedgerView**: The ship's ledger, with CSV and JSON export
 //!
 //! ## Key Features
 //!
@@ -27,12 +29,15 @@
 //! - Creates freight lots with varying tonnage and destinations
 //! - Calculates passenger revenue based on distance and steward skill
 //! - Handles freight revenue with standard Traveller rates
+//! - Offers mail containers and whole-ship charters as further revenue
 //!
 //! ### Ship Manifest Management
 //! - Interactive cargo selection and quantity management
 //! - Real-time manifest updates with tonnage tracking
 //! - Revenue and profit calculations for complete voyages
-//! - Support for mixed cargo (goods, passengers, freight)
+//! - Support for mixed cargo (goods, passengers, freight, mail)
+//! - Every executed trade and applied monthly expense is posted to the
+//!   ship's ledger, kept by the server and exportable to CSV or JSON
 //!
 //! ### Broker Skill Integration
 //! - Ship Broker skill affects purchase prices
@@ -67,6 +72,13 @@
 //! - Distance between origin and destination
 //! - Standard Traveller freight rate tables
 //!
+//! ### Mail and Charters
+//! - **Mail**: Offered from the Mongoose mail table, more likely for an
+//!   armed ship and from worlds with naval or scout bases; each 5-ton
+//!   container pays a flat fee
+//! - **Charter**: The whole ship hired for a number of weeks at a rate set
+//!   by its cargo capacity, staterooms and low berths
+//!
 //! ## Error Handling
 //!
 //! The component includes comprehensive error handling:
@@ -83,10 +95,12 @@
 //! │   ├── Origin World Search (WorldSearch)
 //! │   └── Destination World Search (WorldSearch)
 //! ├── Ship Section
+//! │   ├── Template (fills the design fields from a canonical ship)
 //! │   ├── Ship Broker skill / Steward skill
 //! │   ├── Crew size / Crew staterooms
-//! │   ├── Passenger staterooms / Low berths / Cargo capacity
+//! │   ├── Passenger staterooms / Low berths / Cargo capacity / Weapons
 //! │   ├── Mortgage / Maintenance / Salary per period
+//! │   ├── Crew roster (CrewRoster)
 //! │   └── Monthly expenses readout + Apply button
 //! ├── Trade Leg Section
 //! │   ├── Distance Input (manual override)
@@ -98,9 +112,17 @@
 //! │   ├── Freight Summary
 //! │   ├── Goods Summary
 //! │   └── Revenue Calculations
-//! └── Trade View (TradeView)
-//!     ├── Available Passengers (PassengerView)
-//!     └── Available Goods Table
+//! ├── Trade View (TradeView)
+//! │   ├── Available Passengers, Mail and Charter (PassengerView)
+//! │   └── Available Goods Table
+//! ├── Itinerary (ItineraryView)
+//! │   ├── Stop list + World Search to add stops
+//! │   ├── Budget / Fuel per parsec / Risk
+//! │   └── Per-leg plan table
+//! └── Ledger (LedgerView)
+//!     ├── Totals by account + balance
+//!     ├── Latest entries
+//!     └── Export CSV / Export JSON
 //! ```
 //!
 //! ## Integration Points
@@ -151,10 +173,14 @@ use log::{debug, error, info};
 
 use crate::comms::TradeState;
 use crate::comms::client::{Client, TradeSignals};
+use crate::components::crew_roster::CrewRoster;
 use crate::components::help_tooltip::HelpTooltip;
+use crate::components::ledger::LedgerView;
 use crate::components::tooltip_docs as docs;
 use crate::components::traveller_map::WorldSearch;
-use crate::systems::world::World;
+use crate::simulator::itinerary::{Itinerary, ItineraryPlan, ItineraryStop, LegPlan};
+use crate::simulator::types::Date;
+use crate::systems::world::{Facility, World};
 
 /// localStorage key for remembering which ship the user was last viewing.
 /// Read on mount, written whenever they pick a different ship.
@@ -187,7 +213,13 @@ use crate::trade::Ship;
 use crate::trade::available_goods::{AvailableGoodsTable, Good};
 
 use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::cargo_plan::{CargoLimits, CargoPlan, RiskTolerance, SaleMarket, plan_cargo};
+use crate::trade::contracts::Charter;
+use crate::trade::customs::CustomsRisk;
+use crate::trade::ledger::Ledger;
+use crate::trade::port_fees::PortCharges;
 use crate::trade::ship_manifest::ShipManifest;
+use crate::trade::ship_template::{SHIP_TEMPLATES, ShipTemplate};
 use crate::trade::table::TradeTable;
 use crate::trade::{TradeClass, ZoneClassification};
 
@@ -286,6 +318,17 @@ pub fn Trade(
     // Toggle for including illegal goods in market generation
     let (illegal_goods, write_illegal_goods) = signal::<bool>(false);
 
+    // The ship's trading date. Server-authoritative; it moves on a port
+    // stay and a jump each time trades are executed.
+    let (date, write_date) = signal(Date::default());
+
+    // Multi-leg trade loop, edited here; its plan is the server's.
+    let (itinerary, write_itinerary) = signal(Itinerary::default());
+    let (itinerary_plan, write_itinerary_plan) = signal::<Option<ItineraryPlan>>(None);
+
+    // Every credit the ship has earned or spent. Server-authoritative.
+    let (ledger, write_ledger) = signal(Ledger::default());
+
     // Dialog state for manually adding goods to manifest
     let show_add_manual = RwSignal::new(false);
 
@@ -321,6 +364,7 @@ pub fn Trade(
             .map(|w| w.travel_zone)
             .unwrap_or(ZoneClassification::Green),
     );
+    let origin_bases = RwSignal::new(Vec::<Facility>::new());
     let dest_coords = RwSignal::new(
         dest_world
             .get_untracked()
@@ -334,6 +378,7 @@ pub fn Trade(
             .map(|w| w.travel_zone)
             .unwrap_or(ZoneClassification::Green),
     );
+    let dest_bases = RwSignal::new(Vec::<Facility>::new());
 
     // If we have a saved ship name, queue it for the client so the
     // server hands us that ship's session as soon as the WS opens. The
@@ -353,11 +398,13 @@ pub fn Trade(
             origin_uwp: origin_uwp.write_only(),
             origin_coords: origin_coords.write_only(),
             origin_zone: origin_zone.write_only(),
+            origin_bases: origin_bases.write_only(),
             origin_world: write_origin_world,
             dest_world_name: dest_world_name.write_only(),
             dest_uwp: dest_uwp.write_only(),
             dest_coords: dest_coords.write_only(),
             dest_zone: dest_zone.write_only(),
+            dest_bases: dest_bases.write_only(),
             dest_world: write_dest_world,
             available_goods: write_available_goods,
             available_passengers: write_available_passengers,
@@ -365,6 +412,10 @@ pub fn Trade(
             ship: write_ship,
             system_broker_skill: write_system_broker_skill,
             illegal_goods: write_illegal_goods,
+            date: write_date,
+            itinerary: write_itinerary,
+            itinerary_plan: write_itinerary_plan,
+            ledger: write_ledger,
         };
         client.register_signals(signals);
 
@@ -376,16 +427,22 @@ pub fn Trade(
             let current_origin_uwp = origin_uwp.get();
             let current_origin_coords = origin_coords.get();
             let current_origin_zone = origin_zone.get();
+            let current_origin_bases = origin_bases.get();
             let current_dest_name = dest_world_name.get();
             let current_dest_uwp = dest_uwp.get();
             let current_dest_coords = dest_coords.get();
             let current_dest_zone = dest_zone.get();
+            let current_dest_bases = dest_bases.get();
             let current_goods = available_goods.get();
             let current_passengers = available_passengers.get();
             let current_manifest = ship_manifest.get();
             let current_ship = ship.get();
             let current_system_broker_skill = system_broker_skill.get();
             let current_illegal = illegal_goods.get();
+            let current_date = date.get_untracked();
+            let current_itinerary = itinerary.get();
+            let current_itinerary_plan = itinerary_plan.get_untracked();
+            let current_ledger = ledger.get_untracked();
 
             // Only send if the client is connected
             if !client_for_effect.is_connected() {
@@ -410,17 +467,23 @@ pub fn Trade(
                 origin_uwp: current_origin_uwp,
                 origin_coords: current_origin_coords,
                 origin_zone: current_origin_zone,
+                origin_bases: current_origin_bases,
                 origin_world: None, // Server generates and sends this back
                 dest_world_name: current_dest_name,
                 dest_uwp: current_dest_uwp,
                 dest_coords: current_dest_coords,
                 dest_zone: current_dest_zone,
+                dest_bases: current_dest_bases,
                 dest_world: None, // Server generates and sends this back
                 available_goods: current_goods,
                 available_passengers: current_passengers,
                 ship_manifest: current_manifest,
                 system_broker_skill: current_system_broker_skill,
                 illegal_goods: current_illegal,
+                date: current_date,
+                itinerary: current_itinerary,
+                itinerary_plan: current_itinerary_plan,
+                ledger: current_ledger,
             };
 
             // Skip sending if this is just an echo of what we received from server
@@ -447,10 +510,12 @@ pub fn Trade(
         origin_uwp.set(dest_uwp.get());
         origin_coords.set(dest_coords.get());
         origin_zone.set(dest_zone.get());
+        origin_bases.set(dest_bases.get());
         dest_world_name.set("".to_string());
         dest_uwp.set("".to_string());
         dest_coords.set(None);
         dest_zone.set(ZoneClassification::Green);
+        dest_bases.set(Vec::new());
     };
 
     // Effect to recalculate distance whenever coordinates change
@@ -493,6 +558,15 @@ pub fn Trade(
     let client_for_regenerate = client.clone();
     let client_for_apply = client.clone();
 
+    // Executing trades tells the server first, so it records them in the
+    // market memory before the swap below replaces the origin.
+    let client_for_execute = client.clone();
+    let notify_execute = move || {
+        if let Some(ref c) = client_for_execute {
+            c.send_execute_trades();
+        }
+    };
+
     view! {
         <div class:App>
             <h1 class="d-print-none">Trade Computer</h1>
@@ -552,6 +626,26 @@ pub fn Trade(
             <div class:key-region>
                 <h3 class="trade-section">"Ship"</h3>
                 <div class="skill-entry">
+                    <div>
+                        <label for="ship-template">"Template:"</label>
+                        <HelpTooltip text=docs::SHIP_TEMPLATE />
+                        <select
+                            id="ship-template"
+                            on:change=move |ev| {
+                                if let Some(t) = ShipTemplate::find(&event_target_value(&ev)) {
+                                    write_ship.update(|s| s.apply_template(t));
+                                }
+                            }
+                        >
+                            <option value="" selected>
+                                "Custom"
+                            </option>
+                            {SHIP_TEMPLATES
+                                .iter()
+                                .map(|t| view! { <option value=t.id>{t.name}</option> })
+                                .collect_view()}
+                        </select>
+                    </div>
                     <div>
                         <label for="ship-broker-skill">"Ship Broker skill:"</label>
                         <HelpTooltip text=docs::BROKER_SKILL />
@@ -582,6 +676,36 @@ pub fn Trade(
                             }
                         />
                     </div>
+                    <div>
+                        <label for="ship-deception-skill">"Deception skill:"</label>
+                        <HelpTooltip text=docs::DECEPTION_SKILL />
+                        <input
+                            type="number"
+                            id="ship-deception-skill"
+                            min="0"
+                            max="100"
+                            prop:value=move || ship.with(|s| s.deception_skill)
+                            on:change=move |ev| {
+                                let v: i16 = event_target_value(&ev).parse().unwrap_or(0).max(0);
+                                write_ship.update(|s| s.deception_skill = v);
+                            }
+                        />
+                    </div>
+                    <div>
+                        <label for="ship-admin-skill">"Admin skill:"</label>
+                        <HelpTooltip text=docs::ADMIN_SKILL />
+                        <input
+                            type="number"
+                            id="ship-admin-skill"
+                            min="0"
+                            max="100"
+                            prop:value=move || ship.with(|s| s.admin_skill)
+                            on:change=move |ev| {
+                                let v: i16 = event_target_value(&ev).parse().unwrap_or(0).max(0);
+                                write_ship.update(|s| s.admin_skill = v);
+                            }
+                        />
+                    </div>
                     <div>
                         <label for="ship-crew-size">"Crew size:"</label>
                         <HelpTooltip text=docs::CREW_SIZE />
@@ -654,6 +778,34 @@ pub fn Trade(
                             }
                         />
                     </div>
+                    <div>
+                        <label for="ship-hull-tons">"Hull (tons):"</label>
+                        <HelpTooltip text=docs::HULL_TONS />
+                        <input
+                            type="number"
+                            id="ship-hull-tons"
+                            min="0"
+                            prop:value=move || ship.with(|s| s.hull_tons)
+                            on:change=move |ev| {
+                                let v: i32 = event_target_value(&ev).parse().unwrap_or(0).max(0);
+                                write_ship.update(|s| s.hull_tons = v);
+                            }
+                        />
+                    </div>
+                    <div>
+                        <label for="ship-weapons">"Weapons:"</label>
+                        <HelpTooltip text=docs::WEAPONS />
+                        <input
+                            type="number"
+                            id="ship-weapons"
+                            min="0"
+                            prop:value=move || ship.with(|s| s.weapons)
+                            on:change=move |ev| {
+                                let v: i16 = event_target_value(&ev).parse().unwrap_or(0).max(0);
+                                write_ship.update(|s| s.weapons = v);
+                            }
+                        />
+                    </div>
                 </div>
                 <div class="skill-entry">
                     <div>
@@ -699,6 +851,10 @@ pub fn Trade(
                         />
                     </div>
                 </div>
+                <CrewRoster
+                    crew=Signal::derive(move || ship.with(|s| s.crew.clone()))
+                    on_change=Callback::new(move |crew| write_ship.update(|s| s.crew = crew))
+                />
                 <div class="skill-entry">
                     <div>
                         <span>
@@ -722,6 +878,12 @@ pub fn Trade(
                             "Apply monthly expenses"
                         </button>
                     </div>
+                    <div>
+                        <span>
+                            "Trading date: " <strong>{move || date.get().format()}</strong>
+                        </span>
+                        <HelpTooltip text=docs::TRADING_DATE />
+                    </div>
                 </div>
             </div>
 
@@ -733,6 +895,7 @@ pub fn Trade(
                         uwp=origin_uwp
                         coords=origin_coords
                         zone=origin_zone
+                        bases=origin_bases
                     />
 
                 </div>
@@ -742,6 +905,7 @@ pub fn Trade(
                     uwp=dest_uwp
                     coords=dest_coords
                     zone=dest_zone
+                    bases=dest_bases
                 />
                 <div style="display: flex; align-items: center; padding: 10px;">
                     <button
@@ -819,9 +983,10 @@ pub fn Trade(
                             {move || {
                                 match origin_world.read().as_ref() {
                                     Some(world) => format!(
-                                        "[{}] {}",
+                                        "[{}] {} {}",
                                         world.trade_classes_string(),
                                         world.travel_zone,
+                                        world.facilities_string(),
                                     ),
                                     None => String::new(),
                                 }
@@ -833,9 +998,10 @@ pub fn Trade(
                             {move || {
                                 if let Some(world) = dest_world.get() {
                                     format!(
-                                        "Destination Trade Classes: [{}] {}",
+                                        "Destination Trade Classes: [{}] {} {}",
                                         world.trade_classes_string(),
                                         world.travel_zone,
+                                        world.facilities_string(),
                                     )
                                 } else {
                                     "".to_string()
@@ -847,8 +1013,9 @@ pub fn Trade(
             </div>
             <ShipManifestView
                 origin_swap=dest_to_origin
+                on_execute=notify_execute
                 origin_world=origin_world.into()
-                _dest_world=dest_world.into()
+                dest_world=dest_world.into()
                 ship=ship.into()
                 system_broker_skill=system_broker_skill.into()
                 distance=distance
@@ -871,18 +1038,252 @@ pub fn Trade(
             <TradeView
                 origin_world=origin_world.into()
                 dest_world=dest_world.into()
+                ship=ship.into()
+                system_broker_skill=system_broker_skill.into()
+                distance=distance
                 available_goods=available_goods.into()
                 write_available_goods=write_available_goods
                 available_passengers=available_passengers.into()
                 ship_manifest=ship_manifest.into()
                 write_ship_manifest=write_ship_manifest
             />
+
+            <ItineraryView
+                itinerary=itinerary.into()
+                write_itinerary=write_itinerary
+                itinerary_plan=itinerary_plan.into()
+            />
+
+            <LedgerView
+                ledger=ledger.into()
+                file_stem=Signal::derive(move || ship.with(|s| s.name.clone()))
+            />
             </fieldset>
 
         </div>
     }
 }
 
+/// Multi-leg trade loop planner.
+///
+/// Edits the ship's itinerary — stops, budget, fuel cost and risk — and
+/// shows the server's plan for it, one row per leg.
+#[component]
+fn ItineraryView(
+    itinerary: Signal<Itinerary>,
+    write_itinerary: WriteSignal<Itinerary>,
+    itinerary_plan: Signal<Option<ItineraryPlan>>,
+) -> impl IntoView {
+    let stop_name = RwSignal::new(String::new());
+    let stop_uwp = RwSignal::new(String::new());
+    let stop_coords = RwSignal::new(None);
+    let stop_zone = RwSignal::new(ZoneClassification::Green);
+
+    let add_stop = move |_| {
+        let uwp = stop_uwp.get_untracked();
+        if uwp.len() != 9 {
+            return;
+        }
+        let stop = ItineraryStop {
+            name: stop_name.get_untracked(),
+            uwp,
+            coords: stop_coords.get_untracked(),
+            zone: stop_zone.get_untracked(),
+        };
+        write_itinerary.update(|it| it.stops.push(stop));
+        stop_name.set(String::new());
+        stop_uwp.set(String::new());
+        stop_coords.set(None);
+        stop_zone.set(ZoneClassification::Green);
+    };
+
+    view! {
+        <div class="output-region">
+            <div class="trade-header-row">
+                <h2>"Itinerary"</h2>
+                <HelpTooltip text=docs::ITINERARY />
+            </div>
+            <ol class="itinerary-stops">
+                {move || {
+                    itinerary
+                        .read()
+                        .stops
+                        .iter()
+                        .enumerate()
+                        .map(|(i, stop)| {
+                            view! {
+                                <li>
+                                    {format!("{} ({})", stop.name, stop.uwp)} " "
+                                    <button
+                                        class="manifest-button"
+                                        on:click=move |_| {
+                                            write_itinerary.update(|it| {
+                                                it.stops.remove(i);
+                                            })
+                                        }
+                                    >
+                                        "Remove"
+                                    </button>
+                                </li>
+                            }
+                        })
+                        .collect_view()
+                }}
+            </ol>
+            <div class="world-entry-form">
+                <WorldSearch
+                    label="Next Stop".to_string()
+                    name=stop_name
+                    uwp=stop_uwp
+                    coords=stop_coords
+                    zone=stop_zone
+                />
+                <div style="display: flex; align-items: center; padding: 10px;">
+                    <button class="blue-button" on:click=add_stop>
+                        "Add Stop"
+                    </button>
+                </div>
+            </div>
+            <div class="suggest-manifest">
+                <label>
+                    "Budget (Cr): "
+                    <input
+                        type="number"
+                        min="0"
+                        prop:value=move || itinerary.read().budget
+                        on:change=move |ev| {
+                            let budget = event_target_value(&ev).parse().unwrap_or(0).max(0);
+                            write_itinerary.update(|it| it.budget = budget);
+                        }
+                    />
+                </label>
+                <HelpTooltip text=docs::ITINERARY_BUDGET />
+                <label>
+                    " Fuel per parsec (Cr): "
+                    <input
+                        type="number"
+                        min="0"
+                        prop:value=move || itinerary.read().fuel_cost_per_parsec
+                        on:change=move |ev| {
+                            let fuel = event_target_value(&ev).parse().unwrap_or(0).max(0);
+                            write_itinerary.update(|it| it.fuel_cost_per_parsec = fuel);
+                        }
+                    />
+                </label>
+                <HelpTooltip text=docs::ITINERARY_FUEL />
+                <label>
+                    " Risk: "
+                    <select on:change=move |ev| {
+                        let value = event_target_value(&ev);
+                        if let Some(r) = RiskTolerance::ALL
+                            .into_iter()
+                            .find(|r| r.to_string() == value)
+                        {
+                            write_itinerary.update(|it| it.risk = r);
+                        }
+                    }>
+                        {RiskTolerance::ALL
+                            .into_iter()
+                            .map(|r| {
+                                view! {
+                                    <option
+                                        value=r.to_string()
+                                        selected=move || itinerary.read().risk == r
+                                    >
+                                        {r.to_string()}
+                                    </option>
+                                }
+                            })
+                            .collect_view()}
+                    </select>
+                </label>
+            </div>
+            {move || {
+                itinerary_plan
+                    .get()
+                    .map(|plan| {
+                        view! {
+                            <table class="trade-table">
+                                <thead>
+                                    <tr>
+                                        <th class="table-entry">"Leg"</th>
+                                        <th class="table-entry">"Parsecs"</th>
+                                        <th class="table-entry">"Sell Here"</th>
+                                        <th class="table-entry">"Buy Here"</th>
+                                        <th class="table-entry">"Passengers (H/M/B/L)"</th>
+                                        <th class="table-entry">"Freight"</th>
+                                        <th class="table-entry">"Carried"</th>
+                                        <th class="table-entry">"Life Support + Fuel"</th>
+                                        <th class="table-entry">"Leg Profit"</th>
+                                        <th class="table-entry">"Cumulative"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    {plan.legs.into_iter().map(leg_row).collect_view()}
+                                    <tr>
+                                        <td class="table-entry" colspan="8">
+                                            "Sell off at the last stop: "
+                                            {Credits::from(plan.final_sales).as_string()}
+                                        </td>
+                                        <td class="table-entry">"Total"</td>
+                                        <td class="table-entry">
+                                            {Credits::from(plan.total_profit).as_string()}
+                                        </td>
+                                    </tr>
+                                </tbody>
+                            </table>
+                        }
+                    })
+            }}
+        </div>
+    }
+}
+
+/// One leg of an itinerary plan as a table row.
+fn leg_row(leg: LegPlan) -> impl IntoView {
+    let manifest = &leg.manifest;
+    let buys = if leg.purchases.is_empty() {
+        "-".to_string()
+    } else {
+        leg.purchases
+            .iter()
+            .map(|g| format!("{} {}t", g.name, g.transacted))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let passengers = format!(
+        "{}/{}/{}/{}",
+        manifest.high_passengers,
+        manifest.medium_passengers,
+        manifest.basic_passengers,
+        manifest.low_passengers
+    );
+    let freight_tons = manifest.total_freight_tons(&leg.passengers);
+    view! {
+        <tr>
+            <td class="table-entry">{format!("{} → {}", leg.from, leg.to)}</td>
+            <td class="table-entry">{leg.distance}</td>
+            <td class="table-entry">{Credits::from(leg.sales).as_string()}</td>
+            <td class="table-entry">
+                {buys} " (" {Credits::from(leg.goods_cost).as_string()} ")"
+            </td>
+            <td class="table-entry">
+                {passengers} " (" {Credits::from(leg.passenger_revenue).as_string()} ")"
+            </td>
+            <td class="table-entry">
+                {format!("{freight_tons}t")} " ("
+                {Credits::from(leg.freight_revenue).as_string()} ")"
+            </td>
+            <td class="table-entry">{format!("{}t", leg.carried_tons)}</td>
+            <td class="table-entry">
+                {Credits::from(leg.life_support + leg.jump_cost).as_string()}
+            </td>
+            <td class="table-entry">{Credits::from(leg.profit).as_string()}</td>
+            <td class="table-entry">{Credits::from(leg.cumulative_profit).as_string()}</td>
+        </tr>
+    }
+}
+
 /// Print the current page (currently unused but available for future use)
 ///
 /// Provides a wrapper around the browser's print functionality for generating
@@ -1245,12 +1646,62 @@ pub fn BuyGoodRow(
 pub fn TradeView(
     origin_world: Signal<Option<World>>,
     dest_world: Signal<Option<World>>,
+    ship: Signal<Ship>,
+    system_broker_skill: Signal<i16>,
+    distance: RwSignal<i32>,
     available_goods: Signal<AvailableGoodsTable>,
     write_available_goods: WriteSignal<AvailableGoodsTable>,
     available_passengers: Signal<Option<AvailablePassengers>>,
     ship_manifest: Signal<ShipManifest>,
     write_ship_manifest: WriteSignal<ShipManifest>,
 ) -> impl IntoView {
+    let risk = RwSignal::new(RiskTolerance::default());
+    let budget = RwSignal::new(1_000_000i64);
+    // The last suggestion made, shown until the next one.
+    let suggestion = RwSignal::new(None::<CargoPlan>);
+
+    // Plan purchases for the destination into whatever hold the freight,
+    // mail, passengers' baggage and kept goods leave free, and enter them
+    // as this leg's purchases.
+    let suggest_manifest = move |_| {
+        let Some(world) = dest_world.get_untracked() else {
+            return;
+        };
+        let classes = world.get_trade_classes();
+        let manifest = ship_manifest.get_untracked();
+        let current_ship = ship.get_untracked();
+        let (freight_tons, freight_left) = available_passengers.with_untracked(|p| match p {
+            Some(p) => (
+                manifest.total_freight_tons(p),
+                p.freight_lots.len() > manifest.freight_lot_indices.len(),
+            ),
+            None => (0, false),
+        });
+        let limits = CargoLimits {
+            cargo_capacity: current_ship.cargo_capacity,
+            committed_tons: freight_tons
+                + manifest.mail_tons()
+                + manifest.passenger_cargo_tons().ceil() as i32
+                + manifest.trade_goods_tonnage(),
+            budget: budget.get_untracked(),
+            freight_rate_per_ton: if freight_left {
+                ShipManifest::freight_rate_per_ton(distance.get_untracked())
+            } else {
+                0
+            },
+            risk: risk.get_untracked(),
+        };
+        let market = SaleMarket {
+            trade_classes: &classes,
+            buyer_broker_skill: system_broker_skill.get_untracked(),
+            supplier_broker_skill: current_ship.effective_broker_skill(),
+            use_rolled_prices: false,
+        };
+        let plan = available_goods.with_untracked(|ag| plan_cargo(ag.goods(), &market, &limits));
+        write_available_goods.update(|ag| plan.apply(ag));
+        suggestion.set(Some(plan));
+    };
+
     view! {
         <div class="output-region">
             <h2 class="trade-header-title">
@@ -1287,11 +1738,73 @@ pub fn TradeView(
             <Show when=move || available_passengers.get().is_some()>
                 <PassengerView
                     available_passengers=available_passengers
+                    ship=ship
                     ship_manifest=ship_manifest
                     write_ship_manifest=write_ship_manifest
                 />
             </Show>
             <h4 class="trade-section">"Goods to Buy"</h4>
+            <Show when=move || dest_world.get().is_some()>
+                <div class="suggest-manifest">
+                    <label>
+                        "Budget (Cr): "
+                        <input
+                            type="number"
+                            min="0"
+                            prop:value=move || budget.get()
+                            on:change=move |ev| {
+                                budget.set(event_target_value(&ev).parse().unwrap_or(0).max(0));
+                            }
+                        />
+                    </label>
+                    <label>
+                        " Risk: "
+                        <select on:change=move |ev| {
+                            let value = event_target_value(&ev);
+                            if let Some(r) = RiskTolerance::ALL
+                                .into_iter()
+                                .find(|r| r.to_string() == value)
+                            {
+                                risk.set(r);
+                            }
+                        }>
+                            {RiskTolerance::ALL
+                                .into_iter()
+                                .map(|r| {
+                                    view! {
+                                        <option value=r.to_string() selected=move || risk.get() == r>
+                                            {r.to_string()}
+                                        </option>
+                                    }
+                                })
+                                .collect_view()}
+                        </select>
+                    </label>
+                    " "
+                    <button class="blue-button" on:click=suggest_manifest>
+                        "Suggest manifest"
+                    </button>
+                    <HelpTooltip text=docs::SUGGEST_MANIFEST />
+                    {move || {
+                        suggestion
+                            .get()
+                            .map(|plan| {
+                                if plan.purchases.is_empty() {
+                                    " Nothing here is worth the hold space.".to_string()
+                                } else {
+                                    format!(
+                                        " {} tons for {}: expected profit {} (± {})",
+                                        plan.tons,
+                                        Credits::from(plan.cost).as_string(),
+                                        Credits::from(plan.expected_profit.round() as i64)
+                                            .as_string(),
+                                        Credits::from(plan.std_dev.round() as i64).as_string(),
+                                    )
+                                }
+                            })
+                    }}
+                </div>
+            </Show>
             <table class="trade-table">
                 <thead>
                     {move || {
@@ -1413,6 +1926,7 @@ pub fn TradeView(
 #[component]
 fn PassengerView(
     available_passengers: Signal<Option<AvailablePassengers>>,
+    ship: Signal<Ship>,
     ship_manifest: Signal<ShipManifest>,
     write_ship_manifest: WriteSignal<ShipManifest>,
 ) -> impl IntoView {
@@ -1460,6 +1974,24 @@ fn PassengerView(
         }
     };
 
+    let add_mail_container = move |_| {
+        if let Some(passengers) = available_passengers.get() {
+            let remaining = passengers.mail_containers - ship_manifest.read().mail_containers;
+            if remaining > 0 {
+                write_ship_manifest.update(|manifest| {
+                    manifest.mail_containers += 1;
+                });
+            }
+        }
+    };
+
+    let set_charter_weeks = move |ev| {
+        let weeks: i32 = event_target_value(&ev).parse().unwrap_or(0);
+        write_ship_manifest.update(|manifest| {
+            manifest.charter = Charter::for_ship(&ship.get_untracked(), weeks);
+        });
+    };
+
     view! {
         <h4 class="trade-section">"Available Passengers"</h4>
         <div class="passengers-grid">
@@ -1570,6 +2102,42 @@ fn PassengerView(
                 }
             }}
         </div>
+
+        <h4 class="trade-section">"Mail and Charter"</h4>
+        <div class="passengers-grid">
+            <button class="passenger-type passenger-button" on:click=add_mail_container>
+                <h4>"Mail" <HelpTooltip text=docs::MAIL /></h4>
+                <div class="passenger-count">
+                    {move || {
+                        if let Some(passengers) = available_passengers.get() {
+                            let remaining = passengers.mail_containers
+                                - ship_manifest.read().mail_containers;
+                            remaining.max(0).to_string()
+                        } else {
+                            "0".to_string()
+                        }
+                    }}
+                </div>
+            </button>
+            <div class="passenger-type">
+                <h4>"Charter (weeks)" <HelpTooltip text=docs::CHARTER /></h4>
+                <input
+                    class="distance-input"
+                    type="number"
+                    min="0"
+                    prop:value=move || ship_manifest.read().charter.map_or(0, |c| c.weeks)
+                    on:change=set_charter_weeks
+                />
+                <div>
+                    {move || {
+                        format!(
+                            "{}/week",
+                            Credits::from(Charter::weekly_rate_for(&ship.get())).as_string(),
+                        )
+                    }}
+                </div>
+            </div>
+        </div>
     }
 }
 
@@ -1583,7 +2151,7 @@ fn PassengerView(
 /// ## Display Sections
 ///
 /// ### Manifest Summary
-/// - **Total Cargo**: Combined tonnage of goods and freight
+/// - **Total Cargo**: Combined tonnage of goods, freight and mail
 /// - **Total Passengers**: Count of all passenger types except Low
 /// - **Total Low**: Separate count for Low passage passengers
 ///
@@ -1606,7 +2174,11 @@ fn PassengerView(
 /// ### Revenue Calculations
 /// - **Passenger Revenue**: Income from all passenger types
 /// - **Freight Revenue**: Income from freight transport
+/// - **Mail Revenue**: Flat payment for mail containers carried
+/// - **Charter Revenue**: Weekly charter payments, when the ship is chartered
 /// - **Goods Profit**: Profit/loss from trade goods (when sell prices shown)
+/// - **Port Charges**: Berthing and customs tariffs at the origin
+/// - **Customs Risk**: Odds of contraband being found at the destination
 /// - **Total Revenue**: Combined income from all sources
 ///
 /// ## Interactive Elements
@@ -1641,6 +2213,8 @@ fn PassengerView(
 ///
 /// * `distance` - RwSignal containing current distance between worlds,
 ///   used for passenger and freight revenue calculations
+/// * `on_execute` - Called when trades are executed, before the manifest
+///   is settled and the destination becomes the origin
 ///
 /// ## Returns
 ///
@@ -1649,8 +2223,9 @@ fn PassengerView(
 #[component]
 fn ShipManifestView(
     origin_swap: impl Fn() + Clone + 'static,
+    on_execute: impl Fn() + Clone + 'static,
     origin_world: Signal<Option<World>>,
-    _dest_world: Signal<Option<World>>,
+    dest_world: Signal<Option<World>>,
     ship: Signal<Ship>,
     system_broker_skill: Signal<i16>,
     distance: RwSignal<i32>,
@@ -1665,6 +2240,43 @@ fn ShipManifestView(
     let manual_qty_input = RwSignal::new(String::new());
     let manual_purchase_price_input = RwSignal::new(String::new());
 
+    // Berthing and customs at the origin world, where this leg's goods
+    // are sold and bought.
+    let port_charges = move || match origin_world.get() {
+        Some(world) => PortCharges::assess(
+            &world,
+            &ship.get(),
+            ship_manifest.with(|m| m.trade_goods_proceeds()),
+            available_goods.read().total_buy_cost() as i64,
+        ),
+        None => PortCharges::default(),
+    };
+
+    // Customs odds at the destination for whatever contraband is aboard
+    // after this leg: illegal goods kept unsold plus those being bought.
+    let customs_risk = move || {
+        let Some(world) = dest_world.get() else {
+            return CustomsRisk::default();
+        };
+        let kept: i64 = ship_manifest.with(|m| {
+            m.trade_goods
+                .goods()
+                .iter()
+                .filter(|g| g.is_illegal())
+                .map(|g| (g.quantity - g.transacted) as i64 * g.buy_cost as i64)
+                .sum()
+        });
+        let bought: i64 = available_goods.with(|goods| {
+            goods
+                .goods()
+                .iter()
+                .filter(|g| g.is_illegal())
+                .map(|g| g.transacted as i64 * g.buy_cost as i64)
+                .sum()
+        });
+        CustomsRisk::estimate(&world, &ship.get(), kept + bought)
+    };
+
     let remove_high_passenger = move |_| {
         write_ship_manifest.update(|manifest| {
             if manifest.high_passengers > 0 {
@@ -1697,6 +2309,14 @@ fn ShipManifestView(
         });
     };
 
+    let remove_mail_container = move |_| {
+        write_ship_manifest.update(|manifest| {
+            if manifest.mail_containers > 0 {
+                manifest.mail_containers -= 1;
+            }
+        });
+    };
+
     let on_reset = move |_| {
         // Confirm reset
         let win = leptos::leptos_dom::helpers::window();
@@ -1740,6 +2360,7 @@ fn ShipManifestView(
                         .unwrap_or(0);
                     let goods_tons: i32 = manifest.trade_goods_tonnage()
                         + available_goods.read().total_transacted_size();
+                    let freight_tons = freight_tons + manifest.mail_tons();
                     let pax_cargo_tons = manifest.passenger_cargo_tons();
                     let total_cargo = goods_tons as f64 + freight_tons as f64
                         + pax_cargo_tons;
@@ -1853,6 +2474,19 @@ fn ShipManifestView(
                             </div>
                         }
                     }}
+                    <button class="manifest-item passenger-button" on:click=remove_mail_container>
+                        <span class="manifest-label">"Mail:"</span>
+                        <span class="manifest-value">
+                            {move || {
+                                let manifest = ship_manifest.read();
+                                format!(
+                                    "{} ({} tons)",
+                                    manifest.mail_containers,
+                                    manifest.mail_tons(),
+                                )
+                            }}
+                        </span>
+                    </button>
                 </div>
             </div>
 
@@ -1928,6 +2562,22 @@ fn ShipManifestView(
                             }}
                         </span>
                     </div>
+                    <div class="manifest-item">
+                        <span class="manifest-label">"Mail Revenue:"</span>
+                        <span class="manifest-value">
+                            {move || Credits::from(ship_manifest.read().mail_revenue()).as_string()}
+                        </span>
+                    </div>
+                    <Show when=move || ship_manifest.read().charter.is_some()>
+                        <div class="manifest-item">
+                            <span class="manifest-label">"Charter Revenue:"</span>
+                            <span class="manifest-value">
+                                {move || {
+                                    Credits::from(ship_manifest.read().charter_revenue()).as_string()
+                                }}
+                            </span>
+                        </div>
+                    </Show>
                     <div class="manifest-item">
                         <span class="manifest-label">"Goods Profit:"</span>
                         <span class="manifest-value">
@@ -1940,6 +2590,44 @@ fn ShipManifestView(
                             }}
                         </span>
                     </div>
+                    <div class="manifest-item">
+                        <span class="manifest-label">
+                            "Port Charges:"
+                            <HelpTooltip text=docs::PORT_CHARGES />
+                        </span>
+                        <span class="manifest-value">
+                            {move || {
+                                let charges = port_charges();
+                                format!(
+                                    "{} (berthing {}, tariffs {})",
+                                    Credits::from(-charges.total()).as_string(),
+                                    Credits::from(charges.berthing).as_string(),
+                                    Credits::from(charges.import_tariff + charges.export_tariff)
+                                        .as_string(),
+                                )
+                            }}
+                        </span>
+                    </div>
+                    <Show when=move || { customs_risk().expected_loss > 0.0 }>
+                        <div class="manifest-item">
+                            <span class="manifest-label">
+                                "Customs Risk:"
+                                <HelpTooltip text=docs::CUSTOMS_RISK />
+                            </span>
+                            <span class="manifest-value">
+                                {move || {
+                                    let risk = customs_risk();
+                                    format!(
+                                        "{:.0}% searched, {:.0}% seized, {:.0}% impounded (expected loss {})",
+                                        risk.inspection_chance * 100.0,
+                                        risk.seizure_chance * 100.0,
+                                        risk.impound_chance * 100.0,
+                                        Credits::from(-(risk.expected_loss.round() as i64)).as_string(),
+                                    )
+                                }}
+                            </span>
+                        </div>
+                    </Show>
                     <div class="manifest-item">
                         <span class="manifest-label">"Total:"</span>
                         <span class="manifest-value">
@@ -1956,7 +2644,10 @@ fn ShipManifestView(
                                 };
                                 let goods_profit = manifest.trade_goods_proceeds()
                                     - available_goods.read().total_buy_cost() as i64;
-                                let total = passenger_revenue + freight_revenue + goods_profit;
+                                let contract_revenue = manifest.mail_revenue() as i64
+                                    + manifest.charter_revenue();
+                                let total = passenger_revenue + freight_revenue + contract_revenue
+                                    + goods_profit - port_charges().total();
                                 Credits::from(total).as_string()
                             }}
                         </span>
@@ -1967,6 +2658,8 @@ fn ShipManifestView(
                             class="manifest-button manifest-execute-trades-button"
                             on:click=move |_| {
                                 debug!("ON BUTTON: pricing goods.");
+                                on_execute();
+                                let charges = port_charges();
                                 write_ship_manifest
                                     .update(|manifest| {
                                         manifest
@@ -1974,11 +2667,12 @@ fn ShipManifestView(
                                                 distance.get(),
                                                 &available_goods.read().goods,
                                                 &available_passengers.get(),
+                                                &charges,
                                             );
                                         manifest
                                             .price_goods(
                                                 &origin_world.get(),
-                                                ship.with(|s| s.broker_skill),
+                                                ship.with(|s| s.effective_broker_skill()),
                                                 system_broker_skill.get(),
                                             );
                                         write_available_goods.update(|ag| ag.zero_transacted());
@@ -2094,6 +2788,8 @@ fn ShipManifestView(
                                             quantity_roll: qty / entry.quantity.multiplier as i32,
                                             buy_price_roll: None,
                                             sell_price_roll: None,
+                                            buy_market_dm: 0,
+                                            sell_market_dm: 0,
                                         };
                                         write_ship_manifest
                                             .update(|manifest| {
@@ -95,6 +95,7 @@
 //! - `uwp`: RwSignal for Universal World Profile string
 //! - `coords`: RwSignal for hex coordinates (optional)
 //! - `zone`: RwSignal for zone classification
+//! - `bases`: RwSignal for the world's bases (optional)
 //! - `search_enabled`: Signal to enable/disable search functionality
 //!
 //! **Internal State:**
@@ -207,6 +208,8 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 
+use crate::sector::files::t5_base_codes;
+use crate::systems::world::Facility;
 use crate::trade::ZoneClassification;
 
 /// Maximum number of search results to display in autocomplete
@@ -355,6 +358,8 @@ pub struct WorldDataResponse {
     pub allegiance: Option<String>,
     /// Stellar data string
     pub stellar: Option<String>,
+    /// T5 base codes (e.g. "NS" for naval and scout bases)
+    pub bases: Option<String>,
 }
 
 /// Fetch search results from Traveller Map search API
@@ -641,6 +646,11 @@ pub fn WorldSearch(
     /// to autopopulate Belt and GasGiant rows.
     #[prop(optional)]
     pbg: Option<RwSignal<Option<String>>>,
+    /// Optional: when present, the world's naval, scout and military
+    /// bases are written here on selection, and cleared with the name.
+    /// The trade computer uses them for mail availability.
+    #[prop(optional)]
+    bases: Option<RwSignal<Vec<Facility>>>,
 ) -> impl IntoView {
     let (search_results, set_search_results) =
         signal::<Vec<(String, String, String, i32, i32)>>(vec![]);
@@ -683,6 +693,9 @@ pub fn WorldSearch(
             if let Some(s) = sector {
                 s.set(String::new());
             }
+            if let Some(b) = bases {
+                b.set(Vec::new());
+            }
         }
     };
 
@@ -757,6 +770,9 @@ pub fn WorldSearch(
                     if let Some(p) = pbg {
                         p.set(world_data.pbg.clone());
                     }
+                    if let Some(b) = bases {
+                        b.set(t5_base_codes(world_data.bases.as_deref().unwrap_or("")));
+                    }
                     commit_uwp(world_data.uwp);
                 }
                 Err(err) => {
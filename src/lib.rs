@@ -23,6 +23,7 @@ pub mod backend;
 // depending on worldgen as a Cargo dep (with `default-features = false`)
 // sees these and nothing else.
 pub mod api;
+pub mod sector;
 pub mod seed;
 pub mod sysmap;
 pub mod systems;
@@ -36,8 +37,9 @@ pub mod worldmap;
 // out next to them so consumers can build a `SystemConstraints` value
 // in one `use` statement.
 pub use api::{
-    StarSpec, WorldgenError, build_constraints, generate_planet_png, generate_planet_png_scaled,
-    generate_system_png, generate_system_png_scaled, generate_system_svg, parse_stellar,
+    DEFAULT_SYSTEM_SCALE, StarSpec, WorldgenError, build_constraints, generate_planet_json,
+    generate_planet_png, generate_planet_png_scaled, generate_system_json, generate_system_png,
+    generate_system_png_scaled, generate_system_svg, parse_stellar,
 };
 pub use systems::constraint::{Constraint, PartialUwp, SystemConstraints};
 pub use systems::gas_giant::GasGiantSize;
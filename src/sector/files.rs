@@ -0,0 +1,501 @@
+//! # Sector Files
+//!
+//! Reading and writing the two sector listing formats campaigns are kept
+//! in, so a sector can come from (and go back to) a file on disk instead
+//! of live TravellerMap lookups:
+//!
+//! - **T5 Second Survey tab-delimited** ([`Sector::from_t5_tab`] /
+//!   [`Sector::to_t5_tab`]) — a header row naming the columns, then one
+//!   tab-separated row per world. Columns are found by header name, so
+//!   any column order and extra columns (`{Ix}`, `(Ex)`, `[Cx]`,
+//!   `Nobility`, `RU`, …) are accepted. Both the long names TravellerMap
+//!   exports (`Bases`, `Zone`, `Allegiance`) and the T5 short ones (`B`,
+//!   `Z`, `A`) are understood. Only `Hex` and `UWP` are required.
+//! - **Legacy `.sec`** ([`Sector::from_sec`] / [`Sector::to_sec`]) — the
+//!   older fixed-column text format: name, hex, UWP, a one-letter base
+//!   code, remarks, zone, PBG, allegiance and stellar data. Parsing is by
+//!   token rather than by column, so hand-edited files with drifting
+//!   alignment still load. Comment (`#`) and header lines are skipped.
+//!
+//! Rows become [`SectorHex`]es, so an imported world goes through
+//! [`SectorHex::world`] to a [`World`](crate::systems::world::World) and
+//! through [`SectorHex::constraints`] to a
+//! [`SystemConstraints`](crate::systems::constraint::SystemConstraints)
+//! exactly like a generated one.
+//!
+//! ## Bases
+//!
+//! Worlds only model naval, scout and military bases
+//! ([`Facility::Naval`], [`Facility::Scout`], [`Facility::Military`]), so
+//! base codes are folded into those on import: naval bases and depots of
+//! every polity become `Naval`, scout bases, way stations and relay
+//! stations become `Scout`, and military bases become `Military`. Codes
+//! with no counterpart (embassies, clan and tlaukhu seats, corsair bases)
+//! are dropped. Export writes the canonical `N`/`S`/`M` codes.
+
+use crate::sector::{Pbg, Sector, SectorHex};
+use crate::systems::world::Facility;
+use crate::trade::ZoneClassification;
+
+/// Why a sector file couldn't be read.
+#[derive(Debug, thiserror::Error)]
+pub enum SectorFileError {
+    /// The tab-delimited header lacks a column every row needs.
+    #[error("missing required column {0:?}")]
+    MissingColumn(&'static str),
+
+    /// A row couldn't be turned into a world. `line` is 1-based.
+    #[error("line {line}: {message}")]
+    BadRow { line: usize, message: String },
+
+    /// The file itself couldn't be read.
+    #[error("cannot read sector file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl Sector {
+    /// Parse a T5 Second Survey tab-delimited listing for the sector
+    /// `name`. Blank lines are skipped; any other malformed row is an
+    /// error naming its line.
+    pub fn from_t5_tab(name: &str, text: &str) -> Result<Sector, SectorFileError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty());
+        let header: Vec<String> = lines
+            .next()
+            .map(|(_, l)| l.split('\t').map(|h| h.trim().to_string()).collect())
+            .unwrap_or_default();
+        let column = |names: &[&str]| {
+            header
+                .iter()
+                .position(|h| names.iter().any(|n| h.eq_ignore_ascii_case(n)))
+        };
+        let hex_col = column(&["Hex"]).ok_or(SectorFileError::MissingColumn("Hex"))?;
+        let uwp_col = column(&["UWP"]).ok_or(SectorFileError::MissingColumn("UWP"))?;
+        let name_col = column(&["Name"]);
+        let remarks_col = column(&["Remarks"]);
+        let bases_col = column(&["Bases", "B"]);
+        let zone_col = column(&["Zone", "Z"]);
+        let pbg_col = column(&["PBG"]);
+        let worlds_col = column(&["W", "Worlds"]);
+        let allegiance_col = column(&["Allegiance", "A"]);
+        let stellar_col = column(&["Stellar"]);
+
+        let mut hexes = Vec::new();
+        for (index, line) in lines {
+            let fields: Vec<&str> = line.split('\t').map(str::trim).collect();
+            let field = |col: Option<usize>| col.and_then(|c| fields.get(c)).copied().unwrap_or("");
+            let bad = |message: String| SectorFileError::BadRow {
+                line: index + 1,
+                message,
+            };
+
+            let hex = field(Some(hex_col));
+            let (x, y) = parse_hex(hex).ok_or_else(|| bad(format!("bad hex {hex:?}")))?;
+            let pbg = field(pbg_col);
+            hexes.push(SectorHex {
+                x,
+                y,
+                name: field(name_col).to_string(),
+                uwp: check_uwp(field(Some(uwp_col))).map_err(bad)?,
+                bases: t5_base_codes(field(bases_col)),
+                remarks: split_remarks(field(remarks_col)),
+                zone: parse_zone(field(zone_col)),
+                pbg: parse_pbg(pbg).ok_or_else(|| bad(format!("bad PBG {pbg:?}")))?,
+                worlds: field(worlds_col).parse().ok(),
+                allegiance: parse_allegiance(field(allegiance_col)),
+                stellar: field(stellar_col).to_string(),
+            });
+        }
+
+        Ok(Sector {
+            name: name.to_string(),
+            hexes,
+        })
+    }
+
+    /// Write this sector as a T5 Second Survey tab-delimited listing that
+    /// [`Sector::from_t5_tab`] (and TravellerMap) reads back.
+    pub fn to_t5_tab(&self) -> String {
+        let mut out =
+            String::from("Hex\tName\tUWP\tBases\tRemarks\tZone\tPBG\tAllegiance\tStellar\tW\n");
+        for hex in &self.hexes {
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+                hex.hex(),
+                hex.name,
+                hex.uwp,
+                t5_bases(&hex.bases),
+                hex.remarks.join(" "),
+                zone_code(hex.zone).trim(),
+                hex.pbg,
+                hex.allegiance.as_deref().unwrap_or("--"),
+                hex.stellar,
+                hex.worlds.map(|w| w.to_string()).unwrap_or_default(),
+            ));
+        }
+        out
+    }
+
+    /// Parse a legacy `.sec` listing for the sector `name`. Lines that
+    /// don't contain a hex followed by a UWP (comments, headers, rulers)
+    /// are skipped; a world line with a bad PBG is an error.
+    pub fn from_sec(name: &str, text: &str) -> Result<Sector, SectorFileError> {
+        let mut hexes = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            if let Some(hex) = parse_sec_line(line).map_err(|message| SectorFileError::BadRow {
+                line: index + 1,
+                message,
+            })? {
+                hexes.push(hex);
+            }
+        }
+        Ok(Sector {
+            name: name.to_string(),
+            hexes,
+        })
+    }
+
+    /// Write this sector in the legacy fixed-column `.sec` format that
+    /// [`Sector::from_sec`] reads back.
+    pub fn to_sec(&self) -> String {
+        let mut out = format!("# {}\n#\n", self.name);
+        out.push_str(
+            "# Name               Hex  UWP       B Remarks              Z PBG Al   Stellar\n",
+        );
+        for hex in &self.hexes {
+            out.push_str(&format!(
+                "{:<20} {} {} {} {:<20} {} {} {:<4} {}\n",
+                hex.name,
+                hex.hex(),
+                hex.uwp,
+                sec_base(&hex.bases),
+                hex.remarks.join(" "),
+                zone_code(hex.zone),
+                hex.pbg,
+                hex.allegiance.as_deref().unwrap_or("--"),
+                hex.stellar,
+            ));
+        }
+        out
+    }
+}
+
+/// Parse one `.sec` line: `Ok(None)` if it isn't a world line at all.
+fn parse_sec_line(line: &str) -> Result<Option<SectorHex>, String> {
+    let tokens: Vec<(usize, &str)> = tokens_with_offsets(line);
+    // The world starts at the first "CCRR UWP" pair; everything before
+    // it is the (possibly multi-word, possibly empty) name.
+    let Some(start) = tokens
+        .windows(2)
+        .position(|w| parse_hex(w[0].1).is_some() && check_uwp(w[1].1).is_ok())
+    else {
+        return Ok(None);
+    };
+    let name = line[..tokens[start].0].trim().to_string();
+    let (x, y) = parse_hex(tokens[start].1).expect("matched above");
+    let uwp = tokens[start + 1].1.to_string();
+    let mut rest: Vec<&str> = tokens[start + 2..].iter().map(|(_, t)| *t).collect();
+
+    // A single letter (or `-` for none) straight after the UWP is the
+    // base code; remarks are never a single character.
+    let bases = match rest.first() {
+        Some(t) if t.len() == 1 && t.chars().all(|c| c.is_ascii_alphabetic() || c == '-') => {
+            let code = rest.remove(0);
+            sec_bases(code.chars().next().expect("len 1"))
+        }
+        _ => Vec::new(),
+    };
+
+    // PBG is the last all-digit three-character token; the zone (if any)
+    // sits just before it, allegiance just after, stellar data after that.
+    let pbg_at = rest
+        .iter()
+        .rposition(|t| t.len() == 3 && t.chars().all(|c| c.is_ascii_digit()))
+        .ok_or_else(|| format!("no PBG in {line:?}"))?;
+    let pbg = parse_pbg(rest[pbg_at]).ok_or_else(|| format!("bad PBG {:?}", rest[pbg_at]))?;
+    let mut remarks_end = pbg_at;
+    let zone = match rest[..pbg_at].last() {
+        Some(z) if matches!(*z, "A" | "R" | "G") => {
+            remarks_end -= 1;
+            parse_zone(z)
+        }
+        _ => ZoneClassification::Green,
+    };
+    let remarks = rest[..remarks_end].iter().map(|s| s.to_string()).collect();
+    let allegiance = rest.get(pbg_at + 1).and_then(|a| parse_allegiance(a));
+    let stellar = rest.get(pbg_at + 2..).unwrap_or(&[]).join(" ");
+
+    Ok(Some(SectorHex {
+        x,
+        y,
+        name,
+        uwp,
+        bases,
+        remarks,
+        zone,
+        pbg,
+        worlds: None,
+        allegiance,
+        stellar,
+    }))
+}
+
+/// Whitespace-separated tokens of `line`, each with its byte offset.
+fn tokens_with_offsets(line: &str) -> Vec<(usize, &str)> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                out.push((s, &line[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        out.push((s, &line[s..]));
+    }
+    out
+}
+
+/// `"1910"` → `(19, 10)`, within sector bounds.
+fn parse_hex(s: &str) -> Option<(u8, u8)> {
+    if s.len() != 4 || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let x: u8 = s[0..2].parse().ok()?;
+    let y: u8 = s[2..4].parse().ok()?;
+    ((1..=crate::sector::SECTOR_WIDTH).contains(&x)
+        && (1..=crate::sector::SECTOR_HEIGHT).contains(&y))
+    .then_some((x, y))
+}
+
+/// Accept a 9-character `SAHPGLL-T` UWP whose columns are all ehex (or
+/// `?`/`X` placeholders).
+fn check_uwp(s: &str) -> Result<String, String> {
+    let chars: Vec<char> = s.chars().collect();
+    let ok = chars.len() == 9
+        && chars[7] == '-'
+        && chars
+            .iter()
+            .enumerate()
+            .all(|(i, c)| i == 7 || c.is_ascii_alphanumeric() || *c == '?');
+    if ok {
+        Ok(s.to_string())
+    } else {
+        Err(format!("bad UWP {s:?}"))
+    }
+}
+
+fn split_remarks(s: &str) -> Vec<String> {
+    s.split_whitespace().map(str::to_string).collect()
+}
+
+fn parse_zone(s: &str) -> ZoneClassification {
+    match s.trim() {
+        "A" | "Amber" => ZoneClassification::Amber,
+        "R" | "Red" => ZoneClassification::Red,
+        _ => ZoneClassification::Green,
+    }
+}
+
+/// The zone's one-letter code, a space for Green.
+fn zone_code(zone: ZoneClassification) -> &'static str {
+    match zone {
+        ZoneClassification::Green => " ",
+        ZoneClassification::Amber => "A",
+        ZoneClassification::Red => "R",
+    }
+}
+
+/// A blank or `-` PBG reads as `000`.
+fn parse_pbg(s: &str) -> Option<Pbg> {
+    if s.is_empty() || s == "-" {
+        return Some(Pbg::default());
+    }
+    let digits: Vec<u8> = s
+        .chars()
+        .map(|c| c.to_digit(10).map(|d| d as u8))
+        .collect::<Option<_>>()?;
+    match digits[..] {
+        [p, b, g] => Some(Pbg {
+            population_multiplier: p,
+            belts: b,
+            gas_giants: g,
+        }),
+        _ => None,
+    }
+}
+
+/// Blank and `--` mean unclaimed.
+fn parse_allegiance(s: &str) -> Option<String> {
+    match s.trim() {
+        "" | "--" | "----" => None,
+        a => Some(a.to_string()),
+    }
+}
+
+/// A T5 bases column (`NS`, `KM`, …) — as TravellerMap also reports it —
+/// as the facilities it names. Unknown letters are skipped.
+pub fn t5_base_codes(bases: &str) -> Vec<Facility> {
+    bases.chars().filter_map(t5_base).collect()
+}
+
+/// One T5 base code letter.
+fn t5_base(code: char) -> Option<Facility> {
+    match code {
+        'N' | 'K' | 'D' | 'Z' => Some(Facility::Naval),
+        'S' | 'W' | 'V' | 'X' => Some(Facility::Scout),
+        'M' => Some(Facility::Military),
+        _ => None,
+    }
+}
+
+fn t5_bases(bases: &[Facility]) -> String {
+    let mut out = String::new();
+    for (facility, code) in [
+        (Facility::Naval, 'N'),
+        (Facility::Scout, 'S'),
+        (Facility::Military, 'M'),
+    ] {
+        if bases.contains(&facility) {
+            out.push(code);
+        }
+    }
+    out
+}
+
+/// A legacy `.sec` base letter, some of which stand for two bases.
+fn sec_bases(code: char) -> Vec<Facility> {
+    match code {
+        'A' => vec![Facility::Naval, Facility::Scout],
+        'B' => vec![Facility::Naval, Facility::Scout],
+        'F' | 'Z' => vec![Facility::Naval, Facility::Military],
+        'N' | 'J' | 'K' | 'D' | 'G' | 'L' | 'Y' => vec![Facility::Naval],
+        'S' | 'V' | 'W' | 'X' => vec![Facility::Scout],
+        'M' => vec![Facility::Military],
+        _ => Vec::new(),
+    }
+}
+
+/// The legacy letter for a set of bases, `-` when there are none. There
+/// is no letter for scout + military, so that pair writes as `M`.
+fn sec_base(bases: &[Facility]) -> char {
+    let naval = bases.contains(&Facility::Naval);
+    let scout = bases.contains(&Facility::Scout);
+    let military = bases.contains(&Facility::Military);
+    match (naval, scout, military) {
+        (true, true, _) => 'A',
+        (true, false, true) => 'F',
+        (true, false, false) => 'N',
+        (false, _, true) => 'M',
+        (false, true, false) => 'S',
+        (false, false, false) => '-',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sector::Density;
+
+    const T5: &str = "\
+Hex\tName\tUWP\tRemarks\t{Ix}\t(Ex)\t[Cx]\tN\tB\tZ\tPBG\tW\tA\tStellar
+1910\tRegina\tA788899-C\tRi Pa Ph An Cp (Amindii)2 Varg0 Asla0 Sa\t{ 4 }\t(D7E+5)\t[9C6D]\tBcCeF\tNS\t\t703\t8\tImDd\tF7 V BD M3 V
+2005\tAlell\tB56789C-A\tRi Pa Ph\t{ 3 }\t(C6C+1)\t[9B7A]\tB\tNS\tA\t702\t13\tImDd\tG3 V
+";
+
+    const SEC: &str = "\
+# Spinward Marches
+#
+# Name               Hex  UWP       B Remarks              Z PBG Al   Stellar
+Regina               1910 A788899-C A Ri Pa Ph An Cp         703 Im   F7 V BD M3 V
+Alell                2005 B56789C-A A Ri Pa Ph             A 702 Im   G3 V
+                     2007 E000000-0 -                        000 --
+";
+
+    #[test]
+    fn t5_rows_become_worlds_and_constraints() {
+        let sector = Sector::from_t5_tab("Spinward Marches", T5).unwrap();
+        assert_eq!(sector.hexes.len(), 2);
+
+        let regina = sector.hex(19, 10).unwrap();
+        assert_eq!(regina.uwp, "A788899-C");
+        assert_eq!(regina.bases, vec![Facility::Naval, Facility::Scout]);
+        assert_eq!(regina.zone, ZoneClassification::Green);
+        assert_eq!(regina.pbg.to_string(), "703");
+        assert_eq!(regina.worlds, Some(8));
+        assert_eq!(regina.allegiance.as_deref(), Some("ImDd"));
+        assert_eq!(regina.stellar, "F7 V BD M3 V");
+        assert!(regina.remarks.iter().any(|r| r == "Cp"));
+
+        let world = regina.world().unwrap();
+        assert_eq!(world.coordinates, Some((19, 10)));
+        assert_eq!(world.trade_classes_string(), "Ri");
+        assert_eq!(world.facilities_string(), "Naval, Scout");
+        assert!(regina.constraints().is_ok());
+
+        assert_eq!(sector.hex(20, 5).unwrap().zone, ZoneClassification::Amber);
+    }
+
+    #[test]
+    fn t5_requires_hex_and_uwp_columns() {
+        assert!(matches!(
+            Sector::from_t5_tab("x", "Name\tUWP\nRegina\tA788899-C\n"),
+            Err(SectorFileError::MissingColumn("Hex"))
+        ));
+        assert!(matches!(
+            Sector::from_t5_tab("x", "Hex\tUWP\n1910\tnot-a-uwp\n"),
+            Err(SectorFileError::BadRow { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn sec_lines_parse_by_token() {
+        let sector = Sector::from_sec("Spinward Marches", SEC).unwrap();
+        assert_eq!(sector.hexes.len(), 3);
+
+        let regina = sector.hex(19, 10).unwrap();
+        assert_eq!(regina.name, "Regina");
+        assert_eq!(regina.bases, vec![Facility::Naval, Facility::Scout]);
+        assert_eq!(regina.remarks, vec!["Ri", "Pa", "Ph", "An", "Cp"]);
+        assert_eq!(regina.zone, ZoneClassification::Green);
+        assert_eq!(regina.allegiance.as_deref(), Some("Im"));
+        assert_eq!(regina.stellar, "F7 V BD M3 V");
+
+        assert_eq!(sector.hex(20, 5).unwrap().zone, ZoneClassification::Amber);
+
+        let unnamed = sector.hex(20, 7).unwrap();
+        assert_eq!(unnamed.name, "");
+        assert!(unnamed.bases.is_empty());
+        assert_eq!(unnamed.allegiance, None);
+    }
+
+    #[test]
+    fn generated_sector_round_trips_through_both_formats() {
+        let sector = Sector::generate_subsector("Spinward Marches", 'C', Density::Dense).unwrap();
+        assert_eq!(
+            Sector::from_t5_tab(&sector.name, &sector.to_t5_tab()).unwrap(),
+            sector
+        );
+        assert_eq!(
+            Sector::from_sec(&sector.name, &sector.to_sec()).unwrap(),
+            sector
+        );
+    }
+
+    #[test]
+    fn legacy_base_letters_expand() {
+        assert_eq!(sec_bases('A'), vec![Facility::Naval, Facility::Scout]);
+        assert_eq!(sec_bases('W'), vec![Facility::Scout]);
+        assert_eq!(sec_bases('-'), Vec::<Facility>::new());
+        assert_eq!(sec_base(&[Facility::Naval, Facility::Military]), 'F');
+    }
+}
@@ -0,0 +1,573 @@
+//! # Sector Module
+//!
+//! Generates the map a single system sits in: a whole 32×40 sector or one
+//! of its sixteen 8×10 subsectors. Each hex is rolled for system presence
+//! and, if occupied, gets the one-line profile a sector listing carries —
+//! main-world name and UWP, bases, travel zone, PBG and stellar data.
+//!
+//! The result is a [`Sector`] of [`SectorHex`] profiles, cheap to build
+//! for the whole map at once. Any hex can then be expanded into a full
+//! [`System`] with [`Sector::expand`], which feeds the profile through
+//! [`build_constraints`] / [`parse_stellar`] and seeds generation with
+//! [`seed::system_seed`](crate::seed::system_seed) — exactly the inputs
+//! `/api/system` derives from the same sector, hex, name, UWP, PBG and
+//! stellar query. The expanded system is therefore byte-identical to what
+//! the HTTP endpoint renders for that hex.
+//!
+//! Published sectors can be read and written in the T5 Second Survey
+//! tab-delimited and legacy `.sec` formats; see [`files`].
+//!
+//! ## Determinism
+//!
+//! Each hex's profile is rolled from its own
+//! [`hex_profile_seed`](crate::seed::hex_profile_seed), so a hex's contents
+//! depend only on `(sector name, x, y, density)` — generating a subsector
+//! gives the same worlds as the matching slice of the full sector. The
+//! presence check is the first roll in a hex's stream, so a hex occupied
+//! at two densities has the same profile at both.
+//!
+//! ## Rules
+//!
+//! Main worlds are rolled with the Classic Traveller (Book 3) tables:
+//! starport 2D, size 2D-2, atmosphere and hydrographics 2D-7+size,
+//! population 2D-2, government 2D-7+population, law 2D-7+government, and
+//! tech level 1D plus the usual UWP modifiers. Gas giant and planetoid
+//! belt counts use the same tables as the system generator, and the
+//! stellar data comes from its star tables. Travel zones follow the usual
+//! Amber guideline (atmosphere A+, government 0/7/A, law 0 or 9+ on a
+//! populated world); one Amber candidate in 36 is escalated to Red.
+//!
+//! ```rust,ignore
+//! use worldgen::sector::{Density, Sector};
+//!
+//! let sector = Sector::generate_subsector("Spinward Marches", 'C', Density::Standard).unwrap();
+//! for hex in &sector.hexes {
+//!     println!("{} {} {} {}", hex.hex(), hex.name, hex.uwp, hex.stellar);
+//! }
+//! let system = sector.expand(&sector.hexes[0])?;
+//! ```
+
+pub mod files;
+pub mod origins;
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::{WorldgenError, build_constraints, parse_stellar};
+use crate::seed::{hex_profile_seed, system_seed};
+use crate::systems::constraint::{ConstraintError, SystemConstraints};
+use crate::systems::name_tables::gen_planet_name;
+use crate::systems::system::{System, roll_stellar};
+use crate::systems::world::{Facility, World};
+use crate::trade::{PortCode, TradeClass, ZoneClassification, string_to_trade_class};
+use crate::util::{RngScope, rng_random_range, roll_1d6, roll_2d6, value_to_ehex};
+
+/// Hex columns in a sector.
+pub const SECTOR_WIDTH: u8 = 32;
+/// Hex rows in a sector.
+pub const SECTOR_HEIGHT: u8 = 40;
+/// Hex columns in a subsector.
+pub const SUBSECTOR_WIDTH: u8 = 8;
+/// Hex rows in a subsector.
+pub const SUBSECTOR_HEIGHT: u8 = 10;
+
+/// How thickly a region is settled with star systems.
+///
+/// Each variant is the 1D roll needed for a hex to hold a system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Density {
+    /// A system on 6 (1 hex in 6).
+    Sparse,
+    /// A system on 5+ (1 hex in 3).
+    Scattered,
+    /// A system on 4+ (1 hex in 2).
+    #[default]
+    Standard,
+    /// A system on 3+ (2 hexes in 3).
+    Dense,
+}
+
+impl Density {
+    /// The minimum 1D roll for a hex to be occupied.
+    fn target(self) -> i32 {
+        match self {
+            Density::Sparse => 6,
+            Density::Scattered => 5,
+            Density::Standard => 4,
+            Density::Dense => 3,
+        }
+    }
+}
+
+impl From<&str> for Density {
+    fn from(s: &str) -> Self {
+        match s.to_ascii_lowercase().as_str() {
+            "sparse" => Density::Sparse,
+            "scattered" => Density::Scattered,
+            "dense" => Density::Dense,
+            _ => Density::Standard,
+        }
+    }
+}
+
+/// A system's population multiplier, planetoid belt count and gas giant
+/// count — the three-digit "PBG" column of a sector listing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Pbg {
+    pub population_multiplier: u8,
+    pub belts: u8,
+    pub gas_giants: u8,
+}
+
+impl Display for Pbg {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}{}{}",
+            self.population_multiplier, self.belts, self.gas_giants
+        )
+    }
+}
+
+/// One occupied hex of a sector map: everything a sector listing says
+/// about the system there, without generating the system itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SectorHex {
+    /// Hex column within the sector, 1–32.
+    pub x: u8,
+    /// Hex row within the sector, 1–40.
+    pub y: u8,
+    /// Main world name.
+    pub name: String,
+    /// Main world UWP, e.g. `"A788899-A"`.
+    pub uwp: String,
+    /// Naval, scout and military bases in the system.
+    pub bases: Vec<Facility>,
+    /// The listing's remarks: trade codes plus anything else a sector
+    /// file carries there (e.g. `"Cp"`, `"(Vargr)"`).
+    pub remarks: Vec<String>,
+    pub zone: ZoneClassification,
+    pub pbg: Pbg,
+    /// Total bodies in the system (the T5 `W` column), when known.
+    pub worlds: Option<u8>,
+    /// Allegiance code, e.g. `"ImDd"`. `None` for unclaimed space.
+    pub allegiance: Option<String>,
+    /// Traveller-Map style stellar data, e.g. `"G2 V M9 V"`.
+    pub stellar: String,
+}
+
+impl SectorHex {
+    /// The hex location as the usual four-digit `"CCRR"` string.
+    pub fn hex(&self) -> String {
+        format!("{:02}{:02}", self.x, self.y)
+    }
+
+    /// The system constraints this profile pins: the main world, the
+    /// stars from `stellar`, the PBG's belts and gas giants, and — when
+    /// `worlds` is known — the extra rocky planets left once the main
+    /// world, belts and giants are backed out of it. The same constraints
+    /// `/api/system` builds from the equivalent query.
+    pub fn constraints(&self) -> Result<SystemConstraints, WorldgenError> {
+        let belts = self.pbg.belts as usize;
+        let giants = self.pbg.gas_giants as usize;
+        let planets = self
+            .worlds
+            .map(|w| (w as i32 - 1 - belts as i32 - giants as i32).max(0) as usize)
+            .unwrap_or(0);
+        build_constraints(
+            &self.name,
+            &self.uwp,
+            &parse_stellar(&self.stellar),
+            giants,
+            belts,
+            planets,
+        )
+    }
+
+    /// The main world as a [`World`], with the hex's zone, bases and
+    /// coordinates filled in. Trade classes come from the trade codes in
+    /// `remarks`, or are derived from the UWP if there are none.
+    pub fn world(&self) -> Result<World, WorldgenError> {
+        let mut world = World::from_uwp(&self.name, &self.uwp, false, true).map_err(|e| {
+            WorldgenError::Constraints(vec![ConstraintError::ContradictoryUwp(e.to_string())])
+        })?;
+        let listed: Vec<TradeClass> = self
+            .remarks
+            .iter()
+            .filter_map(|r| string_to_trade_class(r))
+            .collect();
+        if listed.is_empty() {
+            world.gen_trade_classes();
+        } else {
+            world.set_trade_classes(listed);
+        }
+        world.travel_zone = self.zone;
+        world.set_facilities(self.bases.clone());
+        world.coordinates = Some((self.x as i32, self.y as i32));
+        Ok(world)
+    }
+}
+
+/// A generated sector (or subsector): the occupied hexes, in column-major
+/// order, under the sector name their seeds were derived from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Sector {
+    pub name: String,
+    pub hexes: Vec<SectorHex>,
+}
+
+impl Sector {
+    /// Roll every hex of a full 32×40 sector.
+    pub fn generate(name: &str, density: Density) -> Sector {
+        Sector::generate_region(name, 1..=SECTOR_WIDTH, 1..=SECTOR_HEIGHT, density)
+    }
+
+    /// Roll one 8×10 subsector, lettered `A`–`P` left to right, top to
+    /// bottom. The hexes keep their sector-wide coordinates and seeds, so
+    /// they match the same hexes of [`Sector::generate`]. Returns `None`
+    /// for a letter outside `A`–`P`.
+    pub fn generate_subsector(name: &str, subsector: char, density: Density) -> Option<Sector> {
+        let index = match subsector.to_ascii_uppercase() {
+            c @ 'A'..='P' => c as u8 - b'A',
+            _ => return None,
+        };
+        let x0 = (index % 4) * SUBSECTOR_WIDTH;
+        let y0 = (index / 4) * SUBSECTOR_HEIGHT;
+        Some(Sector::generate_region(
+            name,
+            x0 + 1..=x0 + SUBSECTOR_WIDTH,
+            y0 + 1..=y0 + SUBSECTOR_HEIGHT,
+            density,
+        ))
+    }
+
+    fn generate_region(
+        name: &str,
+        columns: std::ops::RangeInclusive<u8>,
+        rows: std::ops::RangeInclusive<u8>,
+        density: Density,
+    ) -> Sector {
+        let hexes = columns
+            .flat_map(|x| rows.clone().map(move |y| (x, y)))
+            .filter_map(|(x, y)| generate_hex(name, x, y, density))
+            .collect();
+        Sector {
+            name: name.to_string(),
+            hexes,
+        }
+    }
+
+    /// The occupied hex at `(x, y)`, if any.
+    pub fn hex(&self, x: u8, y: u8) -> Option<&SectorHex> {
+        self.hexes.iter().find(|h| h.x == x && h.y == y)
+    }
+
+    /// The seed `/api/system` uses for `hex` in this sector.
+    pub fn system_seed(&self, hex: &SectorHex) -> u64 {
+        system_seed(&self.name, hex.x, hex.y)
+    }
+
+    /// Generate the full system for one hex of this sector.
+    pub fn expand(&self, hex: &SectorHex) -> Result<System, WorldgenError> {
+        Ok(System::generate_from_constraints_seeded(
+            self.system_seed(hex),
+            hex.constraints()?,
+        )?)
+    }
+}
+
+/// Roll a single hex of `sector`: `None` if no system is present at
+/// `density`, otherwise its profile.
+pub fn generate_hex(sector: &str, x: u8, y: u8, density: Density) -> Option<SectorHex> {
+    let _guard = RngScope::new(hex_profile_seed(system_seed(sector, x, y)));
+    if roll_1d6() < density.target() {
+        return None;
+    }
+
+    let name = gen_planet_name();
+    let port = match roll_2d6() {
+        2..=4 => PortCode::A,
+        5..=6 => PortCode::B,
+        7..=8 => PortCode::C,
+        9 => PortCode::D,
+        10..=11 => PortCode::E,
+        _ => PortCode::X,
+    };
+    let size = roll_2d6() - 2;
+    let atmosphere = if size == 0 {
+        0
+    } else {
+        (roll_2d6() - 7 + size).clamp(0, 15)
+    };
+    let hydro = if size <= 1 {
+        0
+    } else {
+        let dm = if atmosphere <= 1 || atmosphere >= 10 {
+            -4
+        } else {
+            0
+        };
+        (roll_2d6() - 7 + size + dm).clamp(0, 10)
+    };
+    let population = roll_2d6() - 2;
+    let (government, law, tech) = if population == 0 {
+        (0, 0, 0)
+    } else {
+        let government = (roll_2d6() - 7 + population).clamp(0, 15);
+        let law = (roll_2d6() - 7 + government).clamp(0, 15);
+        let tech = (roll_1d6()
+            + tech_level_dm(port, size, atmosphere, hydro, population, government))
+        .max(0);
+        (government, law, tech)
+    };
+    let uwp = format!(
+        "{port}{}{}{}{}{}{}-{}",
+        value_to_ehex(size as u32),
+        value_to_ehex(atmosphere as u32),
+        value_to_ehex(hydro as u32),
+        value_to_ehex(population as u32),
+        value_to_ehex(government as u32),
+        value_to_ehex(law as u32),
+        value_to_ehex(tech as u32),
+    );
+
+    let mut bases = Vec::new();
+    if matches!(port, PortCode::A | PortCode::B) && roll_2d6() >= 8 {
+        bases.push(Facility::Naval);
+    }
+    let scout_dm = match port {
+        PortCode::A => Some(-3),
+        PortCode::B => Some(-2),
+        PortCode::C => Some(-1),
+        PortCode::D => Some(0),
+        _ => None,
+    };
+    if let Some(dm) = scout_dm
+        && roll_2d6() + dm >= 7
+    {
+        bases.push(Facility::Scout);
+    }
+
+    let amber = population > 0
+        && (atmosphere >= 10 || matches!(government, 0 | 7 | 10) || law == 0 || law >= 9);
+    let zone = match amber {
+        false => ZoneClassification::Green,
+        true if roll_2d6() == 12 => ZoneClassification::Red,
+        true => ZoneClassification::Amber,
+    };
+
+    let gas_giants = if roll_2d6() >= 10 {
+        0
+    } else {
+        match roll_2d6() {
+            2..=3 => 1,
+            4..=5 => 2,
+            6..=7 => 3,
+            8..=10 => 4,
+            _ => 5,
+        }
+    };
+    let mut belts = if roll_2d6() >= 7 {
+        0
+    } else {
+        match roll_2d6() - gas_giants {
+            1..=3 => 3,
+            4..=6 => 2,
+            _ => 1,
+        }
+    };
+    // An asteroid main world is itself a belt.
+    if size == 0 {
+        belts = belts.max(1);
+    }
+    let pbg = Pbg {
+        population_multiplier: if population == 0 {
+            0
+        } else {
+            rng_random_range(1..=9)
+        },
+        belts: belts as u8,
+        gas_giants: gas_giants as u8,
+    };
+
+    // Same primary-star modifier the system generator applies for a
+    // habitable or heavily populated main world.
+    let star_mod = if (4..=9).contains(&atmosphere) || population >= 8 {
+        4
+    } else {
+        0
+    };
+    let stellar = roll_stellar(star_mod)
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let remarks = match World::from_uwp(&name, &uwp, false, true) {
+        Ok(mut world) => {
+            world.gen_trade_classes();
+            world
+                .get_trade_classes()
+                .iter()
+                .map(|tc| tc.to_string())
+                .collect()
+        }
+        Err(_) => Vec::new(),
+    };
+
+    Some(SectorHex {
+        x,
+        y,
+        name,
+        uwp,
+        bases,
+        remarks,
+        zone,
+        pbg,
+        worlds: None,
+        allegiance: None,
+        stellar,
+    })
+}
+
+/// Classic Traveller tech level modifiers for a main world.
+fn tech_level_dm(
+    port: PortCode,
+    size: i32,
+    atmosphere: i32,
+    hydro: i32,
+    population: i32,
+    government: i32,
+) -> i32 {
+    let port_dm = match port {
+        PortCode::A => 6,
+        PortCode::B => 4,
+        PortCode::C => 2,
+        PortCode::X => -4,
+        _ => 0,
+    };
+    let size_dm = match size {
+        0..=1 => 2,
+        2..=4 => 1,
+        _ => 0,
+    };
+    let atmosphere_dm = match atmosphere {
+        0..=3 | 10..=14 => 1,
+        _ => 0,
+    };
+    let hydro_dm = match hydro {
+        9 => 1,
+        10 => 2,
+        _ => 0,
+    };
+    let population_dm = match population {
+        1..=5 => 1,
+        9 => 2,
+        10 => 4,
+        _ => 0,
+    };
+    let government_dm = match government {
+        0 | 5 => 1,
+        13 => -2,
+        _ => 0,
+    };
+    port_dm + size_dm + atmosphere_dm + hydro_dm + population_dm + government_dm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sector_generation_is_deterministic() {
+        let a = Sector::generate("Spinward Marches", Density::Standard);
+        let b = Sector::generate("Spinward Marches", Density::Standard);
+        assert_eq!(a, b);
+        assert_ne!(a, Sector::generate("Deneb", Density::Standard));
+    }
+
+    #[test]
+    fn density_controls_occupancy() {
+        let sparse = Sector::generate("Trojan Reach", Density::Sparse)
+            .hexes
+            .len();
+        let standard = Sector::generate("Trojan Reach", Density::Standard)
+            .hexes
+            .len();
+        let dense = Sector::generate("Trojan Reach", Density::Dense).hexes.len();
+        assert!(sparse < standard && standard < dense);
+        // 1280 hexes at 1-in-2: comfortably within a few hundred of 640.
+        assert!(
+            (500..800).contains(&standard),
+            "standard density gave {standard}"
+        );
+    }
+
+    #[test]
+    fn subsector_matches_the_same_slice_of_the_sector() {
+        let sector = Sector::generate("Spinward Marches", Density::Standard);
+        let c = Sector::generate_subsector("Spinward Marches", 'C', Density::Standard).unwrap();
+        assert!(!c.hexes.is_empty());
+        for hex in &c.hexes {
+            assert!((17..=24).contains(&hex.x) && (1..=10).contains(&hex.y));
+            assert_eq!(sector.hex(hex.x, hex.y), Some(hex));
+        }
+        let expected = sector
+            .hexes
+            .iter()
+            .filter(|h| (17..=24).contains(&h.x) && (1..=10).contains(&h.y))
+            .count();
+        assert_eq!(c.hexes.len(), expected);
+        assert!(Sector::generate_subsector("Spinward Marches", 'Q', Density::Standard).is_none());
+    }
+
+    #[test]
+    fn occupied_hex_keeps_its_profile_across_densities() {
+        let dense = Sector::generate_subsector("Reft", 'F', Density::Dense).unwrap();
+        let sparse = Sector::generate_subsector("Reft", 'F', Density::Sparse).unwrap();
+        for hex in &sparse.hexes {
+            assert_eq!(dense.hex(hex.x, hex.y), Some(hex));
+        }
+    }
+
+    #[test]
+    fn profiles_are_well_formed() {
+        for hex in Sector::generate("Gushemege", Density::Dense).hexes {
+            let world = hex.world().expect("generated UWP parses");
+            assert_eq!(hex.uwp.len(), 9);
+            assert_eq!(hex.hex().len(), 4);
+            assert_eq!(
+                hex.pbg.population_multiplier == 0,
+                world.get_population() == 0
+            );
+            let stars = parse_stellar(&hex.stellar);
+            assert!((1..=3).contains(&stars.len()), "stellar {:?}", hex.stellar);
+            if hex.bases.contains(&Facility::Naval) {
+                assert!(hex.uwp.starts_with('A') || hex.uwp.starts_with('B'));
+            }
+        }
+    }
+
+    #[test]
+    fn expanded_hex_is_the_api_system() {
+        let sector =
+            Sector::generate_subsector("Spinward Marches", 'C', Density::Standard).unwrap();
+        let hex = &sector.hexes[0];
+        let system = sector.expand(hex).unwrap();
+
+        let cs = build_constraints(
+            &hex.name,
+            &hex.uwp,
+            &parse_stellar(&hex.stellar),
+            hex.pbg.gas_giants as usize,
+            hex.pbg.belts as usize,
+            0,
+        )
+        .unwrap();
+        let expected =
+            crate::api::generate_system_json(system_seed("Spinward Marches", hex.x, hex.y), cs)
+                .unwrap();
+        assert_eq!(system.to_json().unwrap(), expected);
+    }
+}
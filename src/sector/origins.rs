@@ -0,0 +1,362 @@
+//! # Sector Origins
+//!
+//! Where each named sector sits in Charted Space, and the galactic hex
+//! coordinates that follow from it. TravellerMap numbers sectors on a
+//! grid with Core at `(0, 0)`, `x` growing trailing and `y` growing
+//! rimward, so Spinward Marches is `(-4, -1)` and Deneb, trailing of it,
+//! is `(-3, -1)`. A hex's galactic position is its sector origin scaled
+//! by the sector size plus its sector-relative column and row, which lets
+//! distances and jump ranges cross sector borders.
+//!
+//! [`SectorOrigins::builtin`] knows the sectors around the Imperial core
+//! that the app uses most. The full table is TravellerMap's universe
+//! listing (`/api/universe`), which [`SectorOrigins::from_universe_json`]
+//! reads — from a saved file for offline use, or straight from the
+//! service. Homebrew sectors can be placed with [`SectorOrigins::insert`].
+//!
+//! Sector names are matched ignoring case, whitespace and punctuation, so
+//! `"Reaver's Deep"`, `"ReaversDeep"` and `"reavers deep"` are the same
+//! sector.
+//!
+//! ```rust,ignore
+//! use worldgen::sector::origins::SectorOrigins;
+//!
+//! let origins = SectorOrigins::builtin();
+//! // Spinward Marches 3210 to Deneb 0110, across the border.
+//! let d = origins.distance(("Spinward Marches", 32, 10), ("Deneb", 1, 10));
+//! assert_eq!(d, Some(1));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::sector::{SECTOR_HEIGHT, SECTOR_WIDTH};
+use crate::util::calculate_hex_distance;
+
+/// Sector origins verified against
+/// `https://travellermap.com/api/coordinates?sector=NAME`.
+const BUILTIN_ORIGINS: &[(&str, (i32, i32))] = &[
+    ("Spinward Marches", (-4, -1)),
+    ("Deneb", (-3, -1)),
+    ("Core", (0, 0)),
+    ("Trojan Reach", (-4, 0)),
+    ("Reaver's Deep", (-4, 1)),
+    ("Gvurrdon", (-4, -2)),
+    ("Tuglikki", (-3, -2)),
+    ("Corridor", (-2, -1)),
+    ("Vland", (-1, -1)),
+    ("Lishun", (-1, 0)),
+    ("Antares", (0, -1)),
+    ("Ilelish", (-1, 1)),
+    ("Fornast", (1, 0)),
+    ("Massilia", (1, 1)),
+    ("Diaspora", (2, 1)),
+];
+
+/// Why a sector-origin table couldn't be read.
+#[derive(Debug, thiserror::Error)]
+pub enum OriginsError {
+    /// The document wasn't TravellerMap universe JSON.
+    #[error("sector origin table invalid: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A lookup between sector names and their grid positions.
+#[derive(Debug, Clone, Default)]
+pub struct SectorOrigins {
+    /// Normalized name → origin.
+    by_name: HashMap<String, (i32, i32)>,
+    /// Origin → display name (the name it was inserted under; aliases
+    /// don't change it).
+    by_origin: HashMap<(i32, i32), String>,
+}
+
+/// One sector of a TravellerMap `/api/universe` response.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct UniverseSector {
+    x: i32,
+    y: i32,
+    #[serde(default)]
+    abbreviation: Option<String>,
+    #[serde(default)]
+    names: Vec<UniverseName>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct UniverseName {
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Universe {
+    sectors: Vec<UniverseSector>,
+}
+
+impl SectorOrigins {
+    /// An empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in table of commonly used sectors, shared process-wide.
+    pub fn builtin() -> &'static SectorOrigins {
+        static BUILTIN: OnceLock<SectorOrigins> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            let mut origins = SectorOrigins::new();
+            for (name, origin) in BUILTIN_ORIGINS {
+                origins.insert(name, *origin);
+            }
+            origins
+        })
+    }
+
+    /// Read a TravellerMap universe listing (the `/api/universe` JSON).
+    /// Every name and abbreviation a sector carries is indexed; the first
+    /// name is its display name.
+    pub fn from_universe_json(json: &str) -> Result<SectorOrigins, OriginsError> {
+        let universe: Universe = serde_json::from_str(json)?;
+        let mut origins = SectorOrigins::new();
+        for sector in universe.sectors {
+            let origin = (sector.x, sector.y);
+            let mut names = sector
+                .names
+                .iter()
+                .map(|n| n.text.as_str())
+                .chain(sector.abbreviation.as_deref());
+            let Some(display) = names.next() else {
+                continue;
+            };
+            origins.insert(display, origin);
+            for alias in names {
+                origins.alias(alias, origin);
+            }
+        }
+        Ok(origins)
+    }
+
+    /// Place the sector `name` at `origin`. A sector already placed
+    /// elsewhere moves, leaving its old position empty, and a sector
+    /// already at `origin` is displaced, along with its other names.
+    pub fn insert(&mut self, name: &str, origin: (i32, i32)) {
+        let key = normalize(name);
+        if let Some(old) = self.by_name.remove(&key)
+            && old != origin
+            && self
+                .by_origin
+                .get(&old)
+                .is_some_and(|n| normalize(n) == key)
+        {
+            self.vacate(old);
+        }
+        if self
+            .by_origin
+            .get(&origin)
+            .is_some_and(|n| normalize(n) != key)
+        {
+            self.vacate(origin);
+        }
+        self.by_name.insert(key, origin);
+        self.by_origin.insert(origin, name.to_string());
+    }
+
+    /// Another name for the sector at `origin`; its display name stays.
+    fn alias(&mut self, name: &str, origin: (i32, i32)) {
+        self.by_name.insert(normalize(name), origin);
+        self.by_origin
+            .entry(origin)
+            .or_insert_with(|| name.to_string());
+    }
+
+    /// Forget the sector at `origin` and every name it goes by.
+    fn vacate(&mut self, origin: (i32, i32)) {
+        self.by_origin.remove(&origin);
+        self.by_name.retain(|_, o| *o != origin);
+    }
+
+    /// Add every entry of `other` that this table doesn't already have.
+    pub fn merge(&mut self, other: &SectorOrigins) {
+        for (key, origin) in &other.by_name {
+            self.by_name.entry(key.clone()).or_insert(*origin);
+        }
+        for (origin, name) in &other.by_origin {
+            self.by_origin
+                .entry(*origin)
+                .or_insert_with(|| name.clone());
+        }
+    }
+
+    /// Number of distinct sector positions known.
+    pub fn len(&self) -> usize {
+        self.by_origin.len()
+    }
+
+    /// Whether the table is empty.
+    pub fn is_empty(&self) -> bool {
+        self.by_origin.is_empty()
+    }
+
+    /// Grid position of the sector `name`.
+    pub fn origin(&self, name: &str) -> Option<(i32, i32)> {
+        self.by_name.get(&normalize(name)).copied()
+    }
+
+    /// Display name of the sector at `origin`.
+    pub fn name_at(&self, origin: (i32, i32)) -> Option<&str> {
+        self.by_origin.get(&origin).map(|s| s.as_str())
+    }
+
+    /// Galactic hex coordinates of sector-relative hex `(hex_x, hex_y)`.
+    /// Column parity is preserved, so hex distances computed on galactic
+    /// coordinates agree with in-sector ones.
+    pub fn to_galactic(&self, sector: &str, hex_x: i32, hex_y: i32) -> Option<(i32, i32)> {
+        let (sx, sy) = self.origin(sector)?;
+        Some((
+            sx * SECTOR_WIDTH as i32 + hex_x,
+            sy * SECTOR_HEIGHT as i32 + hex_y,
+        ))
+    }
+
+    /// The sector and sector-relative hex at galactic `(gx, gy)`, or
+    /// `None` if no known sector covers it.
+    pub fn from_galactic(&self, gx: i32, gy: i32) -> Option<(String, i32, i32)> {
+        let (width, height) = (SECTOR_WIDTH as i32, SECTOR_HEIGHT as i32);
+        let origin = ((gx - 1).div_euclid(width), (gy - 1).div_euclid(height));
+        let name = self.name_at(origin)?;
+        Some((
+            name.to_string(),
+            (gx - 1).rem_euclid(width) + 1,
+            (gy - 1).rem_euclid(height) + 1,
+        ))
+    }
+
+    /// Jump distance in parsecs between two `(sector, hex_x, hex_y)`
+    /// locations. Hexes in the same sector need no table entry; across
+    /// sectors both must be known.
+    pub fn distance(&self, a: (&str, i32, i32), b: (&str, i32, i32)) -> Option<i32> {
+        if same_sector(a.0, b.0) {
+            return Some(calculate_hex_distance(a.1, a.2, b.1, b.2));
+        }
+        let (ax, ay) = self.to_galactic(a.0, a.1, a.2)?;
+        let (bx, by) = self.to_galactic(b.0, b.1, b.2)?;
+        Some(calculate_hex_distance(ax, ay, bx, by))
+    }
+}
+
+/// Whether two sector names refer to the same sector, using the same
+/// loose matching as the table lookups.
+pub fn same_sector(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+/// Lowercase letters and digits only, so spacing and apostrophes in
+/// sector names don't matter.
+pub(crate) fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_match_loosely() {
+        let origins = SectorOrigins::builtin();
+        assert_eq!(origins.origin("Spinward Marches"), Some((-4, -1)));
+        assert_eq!(origins.origin("spinwardmarches"), Some((-4, -1)));
+        assert_eq!(origins.origin("Reavers Deep"), Some((-4, 1)));
+        assert_eq!(origins.origin("Nowhere"), None);
+        assert_eq!(origins.name_at((-3, -1)), Some("Deneb"));
+    }
+
+    #[test]
+    fn galactic_round_trip_and_border_distance() {
+        let origins = SectorOrigins::builtin();
+        let regina = origins.to_galactic("Spinward Marches", 19, 10).unwrap();
+        assert_eq!(
+            origins.from_galactic(regina.0, regina.1),
+            Some(("Spinward Marches".to_string(), 19, 10))
+        );
+        // The last column of the Marches borders the first of Deneb, and
+        // its bottom row borders the top row of Trojan Reach.
+        let edge = origins.to_galactic("Spinward Marches", 32, 10).unwrap();
+        assert_eq!(
+            origins.from_galactic(edge.0 + 1, edge.1),
+            Some(("Deneb".to_string(), 1, 10))
+        );
+        assert_eq!(
+            origins.distance(("Spinward Marches", 32, 10), ("Deneb", 1, 10)),
+            Some(1)
+        );
+        assert_eq!(
+            origins.distance(("Spinward Marches", 10, 40), ("Trojan Reach", 10, 1)),
+            Some(1)
+        );
+        // Same-sector distances don't need the sector to be known.
+        assert_eq!(
+            origins.distance(("Homebrew", 1, 1), ("homebrew", 1, 3)),
+            Some(2)
+        );
+        assert_eq!(origins.distance(("Homebrew", 1, 1), ("Deneb", 1, 1)), None);
+    }
+
+    #[test]
+    fn reinserting_moves_or_displaces_a_sector() {
+        let mut origins = SectorOrigins::builtin().clone();
+        let round_trip = |origins: &SectorOrigins, name: &str| {
+            let (gx, gy) = origins.to_galactic(name, 5, 5)?;
+            origins.from_galactic(gx, gy)
+        };
+
+        // A sector moved to a new origin no longer answers at the old one.
+        origins.insert("Homebrew", (40, 40));
+        origins.insert("homebrew", (41, 40));
+        assert_eq!(origins.name_at((40, 40)), None);
+        assert_eq!(
+            round_trip(&origins, "Homebrew"),
+            Some(("homebrew".to_string(), 5, 5))
+        );
+
+        // A sector placed on an occupied origin displaces the old one.
+        origins.insert("Usurper", (-3, -1));
+        assert_eq!(origins.origin("Deneb"), None);
+        assert_eq!(origins.name_at((-3, -1)), Some("Usurper"));
+        assert_eq!(
+            round_trip(&origins, "Usurper"),
+            Some(("Usurper".to_string(), 5, 5))
+        );
+        assert_eq!(
+            round_trip(&origins, "Spinward Marches").unwrap().0,
+            "Spinward Marches"
+        );
+    }
+
+    #[test]
+    fn universe_json_is_read() {
+        let json = r#"{"Sectors":[
+            {"X":-5,"Y":-1,"Milieu":"M1105","Abbreviation":"Fore",
+             "Names":[{"Text":"Foreven"}]},
+            {"X":-4,"Y":-1,"Abbreviation":"Spin",
+             "Names":[{"Text":"Spinward Marches"},{"Text":"Tloql","Lang":"zh"}]}
+        ]}"#;
+        let origins = SectorOrigins::from_universe_json(json).unwrap();
+        assert_eq!(origins.len(), 2);
+        assert_eq!(origins.origin("Foreven"), Some((-5, -1)));
+        assert_eq!(origins.origin("Spin"), Some((-4, -1)));
+        assert_eq!(origins.origin("Tloql"), Some((-4, -1)));
+        assert_eq!(origins.name_at((-4, -1)), Some("Spinward Marches"));
+        assert!(SectorOrigins::from_universe_json("[]").is_err());
+
+        let mut merged = SectorOrigins::builtin().clone();
+        merged.merge(&origins);
+        assert_eq!(merged.origin("Foreven"), Some((-5, -1)));
+        assert_eq!(merged.name_at((-4, -1)), Some("Spinward Marches"));
+    }
+}
@@ -68,6 +68,21 @@ pub fn planet_seed(system_seed: u64, planet_orbit: i32, planet_name: &str) -> u6
     h.finish()
 }
 
+/// Derive the seed for a hex's sector-map profile — whether a system is
+/// present, and its main-world UWP, bases, zone, PBG and stellar data —
+/// from that hex's [`system_seed`].
+///
+/// Kept separate from the system seed so rolling the sector map doesn't
+/// consume any of the stream the system generator draws from: expanding a
+/// generated hex with `system_seed` still produces exactly the system
+/// `/api/system` renders for it.
+pub fn hex_profile_seed(system_seed: u64) -> u64 {
+    let mut h = new_hasher();
+    h.write(b"hex_profile_v1\0");
+    h.write_u64(system_seed);
+    h.finish()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -119,6 +134,17 @@ mod tests {
         );
     }
 
+    #[test]
+    fn hex_profile_seed_is_stable() {
+        let sys = system_seed("Trojan Reach", 31, 28);
+        assert_eq!(
+            hex_profile_seed(sys),
+            4464780169077382743_u64,
+            "hash recipe changed; bump version"
+        );
+        assert_ne!(hex_profile_seed(sys), sys);
+    }
+
     #[test]
     fn planet_seed_varies_by_inputs() {
         let sys = system_seed("Trojan Reach", 31, 28);
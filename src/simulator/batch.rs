@@ -0,0 +1,388 @@
+//! Monte Carlo batch runs of the ship simulator.
+//!
+//! One voyage is an anecdote; a batch replays the same
+//! [`SimulationParams`] under many seeds and summarizes how the outcomes
+//! spread — the owner's profit distribution, how often the ship is
+//! marooned or comes home on time, and which incidents to expect. That is
+//! what tells a player whether a mortgage is survivable before they sign.
+//!
+//! Run `i` of a batch uses seed `base_seed + i` (wrapping), where the base
+//! seed is `SimulationParams::seed` or a freshly picked one reported in
+//! [`BatchSummary::base_seed`]. Any single run can therefore be replayed on
+//! its own with `executor::run_simulation`.
+//!
+//! The summary types compile with the rest of the simulator so the
+//! frontend can render them; `run_batch` itself needs the `backend`
+//! feature.
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::types::{Action, SimulationParams, SimulationResult};
+
+#[cfg(feature = "backend")]
+use crate::simulator::executor::{ExecutorError, run_simulation};
+#[cfg(feature = "backend")]
+use crate::simulator::world_source::WorldSource;
+
+/// Most voyages a single batch request may ask for.
+pub const MAX_BATCH_RUNS: u32 = 1000;
+
+/// A batch request: the voyage to repeat and how many times.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchParams {
+    /// The voyage. Its `seed`, if set, is the batch's base seed.
+    pub params: SimulationParams,
+    /// Number of voyages to run, `1..=MAX_BATCH_RUNS`.
+    pub runs: u32,
+}
+
+/// Distribution of the owner's profit across a batch, in credits.
+/// Percentiles use the nearest-rank method.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfitStats {
+    pub mean: f64,
+    pub min: i64,
+    pub p5: i64,
+    pub p25: i64,
+    pub median: i64,
+    pub p75: i64,
+    pub p95: i64,
+    pub max: i64,
+}
+
+/// Expected number of each incident kind per voyage.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IncidentRates {
+    pub piracy: f64,
+    pub trade_scam: f64,
+    pub crew_loss: f64,
+    pub accident: f64,
+    pub government: f64,
+    /// Incidents of a referee's own, from a custom incident table.
+    #[serde(default)]
+    pub other: f64,
+    /// Incident rolls the captain talked their way out of.
+    pub avoided: f64,
+}
+
+/// Aggregate outcome of a batch.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchSummary {
+    /// Voyages run.
+    pub runs: u32,
+    /// Seed of run 0; run `i` used `base_seed + i`.
+    pub base_seed: u64,
+    /// Owner's profit after crew share and mortgage.
+    pub owner_profit: ProfitStats,
+    /// Fraction of runs where the owner lost money.
+    pub loss_probability: f64,
+    /// Fraction of runs that ended marooned.
+    pub marooned_probability: f64,
+    /// Fraction of runs whose budget went negative at any point.
+    pub went_negative_probability: f64,
+    /// Fraction of runs that made it back to the home world.
+    pub returned_home_probability: f64,
+    /// Fraction of runs back home on or before the target date.
+    pub on_time_probability: f64,
+    /// Mean number of jumps per voyage.
+    pub mean_jumps: f64,
+    /// Expected incidents per voyage, by kind.
+    pub incidents: IncidentRates,
+}
+
+/// Incidents seen during one voyage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IncidentCounts {
+    pub piracy: u32,
+    pub trade_scam: u32,
+    pub crew_loss: u32,
+    pub accident: u32,
+    pub government: u32,
+    pub other: u32,
+    pub avoided: u32,
+}
+
+impl IncidentCounts {
+    /// Count `action` if it is an incident roll.
+    pub fn observe(&mut self, action: &Action) {
+        match action {
+            Action::IncidentPiracy { .. } => self.piracy += 1,
+            Action::IncidentTradeScam { .. } => self.trade_scam += 1,
+            Action::IncidentCrewLoss { .. } => self.crew_loss += 1,
+            Action::IncidentAccident { .. } => self.accident += 1,
+            Action::IncidentGovernment { .. } => self.government += 1,
+            Action::Incident { .. } => self.other += 1,
+            Action::IncidentAvoided { .. } => self.avoided += 1,
+            _ => {}
+        }
+    }
+}
+
+/// Collects per-voyage outcomes and reduces them to a [`BatchSummary`].
+#[derive(Debug, Clone)]
+pub struct BatchTally {
+    base_seed: u64,
+    params: SimulationParams,
+    profits: Vec<i64>,
+    marooned: u32,
+    went_negative: u32,
+    returned_home: u32,
+    on_time: u32,
+    jumps: u64,
+    incidents: IncidentCounts,
+}
+
+impl BatchTally {
+    /// An empty tally for voyages of `params` seeded from `base_seed`.
+    pub fn new(params: SimulationParams, base_seed: u64) -> Self {
+        BatchTally {
+            base_seed,
+            params,
+            profits: Vec::new(),
+            marooned: 0,
+            went_negative: 0,
+            returned_home: 0,
+            on_time: 0,
+            jumps: 0,
+            incidents: IncidentCounts::default(),
+        }
+    }
+
+    /// Record one finished voyage and the incidents it ran into.
+    pub fn record(&mut self, result: &SimulationResult, incidents: IncidentCounts) {
+        self.profits.push(result.owner_profit);
+        self.marooned += result.marooned as u32;
+        self.went_negative += result.went_negative as u32;
+        self.returned_home += result.returned_home as u32;
+        let on_time = result.returned_home
+            && result
+                .end_date
+                .days_until(self.params.target_completion_date)
+                >= 0;
+        self.on_time += on_time as u32;
+        self.jumps += result.jumps as u64;
+        self.incidents.piracy += incidents.piracy;
+        self.incidents.trade_scam += incidents.trade_scam;
+        self.incidents.crew_loss += incidents.crew_loss;
+        self.incidents.accident += incidents.accident;
+        self.incidents.government += incidents.government;
+        self.incidents.other += incidents.other;
+        self.incidents.avoided += incidents.avoided;
+    }
+
+    /// Voyages recorded so far.
+    pub fn runs(&self) -> u32 {
+        self.profits.len() as u32
+    }
+
+    /// Summarize everything recorded. An empty tally summarizes to zeros.
+    pub fn summary(&self) -> BatchSummary {
+        let runs = self.runs();
+        if runs == 0 {
+            return BatchSummary {
+                base_seed: self.base_seed,
+                ..BatchSummary::default()
+            };
+        }
+        let n = runs as f64;
+        let rate = |count: u32| count as f64 / n;
+
+        let mut sorted = self.profits.clone();
+        sorted.sort_unstable();
+        let owner_profit = ProfitStats {
+            mean: sorted.iter().map(|&p| p as f64).sum::<f64>() / n,
+            min: sorted[0],
+            p5: percentile(&sorted, 5),
+            p25: percentile(&sorted, 25),
+            median: percentile(&sorted, 50),
+            p75: percentile(&sorted, 75),
+            p95: percentile(&sorted, 95),
+            max: sorted[sorted.len() - 1],
+        };
+        let losses = sorted.iter().filter(|&&p| p < 0).count() as u32;
+
+        BatchSummary {
+            runs,
+            base_seed: self.base_seed,
+            owner_profit,
+            loss_probability: rate(losses),
+            marooned_probability: rate(self.marooned),
+            went_negative_probability: rate(self.went_negative),
+            returned_home_probability: rate(self.returned_home),
+            on_time_probability: rate(self.on_time),
+            mean_jumps: self.jumps as f64 / n,
+            incidents: IncidentRates {
+                piracy: rate(self.incidents.piracy),
+                trade_scam: rate(self.incidents.trade_scam),
+                crew_loss: rate(self.incidents.crew_loss),
+                accident: rate(self.incidents.accident),
+                government: rate(self.incidents.government),
+                other: rate(self.incidents.other),
+                avoided: rate(self.incidents.avoided),
+            },
+        }
+    }
+}
+
+/// Nearest-rank percentile `pct` of a non-empty, ascending slice.
+fn percentile(sorted: &[i64], pct: u32) -> i64 {
+    let rank = (pct as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+/// Run `batch.runs` voyages one after another against `source` and
+/// summarize them. Calls `on_progress` with the number of voyages finished
+/// after each one. The first voyage that fails aborts the batch.
+#[cfg(feature = "backend")]
+pub async fn run_batch(
+    batch: BatchParams,
+    source: &mut impl WorldSource,
+    mut on_progress: impl FnMut(u32) + Send,
+) -> Result<BatchSummary, ExecutorError> {
+    let base_seed = batch.params.seed.unwrap_or_else(rand::random);
+    let mut tally = BatchTally::new(batch.params.clone(), base_seed);
+    for i in 0..batch.runs {
+        let mut params = batch.params.clone();
+        params.seed = Some(base_seed.wrapping_add(i as u64));
+        let mut incidents = IncidentCounts::default();
+        let result = run_simulation(params, source, |step| incidents.observe(&step.action)).await?;
+        tally.record(&result, incidents);
+        on_progress(i + 1);
+    }
+    Ok(tally.summary())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::test_support::voyage_from;
+    use crate::simulator::types::{Date, WorldRef};
+    use crate::trade::ZoneClassification;
+
+    /// A voyage due home on day 100, for judging on-time arrivals.
+    fn params() -> SimulationParams {
+        SimulationParams {
+            target_completion_date: Date::new(100, 1105),
+            ..voyage_from(WorldRef {
+                name: "Home".into(),
+                uwp: "A788899-A".into(),
+                sector: "Test".into(),
+                hex_x: 10,
+                hex_y: 10,
+                zone: ZoneClassification::Green,
+            })
+        }
+    }
+
+    fn result(owner_profit: i64, end_day: u16, returned_home: bool) -> SimulationResult {
+        SimulationResult {
+            final_budget: 0,
+            gross_profit: owner_profit,
+            crew_share: 0,
+            owner_profit,
+            end_date: Date::new(end_day, 1105),
+            jumps: 4,
+            completed_normally: true,
+            returned_home,
+            went_negative: false,
+            marooned: false,
+            marooned_at: None,
+            marooned_on: None,
+            rescue_arrives_on: None,
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn percentiles_use_nearest_rank() {
+        let sorted: Vec<i64> = (1..=20).collect();
+        assert_eq!(percentile(&sorted, 5), 1);
+        assert_eq!(percentile(&sorted, 25), 5);
+        assert_eq!(percentile(&sorted, 50), 10);
+        assert_eq!(percentile(&sorted, 95), 19);
+        assert_eq!(percentile(&[7], 5), 7);
+        assert_eq!(percentile(&[7], 95), 7);
+    }
+
+    #[test]
+    fn tally_summarizes_outcomes() {
+        let mut tally = BatchTally::new(params(), 42);
+        let piracy = IncidentCounts {
+            piracy: 1,
+            avoided: 2,
+            ..IncidentCounts::default()
+        };
+        tally.record(&result(-50_000, 90, true), piracy);
+        tally.record(&result(100_000, 120, true), IncidentCounts::default());
+        let mut marooned = result(-300_000, 60, false);
+        marooned.marooned = true;
+        marooned.went_negative = true;
+        marooned.jumps = 2;
+        tally.record(&marooned, piracy);
+        tally.record(&result(250_000, 100, true), IncidentCounts::default());
+
+        let s = tally.summary();
+        assert_eq!(s.runs, 4);
+        assert_eq!(s.base_seed, 42);
+        assert_eq!(s.owner_profit.mean, 0.0);
+        assert_eq!(s.owner_profit.min, -300_000);
+        assert_eq!(s.owner_profit.median, -50_000);
+        assert_eq!(s.owner_profit.max, 250_000);
+        assert_eq!(s.loss_probability, 0.5);
+        assert_eq!(s.marooned_probability, 0.25);
+        assert_eq!(s.went_negative_probability, 0.25);
+        assert_eq!(s.returned_home_probability, 0.75);
+        // Day 120 is past the day-100 target; day 100 itself is on time.
+        assert_eq!(s.on_time_probability, 0.5);
+        assert_eq!(s.mean_jumps, 3.5);
+        assert_eq!(s.incidents.piracy, 0.5);
+        assert_eq!(s.incidents.avoided, 1.0);
+        assert_eq!(s.incidents.accident, 0.0);
+    }
+
+    #[test]
+    fn empty_tally_summarizes_to_zeros() {
+        let s = BatchTally::new(params(), 7).summary();
+        assert_eq!(s.runs, 0);
+        assert_eq!(s.base_seed, 7);
+        assert_eq!(s.owner_profit, ProfitStats::default());
+    }
+
+    #[cfg(feature = "backend")]
+    #[tokio::test]
+    async fn seeded_batches_repeat_and_match_single_runs() {
+        use crate::sector::{Density, Sector};
+        use crate::simulator::test_support::offline_params;
+        use crate::simulator::world_source::SectorFiles;
+
+        let sector = Sector::generate("Batch Test", Density::Dense);
+        let voyage = offline_params(&sector, Some(1000));
+        let batch = BatchParams {
+            params: voyage.clone(),
+            runs: 5,
+        };
+
+        let mut source = SectorFiles::from(sector.clone());
+        let mut progress = Vec::new();
+        let first = run_batch(batch.clone(), &mut source, |n| progress.push(n))
+            .await
+            .unwrap();
+        assert_eq!(progress, vec![1, 2, 3, 4, 5]);
+        assert_eq!(first.runs, 5);
+        assert_eq!(first.base_seed, 1000);
+        assert!(first.owner_profit.min <= first.owner_profit.median);
+        assert!(first.owner_profit.median <= first.owner_profit.max);
+
+        let mut source = SectorFiles::from(sector.clone());
+        let second = run_batch(batch, &mut source, |_| {}).await.unwrap();
+        assert_eq!(first, second);
+
+        // Run 3 of the batch is the single voyage seeded 1003, so it can
+        // be replayed on its own.
+        let mut single = voyage;
+        single.seed = Some(1003);
+        let mut source = SectorFiles::from(sector);
+        let result = run_simulation(single, &mut source, |_| {}).await.unwrap();
+        assert!((first.owner_profit.min..=first.owner_profit.max).contains(&result.owner_profit));
+    }
+}
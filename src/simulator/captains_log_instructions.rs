@@ -3,7 +3,7 @@
 //! [`INSTRUCTIONS`] is the static text that prefixes every captain's-log
 //! call to Vertex AI. The dynamic voyage data (per-port visits,
 //! financials, incidents) is appended *after* this constant by
-//! [`crate::components::captains_log_prompt::build_prompt`].
+//! [`crate::simulator::captains_log_prompt::build_prompt`].
 //!
 //! Edit freely to retune tone, the captain-name distribution, the
 //! Traveller-canon allow/deny lists, the marooned hook, etc. Do **not**
@@ -1,24 +1,29 @@
 //! Build the captain's-log prompt sent to `gemini-3-flash-preview`.
 //!
-//! This module is purely client-side text assembly: it walks the
-//! [`SimulationStep`] stream, coalesces consecutive steps at the same
-//! world into per-port visit blocks, and renders the whole thing into a
-//! single prompt string. The string is then sent (as-is) to the backend,
-//! which forwards it as the user message of `streamGenerateContent`.
+//! This module is pure text assembly: it walks the [`SimulationStep`]
+//! stream, coalesces consecutive steps at the same world into per-port
+//! visit blocks, and renders the whole thing into a single prompt string.
+//! The client sends the string (as-is) to the backend, which forwards it
+//! as the user message of `streamGenerateContent`. The same visits feed
+//! the template-written log in [`crate::simulator::captains_log_template`]
+//! that the backend falls back on, so both live with the simulator rather
+//! than the UI.
 //!
 //! The static instruction header (tone, name distribution, canon rules,
 //! marooned hook, etc.) lives in
-//! [`crate::components::captains_log_instructions`] so it can be edited
+//! [`crate::simulator::captains_log_instructions`] so it can be edited
 //! without touching this file's serialization logic.
 //!
 //! No I/O, no async, no native deps — wasm-friendly.
 
 use std::fmt::Write as _;
 
-use crate::components::captains_log_instructions::INSTRUCTIONS;
+use crate::simulator::captains_log_instructions::INSTRUCTIONS;
+use crate::simulator::condition::{ShipSystem, describe_hits, tally_hits};
 use crate::simulator::types::{
     Action, Date, SimulationParams, SimulationResult, SimulationStep, WorldRef,
 };
+use crate::trade::crew::{CrewMember, CrewStatus};
 use crate::trade::{TradeClass, ZoneClassification, uwp_to_trade_classes};
 
 /// Build the full prompt string from a completed simulation.
@@ -149,14 +154,23 @@ fn write_ship_config(out: &mut String, params: &SimulationParams) {
         "\nShip config: {}t cargo, J-{}, {} crew, {} passenger staterooms, {} low berths,\n  weapons rating {}, broker {}, steward {}, leadership {}.",
         s.cargo_capacity,
         s.jump_rating,
-        s.crew_size,
+        s.effective_crew_size(),
         s.passenger_staterooms,
         s.low_berths,
         s.weapons,
-        s.broker_skill,
-        s.steward_skill,
-        s.leadership_skill,
+        s.effective_broker_skill(),
+        s.effective_steward_skill(),
+        s.effective_leadership_skill(),
     );
+    if !s.crew.is_empty() {
+        let roster = s
+            .crew
+            .iter()
+            .map(|m| format!("{} ({})", m.name, m.role))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(out, "  Crew: {roster}.");
+    }
 }
 
 // ---------------------------------------------------------------------
@@ -167,64 +181,78 @@ fn write_ship_config(out: &mut String, params: &SimulationParams) {
 ///
 /// "Same world" is identity by `(sector, hex_x, hex_y)` so revisits show
 /// up as separate visits even if the name string matches.
-struct Visit<'a> {
-    world: WorldRef,
-    arrived: Date,
-    departed: Date,
-
-    sells: Vec<SoldGood>,
-    holds: Vec<HeldGood>,
-    buys: Vec<BoughtGood>,
-    freight_tons: i32,
-    freight_lots: u32,
-    freight_revenue: i64,
-    pax_high: i32,
-    pax_medium: i32,
-    pax_basic: i32,
-    pax_low: i32,
-    pax_revenue: i64,
-    periodic_ticks: u32,
-    periodic_maintenance: i64,
-    periodic_salary: i64,
-    periodic_mortgage: i64,
-    life_support_paid: u32,
-    life_support_total: i64,
-    incidents: Vec<IncidentSummary>,
-    inbound_arrival: Option<InboundArrival<'a>>,
-    budget_after_last: i64,
-    closing_warning: Option<String>,
-    aborted: bool,
-    marooned_here: bool,
+pub(crate) struct Visit<'a> {
+    pub(crate) world: WorldRef,
+    pub(crate) arrived: Date,
+    pub(crate) departed: Date,
+
+    pub(crate) sells: Vec<SoldGood>,
+    pub(crate) holds: Vec<HeldGood>,
+    pub(crate) buys: Vec<BoughtGood>,
+    pub(crate) freight_tons: i32,
+    pub(crate) freight_lots: u32,
+    pub(crate) freight_revenue: i64,
+    pub(crate) mail_containers: i32,
+    pub(crate) mail_tons: i32,
+    pub(crate) mail_revenue: i64,
+    pub(crate) charter: Option<CharterTaken>,
+    pub(crate) pax_high: i32,
+    pub(crate) pax_medium: i32,
+    pub(crate) pax_basic: i32,
+    pub(crate) pax_low: i32,
+    pub(crate) pax_revenue: i64,
+    pub(crate) periodic_ticks: u32,
+    pub(crate) periodic_maintenance: i64,
+    pub(crate) periodic_salary: i64,
+    pub(crate) periodic_mortgage: i64,
+    pub(crate) life_support_paid: u32,
+    pub(crate) life_support_total: i64,
+    pub(crate) refuelling: Vec<String>,
+    pub(crate) shipyard: Vec<String>,
+    pub(crate) berthing_fee: i64,
+    pub(crate) tariffs_paid: i64,
+    pub(crate) incidents: Vec<IncidentSummary>,
+    pub(crate) inbound_arrival: Option<InboundArrival<'a>>,
+    pub(crate) budget_after_last: i64,
+    pub(crate) closing_warning: Option<String>,
+    pub(crate) aborted: bool,
+    pub(crate) marooned_here: bool,
 }
 
-struct SoldGood {
-    good: String,
-    qty: i32,
-    sell_price: i32,
-    paid: i32,
-    profit: i64,
+pub(crate) struct SoldGood {
+    pub(crate) good: String,
+    pub(crate) qty: i32,
+    pub(crate) sell_price: i32,
+    pub(crate) paid: i32,
+    pub(crate) profit: i64,
+}
+pub(crate) struct HeldGood {
+    pub(crate) good: String,
+    pub(crate) qty: i32,
+    pub(crate) would_sell_at: i32,
+    pub(crate) paid: i32,
+    pub(crate) reason: String,
 }
-struct HeldGood {
-    good: String,
-    qty: i32,
-    would_sell_at: i32,
-    paid: i32,
-    reason: String,
+pub(crate) struct BoughtGood {
+    pub(crate) good: String,
+    pub(crate) qty: i32,
+    pub(crate) unit_cost: i32,
+    pub(crate) total_cost: i64,
 }
-struct BoughtGood {
-    good: String,
-    qty: i32,
-    unit_cost: i32,
-    total_cost: i64,
+
+pub(crate) struct CharterTaken {
+    pub(crate) weeks: i32,
+    pub(crate) weekly_rate: i64,
+    pub(crate) revenue: i64,
 }
 
-struct InboundArrival<'a> {
-    from: &'a WorldRef,
-    distance: i32,
-    fuel_cost: i64,
+pub(crate) struct InboundArrival<'a> {
+    pub(crate) from: &'a WorldRef,
+    pub(crate) distance: i32,
+    pub(crate) fuel_cost: i64,
 }
 
-enum IncidentSummary {
+pub(crate) enum IncidentSummary {
     Piracy {
         cargo_lost_tons: i32,
         cargo_breakdown: Vec<(String, i32)>,
@@ -241,6 +269,8 @@ enum IncidentSummary {
     },
     CrewLoss {
         weeks_lost: u32,
+        /// Who was lost, when the ship carries a roster.
+        crewmember: Option<CrewMember>,
     },
     Accident {
         repair_cost: i64,
@@ -249,9 +279,44 @@ enum IncidentSummary {
         fine_credits: i64,
         weeks_lost: u32,
     },
+    Misjump {
+        /// DM from the ship's condition; 0 for a misjump on unrefined
+        /// fuel alone.
+        dm: i32,
+        repair_cost: i64,
+        weeks_lost: u32,
+    },
+    /// Hits from an incident or a breakdown.
+    Damaged {
+        cause: String,
+        hits: Vec<(ShipSystem, u32)>,
+    },
+    ContrabandSeized {
+        seized: Vec<(String, i32)>,
+        buy_cost_sunk: i64,
+        fine: i64,
+    },
+    Impounded {
+        weeks_lost: u32,
+        release_fee: i64,
+    },
+    /// A referee's own incident, from a custom incident table.
+    Other {
+        name: String,
+        text: String,
+        credits_lost: i64,
+        repair_cost: i64,
+        weeks_lost: u32,
+        cargo_lost_tons: i32,
+        crewmember: Option<CrewMember>,
+    },
+}
+
+fn fuel_grade(refined: bool) -> &'static str {
+    if refined { "refined" } else { "unrefined" }
 }
 
-fn world_eq(a: &WorldRef, b: &WorldRef) -> bool {
+pub(crate) fn world_eq(a: &WorldRef, b: &WorldRef) -> bool {
     a.sector == b.sector && a.hex_x == b.hex_x && a.hex_y == b.hex_y
 }
 
@@ -260,7 +325,7 @@ fn world_eq(a: &WorldRef, b: &WorldRef) -> bool {
 /// Identity is full hex (sector + coords) — name alone is not enough,
 /// since the same name can appear in different sectors and we want
 /// revisits to be treated as separate visits anyway.
-fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
+pub(crate) fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
     let mut visits: Vec<Visit<'_>> = Vec::new();
     let mut cur: Option<Visit<'_>> = None;
 
@@ -289,6 +354,10 @@ fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
                 freight_tons: 0,
                 freight_lots: 0,
                 freight_revenue: 0,
+                mail_containers: 0,
+                mail_tons: 0,
+                mail_revenue: 0,
+                charter: None,
                 pax_high: 0,
                 pax_medium: 0,
                 pax_basic: 0,
@@ -300,6 +369,10 @@ fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
                 periodic_mortgage: 0,
                 life_support_paid: 0,
                 life_support_total: 0,
+                refuelling: Vec::new(),
+                shipyard: Vec::new(),
+                berthing_fee: 0,
+                tariffs_paid: 0,
                 incidents: Vec::new(),
                 inbound_arrival: None,
                 budget_after_last: step.budget_after,
@@ -371,6 +444,26 @@ fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
                 v.freight_lots += lots;
                 v.freight_revenue += revenue_pending;
             }
+            Action::LoadMail {
+                containers,
+                tons,
+                revenue_pending,
+            } => {
+                v.mail_containers += containers;
+                v.mail_tons += tons;
+                v.mail_revenue += revenue_pending;
+            }
+            Action::Charter {
+                weeks,
+                weekly_rate,
+                revenue_pending,
+            } => {
+                v.charter = Some(CharterTaken {
+                    weeks: *weeks,
+                    weekly_rate: *weekly_rate,
+                    revenue: *revenue_pending,
+                });
+            }
             Action::BoardPax {
                 high,
                 medium,
@@ -440,9 +533,14 @@ fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
                 credits_lost: *credits_lost,
                 weeks_lost: *weeks_lost,
             }),
-            Action::IncidentCrewLoss { weeks_lost, .. } => {
+            Action::IncidentCrewLoss {
+                weeks_lost,
+                crewmember,
+                ..
+            } => {
                 v.incidents.push(IncidentSummary::CrewLoss {
                     weeks_lost: *weeks_lost,
+                    crewmember: crewmember.clone(),
                 });
             }
             Action::IncidentAccident { repair_cost, .. } => {
@@ -458,6 +556,93 @@ fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
                 fine_credits: *fine_credits,
                 weeks_lost: *weeks_lost,
             }),
+            Action::PayBerthing { fee, .. } => v.berthing_fee += fee,
+            Action::PayImportTariff { tariff, .. } | Action::PayExportTariff { tariff, .. } => {
+                v.tariffs_paid += tariff;
+            }
+            Action::CustomsInspection { .. } => {
+                // Skip — a search that found nothing leaves no mark.
+            }
+            Action::ContrabandSeized {
+                seized,
+                buy_cost_sunk,
+                fine,
+            } => v.incidents.push(IncidentSummary::ContrabandSeized {
+                seized: seized.clone(),
+                buy_cost_sunk: *buy_cost_sunk,
+                fine: *fine,
+            }),
+            Action::ShipImpounded {
+                weeks_lost,
+                release_fee,
+            } => v.incidents.push(IncidentSummary::Impounded {
+                weeks_lost: *weeks_lost,
+                release_fee: *release_fee,
+            }),
+            Action::BuyFuel {
+                tons,
+                refined,
+                cost,
+                ..
+            } => v.refuelling.push(format!(
+                "bought {tons}t {} fuel for {cost} Cr",
+                fuel_grade(*refined)
+            )),
+            Action::SkimGasGiant {
+                tons,
+                refined,
+                gas_giants,
+            } => v.refuelling.push(format!(
+                "skimmed {tons}t {} fuel from a gas giant ({gas_giants} in system)",
+                fuel_grade(*refined)
+            )),
+            Action::ScoopWater { tons, refined } => v.refuelling.push(format!(
+                "scooped {tons}t {} fuel from surface water",
+                fuel_grade(*refined)
+            )),
+            Action::MisjumpCheck {
+                dm,
+                misjumped,
+                weeks_lost,
+                repair_cost,
+                ..
+            } => {
+                if *misjumped {
+                    v.incidents.push(IncidentSummary::Misjump {
+                        dm: *dm,
+                        repair_cost: *repair_cost,
+                        weeks_lost: *weeks_lost,
+                    });
+                }
+            }
+            Action::ShipDamaged { cause, hits } => {
+                v.incidents.push(IncidentSummary::Damaged {
+                    cause: cause.clone(),
+                    hits: tally_hits(hits),
+                });
+            }
+            Action::AnnualMaintenance { weeks, missed } => v.shipyard.push(format!(
+                "annual overhaul, {weeks} weeks in the yard{}",
+                match missed {
+                    ..=1 => String::new(),
+                    2 => " after missing one".to_string(),
+                    n => format!(" after missing {}", n - 1),
+                }
+            )),
+            Action::Repair {
+                repaired,
+                cost,
+                days,
+                deferred,
+            } => v.shipyard.push(format!(
+                "repaired {} for {cost} Cr in {days} days{}",
+                describe_hits(repaired),
+                match deferred {
+                    0 => String::new(),
+                    1 => ", 1 hit left for a better yard".to_string(),
+                    n => format!(", {n} hits left for a better yard"),
+                }
+            )),
             Action::BudgetWarning { note } => {
                 v.closing_warning = Some(note.clone());
             }
@@ -469,6 +654,26 @@ fn coalesce_visits(steps: &[SimulationStep]) -> Vec<Visit<'_>> {
                 v.closing_warning = Some(format!("Aborted — {days_past_target} days past target."));
                 v.aborted = true;
             }
+            Action::Incident {
+                name,
+                text,
+                credits_lost,
+                repair_cost,
+                weeks_lost,
+                cargo_lost_tons,
+                crewmember,
+                ..
+            } => {
+                v.incidents.push(IncidentSummary::Other {
+                    name: name.clone(),
+                    text: text.clone(),
+                    credits_lost: *credits_lost,
+                    repair_cost: *repair_cost,
+                    weeks_lost: *weeks_lost,
+                    cargo_lost_tons: *cargo_lost_tons,
+                    crewmember: crewmember.clone(),
+                });
+            }
             Action::Marooned { .. } => {
                 v.marooned_here = true;
             }
@@ -602,6 +807,20 @@ fn write_visit(out: &mut String, label: &str, v: &Visit<'_>, is_voyage_start: bo
         "Boarded passengers: {}H/{}M/{}B/{}L, pending revenue {} Cr",
         v.pax_high, v.pax_medium, v.pax_basic, v.pax_low, v.pax_revenue
     );
+    if v.mail_containers > 0 {
+        let _ = writeln!(
+            out,
+            "Loaded mail: {} containers ({}t), pending revenue {} Cr",
+            v.mail_containers, v.mail_tons, v.mail_revenue
+        );
+    }
+    if let Some(c) = &v.charter {
+        let _ = writeln!(
+            out,
+            "Chartered: whole ship hired for {} weeks at {} Cr/week, pending revenue {} Cr",
+            c.weeks, c.weekly_rate, c.revenue
+        );
+    }
 
     if v.periodic_ticks > 0 {
         let total = v.periodic_maintenance + v.periodic_salary + v.periodic_mortgage;
@@ -619,6 +838,20 @@ fn write_visit(out: &mut String, label: &str, v: &Visit<'_>, is_voyage_start: bo
         );
     }
 
+    if v.berthing_fee > 0 || v.tariffs_paid > 0 {
+        let _ = writeln!(
+            out,
+            "Port charges: berthing {} Cr, customs tariffs {} Cr",
+            v.berthing_fee, v.tariffs_paid
+        );
+    }
+    if !v.refuelling.is_empty() {
+        let _ = writeln!(out, "Refuelled: {}", v.refuelling.join("; "));
+    }
+    if !v.shipyard.is_empty() {
+        let _ = writeln!(out, "Shipyard: {}", v.shipyard.join("; "));
+    }
+
     for inc in &v.incidents {
         write_incident(out, inc, v.inbound_arrival.is_some());
     }
@@ -689,12 +922,24 @@ fn write_incident(out: &mut String, inc: &IncidentSummary, has_inbound_jump: boo
                 "INCIDENT — Trade scam: lost {credits_lost} Cr, delayed {weeks_lost} weeks."
             );
         }
-        IncidentSummary::CrewLoss { weeks_lost } => {
+        IncidentSummary::CrewLoss {
+            weeks_lost,
+            crewmember,
+        } => {
+            let who = crewmember
+                .as_ref()
+                .map(|m| format!("{}; ", crewmember_lost(m)))
+                .unwrap_or_default();
             let _ = writeln!(
                 out,
-                "INCIDENT — Crew loss: hiring/paperwork delay of {weeks_lost} weeks (no credit penalty)."
+                "INCIDENT — Crew loss: {who}hiring/paperwork delay of {weeks_lost} weeks (no credit penalty)."
             );
         }
+        IncidentSummary::Accident { repair_cost: 0 } => {
+            // The damage follows as hits when the ship's condition is
+            // tracked.
+            out.push_str("INCIDENT — Mechanical accident.\n");
+        }
         IncidentSummary::Accident { repair_cost } => {
             let _ = writeln!(
                 out,
@@ -710,6 +955,98 @@ fn write_incident(out: &mut String, inc: &IncidentSummary, has_inbound_jump: boo
                 "INCIDENT — Government complication: {fine_credits} Cr fine, delayed {weeks_lost} weeks."
             );
         }
+        IncidentSummary::Misjump {
+            dm,
+            repair_cost,
+            weeks_lost,
+        } => {
+            let cause = if *dm > 0 {
+                format!("with the drives in poor repair (DM +{dm})")
+            } else {
+                "on unrefined fuel".to_string()
+            };
+            let _ = writeln!(
+                out,
+                "INCIDENT — Misjump {cause}: {weeks_lost} weeks adrift, {repair_cost} Cr of drive repairs."
+            );
+        }
+        IncidentSummary::Damaged { cause, hits } => {
+            let _ = writeln!(
+                out,
+                "INCIDENT — {cause} damaged the ship: {}.",
+                describe_hits(hits)
+            );
+        }
+        IncidentSummary::ContrabandSeized {
+            seized,
+            buy_cost_sunk,
+            fine,
+        } => {
+            let goods = seized
+                .iter()
+                .map(|(g, t)| format!("{t}t {g}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                out,
+                "INCIDENT — Customs found contraband: confiscated {goods} ({buy_cost_sunk} Cr sunk), fined {fine} Cr."
+            );
+        }
+        IncidentSummary::Impounded {
+            weeks_lost,
+            release_fee,
+        } => {
+            let _ = writeln!(
+                out,
+                "INCIDENT — Ship impounded by customs: held {weeks_lost} weeks, {release_fee} Cr to release."
+            );
+        }
+        IncidentSummary::Other {
+            name,
+            text,
+            credits_lost,
+            repair_cost,
+            weeks_lost,
+            cargo_lost_tons,
+            crewmember,
+        } => {
+            let mut effects = Vec::new();
+            if *credits_lost > 0 {
+                effects.push(format!("lost {credits_lost} Cr"));
+            } else if *credits_lost < 0 {
+                effects.push(format!("gained {} Cr", -credits_lost));
+            }
+            if *repair_cost != 0 {
+                effects.push(format!("{repair_cost} Cr in repairs"));
+            }
+            if *cargo_lost_tons > 0 {
+                effects.push(format!("{cargo_lost_tons}t of cargo lost"));
+            }
+            if *weeks_lost > 0 {
+                effects.push(format!("delayed {weeks_lost} weeks"));
+            }
+            if let Some(m) = crewmember {
+                effects.push(crewmember_lost(m));
+            }
+            let _ = write!(out, "INCIDENT — {name}:");
+            if !text.is_empty() {
+                let _ = write!(out, " {text}");
+            }
+            if !effects.is_empty() {
+                let _ = write!(out, " ({})", effects.join(", "));
+            }
+            let _ = writeln!(out);
+        }
+    }
+}
+
+/// "Name (Role) died" or "... left the ship" for a crewmember an incident
+/// took off the roster.
+pub(crate) fn crewmember_lost(m: &CrewMember) -> String {
+    if m.status == CrewStatus::Dead {
+        format!("{} ({}) died", m.name, m.role)
+    } else {
+        format!("{} ({}) left the ship", m.name, m.role)
     }
 }
 
@@ -728,16 +1065,7 @@ fn zone_label(z: ZoneClassification) -> &'static str {
 /// Pretty trade-class names for the prompt. Skips the zone classes
 /// (Amber/Red are already on the visit line above).
 fn trade_classes_for(uwp: &str) -> Vec<&'static str> {
-    let chars: Vec<char> = uwp.chars().collect();
-    if chars.len() < 9 || chars[7] != '-' {
-        return Vec::new();
-    }
-    // uwp_to_trade_classes wants 8 chars: starport + 6 stat digits +
-    // tech-level. Skip the dash at index 7.
-    let uwp: [char; 8] = [
-        chars[0], chars[1], chars[2], chars[3], chars[4], chars[5], chars[6], chars[8],
-    ];
-    uwp_to_trade_classes(&uwp)
+    uwp_trade_classes(uwp)
         .into_iter()
         .map(|tc| match tc {
             TradeClass::Agricultural => "Agricultural",
@@ -765,6 +1093,21 @@ fn trade_classes_for(uwp: &str) -> Vec<&'static str> {
         .collect()
 }
 
+/// Trade classes of a world by its full UWP (`A788899-A`); none if the
+/// UWP doesn't parse.
+pub(crate) fn uwp_trade_classes(uwp: &str) -> Vec<TradeClass> {
+    let chars: Vec<char> = uwp.chars().collect();
+    if chars.len() < 9 || chars[7] != '-' {
+        return Vec::new();
+    }
+    // uwp_to_trade_classes wants 8 chars: starport + 6 stat digits +
+    // tech-level. Skip the dash at index 7.
+    let uwp: [char; 8] = [
+        chars[0], chars[1], chars[2], chars[3], chars[4], chars[5], chars[6], chars[8],
+    ];
+    uwp_to_trade_classes(&uwp)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -824,6 +1167,69 @@ mod tests {
         assert!(world_eq(&visits[1].world, &efate));
     }
 
+    #[test]
+    fn visit_reports_mail_and_charter_revenue() {
+        let regina = wr("Regina", "Spinward Marches", 19, 10, "A788899-A");
+        let step = |action| SimulationStep {
+            date: Date::new(91, 1108),
+            location: regina.clone(),
+            budget_after: 500_000,
+            action,
+        };
+        let steps = vec![
+            step(Action::LoadMail {
+                containers: 2,
+                tons: 10,
+                revenue_pending: 50_000,
+            }),
+            step(Action::Charter {
+                weeks: 2,
+                weekly_rate: 60_000,
+                revenue_pending: 120_000,
+            }),
+        ];
+        let visits = coalesce_visits(&steps);
+        let mut out = String::new();
+        write_visit(&mut out, "Stop 1", &visits[0], false);
+        assert!(out.contains("Loaded mail: 2 containers (10t), pending revenue 50000 Cr"));
+        assert!(out.contains("hired for 2 weeks at 60000 Cr/week, pending revenue 120000 Cr"));
+    }
+
+    #[test]
+    fn visit_reports_damage_and_the_shipyard() {
+        let regina = wr("Regina", "Spinward Marches", 19, 10, "A788899-A");
+        let step = |action| SimulationStep {
+            date: Date::new(91, 1108),
+            location: regina.clone(),
+            budget_after: 500_000,
+            action,
+        };
+        let steps = vec![
+            step(Action::ShipDamaged {
+                cause: "Breakdown".to_string(),
+                hits: vec![ShipSystem::Hull, ShipSystem::JDrive, ShipSystem::Hull],
+            }),
+            step(Action::AnnualMaintenance {
+                weeks: 2,
+                missed: 2,
+            }),
+            step(Action::Repair {
+                repaired: vec![(ShipSystem::JDrive, 1), (ShipSystem::Hull, 1)],
+                cost: 125_000,
+                days: 4,
+                deferred: 1,
+            }),
+        ];
+        let visits = coalesce_visits(&steps);
+        let mut out = String::new();
+        write_visit(&mut out, "Stop 1", &visits[0], false);
+        assert!(out.contains("INCIDENT — Breakdown damaged the ship: J-drive ×1, hull ×2."));
+        assert!(out.contains(
+            "Shipyard: annual overhaul, 2 weeks in the yard after missing one; \
+             repaired J-drive ×1, hull ×1 for 125000 Cr in 4 days, 1 hit left for a better yard"
+        ));
+    }
+
     #[test]
     fn build_prompt_includes_named_ship_and_dates() {
         let regina = wr("Regina", "Spinward Marches", 19, 10, "A788899-A");
@@ -835,6 +1241,7 @@ mod tests {
                 ..Default::default()
             },
             fuel_cost_per_parsec: 500,
+            fuel: None,
             crew_profit_share: 0.10,
             starting_budget: 500_000,
             home_world: regina.clone(),
@@ -842,6 +1249,14 @@ mod tests {
             target_completion_date: Date::new(180, 1108),
             illegal_goods: false,
             planetary_broker_skill: 2,
+            seed: None,
+            route: Default::default(),
+            route_weights: Default::default(),
+            cargo_plan: None,
+            accept_charters: false,
+            markets: Default::default(),
+            incidents: None,
+            condition: None,
         };
         let result = SimulationResult {
             final_budget: 612_400,
@@ -857,6 +1272,7 @@ mod tests {
             marooned_at: None,
             marooned_on: None,
             rescue_arrives_on: None,
+            seed: 0,
         };
         let s = build_prompt("Free Trader Beowulf", &params, &[], &result);
         assert!(s.contains("Free Trader Beowulf"));
@@ -871,6 +1287,7 @@ mod tests {
         let params = SimulationParams {
             ship: Ship::default(),
             fuel_cost_per_parsec: 500,
+            fuel: None,
             crew_profit_share: 0.10,
             starting_budget: 500_000,
             home_world: regina.clone(),
@@ -878,6 +1295,14 @@ mod tests {
             target_completion_date: Date::new(180, 1108),
             illegal_goods: false,
             planetary_broker_skill: 2,
+            seed: None,
+            route: Default::default(),
+            route_weights: Default::default(),
+            cargo_plan: None,
+            accept_charters: false,
+            markets: Default::default(),
+            incidents: None,
+            condition: None,
         };
         let result = SimulationResult {
             final_budget: 0,
@@ -893,6 +1318,7 @@ mod tests {
             marooned_at: None,
             marooned_on: None,
             rescue_arrives_on: None,
+            seed: 0,
         };
         let s = build_prompt("", &params, &[], &result);
         assert!(s.contains("(unregistered"));
@@ -0,0 +1,1030 @@
+//! Write a captain's log from phrase tables, with no model behind it.
+//!
+//! [`write_log`] walks the same coalesced port visits that
+//! [`build_prompt`](crate::simulator::captains_log_prompt::build_prompt)
+//! sends to the model, and writes the log the instruction header asks
+//! for — an opening paragraph, one paragraph per port visit, a closing
+//! paragraph and the closing `Ω` — from the tables below. Phrases are keyed
+//! on the [`Action`](crate::simulator::types::Action)s a visit coalesced,
+//! on the world's trade classes and on its travel zone, and figures come
+//! straight from the voyage.
+//!
+//! Each phrase is picked by hashing the voyage's seed with the visit and
+//! the slot being filled, so a voyage always reads the same and two
+//! voyages read differently. The backend falls back on this log when
+//! Vertex AI is unavailable or the request is rate-limited, and
+//! `worldgen log` writes one from the command line.
+//!
+//! No I/O, no async, no native deps — wasm-friendly.
+
+use std::fmt::Write as _;
+
+use crate::simulator::captains_log_prompt::{
+    IncidentSummary, Visit, coalesce_visits, crewmember_lost, uwp_trade_classes, world_eq,
+};
+use crate::simulator::condition::describe_hits;
+use crate::simulator::types::{SimulationParams, SimulationResult, SimulationStep};
+use crate::trade::{TradeClass, ZoneClassification};
+
+/// Captains to sign the log, in the instruction header's mix of Vilani,
+/// Solomani, Aslan and Vargr names.
+const CAPTAINS: &[&str] = &[
+    "Sumarrgha Lugaadiin",
+    "Khimkhi Naashar",
+    "Sarah Chen",
+    "Marcus Beaumont",
+    "Adaeze Okonkwo",
+    "Diego Marquez",
+    "Ftahalr",
+    "Gvaeknae",
+];
+
+/// Names for a ship the simulator left unregistered.
+const SHIP_NAMES: &[&str] = &[
+    "Beowulf",
+    "Empress Marava",
+    "March Harrier",
+    "Cassandra's Folly",
+    "Bonaventure",
+];
+
+const OPENINGS: &[&str] = &[
+    "Captain {captain}, master of the {ship}, recording. We lifted from {home} in the {sector} sector on {start} and the voyage ran to {end}: {jumps} and {parsecs} in {days}.",
+    "This is the log of the {ship} out of {home}, {sector}, kept by Captain {captain}. {days}, {jumps} and {parsecs} between {start} and {end}.",
+    "Captain {captain} of the {ship}, home port {home} in {sector}. From {start} to {end} we made {jumps}, {parsecs} in all, over {days}.",
+];
+
+const LAUNCHES: &[&str] = &[
+    "We made ready at {world} from {arrived} and jumped outbound on {departed}.",
+    "The {ship} sat at {world} from {arrived} while we loaded, and lifted on {departed}.",
+    "Launch from {world}: aboard on {arrived}, outbound on {departed}.",
+];
+
+const ARRIVALS: &[&str] = &[
+    "Arrived {world} {arrived} after {distance} from {from}; departed {departed}.",
+    "We broke out of jump at {world} on {arrived}, {distance} out from {from}, and were away again {departed}.",
+    "{world}, arrived {arrived}, departed {departed}. The jump from {from} was {distance}.",
+];
+
+const HOMECOMINGS: &[&str] = &[
+    "Home to {world} on {arrived}, {distance} from {from}, and the voyage closed out on {departed}.",
+    "We came back down at {world} on {arrived} after the last {distance} from {from}. The books closed on {departed}.",
+];
+
+const ZONE_AMBER: &[&str] = &[
+    "{world} is an Amber zone, and we kept the turrets manned for the whole stay.",
+    "The Scouts have {world} flagged Amber; nobody went ashore alone.",
+];
+
+const ZONE_RED: &[&str] = &[
+    "{world} is interdicted, a Red zone, and we had no business being there at all.",
+    "Red zone. Least said about {world} the better.",
+];
+
+const SOLD_WELL: &[&str] = &[
+    "The {good} sold for {profit} clear.",
+    "Our {good} fetched {price} a ton, {profit} over what we paid.",
+    "We did well on the {good}: {profit} profit.",
+];
+
+const SOLD_POORLY: &[&str] = &[
+    "We took a {loss} loss on the {good}.",
+    "The {good} went for {price} a ton, {loss} short of cost.",
+];
+
+const HELD: &[&str] = &[
+    "Nobody here would pay a fair price for the {good}, so it stayed in the hold.",
+    "We kept the {good} aboard for a better market.",
+];
+
+const BOUGHT: &[&str] = &[
+    "We bought {qty} of {good} on speculation for {cost}.",
+    "We took on {qty} of {good} at {price} a ton to sell down the line.",
+    "{cost} went on {qty} of {good}.",
+];
+
+const LOADED: &[&str] = &[
+    "We boarded {list}, worth {revenue} on delivery.",
+    "Outbound we carried {list}, {revenue} owed at the far end.",
+];
+
+const CHARTERED: &[&str] = &[
+    "A charterer hired the whole ship for {weeks} at {rate} a week.",
+    "We let the {ship} out on charter, {weeks} at {rate} a week.",
+];
+
+const QUIET: &[&str] = &[
+    "Little else to report.",
+    "An uneventful stay.",
+    "Routine port call.",
+];
+
+const PIRACY_LOST: &[&str] = &[
+    "Pirates jumped us {when}. We were outgunned and gave up {tons} of cargo to be let go, and lost {weeks} getting back on course.",
+    "A raider ran us down {when}. We bought our way clear with {tons} from the hold, with {weeks} lost all told.",
+];
+
+const PIRACY_BEATEN: &[&str] = &[
+    "Pirates came at us {when}. Our guns drove them off, and the fight cost us {weeks}.",
+    "We traded fire with a raider {when} and saw it off, with {weeks} lost to it.",
+];
+
+/// Follows a piracy sentence when the fight left a repair bill.
+const PIRACY_REPAIRS: &[&str] = &[
+    "The repairs came to {repairs}.",
+    "The yard bill was {repairs}.",
+];
+
+const TRADE_SCAM: &[&str] = &[
+    "A broker here sold us a story along with the cargo. {credits} gone, and {weeks} chasing it.",
+    "We were swindled at {world}: {credits} lost and {weeks} wasted on the lawyers.",
+];
+
+const CREW_LOSS: &[&str] = &[
+    "{who}. Finding a replacement and filing the papers took {weeks}.",
+    "{who}, and we lost {weeks} signing on someone new.",
+];
+
+const CREW_LOSS_UNNAMED: &[&str] = &[
+    "We lost a crew member here. Finding a replacement and filing the papers took {weeks}.",
+    "One of the crew signed off without notice, and we lost {weeks} hiring.",
+];
+
+const ACCIDENT: &[&str] = &[
+    "An accident in engineering cost us {repairs} in repairs.",
+    "A power coupling let go in the engine room. {repairs} to put right.",
+];
+
+const ACCIDENT_HITS: &[&str] = &[
+    "We had an accident in engineering.",
+    "A power coupling let go in the engine room.",
+];
+
+const GOVERNMENT: &[&str] = &[
+    "The local authorities found fault with our papers: a {fine} fine and {weeks} held up.",
+    "Government trouble at {world}. {fine} in fines and {weeks} waiting on a stamp.",
+];
+
+const MISJUMP: &[&str] = &[
+    "The jump went wrong {cause}. We spent {weeks} adrift and {repairs} on the drives.",
+    "Misjump {cause}: {weeks} lost in the wrong system and {repairs} for the drive repairs.",
+];
+
+const DAMAGED: &[&str] = &[
+    "{cause} left us with damage: {hits}.",
+    "The damage from {cause_lower}: {hits}.",
+];
+
+const CONTRABAND: &[&str] = &[
+    "Customs found contraband in the hold and took {goods}. {sunk} of cargo gone and a {fine} fine.",
+    "The customs search turned up {goods}. Confiscated, {sunk} sunk, and a {fine} fine besides.",
+];
+
+const IMPOUNDED: &[&str] = &[
+    "Customs impounded the {ship} for {weeks}, and it cost {fee} to get her back.",
+    "The {ship} sat impounded for {weeks} until we paid the {fee} release fee.",
+];
+
+const NEGATIVE: &[&str] = &[
+    "The accounts went into the red here.",
+    "We left owing more than we had.",
+];
+
+const ABORTED: &[&str] = &[
+    "There was nowhere left worth going, and the voyage ended here.",
+    "We called the voyage here.",
+];
+
+const MAROONED_HERE: &[&str] = &[
+    "We could not pay to leave, and here the {ship} stays.",
+    "There was no money for the next jump. The {ship} is grounded at {world}.",
+];
+
+const MAROONED: &[&str] = &[
+    "We are marooned at {world} since {on}. The distress packet went out on the first ship bound up the line and should reach {home} by {signal}; any rescue will be weeks behind that. Life support is holding, the crew is on short rations, and every ship that breaks out of jump could be help or could be someone who heard we are here. Captain {captain}, {ship}, awaiting rescue.",
+    "Marooned at {world}, {on}. Our packet rides the x-boats home and reaches {home} on {signal} if it is lucky, and then someone has to decide we are worth the trip. The power plant is cold to save fuel and the crew takes turns on watch. Whoever comes, we will be here. Captain {captain} of the {ship}.",
+];
+
+const PROFIT: &[&str] = &[
+    "All told the voyage cleared {gross}, {owner} of it to the owners after the crew's {crew} share.",
+    "The books closed {gross} ahead. The crew took {crew}; the owners take {owner}.",
+];
+
+const LOSS: &[&str] = &[
+    "All told the voyage lost {loss}.",
+    "The books closed {loss} down. There will be hard questions from the owners.",
+];
+
+const HOME: &[&str] = &["The {ship} is home and the crew is ashore.", "We are home."];
+
+const NOT_HOME: &[&str] = &[
+    "The {ship} is still out at {world}.",
+    "We ended the voyage short of home, at {world}.",
+];
+
+/// A world's character, by its trade classes.
+fn trade_class_phrases(class: TradeClass) -> &'static [&'static str] {
+    match class {
+        TradeClass::Agricultural => &[
+            "Grain barges crowded the downport at {world}.",
+            "{world} smells of harvest even from the landing field.",
+        ],
+        TradeClass::Asteroid => {
+            &["{world} is rock and pressure domes, and every berth is a vacuum berth."]
+        }
+        TradeClass::Barren => &["Nothing lives on {world} that wasn't shipped in."],
+        TradeClass::Desert => &["{world} is all dust and glare, and the crew kept to the ship."],
+        TradeClass::FluidOceans => &["The strange seas of {world} stank through the air filters."],
+        TradeClass::Garden => &[
+            "{world} is a garden world, and the crew were sorry to leave it.",
+            "Blue sky and breathable air on {world}; shore leave was popular.",
+        ],
+        TradeClass::HighPopulation => &[
+            "{world}'s downport never sleeps; we waited an hour for a cargo lift.",
+            "Billions live on {world}, and it felt like most of them were at the starport.",
+        ],
+        TradeClass::HighTech => &["{world}'s markets hum with the latest technology."],
+        TradeClass::IceCapped => &["{world} is ice from pole to pole."],
+        TradeClass::Industrial => &[
+            "The foundries of {world} keep the sky orange.",
+            "{world} builds things, and it was buying raw materials.",
+        ],
+        TradeClass::LowPopulation => {
+            &["Hardly anyone lives on {world}; the port master doubled as customs."]
+        }
+        TradeClass::LowTech => &["{world} trades in goods a generation behind the Imperium's."],
+        TradeClass::Poor => &["{world} is poor, and the port showed it."],
+        TradeClass::Rich => &["{world} is rich, and the brokers dressed like it."],
+        TradeClass::Vacuum => &["No air on {world}; we stayed suited from lock to warehouse."],
+        TradeClass::WaterWorld => {
+            &["{world} is ocean from pole to pole, and we came down on a floating port."]
+        }
+        TradeClass::NonAgricultural
+        | TradeClass::NonIndustrial
+        | TradeClass::AmberZone
+        | TradeClass::RedZone => &[],
+    }
+}
+
+/// The slots a phrase is picked for. Each slot draws its own hash, so
+/// two slots of one visit don't always pick the same row.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    Captain,
+    Ship,
+    Opening,
+    Dates,
+    Class,
+    World,
+    Zone,
+    Sold,
+    Held,
+    Bought,
+    Loaded,
+    Charter,
+    Quiet,
+    Incident,
+    Warning,
+    Closing,
+    Ending,
+}
+
+/// Picks phrases for one voyage.
+struct Phrases {
+    seed: u64,
+}
+
+impl Phrases {
+    /// Which of `n` choices to make for `slot` at visit `visit` (or
+    /// `usize::MAX` for the voyage as a whole).
+    fn index(&self, visit: usize, slot: Slot, n: usize) -> usize {
+        // A splitmix64 finish over the seed, the visit and the slot.
+        let mut z = self.seed
+            ^ (visit as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            ^ (slot as u64 + 1).wrapping_mul(0xD1B5_4A32_D192_ED03);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z % n as u64) as usize
+    }
+
+    /// The phrase from `table` for `slot` at visit `visit`.
+    fn pick(&self, visit: usize, slot: Slot, table: &[&'static str]) -> &'static str {
+        table[self.index(visit, slot, table.len())]
+    }
+}
+
+/// Fill each `{key}` in `template` with its value.
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (key, value)| {
+            text.replace(&format!("{{{key}}}"), value)
+        })
+}
+
+/// `1234567` → `"1,234,567 Cr"`.
+fn credits(amount: i64) -> String {
+    let digits = amount.unsigned_abs().to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    format!("{}{out} Cr", if amount < 0 { "-" } else { "" })
+}
+
+/// Write the whole log for a completed simulation. Same inputs as
+/// [`build_prompt`](crate::simulator::captains_log_prompt::build_prompt).
+/// A blank `ship_name` gets a name from the table.
+pub fn write_log(
+    ship_name: &str,
+    params: &SimulationParams,
+    steps: &[SimulationStep],
+    result: &SimulationResult,
+) -> String {
+    let phrases = Phrases { seed: result.seed };
+    let voyage = usize::MAX;
+    let ship = match ship_name.trim() {
+        "" => phrases.pick(voyage, Slot::Ship, SHIP_NAMES),
+        name => name,
+    };
+    let captain = phrases.pick(voyage, Slot::Captain, CAPTAINS);
+    let home = &params.home_world;
+    let visits = coalesce_visits(steps);
+
+    let parsecs: i32 = visits
+        .iter()
+        .filter_map(|v| v.inbound_arrival.as_ref())
+        .map(|a| a.distance)
+        .sum();
+    let mut out = fill(
+        phrases.pick(voyage, Slot::Opening, OPENINGS),
+        &[
+            ("captain", captain),
+            ("ship", ship),
+            ("home", &home.name),
+            ("sector", &home.sector),
+            ("start", &params.start_date.format()),
+            ("end", &result.end_date.format()),
+            ("jumps", &plural(result.jumps, "jump", "jumps")),
+            ("parsecs", &plural(parsecs, "parsec", "parsecs")),
+            (
+                "days",
+                &plural(
+                    params.start_date.days_until(result.end_date).max(0),
+                    "day",
+                    "days",
+                ),
+            ),
+        ],
+    );
+    out.push_str("\n\n");
+
+    for (idx, v) in visits.iter().enumerate() {
+        let is_voyage_start = idx == 0 && world_eq(&v.world, home);
+        let is_homecoming = idx > 0 && idx == visits.len() - 1 && world_eq(&v.world, home);
+        write_visit(
+            &mut out,
+            &phrases,
+            idx,
+            ship,
+            v,
+            is_voyage_start,
+            is_homecoming,
+        );
+        out.push_str("\n\n");
+    }
+
+    write_closing(
+        &mut out,
+        &phrases,
+        captain,
+        ship,
+        params,
+        result,
+        visits.last(),
+    );
+    out.push_str("\n\nΩ\n");
+    out
+}
+
+/// One paragraph for one port visit.
+fn write_visit(
+    out: &mut String,
+    phrases: &Phrases,
+    idx: usize,
+    ship: &str,
+    v: &Visit<'_>,
+    is_voyage_start: bool,
+    is_homecoming: bool,
+) {
+    let world = v.world.name.as_str();
+    let arrived = v.arrived.format();
+    let departed = v.departed.format();
+    let mut sentences: Vec<String> = Vec::new();
+
+    // Dates first: the instructions ask for every visit to be stamped.
+    let dates = match &v.inbound_arrival {
+        Some(arrival) if !is_voyage_start => fill(
+            phrases.pick(
+                idx,
+                Slot::Dates,
+                if is_homecoming { HOMECOMINGS } else { ARRIVALS },
+            ),
+            &[
+                ("world", world),
+                ("arrived", &arrived),
+                ("departed", &departed),
+                ("from", &arrival.from.name),
+                ("distance", &plural(arrival.distance, "parsec", "parsecs")),
+            ],
+        ),
+        _ => fill(
+            phrases.pick(idx, Slot::Dates, LAUNCHES),
+            &[
+                ("world", world),
+                ("ship", ship),
+                ("arrived", &arrived),
+                ("departed", &departed),
+            ],
+        ),
+    };
+    sentences.push(dates);
+
+    // The world's character: one of its trade classes, then its zone.
+    let described: Vec<&'static [&'static str]> = uwp_trade_classes(&v.world.uwp)
+        .into_iter()
+        .map(trade_class_phrases)
+        .filter(|table| !table.is_empty())
+        .collect();
+    if !described.is_empty() && !is_voyage_start {
+        let table = described[phrases.index(idx, Slot::Class, described.len())];
+        sentences.push(fill(
+            phrases.pick(idx, Slot::World, table),
+            &[("world", world)],
+        ));
+    }
+    let zone = match v.world.zone {
+        ZoneClassification::Green => None,
+        ZoneClassification::Amber => Some(ZONE_AMBER),
+        ZoneClassification::Red => Some(ZONE_RED),
+    };
+    if let Some(table) = zone {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Zone, table),
+            &[("world", world)],
+        ));
+    }
+
+    // Trade: the best and worst sale, one held lot, the biggest buy.
+    if let Some(sale) = v
+        .sells
+        .iter()
+        .max_by_key(|s| s.profit)
+        .filter(|s| s.profit > 0)
+    {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Sold, SOLD_WELL),
+            &[
+                ("good", &sale.good),
+                ("price", &credits(sale.sell_price as i64)),
+                ("profit", &credits(sale.profit)),
+            ],
+        ));
+    }
+    if let Some(sale) = v
+        .sells
+        .iter()
+        .min_by_key(|s| s.profit)
+        .filter(|s| s.profit < 0)
+    {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Sold, SOLD_POORLY),
+            &[
+                ("good", &sale.good),
+                ("price", &credits(sale.sell_price as i64)),
+                ("loss", &credits(-sale.profit)),
+            ],
+        ));
+    }
+    if let Some(held) = v.holds.first() {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Held, HELD),
+            &[("good", &held.good)],
+        ));
+    }
+    if let Some(buy) = v.buys.iter().max_by_key(|b| b.total_cost) {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Bought, BOUGHT),
+            &[
+                ("good", &buy.good),
+                ("qty", &plural(buy.qty, "ton", "tons")),
+                ("price", &credits(buy.unit_cost as i64)),
+                ("cost", &credits(buy.total_cost)),
+            ],
+        ));
+    }
+
+    // Passengers, freight and mail, or a charter.
+    let pax = v.pax_high + v.pax_medium + v.pax_basic + v.pax_low;
+    let mut carried = Vec::new();
+    if pax > 0 {
+        carried.push(plural(pax, "passenger", "passengers"));
+    }
+    if v.freight_tons > 0 {
+        carried.push(format!("{} tons of freight", v.freight_tons));
+    }
+    if v.mail_containers > 0 {
+        carried.push(plural(
+            v.mail_containers,
+            "mail container",
+            "mail containers",
+        ));
+    }
+    if !carried.is_empty() {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Loaded, LOADED),
+            &[
+                ("list", &join_list(&carried)),
+                (
+                    "revenue",
+                    &credits(v.pax_revenue + v.freight_revenue + v.mail_revenue),
+                ),
+            ],
+        ));
+    }
+    if let Some(charter) = &v.charter {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Charter, CHARTERED),
+            &[
+                ("ship", ship),
+                ("weeks", &plural(charter.weeks, "week", "weeks")),
+                ("rate", &credits(charter.weekly_rate)),
+            ],
+        ));
+    }
+
+    if !v.refuelling.is_empty() {
+        sentences.push(format!("We {}.", v.refuelling.join(", then ")));
+    }
+    if !v.shipyard.is_empty() {
+        sentences.push(format!("At the yard: {}.", v.shipyard.join("; ")));
+    }
+
+    for incident in &v.incidents {
+        sentences.push(incident_sentence(
+            phrases,
+            idx,
+            ship,
+            world,
+            incident,
+            v.inbound_arrival.is_some(),
+        ));
+    }
+
+    if v.closing_warning.is_some() && !v.aborted {
+        sentences.push(phrases.pick(idx, Slot::Warning, NEGATIVE).to_string());
+    }
+    if v.aborted {
+        sentences.push(phrases.pick(idx, Slot::Warning, ABORTED).to_string());
+    }
+    if v.marooned_here {
+        sentences.push(fill(
+            phrases.pick(idx, Slot::Warning, MAROONED_HERE),
+            &[("ship", ship), ("world", world)],
+        ));
+    }
+    if sentences.len() == 1 {
+        sentences.push(phrases.pick(idx, Slot::Quiet, QUIET).to_string());
+    }
+
+    out.push_str(&sentences.join(" "));
+}
+
+/// One sentence for an incident, with its figures from the data.
+fn incident_sentence(
+    phrases: &Phrases,
+    idx: usize,
+    ship: &str,
+    world: &str,
+    incident: &IncidentSummary,
+    has_inbound_jump: bool,
+) -> String {
+    let pick = |table| phrases.pick(idx, Slot::Incident, table);
+    match incident {
+        IncidentSummary::Piracy {
+            cargo_lost_tons,
+            credits_lost,
+            weeks_lost,
+            ..
+        } => {
+            let when = if has_inbound_jump {
+                "on the way in"
+            } else {
+                "in the system"
+            };
+            let table = if *cargo_lost_tons > 0 {
+                PIRACY_LOST
+            } else {
+                PIRACY_BEATEN
+            };
+            let mut sentence = fill(
+                pick(table),
+                &[
+                    ("when", when),
+                    ("tons", &plural(*cargo_lost_tons, "ton", "tons")),
+                    ("weeks", &weeks(*weeks_lost)),
+                ],
+            );
+            if *credits_lost > 0 {
+                sentence.push(' ');
+                sentence.push_str(&fill(
+                    pick(PIRACY_REPAIRS),
+                    &[("repairs", &credits(*credits_lost))],
+                ));
+            }
+            sentence
+        }
+        IncidentSummary::TradeScam {
+            credits_lost,
+            weeks_lost,
+        } => fill(
+            pick(TRADE_SCAM),
+            &[
+                ("world", world),
+                ("credits", &credits(*credits_lost)),
+                ("weeks", &weeks(*weeks_lost)),
+            ],
+        ),
+        IncidentSummary::CrewLoss {
+            weeks_lost,
+            crewmember,
+        } => match crewmember {
+            Some(m) => fill(
+                pick(CREW_LOSS),
+                &[("who", &crewmember_lost(m)), ("weeks", &weeks(*weeks_lost))],
+            ),
+            None => fill(pick(CREW_LOSS_UNNAMED), &[("weeks", &weeks(*weeks_lost))]),
+        },
+        // The damage follows as hits when the ship's condition is
+        // tracked.
+        IncidentSummary::Accident { repair_cost: 0 } => pick(ACCIDENT_HITS).to_string(),
+        IncidentSummary::Accident { repair_cost } => {
+            fill(pick(ACCIDENT), &[("repairs", &credits(*repair_cost))])
+        }
+        IncidentSummary::Government {
+            fine_credits,
+            weeks_lost,
+        } => fill(
+            pick(GOVERNMENT),
+            &[
+                ("world", world),
+                ("fine", &credits(*fine_credits)),
+                ("weeks", &weeks(*weeks_lost)),
+            ],
+        ),
+        IncidentSummary::Misjump {
+            dm,
+            repair_cost,
+            weeks_lost,
+        } => {
+            let cause = if *dm > 0 {
+                "with the drives in poor repair"
+            } else {
+                "on unrefined fuel"
+            };
+            fill(
+                pick(MISJUMP),
+                &[
+                    ("cause", cause),
+                    ("weeks", &weeks(*weeks_lost)),
+                    ("repairs", &credits(*repair_cost)),
+                ],
+            )
+        }
+        IncidentSummary::Damaged { cause, hits } => fill(
+            pick(DAMAGED),
+            &[
+                ("cause", cause),
+                ("cause_lower", &cause.to_lowercase()),
+                ("hits", &describe_hits(hits)),
+            ],
+        ),
+        IncidentSummary::ContrabandSeized {
+            seized,
+            buy_cost_sunk,
+            fine,
+        } => {
+            let goods: Vec<String> = seized
+                .iter()
+                .map(|(good, tons)| format!("{tons} of {good}"))
+                .collect();
+            fill(
+                pick(CONTRABAND),
+                &[
+                    ("goods", &join_list(&goods)),
+                    ("sunk", &credits(*buy_cost_sunk)),
+                    ("fine", &credits(*fine)),
+                ],
+            )
+        }
+        IncidentSummary::Impounded {
+            weeks_lost,
+            release_fee,
+        } => fill(
+            pick(IMPOUNDED),
+            &[
+                ("ship", ship),
+                ("weeks", &weeks(*weeks_lost)),
+                ("fee", &credits(*release_fee)),
+            ],
+        ),
+        // A referee's own incident carries its own text.
+        IncidentSummary::Other { name, text, .. } => {
+            if text.is_empty() {
+                format!("{name}.")
+            } else {
+                format!("{name}: {text}")
+            }
+        }
+    }
+}
+
+/// The closing paragraph: the bottom line, and the distress call if the
+/// ship is marooned.
+fn write_closing(
+    out: &mut String,
+    phrases: &Phrases,
+    captain: &str,
+    ship: &str,
+    params: &SimulationParams,
+    result: &SimulationResult,
+    last: Option<&Visit<'_>>,
+) {
+    let voyage = usize::MAX;
+    let bottom_line = if result.gross_profit >= 0 {
+        fill(
+            phrases.pick(voyage, Slot::Closing, PROFIT),
+            &[
+                ("gross", &credits(result.gross_profit)),
+                ("owner", &credits(result.owner_profit)),
+                ("crew", &credits(result.crew_share)),
+            ],
+        )
+    } else {
+        fill(
+            phrases.pick(voyage, Slot::Closing, LOSS),
+            &[("loss", &credits(-result.gross_profit))],
+        )
+    };
+    let _ = write!(out, "{bottom_line} ");
+
+    if result.marooned
+        && let (Some(at), Some(on)) = (&result.marooned_at, result.marooned_on)
+    {
+        let signal = result
+            .rescue_arrives_on
+            .map(|d| d.format())
+            .unwrap_or_else(|| "no one knows when".to_string());
+        out.push_str(&fill(
+            phrases.pick(voyage, Slot::Ending, MAROONED),
+            &[
+                ("world", &at.name),
+                ("on", &on.format()),
+                ("home", &params.home_world.name),
+                ("signal", &signal),
+                ("captain", captain),
+                ("ship", ship),
+            ],
+        ));
+    } else if result.returned_home {
+        out.push_str(&fill(
+            phrases.pick(voyage, Slot::Ending, HOME),
+            &[("ship", ship)],
+        ));
+    } else {
+        let world = last.map_or(params.home_world.name.as_str(), |v| &v.world.name);
+        out.push_str(&fill(
+            phrases.pick(voyage, Slot::Ending, NOT_HOME),
+            &[("ship", ship), ("world", world)],
+        ));
+    }
+}
+
+/// "no time", "1 week", "3 weeks".
+fn weeks(n: impl Into<i64>) -> String {
+    match n.into() {
+        0 => "no time".to_string(),
+        n => plural(n, "week", "weeks"),
+    }
+}
+
+/// "1 passenger", "3 passengers".
+fn plural(n: impl Into<i64>, one: &str, many: &str) -> String {
+    let n = n.into();
+    format!("{n} {}", if n == 1 { one } else { many })
+}
+
+/// "a", "a and b", "a, b and c".
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::test_support::voyage_from;
+    use crate::simulator::types::{Action, Date, WorldRef};
+
+    fn wr(name: &str, sector: &str, x: i32, y: i32, uwp: &str) -> WorldRef {
+        WorldRef {
+            name: name.to_string(),
+            sector: sector.to_string(),
+            hex_x: x,
+            hex_y: y,
+            uwp: uwp.to_string(),
+            zone: ZoneClassification::Green,
+        }
+    }
+
+    fn params(home: &WorldRef) -> SimulationParams {
+        SimulationParams {
+            start_date: Date::new(91, 1108),
+            target_completion_date: Date::new(180, 1108),
+            ..voyage_from(home.clone())
+        }
+    }
+
+    fn result(seed: u64, end_date: Date) -> SimulationResult {
+        SimulationResult {
+            final_budget: 612_400,
+            gross_profit: 112_400,
+            crew_share: 11_240,
+            owner_profit: 101_160,
+            end_date,
+            jumps: 2,
+            completed_normally: true,
+            returned_home: true,
+            went_negative: false,
+            marooned: false,
+            marooned_at: None,
+            marooned_on: None,
+            rescue_arrives_on: None,
+            seed,
+        }
+    }
+
+    /// Regina → Efate → Regina, buying computers out and selling them.
+    fn round_trip(regina: &WorldRef, efate: &WorldRef) -> Vec<SimulationStep> {
+        let step = |day, location: &WorldRef, action| SimulationStep {
+            date: Date::new(day, 1108),
+            location: location.clone(),
+            budget_after: 500_000,
+            action,
+        };
+        vec![
+            step(
+                91,
+                regina,
+                Action::BuyGood {
+                    good: "Computers".to_string(),
+                    qty: 40,
+                    unit_cost: 9_000,
+                    total_cost: 360_000,
+                },
+            ),
+            step(
+                93,
+                regina,
+                Action::Jump {
+                    to: efate.clone(),
+                    distance: 2,
+                    fuel_cost: 1_000,
+                },
+            ),
+            step(
+                100,
+                efate,
+                Action::Arrive {
+                    from: regina.clone(),
+                    distance: 2,
+                    fuel_cost: 1_000,
+                },
+            ),
+            step(
+                101,
+                efate,
+                Action::SellGood {
+                    good: "Computers".to_string(),
+                    qty: 40,
+                    sell_price: 12_000,
+                    paid: 9_000,
+                    profit: 120_000,
+                },
+            ),
+            step(
+                103,
+                efate,
+                Action::Jump {
+                    to: regina.clone(),
+                    distance: 2,
+                    fuel_cost: 1_000,
+                },
+            ),
+            step(
+                110,
+                regina,
+                Action::Arrive {
+                    from: efate.clone(),
+                    distance: 2,
+                    fuel_cost: 1_000,
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn log_is_deterministic_and_dates_every_visit() {
+        let regina = wr("Regina", "Spinward Marches", 19, 10, "A788899-A");
+        let efate = wr("Efate", "Spinward Marches", 17, 5, "A646930-D");
+        let params = params(&regina);
+        let steps = round_trip(&regina, &efate);
+        let done = result(7, Date::new(110, 1108));
+
+        let log = write_log("Free Trader Beowulf", &params, &steps, &done);
+        assert_eq!(
+            log,
+            write_log("Free Trader Beowulf", &params, &steps, &done)
+        );
+        assert!(log.contains("Free Trader Beowulf"));
+        for date in ["091-1108", "100-1108", "103-1108", "110-1108"] {
+            assert!(log.contains(date), "{date} missing from:\n{log}");
+        }
+        assert!(log.contains("Efate"));
+        assert!(log.ends_with("\n\nΩ\n"));
+
+        let reseeded = write_log("", &params, &steps, &result(8, Date::new(110, 1108)));
+        assert_ne!(log, reseeded);
+        assert!(reseeded.contains("Efate"));
+    }
+
+    #[test]
+    fn voyages_beyond_the_caps_are_refused() {
+        use crate::comms::captains_log::{LogVoyage, MAX_LOG_STEPS, MAX_SHIP_NAME_BYTES};
+
+        let regina = wr("Regina", "Spinward Marches", 19, 10, "A788899-A");
+        let efate = wr("Efate", "Spinward Marches", 17, 5, "A646930-D");
+        let mut voyage = LogVoyage {
+            ship_name: "Free Trader Beowulf".to_string(),
+            params: params(&regina),
+            steps: round_trip(&regina, &efate),
+            result: result(7, Date::new(110, 1108)),
+        };
+        assert_eq!(voyage.check_size(), Ok(()));
+
+        let step = voyage.steps[0].clone();
+        voyage.steps.resize(MAX_LOG_STEPS + 1, step);
+        assert!(voyage.check_size().unwrap_err().contains("steps"));
+
+        voyage.steps.truncate(MAX_LOG_STEPS);
+        assert_eq!(voyage.check_size(), Ok(()));
+        voyage.ship_name = "B".repeat(MAX_SHIP_NAME_BYTES + 1);
+        assert!(voyage.check_size().unwrap_err().contains("ship name"));
+    }
+
+    #[test]
+    fn marooned_log_ends_with_the_distress_call() {
+        let regina = wr("Regina", "Spinward Marches", 19, 10, "A788899-A");
+        let efate = wr("Efate", "Spinward Marches", 17, 5, "A646930-D");
+        let params = params(&regina);
+        let mut steps = round_trip(&regina, &efate);
+        steps.truncate(4);
+        let mut done = result(7, Date::new(101, 1108));
+        done.returned_home = false;
+        done.completed_normally = false;
+        done.marooned = true;
+        done.marooned_at = Some(efate.clone());
+        done.marooned_on = Some(Date::new(101, 1108));
+        done.rescue_arrives_on = Some(Date::new(130, 1108));
+
+        let log = write_log("Free Trader Beowulf", &params, &steps, &done);
+        let closing = log.rsplit("\n\n").nth(1).unwrap();
+        assert!(closing.contains("Efate"), "{closing}");
+        assert!(closing.contains("101-1108"), "{closing}");
+        assert!(closing.contains("130-1108"), "{closing}");
+    }
+
+    #[test]
+    fn credits_are_grouped_in_thousands() {
+        assert_eq!(credits(0), "0 Cr");
+        assert_eq!(credits(1_234_567), "1,234,567 Cr");
+        assert_eq!(credits(-950), "-950 Cr");
+    }
+}
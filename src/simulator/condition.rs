@@ -0,0 +1,404 @@
+//! Ship condition model for the ship simulator.
+//!
+//! Without a [`ConditionModel`] an incident's damage is a one-off repair
+//! bill and the ship is as good as new. With one, the ship carries its
+//! damage from port to port:
+//!
+//! * piracy and accidents land hits on the hull, the drives or the power
+//!   plant (the incident table's `hits` effect),
+//! * a port repairs only what its yard can handle — a D port patches the
+//!   hull, a C port the hull, M-drive and power plant, and only an A or B
+//!   shipyard can fix a J-drive — and only what the budget covers; the
+//!   rest waits for a better port,
+//! * annual maintenance needs an A or B shipyard. Every year it goes
+//!   undone is a cumulative DM on breakdowns, misjumps and incident
+//!   avoidance.
+//!
+//! Damaged drives make every jump a misjump risk, and a damaged M-drive
+//! makes the ship easier prey in port.
+//!
+//! The pure pieces — the damage record, the repair plan, the rolls —
+//! live here; the executor threads them through a voyage.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::incidents::{roll_1d6, roll_2d6};
+use crate::simulator::types::Date;
+use crate::trade::PortCode;
+
+/// A 2d6 roll plus missed maintenance at or above this breaks something.
+pub const BREAKDOWN_ROLL: i32 = 12;
+/// Days in the yard per hit repaired.
+pub const REPAIR_DAYS_PER_HIT: u32 = 2;
+
+/// How the ship's condition is tracked and what repairs cost. Every
+/// field has a default, so a client can send `{}` to switch the model on
+/// with a freshly overhauled ship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConditionModel {
+    /// Days between annual maintenance overhauls.
+    pub maintenance_interval_days: u32,
+    /// Days since the last overhaul when the voyage begins.
+    pub days_since_maintenance: u32,
+    /// Weeks in the shipyard for an overhaul.
+    pub overhaul_weeks: u32,
+    /// Credits to repair one hit on the hull.
+    pub hull_repair_cost: i64,
+    /// Credits to repair one hit on a drive or the power plant.
+    pub drive_repair_cost: i64,
+}
+
+impl Default for ConditionModel {
+    fn default() -> Self {
+        ConditionModel {
+            maintenance_interval_days: 365,
+            days_since_maintenance: 0,
+            overhaul_weeks: 2,
+            hull_repair_cost: 25_000,
+            drive_repair_cost: 100_000,
+        }
+    }
+}
+
+impl ConditionModel {
+    /// Credits to repair one hit on `system`.
+    pub fn repair_cost(&self, system: ShipSystem) -> i64 {
+        match system {
+            ShipSystem::Hull => self.hull_repair_cost,
+            _ => self.drive_repair_cost,
+        }
+    }
+
+    /// The repairs a port of class `port` can make on `condition` within
+    /// `budget`: the worst-placed hits first (J-drive, power plant,
+    /// M-drive, hull), hit by hit while the budget lasts.
+    pub fn plan_repairs(
+        &self,
+        condition: &ShipCondition,
+        port: PortCode,
+        budget: i64,
+    ) -> RepairPlan {
+        let mut plan = RepairPlan::default();
+        let mut budget = budget;
+        for system in ShipSystem::REPAIR_ORDER {
+            if !system.repairable_at(port) {
+                continue;
+            }
+            let cost = self.repair_cost(system);
+            let hits = match cost {
+                ..=0 => condition.hits(system),
+                _ => condition.hits(system).min((budget.max(0) / cost) as u32),
+            };
+            if hits > 0 {
+                budget -= hits as i64 * cost;
+                plan.cost += hits as i64 * cost;
+                plan.days += hits * REPAIR_DAYS_PER_HIT;
+                plan.repaired.push((system, hits));
+            }
+        }
+        plan
+    }
+}
+
+/// Where a hit can land.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShipSystem {
+    Hull,
+    MDrive,
+    JDrive,
+    PowerPlant,
+}
+
+impl ShipSystem {
+    /// Most urgent repair first.
+    pub const REPAIR_ORDER: [ShipSystem; 4] = [
+        ShipSystem::JDrive,
+        ShipSystem::PowerPlant,
+        ShipSystem::MDrive,
+        ShipSystem::Hull,
+    ];
+
+    /// Where a hit lands on a 1d6 location roll: the hull on 1-3, then
+    /// the M-drive, J-drive and power plant.
+    pub fn from_location_roll(roll: i32) -> ShipSystem {
+        match roll {
+            ..=3 => ShipSystem::Hull,
+            4 => ShipSystem::MDrive,
+            5 => ShipSystem::JDrive,
+            _ => ShipSystem::PowerPlant,
+        }
+    }
+
+    /// Whether a starport of class `port` can repair this system.
+    pub fn repairable_at(self, port: PortCode) -> bool {
+        match port {
+            PortCode::A | PortCode::B => true,
+            PortCode::C => self != ShipSystem::JDrive,
+            PortCode::D => self == ShipSystem::Hull,
+            _ => false,
+        }
+    }
+}
+
+impl Display for ShipSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ShipSystem::Hull => "hull",
+            ShipSystem::MDrive => "M-drive",
+            ShipSystem::JDrive => "J-drive",
+            ShipSystem::PowerPlant => "power plant",
+        })
+    }
+}
+
+/// Whether a starport of class `port` has the shipyard an annual
+/// overhaul needs.
+pub fn can_overhaul(port: PortCode) -> bool {
+    matches!(port, PortCode::A | PortCode::B)
+}
+
+/// Damage the ship carries and when she was last overhauled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShipCondition {
+    pub hull: u32,
+    pub m_drive: u32,
+    pub j_drive: u32,
+    pub power_plant: u32,
+    /// Imperial day number of the last annual overhaul.
+    pub overhauled_on_day: i64,
+}
+
+impl ShipCondition {
+    /// An undamaged ship starting out on `start`, last overhauled as
+    /// long ago as `model` says.
+    pub fn new(model: &ConditionModel, start: Date) -> ShipCondition {
+        ShipCondition {
+            overhauled_on_day: start.day_number() - model.days_since_maintenance as i64,
+            ..ShipCondition::default()
+        }
+    }
+
+    /// Unrepaired hits on `system`.
+    pub fn hits(&self, system: ShipSystem) -> u32 {
+        match system {
+            ShipSystem::Hull => self.hull,
+            ShipSystem::MDrive => self.m_drive,
+            ShipSystem::JDrive => self.j_drive,
+            ShipSystem::PowerPlant => self.power_plant,
+        }
+    }
+
+    fn hits_mut(&mut self, system: ShipSystem) -> &mut u32 {
+        match system {
+            ShipSystem::Hull => &mut self.hull,
+            ShipSystem::MDrive => &mut self.m_drive,
+            ShipSystem::JDrive => &mut self.j_drive,
+            ShipSystem::PowerPlant => &mut self.power_plant,
+        }
+    }
+
+    /// Unrepaired hits on the whole ship.
+    pub fn total_hits(&self) -> u32 {
+        self.hull + self.m_drive + self.j_drive + self.power_plant
+    }
+
+    /// Record a hit on `system`.
+    pub fn take_hit(&mut self, system: ShipSystem) {
+        *self.hits_mut(system) += 1;
+    }
+
+    /// Clear the hits a [`RepairPlan`] repaired.
+    pub fn repair(&mut self, plan: &RepairPlan) {
+        for &(system, hits) in &plan.repaired {
+            let left = self.hits_mut(system);
+            *left = left.saturating_sub(hits);
+        }
+    }
+
+    /// Whole maintenance intervals gone by since the last overhaul, as
+    /// of `today`. Zero while the ship is in date.
+    pub fn missed_maintenance(&self, model: &ConditionModel, today: Date) -> u32 {
+        let days = (today.day_number() - self.overhauled_on_day).max(0);
+        (days / model.maintenance_interval_days.max(1) as i64) as u32
+    }
+
+    /// Mark the ship overhauled on `today`.
+    pub fn overhaul(&mut self, today: Date) {
+        self.overhauled_on_day = today.day_number();
+    }
+
+    /// DM added to the misjump roll: a hit on the J-drive or power plant
+    /// is +1 each, as is every missed overhaul.
+    pub fn jump_dm(&self, model: &ConditionModel, today: Date) -> i32 {
+        (self.j_drive + self.power_plant + self.missed_maintenance(model, today)) as i32
+    }
+
+    /// DM added to the incident avoidance roll: -1 for each hit on the
+    /// M-drive and each missed overhaul.
+    pub fn avoidance_dm(&self, model: &ConditionModel, today: Date) -> i32 {
+        -((self.m_drive + self.missed_maintenance(model, today)) as i32)
+    }
+}
+
+/// Repairs a port can make, and what they cost.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairPlan {
+    /// Per-system `(system, hits)` repaired.
+    pub repaired: Vec<(ShipSystem, u32)>,
+    pub cost: i64,
+    /// Days in the yard.
+    pub days: u32,
+}
+
+/// Hits counted per system, in repair order.
+pub fn tally_hits(hits: &[ShipSystem]) -> Vec<(ShipSystem, u32)> {
+    ShipSystem::REPAIR_ORDER
+        .into_iter()
+        .map(|system| (system, hits.iter().filter(|&&h| h == system).count() as u32))
+        .filter(|&(_, n)| n > 0)
+        .collect()
+}
+
+/// Per-system hit counts for the log, e.g. `"J-drive ×1, hull ×2"`.
+pub fn describe_hits(hits: &[(ShipSystem, u32)]) -> String {
+    hits.iter()
+        .map(|(system, n)| format!("{system} ×{n}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Roll where each of `count` hits lands.
+pub fn roll_hits(count: u32) -> Vec<ShipSystem> {
+    (0..count)
+        .map(|_| ShipSystem::from_location_roll(roll_1d6()))
+        .collect()
+}
+
+/// Roll the breakdown check for a ship `missed` overhauls behind: 2d6 +
+/// `missed`, breaking something at [`BREAKDOWN_ROLL`] or more. Returns
+/// the roll and where the breakdown hit, if one did.
+pub fn roll_breakdown(missed: u32) -> (i32, Option<ShipSystem>) {
+    let roll = roll_2d6();
+    if roll + missed as i32 >= BREAKDOWN_ROLL {
+        (roll, Some(ShipSystem::from_location_roll(roll_1d6())))
+    } else {
+        (roll, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::RngScope;
+
+    fn damaged() -> ShipCondition {
+        ShipCondition {
+            hull: 2,
+            m_drive: 1,
+            j_drive: 1,
+            power_plant: 0,
+            overhauled_on_day: 0,
+        }
+    }
+
+    #[test]
+    fn each_port_class_repairs_what_its_yard_can() {
+        let model = ConditionModel::default();
+        let condition = damaged();
+        let repaired = |port| model.plan_repairs(&condition, port, i64::MAX).repaired;
+        assert_eq!(
+            repaired(PortCode::A),
+            vec![
+                (ShipSystem::JDrive, 1),
+                (ShipSystem::MDrive, 1),
+                (ShipSystem::Hull, 2)
+            ]
+        );
+        assert_eq!(
+            repaired(PortCode::C),
+            vec![(ShipSystem::MDrive, 1), (ShipSystem::Hull, 2)]
+        );
+        assert_eq!(repaired(PortCode::D), vec![(ShipSystem::Hull, 2)]);
+        assert!(repaired(PortCode::E).is_empty());
+        assert!(repaired(PortCode::X).is_empty());
+        assert!(can_overhaul(PortCode::B) && !can_overhaul(PortCode::C));
+    }
+
+    #[test]
+    fn repairs_the_budget_cannot_cover_are_deferred() {
+        let model = ConditionModel::default();
+        let mut condition = damaged();
+        // Enough for the J-drive and one hull hit, not the M-drive.
+        let plan = model.plan_repairs(&condition, PortCode::A, 130_000);
+        assert_eq!(
+            plan.repaired,
+            vec![(ShipSystem::JDrive, 1), (ShipSystem::Hull, 1)]
+        );
+        assert_eq!(plan.cost, 125_000);
+        assert_eq!(plan.days, 2 * REPAIR_DAYS_PER_HIT);
+        condition.repair(&plan);
+        assert_eq!((condition.m_drive, condition.hull), (1, 1));
+        assert_eq!(condition.total_hits(), 2);
+        // A broke ship repairs nothing.
+        assert!(
+            model
+                .plan_repairs(&condition, PortCode::A, -1)
+                .repaired
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn missed_maintenance_adds_up_and_feeds_the_dms() {
+        let model = ConditionModel {
+            days_since_maintenance: 300,
+            ..ConditionModel::default()
+        };
+        let start = Date::new(100, 1105);
+        let mut condition = ShipCondition::new(&model, start);
+        assert_eq!(condition.missed_maintenance(&model, start), 0);
+        assert_eq!(condition.jump_dm(&model, start), 0);
+        assert_eq!(condition.avoidance_dm(&model, start), 0);
+
+        let later = start.add_days(65);
+        assert_eq!(condition.missed_maintenance(&model, later), 1);
+        let much_later = start.add_days(65 + 365);
+        assert_eq!(condition.missed_maintenance(&model, much_later), 2);
+
+        condition.take_hit(ShipSystem::JDrive);
+        condition.take_hit(ShipSystem::MDrive);
+        condition.take_hit(ShipSystem::Hull);
+        assert_eq!(condition.jump_dm(&model, much_later), 3);
+        assert_eq!(condition.avoidance_dm(&model, much_later), -3);
+
+        condition.overhaul(much_later);
+        assert_eq!(condition.missed_maintenance(&model, much_later), 0);
+        assert_eq!(condition.jump_dm(&model, much_later), 1);
+    }
+
+    #[test]
+    fn hits_and_breakdowns_land_on_rolled_locations() {
+        assert_eq!(ShipSystem::from_location_roll(1), ShipSystem::Hull);
+        assert_eq!(ShipSystem::from_location_roll(3), ShipSystem::Hull);
+        assert_eq!(ShipSystem::from_location_roll(4), ShipSystem::MDrive);
+        assert_eq!(ShipSystem::from_location_roll(5), ShipSystem::JDrive);
+        assert_eq!(ShipSystem::from_location_roll(6), ShipSystem::PowerPlant);
+
+        let hits = [ShipSystem::Hull, ShipSystem::JDrive, ShipSystem::Hull];
+        assert_eq!(describe_hits(&tally_hits(&hits)), "J-drive ×1, hull ×2");
+
+        let _guard = RngScope::new(7);
+        assert_eq!(roll_hits(5).len(), 5);
+        // Ten missed overhauls break something on any roll.
+        for _ in 0..20 {
+            let (roll, broke) = roll_breakdown(10);
+            assert!((2..=12).contains(&roll));
+            assert!(broke.is_some());
+        }
+    }
+}
@@ -3,13 +3,17 @@
 //! These are the fixed-rate ship-running costs the executor charges as
 //! the simulation proceeds: stateroom rentals, passenger life support,
 //! low-berth life support, and the simulation's day-counter constants
-//! used by the periodic-maintenance/salary tick.
+//! used by the periodic-maintenance/salary tick, plus the greedy
+//! passenger and freight fillers shared by the executor and the
+//! itinerary planner.
 
 // `STATEROOM_COST` and `CREW_LIFE_SUPPORT_PER_MEMBER` are defined on
 // `crate::trade::ship` (the unified Ship struct's home) and re-exported
 // here so existing simulator imports keep working.
 pub use crate::trade::ship::{CREW_LIFE_SUPPORT_PER_MEMBER, STATEROOM_COST};
 
+use crate::trade::available_passengers::{AvailablePassengers, FreightLot};
+
 /// Per-jump life-support cost for a high-passage passenger.
 pub const HIGH_LIFE_SUPPORT: i64 = 2_000;
 /// Per-jump life-support cost for a medium-passage passenger.
@@ -34,6 +38,13 @@ pub const ABORT_OVERFLOW_DAYS: i64 = 100;
 /// Days per (Imperial) week.
 pub const DAYS_PER_WEEK: u32 = 7;
 
+/// A charterer offers to hire the ship when 2d6 plus the route's banded
+/// traffic DM reaches this.
+pub const CHARTER_OFFER_ROLL: i32 = 10;
+
+/// Weeks a charter runs: the port stay plus the jump, rounded up.
+pub const CHARTER_WEEKS: i32 = (DAYS_IN_PORT + DAYS_PER_JUMP).div_ceil(DAYS_PER_WEEK) as i32;
+
 /// Sum-and-leadership threshold for avoiding an incident: a 2d6 roll plus
 /// the captain's leadership plus port/zone/law modifiers must reach this
 /// to dodge.
@@ -51,14 +62,9 @@ pub const WEAPONS_MAX: i16 = 24;
 /// `ceil(parsecs_jumped_from_home / RESCUE_PARSECS_PER_WEEK)`.
 pub const RESCUE_PARSECS_PER_WEEK: u32 = 4;
 
-/// Multiplier on the `(2d6 - broker)` clamp for a Trade Scam credit loss.
-pub const TRADE_SCAM_CR_PER_STEP: i64 = 100_000;
-
-/// Multiplier on a 1d6 roll for an Accident credit loss (per spec).
-pub const ACCIDENT_CR_PER_STEP: i64 = 100_000;
-
-/// Multiplier on a 1d6 roll for a Government Complication fine (per spec).
-pub const GOV_FINE_CR_PER_STEP: i64 = 100_000;
+/// A Crew Loss incident kills the crewmember on this 1d6 roll or under;
+/// otherwise they leave the ship.
+pub const CREW_LOSS_DEATH_ROLL: i32 = 1;
 
 /// Compute how many staterooms are needed to house `high` high-passage,
 /// `medium` medium-passage, and `basic` basic-passage passengers.
@@ -90,6 +96,52 @@ pub fn passenger_costs(high: i32, medium: i32, basic: i32, low: i32) -> (i64, i6
     (stateroom_cost, ls_cost, low_cost)
 }
 
+/// Pick freight lots largest-first into the available cargo. Returns
+/// `(indices_into_freight_lots, total_tons)`.
+pub fn pick_freight(freight_lots: &[FreightLot], cargo_remaining: i32) -> (Vec<usize>, i32) {
+    if cargo_remaining <= 0 {
+        return (Vec::new(), 0);
+    }
+    // Sort indices by lot size descending.
+    let mut order: Vec<usize> = (0..freight_lots.len()).collect();
+    order.sort_by(|&a, &b| freight_lots[b].size.cmp(&freight_lots[a].size));
+
+    let mut chosen: Vec<usize> = Vec::new();
+    let mut tons = 0;
+    let mut left = cargo_remaining;
+    for idx in order {
+        let lot = &freight_lots[idx];
+        if lot.size <= left {
+            chosen.push(idx);
+            tons += lot.size;
+            left -= lot.size;
+            if left <= 0 {
+                break;
+            }
+        }
+    }
+    (chosen, tons)
+}
+
+/// Greedy passenger filler: high → medium → basic until staterooms run
+/// out, then low until low berths run out.
+pub fn pick_passengers(
+    staterooms: i32,
+    low_berths: i32,
+    available: &AvailablePassengers,
+) -> (i32, i32, i32, i32) {
+    let mut rooms = staterooms.max(0);
+    let high = available.high.max(0).min(rooms);
+    rooms -= high;
+    let medium = available.medium.max(0).min(rooms);
+    rooms -= medium;
+    // Basics share two-to-a-room: each remaining stateroom can take 2.
+    let basic_capacity = rooms.saturating_mul(2);
+    let basic = available.basic.max(0).min(basic_capacity);
+    let low = available.low.max(0).min(low_berths.max(0));
+    (high, medium, basic, low)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,4 +208,47 @@ mod tests {
         assert_eq!(ls, 6_500);
         assert_eq!(low, 400);
     }
+
+    #[test]
+    fn pick_passengers_high_first_then_basic_pairs() {
+        let avail = AvailablePassengers {
+            high: 2,
+            medium: 3,
+            basic: 5,
+            low: 4,
+            ..Default::default()
+        };
+        // 4 staterooms, 2 low berths.
+        let (h, m, b, l) = pick_passengers(4, 2, &avail);
+        // 2 high uses 2 rooms; 2 medium uses 2 more rooms; basics get 0
+        // because no rooms left.
+        assert_eq!(h, 2);
+        assert_eq!(m, 2);
+        assert_eq!(b, 0);
+        assert_eq!(l, 2);
+    }
+
+    #[test]
+    fn pick_freight_largest_first() {
+        let lots = vec![
+            FreightLot {
+                size: 20,
+                size_roll: 2,
+            },
+            FreightLot {
+                size: 50,
+                size_roll: 5,
+            },
+            FreightLot {
+                size: 10,
+                size_roll: 1,
+            },
+        ];
+        let (chosen, tons) = pick_freight(&lots, 60);
+        // Largest 50 then 10 → 60 tons; 20 doesn't fit.
+        assert_eq!(tons, 60);
+        assert_eq!(chosen.len(), 2);
+        assert!(chosen.contains(&1));
+        assert!(chosen.contains(&2));
+    }
 }
@@ -1,31 +1,61 @@
 //! Server-side simulation executor.
 //!
-//! Runs the per-jump loop that ties the route planner, market
+//! Runs the per-port-call loop that ties the route planner, market
 //! generation, `process_trades`, and the periodic accounting together,
 //! streaming `SimulationStep`s out as they happen.
 //!
-//! This file is async because [`WorldCache`] does network I/O. The
-//! route planner itself (`route::pick_next`) is sync.
+//! This file is async because a [`WorldSource`] may do network I/O
+//! (the TravellerMap-backed `WorldCache` does; the in-memory and
+//! sector-file sources don't). The route planner itself
+//! (`route::pick_next`) is sync.
+//!
+//! Every roll a voyage makes — markets, passengers, freight, incidents —
+//! comes from one ChaCha stream seeded from `SimulationParams::seed`. It
+//! is a [`ParkedRng`] installed for the synchronous stretches of the loop
+//! and parked across the world-source await, so the run replays exactly
+//! whichever thread the task resumes on.
+//!
+//! A run is a [`Voyage`] stepped one port call at a time. Between port
+//! calls its whole state — RNG position included — can be taken as a
+//! [`Checkpoint`], stored, and resumed later or forked.
 
+use rand::Rng;
+
+use crate::sector::origins::same_sector;
+use crate::seed::system_seed;
+use crate::simulator::condition::{self, ConditionModel, ShipCondition};
 use crate::simulator::economy::{
-    self, ABORT_OVERFLOW_DAYS, ACCIDENT_CR_PER_STEP, DAYS_IN_PORT, DAYS_PER_JUMP, DAYS_PER_WEEK,
-    GOV_FINE_CR_PER_STEP, INCIDENT_AVOID_THRESHOLD, NATURAL_INCIDENT_ROLL, PERIOD_DAYS,
-    TRADE_SCAM_CR_PER_STEP,
+    self, ABORT_OVERFLOW_DAYS, CHARTER_OFFER_ROLL, CHARTER_WEEKS, CREW_LOSS_DEATH_ROLL,
+    DAYS_IN_PORT, DAYS_PER_JUMP, DAYS_PER_WEEK, INCIDENT_AVOID_THRESHOLD, NATURAL_INCIDENT_ROLL,
+    PERIOD_DAYS, pick_freight, pick_passengers,
 };
+use crate::simulator::fuel::{self, FuelModel, FuelSource, FuelTank, WILDERNESS_REFUEL_DAYS};
+use crate::simulator::incident_table::{IncidentKind, IncidentTable, IncidentTableError};
 use crate::simulator::incidents::{
-    avoidance_modifier, incident_table_modifier, pirate_cargo, rescue_eta_days, roll_1d3, roll_1d6,
-    roll_2d6,
+    avoidance_modifier, incident_table_modifier, pirate_cargo, rescue_eta_days, roll_1d6, roll_2d6,
 };
-use crate::simulator::route::{self, RouteContext};
+use crate::simulator::route::{self, Candidate, RouteContext};
+use crate::simulator::strategy::{Onward, RouteStrategy};
+use crate::simulator::traffic::{self, TrafficLedger};
 use crate::simulator::types::{
-    Action, Date, SimulationParams, SimulationResult, SimulationStep, WorldRef,
+    Action, Checkpoint, Date, SimulationParams, SimulationResult, SimulationStep, WorldRef,
 };
-use crate::simulator::world_fetch::{FetchError, WorldCache};
+use crate::simulator::world_fetch::FetchError;
+use crate::simulator::world_source::WorldSource;
+use crate::systems::system::System;
 use crate::systems::world::World;
+use crate::trade::Ship;
 use crate::trade::available_goods::{AvailableGoodsTable, Good};
 use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::cargo_plan::{CargoLimits, SaleMarket, plan_cargo};
+use crate::trade::contracts::{Charter, MAIL_CONTAINER_TONS, traffic_band};
+use crate::trade::crew::{CrewMember, CrewStatus};
+use crate::trade::customs;
+use crate::trade::market::{MarketKey, MarketLedger};
+use crate::trade::port_fees::{MIN_BERTHING_HULL_TONS, PortCharges, TariffRates, berthing_fee};
 use crate::trade::ship_manifest::ShipManifest;
 use crate::trade::table::TradeTable;
+use crate::util::{ParkedRng, WorldgenRng};
 
 /// Errors the executor can return before producing a [`SimulationResult`].
 #[derive(Debug, thiserror::Error)]
@@ -36,119 +66,232 @@ pub enum ExecutorError {
     /// Network or parsing failure fetching candidate worlds.
     #[error("world fetch failed: {0}")]
     Fetch(#[from] FetchError),
+    /// The params carry an incident table that can't be rolled on.
+    #[error("{0}")]
+    IncidentTable(#[from] IncidentTableError),
+    /// A fleet was asked to sail without any ships.
+    #[error("a fleet needs at least one ship")]
+    EmptyFleet,
     /// Internal logic error — should never fire in practice.
     #[error("simulation invariant violated: {0}")]
     Invariant(String),
 }
 
 /// Run a full simulation. Calls `on_step` for each step as it happens;
-/// returns the final result. The `cache` is reused across the whole run
-/// so candidate world fetches are de-duplicated.
+/// returns the final result. Candidate worlds come from `source`, which
+/// is reused across the whole run so a caching source de-duplicates its
+/// lookups.
 pub async fn run_simulation(
     params: SimulationParams,
-    cache: &mut WorldCache,
+    source: &mut impl WorldSource,
     mut on_step: impl FnMut(SimulationStep) + Send,
 ) -> Result<SimulationResult, ExecutorError> {
-    // === setup ===========================================================
-    let mut budget: i64 = params.starting_budget;
-    let mut manifest = ShipManifest::default();
-    let mut current_date = params.start_date;
-    let mut days_since_payment: u32 = 0;
-    let mut periods_paid: u32 = 0;
-    let mut total_mortgage_paid: i64 = 0;
-    let mut went_negative = false;
-    let mut returned_home = false;
-    let mut completed_normally = true;
-    let mut history: Vec<WorldRef> = Vec::new();
-    let mut jumps_taken: u32 = 0;
-
-    let mut current_world =
-        World::from_uwp(&params.home_world.name, &params.home_world.uwp, false, true).map_err(
-            |e| ExecutorError::InvalidHomeUwp(format!("{}: {}", params.home_world.uwp, e)),
-        )?;
-    current_world.gen_trade_classes();
-    current_world.coordinates = Some((params.home_world.hex_x, params.home_world.hex_y));
-    current_world.travel_zone = params.home_world.zone;
-    let mut current_ref = params.home_world.clone();
-    // Allegiance of the world we're currently at. The home world's
-    // allegiance isn't supplied on `SimulationParams.home_world`, so we
-    // start with `None` (treated as friendly). Each post-jump arrival
-    // updates this from the chosen `Candidate.allegiance`.
-    let mut current_allegiance: Option<String> = None;
-
-    // Incident/marooning state, threaded through the loop.
-    let mut total_parsecs_jumped: u32 = 0;
-    let mut marooned: bool = false;
-    let mut marooned_state: Option<(WorldRef, Date, Date)> = None;
-    let mut incident_eligible: bool = false;
-
-    // Initial Arrive at the home world (distance 0).
-    emit(
-        &mut on_step,
-        current_date,
-        &current_ref,
-        budget,
-        Action::Arrive {
-            from: current_ref.clone(),
-            distance: 0,
-            fuel_cost: 0,
-        },
-    );
+    let mut voyage = Voyage::new(params)?;
+    while !voyage.is_finished() {
+        voyage.step(source, &mut on_step).await?;
+    }
+    Ok(voyage.result())
+}
+
+/// A voyage in progress. Advances one port call per [`Voyage::step`],
+/// so a caller can pause between port calls, and can be captured as a
+/// [`Checkpoint`] at any of those points and picked up again with
+/// [`Voyage::from_checkpoint`].
+pub struct Voyage {
+    /// Everything but the live RNG and planner, which are folded back in
+    /// by [`Voyage::checkpoint`].
+    state: Checkpoint,
+    rng: ParkedRng,
+    strategy: Box<dyn RouteStrategy>,
+}
+
+impl Voyage {
+    /// A voyage about to make its first port call, at the home world.
+    pub fn new(params: SimulationParams) -> Result<Voyage, ExecutorError> {
+        if let Some(table) = &params.incidents {
+            table.validate()?;
+        }
+        let seed = params.seed.unwrap_or_else(rand::random);
+        let mut current_world =
+            World::from_uwp(&params.home_world.name, &params.home_world.uwp, false, true).map_err(
+                |e| ExecutorError::InvalidHomeUwp(format!("{}: {}", params.home_world.uwp, e)),
+            )?;
+        current_world.gen_trade_classes();
+        current_world.coordinates = Some((params.home_world.hex_x, params.home_world.hex_y));
+        current_world.travel_zone = params.home_world.zone;
+
+        Ok(Voyage::from_checkpoint(Checkpoint {
+            seed,
+            rng_word_pos: 0,
+            port_calls: 0,
+            finished: false,
+            budget: params.starting_budget,
+            manifest: ShipManifest::default(),
+            current_date: params.start_date,
+            days_since_payment: 0,
+            periods_paid: 0,
+            total_mortgage_paid: 0,
+            current_world,
+            current_ref: params.home_world.clone(),
+            // The home world's allegiance isn't supplied on
+            // `SimulationParams.home_world`, so we start with `None`
+            // (treated as friendly). Each arrival updates this from the
+            // chosen `Candidate.allegiance`.
+            current_allegiance: None,
+            current_gas_giants: None,
+            // A fuel-modelling voyage leaves home with full tanks.
+            fuel: params
+                .fuel
+                .map(|model| FuelTank::full(model.tankage))
+                .unwrap_or_default(),
+            condition: params
+                .condition
+                .map(|model| ShipCondition::new(&model, params.start_date))
+                .unwrap_or_default(),
+            markets: params.markets.clone(),
+            traffic: TrafficLedger::new(),
+            history: Vec::new(),
+            jumps_taken: 0,
+            total_parsecs_jumped: 0,
+            route_cursor: 0,
+            incident_eligible: false,
+            went_negative: false,
+            returned_home: false,
+            completed_normally: true,
+            marooned: false,
+            marooned_at: None,
+            marooned_on: None,
+            rescue_arrives_on: None,
+            params,
+        }))
+    }
+
+    /// Pick a voyage up from a checkpoint, with its random stream and
+    /// route planner where they were when it was taken.
+    pub fn from_checkpoint(checkpoint: Checkpoint) -> Voyage {
+        let rng = ParkedRng::resume(checkpoint.seed, checkpoint.rng_word_pos);
+        let mut strategy = checkpoint.params.route.build();
+        strategy.set_cursor(checkpoint.route_cursor);
+        Voyage {
+            state: checkpoint,
+            rng,
+            strategy,
+        }
+    }
+
+    /// The voyage's full state as it stands between port calls.
+    pub fn checkpoint(&self) -> Checkpoint {
+        let mut checkpoint = self.state.clone();
+        checkpoint.rng_word_pos = self.rng.word_pos();
+        checkpoint.route_cursor = self.strategy.cursor();
+        checkpoint
+    }
+
+    /// Whether the voyage has ended. Further steps do nothing.
+    pub fn is_finished(&self) -> bool {
+        self.state.finished
+    }
+
+    /// The date of the voyage's next port call, or its last once it has
+    /// finished.
+    pub fn current_date(&self) -> Date {
+        self.state.current_date
+    }
+
+    /// Swap the voyage's markets and route traffic with a fleet's, so the
+    /// next port call trades against the fleet's and a second swap hands
+    /// them back.
+    pub(crate) fn share(&mut self, markets: &mut MarketLedger, traffic: &mut TrafficLedger) {
+        std::mem::swap(&mut self.state.markets, markets);
+        std::mem::swap(&mut self.state.traffic, traffic);
+    }
+
+    /// Make one port call: pay what's due, roll for an incident, trade,
+    /// pick the next world and jump to it — or end the voyage. Calls
+    /// `on_step` for each step as it happens.
+    pub async fn step(
+        &mut self,
+        source: &mut impl WorldSource,
+        on_step: &mut (impl FnMut(SimulationStep) + Send),
+    ) -> Result<(), ExecutorError> {
+        let Voyage {
+            state: s,
+            rng,
+            strategy,
+        } = self;
+        if s.finished {
+            return Ok(());
+        }
+        if s.port_calls == 0 {
+            // Initial Arrive at the home world (distance 0).
+            emit(
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
+                Action::Arrive {
+                    from: s.current_ref.clone(),
+                    distance: 0,
+                    fuel_cost: 0,
+                },
+            );
+        }
+        s.port_calls += 1;
+        let _rolls = rng.enter();
 
-    // === main loop =======================================================
-    loop {
         // (1) Periodic costs.
-        while days_since_payment >= PERIOD_DAYS {
-            let maintenance = params.ship.maintenance_per_period;
-            let salary = params.ship.salary_per_period;
-            let mortgage = params.ship.mortgage_per_period;
-            budget -= maintenance + salary + mortgage;
-            total_mortgage_paid += mortgage;
+        while s.days_since_payment >= PERIOD_DAYS {
+            let maintenance = s.params.ship.maintenance_per_period;
+            let salary = s.params.ship.effective_salary_per_period();
+            let mortgage = s.params.ship.mortgage_per_period;
+            s.budget -= maintenance + salary + mortgage;
+            s.total_mortgage_paid += mortgage;
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::PayPeriodic {
                     maintenance,
                     salary,
                     mortgage,
-                    period_index: periods_paid,
+                    period_index: s.periods_paid,
                 },
             );
-            periods_paid += 1;
-            if budget < 0 && !went_negative {
-                went_negative = true;
+            s.periods_paid += 1;
+            if s.budget < 0 && !s.went_negative {
+                s.went_negative = true;
                 emit(
-                    &mut on_step,
-                    current_date,
-                    &current_ref,
-                    budget,
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
                     Action::BudgetWarning {
                         note: format!(
                             "Budget went negative ({}) after period {}.",
-                            budget, periods_paid
+                            s.budget, s.periods_paid
                         ),
                     },
                 );
             }
-            days_since_payment -= PERIOD_DAYS;
+            s.days_since_payment -= PERIOD_DAYS;
         }
 
         // (2) Abort check: too far past target.
-        let overflow_days = current_date.days_until(params.target_completion_date);
+        let overflow_days = s.current_date.days_until(s.params.target_completion_date);
         if overflow_days < -ABORT_OVERFLOW_DAYS {
-            completed_normally = false;
+            s.completed_normally = false;
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::AbortOverflow {
                     days_past_target: -overflow_days,
                 },
             );
-            break;
+            s.finished = true;
+            return Ok(());
         }
 
         // (2b) Port stay: every visit eats `DAYS_IN_PORT` days for
@@ -157,75 +300,138 @@ pub async fn run_simulation(
         // makes the per-visit "Arrived" and "Departed" dates differ in
         // the captain's log. Incidents and complications add days *on
         // top* of this in step (3).
-        current_date = current_date.add_days(DAYS_IN_PORT);
-        days_since_payment += DAYS_IN_PORT;
+        s.current_date = s.current_date.add_days(DAYS_IN_PORT);
+        s.days_since_payment += DAYS_IN_PORT;
+
+        // (2c) Berthing. The voyage starts already docked at home, so the
+        // first port call is free; every arrival after that pays.
+        if s.jumps_taken > 0 {
+            let fee = berthing_fee(s.current_world.port, s.params.ship.hull_tons);
+            if fee > 0 {
+                s.budget -= fee;
+                emit(
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
+                    Action::PayBerthing {
+                        hull_tons: s.params.ship.hull_tons.max(MIN_BERTHING_HULL_TONS),
+                        fee,
+                    },
+                );
+            }
+        }
 
-        // (3) Incident roll. Skipped on the very first iteration so the
+        // (2d) Customs. A ship arriving with contraband in the hold risks
+        // a search; what's found is confiscated and fined, and a badly
+        // failed search impounds the ship for weeks.
+        if s.jumps_taken > 0
+            && s.manifest
+                .trade_goods
+                .goods
+                .iter()
+                .any(|g| g.quantity > 0 && g.is_illegal())
+        {
+            run_customs(s, on_step);
+        }
+
+        // (3) Incident roll. Skipped on the very first port call so the
         // initial Arrive at the home world doesn't trigger one. Any "weeks
         // lost" from an incident pushes the date forward; the periodic and
-        // abort blocks above handle that on the *next* iteration.
-        if incident_eligible {
-            let is_foreign = !route::is_allegiance_friendly(current_allegiance.as_deref());
+        // abort blocks above handle that on the *next* port call.
+        if s.incident_eligible {
+            let is_foreign = !route::is_allegiance_friendly(s.current_allegiance.as_deref());
+            let table = s
+                .params
+                .incidents
+                .as_ref()
+                .unwrap_or_else(|| IncidentTable::builtin());
+            let condition_dm = s
+                .params
+                .condition
+                .map_or(0, |model| s.condition.avoidance_dm(&model, s.current_date));
             let weeks_lost = run_incident_roll(
-                &params,
-                &current_world,
-                &current_ref,
+                table,
+                &mut s.params.ship,
+                &s.current_world,
+                &s.current_ref,
                 is_foreign,
-                &mut budget,
-                &mut manifest,
-                current_date,
-                &mut on_step,
+                condition_dm,
+                s.params.condition.is_some().then_some(&mut s.condition),
+                &mut s.budget,
+                &mut s.manifest,
+                s.current_date,
+                on_step,
             );
             if weeks_lost > 0 {
                 let added_days = weeks_lost * DAYS_PER_WEEK;
-                current_date = current_date.add_days(added_days);
-                days_since_payment += added_days;
+                s.current_date = s.current_date.add_days(added_days);
+                s.days_since_payment += added_days;
             }
-            if budget < 0 && !went_negative {
-                went_negative = true;
+            if s.budget < 0 && !s.went_negative {
+                s.went_negative = true;
                 emit(
-                    &mut on_step,
-                    current_date,
-                    &current_ref,
-                    budget,
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
                     Action::BudgetWarning {
-                        note: format!("Budget went negative ({}) after incident.", budget),
+                        note: format!("Budget went negative ({}) after incident.", s.budget),
                     },
                 );
             }
         }
 
+        // (3b) Condition: a ship overdue for maintenance may break down;
+        // then the yard overhauls and repairs what it can.
+        if let Some(model) = s.params.condition {
+            tend_condition(s, &model, on_step);
+        }
+
         // (4) End-of-trip detection. We're home and have actually travelled.
         // Price and sell whatever's still in the hold (anything we bought on
-        // the last leg expecting to sell at home), then break out of the
-        // loop. Without this, profitable cargo bought for the home leg sits
+        // the last leg expecting to sell at home), then end the voyage.
+        // Without this, profitable cargo bought for the home leg sits
         // unrealized and the trip P&L is skewed by hundreds of kCr.
-        let at_home = jumps_taken > 0 && worldref_same_hex(&current_ref, &params.home_world);
+        let at_home = s.jumps_taken > 0 && worldref_same_hex(&s.current_ref, &s.params.home_world);
 
-        // (5) Generate this port's market and price to buy locally.
+        // (5) Generate this port's market, shifted by what's been bought
+        // and sold here lately, and price to buy locally.
         let trade_table = TradeTable::global();
-        let pop = current_world.get_population();
+        let pop = s.current_world.get_population();
+        let here = MarketKey::placed(
+            source.sector_origins(),
+            &s.current_ref.sector,
+            s.current_ref.hex_x,
+            s.current_ref.hex_y,
+        );
+        let today = s.current_date.day_number();
         let mut market = AvailableGoodsTable::for_world(
             trade_table,
-            &current_world.get_trade_classes(),
+            &s.current_world.get_trade_classes(),
             pop,
-            params.illegal_goods,
+            s.params.illegal_goods,
         )
         .map_err(ExecutorError::Invariant)?;
+        s.markets.adjust_supply(&here, today, pop, &mut market);
         market.price_goods_to_buy(
-            &current_world.get_trade_classes(),
-            params.ship.broker_skill,
-            params.planetary_broker_skill,
+            &s.current_world.get_trade_classes(),
+            s.params.ship.effective_broker_skill(),
+            s.params.planetary_broker_skill,
         );
 
         // (6) SELL phase: price what's already in the manifest at this
         // world; sell anything that beats its buy_cost, hold the rest.
-        manifest.trade_goods.price_goods_to_sell(
-            Some(current_world.get_trade_classes()),
-            params.planetary_broker_skill,
-            params.ship.broker_skill,
+        // Each sale pays this world's import tariff.
+        let tariffs = TariffRates::for_world(&s.current_world);
+        s.markets
+            .adjust_demand(&here, today, pop, &mut s.manifest.trade_goods);
+        s.manifest.trade_goods.price_goods_to_sell(
+            Some(s.current_world.get_trade_classes()),
+            s.params.planetary_broker_skill,
+            s.params.ship.effective_broker_skill(),
         );
-        for good in manifest.trade_goods.goods.iter_mut() {
+        for good in s.manifest.trade_goods.goods.iter_mut() {
             if good.quantity <= 0 {
                 continue;
             }
@@ -238,13 +444,14 @@ pub async fn run_simulation(
                 // matching deduction was paid when these goods were bought
                 // on a previous turn (and is recorded in `good.buy_cost`).
                 let sell_proceeds = sell_price as i64 * qty as i64;
-                budget += sell_proceeds;
+                s.budget += sell_proceeds;
+                s.markets.record_sale(&here, today, good.source_index, qty);
                 let profit = sell_proceeds - good.buy_cost as i64 * qty as i64;
                 emit(
-                    &mut on_step,
-                    current_date,
-                    &current_ref,
-                    budget,
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
                     Action::SellGood {
                         good: good.name.clone(),
                         qty,
@@ -253,13 +460,29 @@ pub async fn run_simulation(
                         profit,
                     },
                 );
+                let tariff = tariffs.import_on(sell_proceeds);
+                if tariff > 0 {
+                    s.budget -= tariff;
+                    emit(
+                        on_step,
+                        s.current_date,
+                        &s.current_ref,
+                        s.budget,
+                        Action::PayImportTariff {
+                            good: good.name.clone(),
+                            value: sell_proceeds,
+                            rate_pct: tariffs.import_pct,
+                            tariff,
+                        },
+                    );
+                }
             } else {
                 good.transacted = 0;
                 emit(
-                    &mut on_step,
-                    current_date,
-                    &current_ref,
-                    budget,
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
                     Action::HoldGood {
                         good: good.name.clone(),
                         qty: good.quantity,
@@ -276,129 +499,272 @@ pub async fn run_simulation(
         }
 
         // (5b) End-of-trip settlement. If we're back home, realize the sale
-        // revenue from the cargo we just priced and break. We pass empty
+        // revenue from the cargo we just priced and finish. We pass empty
         // `buy_goods` and `None` passengers because the next-jump prep
         // (steps 7-10) is skipped when at home.
         if at_home {
             // Sale proceeds were already applied to `budget` per-good in
             // step (5); we just need to flush the manifest mutations
             // (clear sold cargo) without double-counting revenue.
-            manifest.process_trades(0, &[], &None);
-            returned_home = true;
-            break;
+            s.manifest
+                .process_trades(0, &[], &None, &PortCharges::default());
+            s.returned_home = true;
+            s.finished = true;
+            return Ok(());
+        }
+
+        // (5c) Refuel. Under a fuel model the tanks are topped up before
+        // any cargo is bought, so fuel has first call on the budget.
+        if let Some(model) = s.params.fuel {
+            refuel(s, &model, on_step);
         }
 
         // (6) ROUTE phase: gather candidates, pick the next destination.
-        let candidates = cache
+        // The seeded stream is parked across the await.
+        drop(_rolls);
+        let mut candidates = source
             .candidates_within(
-                &current_ref.sector,
-                (current_ref.hex_x, current_ref.hex_y),
-                params.ship.jump_rating as i32,
+                &s.current_ref.sector,
+                (s.current_ref.hex_x, s.current_ref.hex_y),
+                s.params.ship.jump_rating as i32,
             )
             .await?;
+        let any_in_range = !candidates.is_empty();
+        if let Some(model) = &s.params.fuel {
+            let aboard = s.fuel.tons;
+            candidates.retain(|c| model.tons_for(c.distance) <= aboard);
+        }
+        let onward = gather_onward(
+            source,
+            &candidates,
+            s.params.ship.jump_rating as i32,
+            strategy.horizon(),
+        )
+        .await?;
+        let _rolls = rng.enter();
         if candidates.is_empty() {
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::NoCandidate {
-                    note: "No reachable worlds within jump range.".to_string(),
+                    note: if any_in_range {
+                        "Not enough fuel aboard to reach any world in range.".to_string()
+                    } else {
+                        "No reachable worlds within jump range.".to_string()
+                    },
                 },
             );
-            completed_normally = false;
-            break;
+            s.completed_normally = false;
+            s.finished = true;
+            return Ok(());
         }
 
         let ctx = RouteContext {
-            home: &params.home_world,
-            current_date,
-            start_date: params.start_date,
-            target_date: params.target_completion_date,
-            jump: params.ship.jump_rating as i32,
-            fuel_cost_per_parsec: params.fuel_cost_per_parsec,
-            history: &history,
+            home: &s.params.home_world,
+            current: &s.current_ref,
+            current_date: s.current_date,
+            start_date: s.params.start_date,
+            target_date: s.params.target_completion_date,
+            jump: s.params.ship.jump_rating as i32,
+            fuel_cost_per_parsec: s
+                .params
+                .fuel
+                .map_or(s.params.fuel_cost_per_parsec, |model| {
+                    model.cost_per_parsec()
+                }),
+            history: &s.history,
+            sectors: source.sector_origins(),
+            weights: s.params.route_weights,
         };
-        let next = match route::pick_next(&candidates, &market, &ctx) {
+        let next = match strategy.pick(&candidates, &onward, &market, &ctx) {
             Some(n) => n,
             None => {
                 emit(
-                    &mut on_step,
-                    current_date,
-                    &current_ref,
-                    budget,
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
                     Action::NoCandidate {
                         note: "Route planner returned no destination.".to_string(),
                     },
                 );
-                completed_normally = false;
-                break;
+                s.completed_normally = false;
+                s.finished = true;
+                return Ok(());
             }
         };
-        let next_ref = world_ref_for(&next.world, &current_ref.sector);
+        let next_ref = next.world_ref();
+
+        // (6b) CHARTER: with nothing of its own left in the hold, the ship
+        // may be offered a whole-ship charter for the coming leg. A
+        // chartered leg carries no passengers, mail, freight or cargo of
+        // the ship's own, and the fee is paid on arrival.
+        let charter = if s.params.accept_charters
+            && s.manifest.trade_goods_tonnage() == 0
+            && roll_2d6()
+                + traffic_band(AvailablePassengers::traffic_dm(
+                    s.current_world.get_population(),
+                    s.current_world.port,
+                    s.current_world.travel_zone,
+                    s.current_world.tech_level,
+                    next.world.get_population(),
+                    next.world.port,
+                    next.world.travel_zone,
+                    next.world.tech_level,
+                    next.distance,
+                ))
+                >= CHARTER_OFFER_ROLL
+        {
+            Charter::for_ship(&s.params.ship, CHARTER_WEEKS)
+        } else {
+            None
+        };
+        s.manifest.charter = charter;
+        if let Some(charter) = charter {
+            emit(
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
+                Action::Charter {
+                    weeks: charter.weeks,
+                    weekly_rate: charter.weekly_rate,
+                    revenue_pending: charter.revenue(),
+                },
+            );
+        }
 
         // (7) PAX FIRST: each passenger reserves a personal-cargo
         // allotment (1 ton high, 0.1 medium, 0.01 basic, 0 low), so we
         // can't size the buy/freight loadout until we know the pax mix.
-        let mut available_pax = AvailablePassengers::default();
-        available_pax.generate(
-            current_world.get_population(),
-            current_world.port,
-            current_world.travel_zone,
-            current_world.tech_level,
-            next.world.get_population(),
-            next.world.port,
-            next.world.travel_zone,
-            next.world.tech_level,
-            next.distance,
-            params.ship.steward_skill as i32,
-            params.ship.broker_skill as i32,
+        // A route another ship loaded for this week offers only what it
+        // left behind, with no fresh rolls.
+        let there = MarketKey::placed(
+            source.sector_origins(),
+            &next_ref.sector,
+            next_ref.hex_x,
+            next_ref.hex_y,
         );
+        let waiting = s.traffic.offered(&here, &there, today).cloned();
+        let fresh = waiting.is_none();
+        let mut available_pax = waiting.unwrap_or_default();
+        if charter.is_none() && fresh {
+            available_pax.generate(
+                s.current_world.get_population(),
+                s.current_world.port,
+                s.current_world.travel_zone,
+                s.current_world.tech_level,
+                next.world.get_population(),
+                next.world.port,
+                next.world.travel_zone,
+                next.world.tech_level,
+                next.distance,
+                s.params.ship.effective_steward_skill() as i32,
+                s.params.ship.effective_broker_skill() as i32,
+            );
+        }
         let (h, m, b, l) = pick_passengers(
-            params.ship.passenger_staterooms,
-            params.ship.low_berths,
+            s.params.ship.passenger_staterooms,
+            s.params.ship.low_berths,
             &available_pax,
         );
-        manifest.high_passengers = h;
-        manifest.medium_passengers = m;
-        manifest.basic_passengers = b;
-        manifest.low_passengers = l;
+        s.manifest.high_passengers = h;
+        s.manifest.medium_passengers = m;
+        s.manifest.basic_passengers = b;
+        s.manifest.low_passengers = l;
         // Reserve passenger personal-cargo space, rounding up to the
         // ton — pick_to_buy/pick_freight work in integer tons and we'd
         // rather under-fill than overload.
-        let pax_cargo_tons = manifest.passenger_cargo_tons().ceil() as i32;
-        let cargo_after_pax = (params.ship.cargo_capacity - pax_cargo_tons).max(0);
+        let pax_cargo_tons = s.manifest.passenger_cargo_tons().ceil() as i32;
+        let cargo_after_pax = (s.params.ship.cargo_capacity - pax_cargo_tons).max(0);
+
+        // (7b) MAIL: a flat Cr5,000 a ton beats freight at any distance,
+        // so whatever mail is offered goes aboard ahead of cargo.
+        if charter.is_none() {
+            if fresh {
+                available_pax.generate_mail(
+                    &s.current_world,
+                    &next.world,
+                    next.distance,
+                    s.params.ship.weapons,
+                );
+            }
+            s.manifest.mail_containers = available_pax
+                .mail_containers
+                .min(cargo_after_pax / MAIL_CONTAINER_TONS);
+        }
+        let cargo_after_mail = cargo_after_pax - s.manifest.mail_tons();
 
         // (8) BUY phase: re-price the market for the chosen destination
         // and pick the most-profit-per-ton lots that fit in budget+hold
-        // (after passenger cargo is reserved).
+        // (after passenger cargo and mail are reserved), or plan the buy
+        // on expected prices if the params ask for a cargo plan. Nothing
+        // is bought for a chartered leg.
         let next_classes = next.world.get_trade_classes();
+        s.markets
+            .adjust_demand(&there, today, next.world.get_population(), &mut market);
         market.price_goods_to_sell(
             Some(next_classes.clone()),
-            params.planetary_broker_skill,
-            params.ship.broker_skill,
+            s.params.planetary_broker_skill,
+            s.params.ship.effective_broker_skill(),
         );
-        let fuel_for_jump = (next.distance as i64) * params.fuel_cost_per_parsec;
+        // Under a fuel model the fuel was already paid for at refuelling.
+        let fuel_for_jump = match s.params.fuel {
+            Some(_) => 0,
+            None => (next.distance as i64) * s.params.fuel_cost_per_parsec,
+        };
         // Keep some headroom for upcoming life-support costs. We now
         // know the exact pax mix, but the reserve still hedges against
         // the next leg's worst case.
-        let pax_reserve = pax_reserve_estimate(&params);
-        let buy_budget = (budget - fuel_for_jump - pax_reserve).max(0);
+        let pax_reserve = pax_reserve_estimate(&s.params);
+        // Scale the budget down so purchases plus their export tariff fit.
+        let buy_budget =
+            (s.budget - fuel_for_jump - pax_reserve).max(0) * 100 / (100 + tariffs.export_pct);
         // Goods that won't beat the freight rate at this distance aren't
         // worth the hold space — filling that ton with freight would pay
         // more reliably. Use the freight Cr/ton as a per-ton profit floor.
         let freight_floor = ShipManifest::freight_rate_per_ton(next.distance);
-        let buy_goods = pick_to_buy(&market, cargo_after_pax, buy_budget, freight_floor);
+        let buy_goods = match s.params.cargo_plan {
+            _ if charter.is_some() => Vec::new(),
+            None => pick_to_buy(&market, cargo_after_mail, buy_budget, freight_floor),
+            Some(risk) => {
+                // The planner weighs the whole spread of sale rolls rather
+                // than the one rolled above. A ton only has to beat freight
+                // if there is freight to fill it with.
+                let sale = SaleMarket {
+                    trade_classes: &next_classes,
+                    buyer_broker_skill: s.params.planetary_broker_skill,
+                    supplier_broker_skill: s.params.ship.effective_broker_skill(),
+                    use_rolled_prices: false,
+                };
+                let limits = CargoLimits {
+                    cargo_capacity: cargo_after_mail,
+                    committed_tons: 0,
+                    budget: buy_budget,
+                    freight_rate_per_ton: if available_pax.freight_lots.is_empty() {
+                        0
+                    } else {
+                        freight_floor
+                    },
+                    risk,
+                };
+                plan_cargo(&market.goods, &sale, &limits).purchases
+            }
+        };
         for g in &buy_goods {
             // Apply purchase cost to budget *now* so each Buy step reflects
             // the cash outflow.
             let total_cost = g.transacted as i64 * g.buy_cost as i64;
-            budget -= total_cost;
+            s.budget -= total_cost;
+            s.markets
+                .record_purchase(&here, today, g.source_index, g.transacted);
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::BuyGood {
                     good: g.name.clone(),
                     qty: g.transacted,
@@ -406,36 +772,75 @@ pub async fn run_simulation(
                     total_cost,
                 },
             );
+            let tariff = tariffs.export_on(total_cost);
+            if tariff > 0 {
+                s.budget -= tariff;
+                emit(
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
+                    Action::PayExportTariff {
+                        good: g.name.clone(),
+                        value: total_cost,
+                        rate_pct: tariffs.export_pct,
+                        tariff,
+                    },
+                );
+            }
         }
 
-        // (9) FREIGHT: fill remaining hold (after pax cargo + bought goods).
+        // (9) FREIGHT: fill remaining hold (after pax cargo, mail and
+        // bought goods).
         let total_buy_tons: i32 = buy_goods.iter().map(|g| g.transacted).sum();
-        let cargo_remaining = (cargo_after_pax - total_buy_tons).max(0);
+        let cargo_remaining = (cargo_after_mail - total_buy_tons).max(0);
         let (chosen_lots, freight_tons) =
             pick_freight(&available_pax.freight_lots, cargo_remaining);
-        manifest.freight_lot_indices = chosen_lots;
-        let pax_revenue_pending = manifest.passenger_revenue(next.distance) as i64;
+        s.manifest.freight_lot_indices = chosen_lots;
+        let pax_revenue_pending = s.manifest.passenger_revenue(next.distance) as i64;
         let freight_revenue_pending =
-            manifest.freight_revenue(next.distance, &available_pax) as i64;
-        if !manifest.freight_lot_indices.is_empty() {
+            s.manifest.freight_revenue(next.distance, &available_pax) as i64;
+        let mail_revenue_pending = s.manifest.mail_revenue() as i64;
+        if charter.is_none() {
+            s.traffic.record(
+                &here,
+                &there,
+                today,
+                traffic::left_after(&available_pax, &s.manifest),
+            );
+        }
+        if !s.manifest.freight_lot_indices.is_empty() {
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::LoadFreight {
                     tons: freight_tons,
-                    lots: manifest.freight_lot_indices.len() as u32,
+                    lots: s.manifest.freight_lot_indices.len() as u32,
                     revenue_pending: freight_revenue_pending,
                 },
             );
         }
+        if s.manifest.mail_containers > 0 {
+            emit(
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
+                Action::LoadMail {
+                    containers: s.manifest.mail_containers,
+                    tons: s.manifest.mail_tons(),
+                    revenue_pending: mail_revenue_pending,
+                },
+            );
+        }
         if h + m + b + l > 0 {
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::BoardPax {
                     high: h,
                     medium: m,
@@ -448,13 +853,13 @@ pub async fn run_simulation(
 
         // (10) Pay life support.
         let (sr_cost, ls_cost, low_cost) = economy::passenger_costs(h, m, b, l);
-        let crew_cost = params.ship.crew_life_support_per_jump();
-        budget -= sr_cost + ls_cost + low_cost + crew_cost;
+        let crew_cost = s.params.ship.crew_life_support_per_jump();
+        s.budget -= sr_cost + ls_cost + low_cost + crew_cost;
         emit(
-            &mut on_step,
-            current_date,
-            &current_ref,
-            budget,
+            on_step,
+            s.current_date,
+            &s.current_ref,
+            s.budget,
             Action::PayLifeSupport {
                 stateroom_cost: sr_cost,
                 ls_cost,
@@ -462,15 +867,15 @@ pub async fn run_simulation(
                 crew_cost,
             },
         );
-        if budget < 0 && !went_negative {
-            went_negative = true;
+        if s.budget < 0 && !s.went_negative {
+            s.went_negative = true;
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::BudgetWarning {
-                    note: format!("Budget went negative ({}) paying life support.", budget),
+                    note: format!("Budget went negative ({}) paying life support.", s.budget),
                 },
             );
         }
@@ -478,35 +883,42 @@ pub async fn run_simulation(
         // (9b) Marooning check. After life support and any incident
         // penalties, if the budget is negative the ship can't pay to
         // leave port. The run terminates here.
-        if budget < 0 {
-            marooned = true;
-            let eta = rescue_eta_days(total_parsecs_jumped);
-            let arrives = current_date.add_days(eta);
-            marooned_state = Some((current_ref.clone(), current_date, arrives));
+        if s.budget < 0 {
+            s.marooned = true;
+            let eta = rescue_eta_days(s.total_parsecs_jumped);
+            let arrives = s.current_date.add_days(eta);
+            s.marooned_at = Some(s.current_ref.clone());
+            s.marooned_on = Some(s.current_date);
+            s.rescue_arrives_on = Some(arrives);
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::Marooned {
-                    budget,
-                    total_parsecs_jumped,
+                    budget: s.budget,
+                    total_parsecs_jumped: s.total_parsecs_jumped,
                     rescue_eta_days: eta,
                     rescue_arrives_on: arrives,
                 },
             );
-            completed_normally = false;
-            break;
+            s.completed_normally = false;
+            s.finished = true;
+            return Ok(());
         }
 
-        // (11) Pay fuel.
-        budget -= fuel_for_jump;
-        let from_ref = current_ref.clone();
+        // (11) Pay fuel, or burn it from the tanks.
+        s.budget -= fuel_for_jump;
+        let unrefined_burned = match &s.params.fuel {
+            Some(model) => s.fuel.burn(model.tons_for(next.distance)),
+            None => 0,
+        };
+        let from_ref = s.current_ref.clone();
         emit(
-            &mut on_step,
-            current_date,
-            &current_ref,
-            budget,
+            on_step,
+            s.current_date,
+            &s.current_ref,
+            s.budget,
             Action::Jump {
                 to: next_ref.clone(),
                 distance: next.distance,
@@ -517,46 +929,98 @@ pub async fn run_simulation(
         // (12) process_trades — mutates the manifest (sells transacted
         // goods, adds buy_goods, clears pax/freight) and accumulates a
         // settlement delta on `manifest.profit`. We *don't* use that delta
-        // for the budget; the goods part is already applied via the per-
-        // action sell/buy updates above. What's still pending is the
-        // pax + freight revenue, which only realizes when the ship arrives
-        // at the destination.
-        manifest.process_trades(next.distance, &buy_goods, &Some(available_pax));
-        let pending_revenue = pax_revenue_pending + freight_revenue_pending;
-        budget += pending_revenue;
-        if budget < 0 && !went_negative {
-            went_negative = true;
+        // for the budget; the goods part and the port charges are already
+        // applied via the per-action updates above. What's still pending is the
+        // pax, freight, mail and charter revenue, which only realizes when
+        // the ship arrives at the destination.
+        s.manifest.process_trades(
+            next.distance,
+            &buy_goods,
+            &Some(available_pax),
+            &PortCharges::default(),
+        );
+        let charter_revenue_pending = charter.map_or(0, |c| c.revenue());
+        let pending_revenue = pax_revenue_pending
+            + freight_revenue_pending
+            + mail_revenue_pending
+            + charter_revenue_pending;
+        s.budget += pending_revenue;
+        if s.budget < 0 && !s.went_negative {
+            s.went_negative = true;
             emit(
-                &mut on_step,
-                current_date,
-                &current_ref,
-                budget,
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
                 Action::BudgetWarning {
-                    note: format!("Budget went negative ({}) after settling trades.", budget),
+                    note: format!("Budget went negative ({}) after settling trades.", s.budget),
                 },
             );
         }
 
         // (13) Advance state to the new world; bump time.
-        history.insert(0, current_ref.clone());
-        if history.len() > 8 {
-            history.truncate(8);
+        s.history.insert(0, s.current_ref.clone());
+        if s.history.len() > 8 {
+            s.history.truncate(8);
         }
-        current_world = next.world.clone();
-        current_ref = next_ref.clone();
-        current_allegiance = next.allegiance.clone();
+        s.current_world = next.world.clone();
+        s.current_ref = next_ref.clone();
+        s.current_allegiance = next.allegiance.clone();
+        s.current_gas_giants = next.gas_giants;
         // Jump time only — the port stay was already added at step (2b).
-        current_date = current_date.add_days(DAYS_PER_JUMP);
-        days_since_payment += DAYS_PER_JUMP;
-        jumps_taken += 1;
-        total_parsecs_jumped += next.distance.max(0) as u32;
+        s.current_date = s.current_date.add_days(DAYS_PER_JUMP);
+        s.days_since_payment += DAYS_PER_JUMP;
+        s.jumps_taken += 1;
+        s.total_parsecs_jumped += next.distance.max(0) as u32;
+
+        // (13b) Misjump check for a jump on unrefined fuel or damaged
+        // drives. Recorded at the destination: a misjumped ship still gets
+        // there, late and with its drives in need of repair.
+        let jump_dm = s
+            .params
+            .condition
+            .map_or(0, |model| s.condition.jump_dm(&model, s.current_date));
+        if unrefined_burned > 0 || jump_dm > 0 {
+            let check = fuel::roll_misjump(jump_dm);
+            s.budget -= check.repair_cost;
+            if check.weeks_lost > 0 {
+                let added_days = check.weeks_lost * DAYS_PER_WEEK;
+                s.current_date = s.current_date.add_days(added_days);
+                s.days_since_payment += added_days;
+            }
+            emit(
+                on_step,
+                s.current_date,
+                &s.current_ref,
+                s.budget,
+                Action::MisjumpCheck {
+                    roll: check.roll,
+                    dm: check.dm,
+                    misjumped: check.misjumped,
+                    weeks_lost: check.weeks_lost,
+                    repair_cost: check.repair_cost,
+                },
+            );
+            if s.budget < 0 && !s.went_negative {
+                s.went_negative = true;
+                emit(
+                    on_step,
+                    s.current_date,
+                    &s.current_ref,
+                    s.budget,
+                    Action::BudgetWarning {
+                        note: format!("Budget went negative ({}) after a misjump.", s.budget),
+                    },
+                );
+            }
+        }
 
         // (14) Emit the Arrive at the new world.
         emit(
-            &mut on_step,
-            current_date,
-            &current_ref,
-            budget,
+            on_step,
+            s.current_date,
+            &s.current_ref,
+            s.budget,
             Action::Arrive {
                 from: from_ref,
                 distance: next.distance,
@@ -566,53 +1030,272 @@ pub async fn run_simulation(
 
         // After the first arrival a future port stay can roll an
         // incident.
-        incident_eligible = true;
+        s.incident_eligible = true;
+        Ok(())
     }
 
-    // === final tally =====================================================
-    // Crew profit share is computed on the gross *before* mortgage. We
-    // add the mortgage back in to get the pre-mortgage gross, take the
-    // crew's cut, and then leave the owner bearing the mortgage cost
-    // alone. (Equivalent to: temporarily refund the mortgage, split,
-    // then re-deduct it from the owner.)
-    let gross = budget - params.starting_budget;
-    let gross_excl_mortgage = gross + total_mortgage_paid;
-    let crew = if gross_excl_mortgage > 0 {
-        (gross_excl_mortgage as f64 * params.crew_profit_share as f64).round() as i64
-    } else {
-        0
-    };
-    let owner = gross - crew;
-
-    Ok(SimulationResult {
-        final_budget: budget,
-        gross_profit: gross,
-        crew_share: crew,
-        owner_profit: owner,
-        end_date: current_date,
-        jumps: jumps_taken,
-        completed_normally: completed_normally && !marooned,
-        returned_home: returned_home && !marooned,
-        went_negative,
-        marooned,
-        marooned_at: marooned_state.as_ref().map(|(w, _, _)| w.clone()),
-        marooned_on: marooned_state.as_ref().map(|(_, d, _)| *d),
-        rescue_arrives_on: marooned_state.as_ref().map(|(_, _, eta)| *eta),
-    })
+    /// The voyage's tally. Final once the voyage has finished; before
+    /// that it is the tally as if the voyage stopped here.
+    pub fn result(&self) -> SimulationResult {
+        let s = &self.state;
+        // Crew profit share is computed on the gross *before* mortgage. We
+        // add the mortgage back in to get the pre-mortgage gross, take the
+        // crew's cut, and then leave the owner bearing the mortgage cost
+        // alone. (Equivalent to: temporarily refund the mortgage, split,
+        // then re-deduct it from the owner.)
+        let gross = s.budget - s.params.starting_budget;
+        let gross_excl_mortgage = gross + s.total_mortgage_paid;
+        let crew = if gross_excl_mortgage > 0 {
+            (gross_excl_mortgage as f64 * s.params.crew_profit_share as f64).round() as i64
+        } else {
+            0
+        };
+        let owner = gross - crew;
+
+        SimulationResult {
+            final_budget: s.budget,
+            gross_profit: gross,
+            crew_share: crew,
+            owner_profit: owner,
+            end_date: s.current_date,
+            jumps: s.jumps_taken,
+            completed_normally: s.completed_normally && !s.marooned,
+            returned_home: s.returned_home && !s.marooned,
+            went_negative: s.went_negative,
+            marooned: s.marooned,
+            marooned_at: s.marooned_at.clone(),
+            marooned_on: s.marooned_on,
+            rescue_arrives_on: s.rescue_arrives_on,
+            seed: s.seed,
+        }
+    }
 }
 
 // ===== helpers ==========================================================
 
-/// Roll for an incident at the current port. Mutates `budget` and
-/// `manifest` to apply effects, emits one of the `Incident*` action
-/// variants, and returns the number of weeks lost so the caller can
-/// advance the simulation clock.
+/// Top up the tanks from whatever the current system offers, buying only
+/// what the budget covers. Skimming or scooping adds a day in system.
+fn refuel(s: &mut Checkpoint, model: &FuelModel, on_step: &mut impl FnMut(SimulationStep)) {
+    let room = model.tankage.saturating_sub(s.fuel.tons);
+    if room == 0 {
+        return;
+    }
+    let source = model.refuel_source(s.current_world.port, s.current_world.hydro, || {
+        s.current_gas_giants
+            .unwrap_or_else(|| system_gas_giants(&s.current_world, &s.current_ref))
+    });
+    let action = match source {
+        Some(source @ (FuelSource::Refined | FuelSource::Unrefined)) => {
+            let refined = source == FuelSource::Refined;
+            let price_per_ton = if refined {
+                model.refined_price
+            } else {
+                model.unrefined_price
+            };
+            let tons = if price_per_ton > 0 {
+                (s.budget.max(0) / price_per_ton).min(room as i64) as u32
+            } else {
+                room
+            };
+            if tons == 0 {
+                return;
+            }
+            let cost = tons as i64 * price_per_ton;
+            s.budget -= cost;
+            s.fuel.fill(tons, refined);
+            Action::BuyFuel {
+                tons,
+                refined,
+                price_per_ton,
+                cost,
+            }
+        }
+        Some(wilderness) => {
+            s.fuel.fill(room, model.processor);
+            s.current_date = s.current_date.add_days(WILDERNESS_REFUEL_DAYS);
+            s.days_since_payment += WILDERNESS_REFUEL_DAYS;
+            match wilderness {
+                FuelSource::GasGiant { gas_giants } => Action::SkimGasGiant {
+                    tons: room,
+                    refined: model.processor,
+                    gas_giants,
+                },
+                _ => Action::ScoopWater {
+                    tons: room,
+                    refined: model.processor,
+                },
+            }
+        }
+        None => return,
+    };
+    emit(on_step, s.current_date, &s.current_ref, s.budget, action);
+}
+
+/// Roll customs at the current port for a ship carrying contraband,
+/// applying any seizure, fine and impound to the voyage.
+fn run_customs(s: &mut Checkpoint, on_step: &mut impl FnMut(SimulationStep)) {
+    let check = customs::inspect(&s.current_world, &s.params.ship);
+    emit(
+        on_step,
+        s.current_date,
+        &s.current_ref,
+        s.budget,
+        Action::CustomsInspection {
+            inspection_roll: check.inspection_roll,
+            inspection_dm: check.inspection_dm,
+            inspected: check.inspected,
+            concealment_roll: check.concealment.map(|(roll, _)| roll),
+            concealment_dm: check.concealment.map(|(_, dm)| dm),
+            found: check.found,
+        },
+    );
+    if !check.found {
+        return;
+    }
+
+    let mut seized = Vec::new();
+    let mut buy_cost_sunk = 0;
+    for good in s.manifest.trade_goods.goods.iter_mut() {
+        if good.quantity > 0 && good.is_illegal() {
+            buy_cost_sunk += good.buy_cost as i64 * good.quantity as i64;
+            seized.push((good.name.clone(), good.quantity));
+            good.quantity = 0;
+        }
+    }
+    let fine = customs::contraband_fine(&s.current_world, buy_cost_sunk);
+    s.budget -= fine;
+    emit(
+        on_step,
+        s.current_date,
+        &s.current_ref,
+        s.budget,
+        Action::ContrabandSeized {
+            seized,
+            buy_cost_sunk,
+            fine,
+        },
+    );
+
+    if check.impounded {
+        let (weeks_lost, release_fee) = customs::roll_impound();
+        s.budget -= release_fee;
+        let added_days = weeks_lost * DAYS_PER_WEEK;
+        s.current_date = s.current_date.add_days(added_days);
+        s.days_since_payment += added_days;
+        emit(
+            on_step,
+            s.current_date,
+            &s.current_ref,
+            s.budget,
+            Action::ShipImpounded {
+                weeks_lost,
+                release_fee,
+            },
+        );
+    }
+    if s.budget < 0 && !s.went_negative {
+        s.went_negative = true;
+        emit(
+            on_step,
+            s.current_date,
+            &s.current_ref,
+            s.budget,
+            Action::BudgetWarning {
+                note: format!("Budget went negative ({}) after customs.", s.budget),
+            },
+        );
+    }
+}
+
+/// Look after the ship's condition at the current port: roll for a
+/// breakdown if she's overdue for maintenance, overhaul her if the port
+/// has a shipyard, and repair what the yard and the budget allow. Yard
+/// time pushes the date on.
+fn tend_condition(
+    s: &mut Checkpoint,
+    model: &ConditionModel,
+    on_step: &mut impl FnMut(SimulationStep),
+) {
+    let port = s.current_world.port;
+    let missed = s.condition.missed_maintenance(model, s.current_date);
+    if missed > 0
+        && s.jumps_taken > 0
+        && let (_, Some(system)) = condition::roll_breakdown(missed)
+    {
+        s.condition.take_hit(system);
+        emit(
+            on_step,
+            s.current_date,
+            &s.current_ref,
+            s.budget,
+            Action::ShipDamaged {
+                cause: "Breakdown".to_string(),
+                hits: vec![system],
+            },
+        );
+    }
+    if missed > 0 && condition::can_overhaul(port) {
+        let added_days = model.overhaul_weeks * DAYS_PER_WEEK;
+        s.current_date = s.current_date.add_days(added_days);
+        s.days_since_payment += added_days;
+        s.condition.overhaul(s.current_date);
+        emit(
+            on_step,
+            s.current_date,
+            &s.current_ref,
+            s.budget,
+            Action::AnnualMaintenance {
+                weeks: model.overhaul_weeks,
+                missed,
+            },
+        );
+    }
+
+    let plan = model.plan_repairs(&s.condition, port, s.budget);
+    if plan.repaired.is_empty() {
+        return;
+    }
+    s.condition.repair(&plan);
+    s.budget -= plan.cost;
+    s.current_date = s.current_date.add_days(plan.days);
+    s.days_since_payment += plan.days;
+    emit(
+        on_step,
+        s.current_date,
+        &s.current_ref,
+        s.budget,
+        Action::Repair {
+            repaired: plan.repaired,
+            cost: plan.cost,
+            days: plan.days,
+            deferred: s.condition.total_hits(),
+        },
+    );
+}
+
+/// Gas giants in a system whose world data listed no PBG, from the same
+/// seeded generation the system view uses. Runs under its own RNG scope,
+/// so the voyage's stream is untouched.
+fn system_gas_giants(world: &World, at: &WorldRef) -> u8 {
+    let seed = system_seed(&at.sector, at.hex_x as u8, at.hex_y as u8);
+    System::generate_system_seeded(seed, world.clone()).count_gas_giants()
+}
+
+/// Roll for an incident at the current port, on `table` if the ship
+/// fails to avoid one, with `condition_dm` on the avoidance roll. Mutates
+/// `budget`, `manifest`, the `ship`'s roster and any tracked `condition`
+/// to apply effects, emits one of the `Incident*` action variants (and a
+/// `ShipDamaged` for any hits), and returns the number of weeks lost so
+/// the caller can advance the simulation clock.
 #[allow(clippy::too_many_arguments)]
 fn run_incident_roll(
-    params: &SimulationParams,
+    table: &IncidentTable,
+    ship: &mut Ship,
     current_world: &World,
     current_ref: &WorldRef,
     is_foreign: bool,
+    condition_dm: i32,
+    condition: Option<&mut ShipCondition>,
     budget: &mut i64,
     manifest: &mut ShipManifest,
     current_date: Date,
@@ -626,8 +1309,8 @@ fn run_incident_roll(
     let port_mod_v = port_mod_value(port);
     let zone_mod_v = zone_mod_value(zone);
     let avoidance_law_mod_v = avoidance_law_mod_value(law);
-    let avoidance_modifier_total = avoidance_modifier(port, zone, law, is_foreign);
-    let leadership = params.ship.leadership_skill;
+    let avoidance_modifier_total = avoidance_modifier(port, zone, law, is_foreign) + condition_dm;
+    let leadership = ship.effective_leadership_skill();
     let avoidance_total = avoidance_roll + leadership as i32 + avoidance_modifier_total;
 
     let avoided =
@@ -644,6 +1327,7 @@ fn run_incident_roll(
                 port_mod: port_mod_v,
                 zone_mod: zone_mod_v,
                 law_mod: avoidance_law_mod_v,
+                condition_dm,
                 modifier_total: avoidance_modifier_total,
                 avoidance_total,
             },
@@ -655,133 +1339,167 @@ fn run_incident_roll(
     let table_roll = roll_2d6();
     let table_modifier_total = incident_table_modifier(port, zone, law, is_foreign);
     let table_total = table_roll + table_modifier_total;
+    let Some(entry) = table.entry_for(table_total, port, zone, law, is_foreign) else {
+        return 0;
+    };
 
-    if table_total <= 4 {
-        // Piracy.
-        let weapons = params.ship.weapons;
-        let cargo_loss_pct = (roll_2d6() - weapons as i32).clamp(0, 10) * 10;
-        let total_tons: i32 = manifest.trade_goods.goods.iter().map(|g| g.quantity).sum();
-        let target_tons = total_tons * cargo_loss_pct / 100;
-        let mut rng = rand::rng();
-        let (cargo_lost_breakdown, buy_cost_sunk) =
-            pirate_cargo(&mut manifest.trade_goods, target_tons, &mut rng);
-        let cargo_lost_tons: i32 = cargo_lost_breakdown.iter().map(|(_, q)| q).sum();
-        let credits_lost = (roll_2d6() - params.ship.leadership_skill as i32).max(0) as i64
-            * params.ship.maintenance_per_period;
-        let weeks_lost = roll_1d6() as u32;
-        *budget -= credits_lost;
-        emit(
-            on_step,
-            current_date,
-            current_ref,
-            *budget,
-            Action::IncidentPiracy {
-                avoidance_roll,
-                leadership,
-                avoidance_modifier_total,
-                avoidance_total,
-                table_roll,
-                table_modifier_total,
-                table_total,
-                weapons,
-                cargo_lost_tons,
-                cargo_lost_breakdown,
-                buy_cost_sunk,
-                credits_lost,
-                weeks_lost,
-            },
-        );
-        weeks_lost
-    } else if table_total <= 6 {
-        // Trade Scam.
-        let credits_lost =
-            (roll_2d6() - params.ship.broker_skill as i32).max(0) as i64 * TRADE_SCAM_CR_PER_STEP;
-        let weeks_lost = (roll_1d3() - params.ship.leadership_skill as i32).max(0) as u32;
-        *budget -= credits_lost;
-        emit(
-            on_step,
-            current_date,
-            current_ref,
-            *budget,
-            Action::IncidentTradeScam {
-                avoidance_roll,
-                leadership,
-                avoidance_modifier_total,
-                avoidance_total,
-                table_roll,
-                table_modifier_total,
-                table_total,
-                broker: params.ship.broker_skill,
-                credits_lost,
-                weeks_lost,
-            },
-        );
-        weeks_lost
-    } else if table_total == 7 {
-        // Crew loss.
-        let weeks_lost = ((roll_2d6() / 2) - params.ship.leadership_skill as i32).max(0) as u32;
-        emit(
-            on_step,
-            current_date,
-            current_ref,
-            *budget,
-            Action::IncidentCrewLoss {
-                avoidance_roll,
-                leadership,
-                avoidance_modifier_total,
-                avoidance_total,
-                table_roll,
-                table_modifier_total,
-                table_total,
-                weeks_lost,
-            },
-        );
-        weeks_lost
-    } else if table_total <= 9 {
-        // Accident.
-        let repair_cost = roll_1d6() as i64 * ACCIDENT_CR_PER_STEP;
-        *budget -= repair_cost;
-        emit(
-            on_step,
-            current_date,
-            current_ref,
-            *budget,
-            Action::IncidentAccident {
-                avoidance_roll,
-                leadership,
-                avoidance_modifier_total,
-                avoidance_total,
-                table_roll,
-                table_modifier_total,
-                table_total,
-                repair_cost,
-            },
-        );
-        0
+    // Effects roll in a fixed order — cargo, credits, repairs or hits,
+    // weeks, crew — so a seeded voyage replays them. A ship whose
+    // condition is tracked takes an entry's hits instead of its repair
+    // bill.
+    let effects = &entry.effects;
+    let (cargo_lost_breakdown, buy_cost_sunk) = match &effects.cargo_lost_pct {
+        Some(pct) => {
+            let cargo_loss_pct = pct.roll(ship);
+            let total_tons: i64 = manifest
+                .trade_goods
+                .goods
+                .iter()
+                .map(|g| g.quantity as i64)
+                .sum();
+            let target_tons = (total_tons * cargo_loss_pct / 100) as i32;
+            let mut rng = WorldgenRng;
+            pirate_cargo(&mut manifest.trade_goods, target_tons, &mut rng)
+        }
+        None => (Vec::new(), 0),
+    };
+    let cargo_lost_tons: i32 = cargo_lost_breakdown.iter().map(|(_, q)| q).sum();
+    let credits_lost = effects.credits.as_ref().map_or(0, |r| r.roll(ship));
+    let (repair_cost, hits) = match (&condition, &effects.hits) {
+        (Some(_), Some(hits)) => (0, condition::roll_hits(hits.roll(ship).max(0) as u32)),
+        _ => (
+            effects.damage.as_ref().map_or(0, |r| r.roll(ship)),
+            Vec::new(),
+        ),
+    };
+    let weeks_lost = effects.weeks.as_ref().map_or(0, |r| r.roll(ship)).max(0) as u32;
+    let crewmember = if effects.crew_lost {
+        lose_crewmember(ship)
     } else {
-        // Government Complication.
-        let fine_credits = roll_1d6() as i64 * GOV_FINE_CR_PER_STEP;
-        let weeks_lost = roll_2d6() as u32;
-        *budget -= fine_credits;
+        None
+    };
+    *budget -= credits_lost + repair_cost;
+
+    let action = match entry.kind {
+        IncidentKind::Piracy => Action::IncidentPiracy {
+            avoidance_roll,
+            leadership,
+            avoidance_modifier_total,
+            avoidance_total,
+            table_roll,
+            table_modifier_total,
+            table_total,
+            weapons: ship.weapons,
+            gunner: ship.effective_gunner_skill(),
+            cargo_lost_tons,
+            cargo_lost_breakdown,
+            buy_cost_sunk,
+            credits_lost,
+            weeks_lost,
+        },
+        IncidentKind::TradeScam => Action::IncidentTradeScam {
+            avoidance_roll,
+            leadership,
+            avoidance_modifier_total,
+            avoidance_total,
+            table_roll,
+            table_modifier_total,
+            table_total,
+            broker: ship.effective_broker_skill(),
+            credits_lost,
+            weeks_lost,
+        },
+        IncidentKind::CrewLoss => Action::IncidentCrewLoss {
+            avoidance_roll,
+            leadership,
+            avoidance_modifier_total,
+            avoidance_total,
+            table_roll,
+            table_modifier_total,
+            table_total,
+            weeks_lost,
+            crewmember,
+        },
+        IncidentKind::Accident => Action::IncidentAccident {
+            avoidance_roll,
+            leadership,
+            avoidance_modifier_total,
+            avoidance_total,
+            table_roll,
+            table_modifier_total,
+            table_total,
+            repair_cost,
+        },
+        IncidentKind::Government => Action::IncidentGovernment {
+            avoidance_roll,
+            leadership,
+            avoidance_modifier_total,
+            avoidance_total,
+            table_roll,
+            table_modifier_total,
+            table_total,
+            fine_credits: credits_lost,
+            weeks_lost,
+        },
+        IncidentKind::Other => Action::Incident {
+            avoidance_roll,
+            leadership,
+            avoidance_modifier_total,
+            avoidance_total,
+            table_roll,
+            table_modifier_total,
+            table_total,
+            name: entry.name.clone(),
+            text: entry.text.clone(),
+            credits_lost,
+            repair_cost,
+            weeks_lost,
+            cargo_lost_tons,
+            cargo_lost_breakdown,
+            buy_cost_sunk,
+            crewmember,
+        },
+    };
+    emit(on_step, current_date, current_ref, *budget, action);
+    if let Some(condition) = condition
+        && !hits.is_empty()
+    {
+        for &system in &hits {
+            condition.take_hit(system);
+        }
         emit(
             on_step,
             current_date,
             current_ref,
             *budget,
-            Action::IncidentGovernment {
-                avoidance_roll,
-                leadership,
-                avoidance_modifier_total,
-                avoidance_total,
-                table_roll,
-                table_modifier_total,
-                table_total,
-                fine_credits,
-                weeks_lost,
+            Action::ShipDamaged {
+                cause: entry.name.clone(),
+                hits,
             },
         );
-        weeks_lost
     }
+    weeks_lost
+}
+
+/// Take one crewmember still aboard off the roster, at random: they die
+/// on a 1d6 of [`CREW_LOSS_DEATH_ROLL`] or under and leave otherwise,
+/// taking their skills and salary with them. `None`, rolling nothing,
+/// when nobody is aboard — as on a ship without a roster.
+fn lose_crewmember(ship: &mut Ship) -> Option<CrewMember> {
+    let aboard: Vec<usize> = (0..ship.crew.len())
+        .filter(|&i| ship.crew[i].is_aboard())
+        .collect();
+    if aboard.is_empty() {
+        return None;
+    }
+    let mut rng = WorldgenRng;
+    let lost = &mut ship.crew[aboard[rng.random_range(0..aboard.len())]];
+    lost.status = if roll_1d6() <= CREW_LOSS_DEATH_ROLL {
+        CrewStatus::Dead
+    } else {
+        CrewStatus::Left
+    };
+    Some(lost.clone())
 }
 
 /// Decompose `avoidance_modifier` into its `(port, zone, law)` parts so
@@ -818,7 +1536,7 @@ fn avoidance_law_mod_value(law: i32) -> i32 {
     }
 }
 
-/// Build a [`SimulationStep`] from the current loop state and call the
+/// Build a [`SimulationStep`] from the current voyage state and call the
 /// callback. Inlined to avoid juggling closure types across awaits.
 fn emit(
     on_step: &mut impl FnMut(SimulationStep),
@@ -835,26 +1553,39 @@ fn emit(
     });
 }
 
-/// Build a `WorldRef` for one of the executor's neighbouring worlds.
-/// In v1 the simulator stays in-sector so we propagate the current
-/// sector unchanged.
-fn world_ref_for(world: &World, sector: &str) -> WorldRef {
-    let (hex_x, hex_y) = world.coordinates.unwrap_or((0, 0));
-    WorldRef {
-        name: world.name.clone(),
-        uwp: world.to_uwp(),
-        sector: sector.to_string(),
-        hex_x,
-        hex_y,
-        zone: world.travel_zone,
+/// The worlds reachable from each of `candidates`, `horizon - 1` jumps
+/// deep, for strategies that plan more than one jump ahead. Empty for
+/// one-jump strategies.
+async fn gather_onward(
+    source: &mut impl WorldSource,
+    candidates: &[Candidate],
+    jump: i32,
+    horizon: u32,
+) -> Result<Onward, FetchError> {
+    let mut onward = Onward::new();
+    let hex_of = |c: &Candidate| c.world.coordinates.map(|hex| (c.sector.clone(), hex));
+    let mut frontier: Vec<(String, (i32, i32))> = candidates.iter().filter_map(hex_of).collect();
+    for _ in 1..horizon {
+        let mut next = Vec::new();
+        for (sector, hex) in frontier {
+            if onward.contains(&sector, hex) {
+                continue;
+            }
+            let reachable = source.candidates_within(&sector, hex, jump).await?;
+            next.extend(reachable.iter().filter_map(hex_of));
+            onward.insert(&sector, hex, reachable);
+        }
+        frontier = next;
     }
+    Ok(onward)
 }
 
 /// Two `WorldRef`s point at the same world iff they share sector and
 /// hex. We don't compare names or UWPs because either could differ
-/// between the user's input and what TravellerMap returned.
+/// between the user's input and what TravellerMap returned; sector
+/// names are matched loosely for the same reason.
 fn worldref_same_hex(a: &WorldRef, b: &WorldRef) -> bool {
-    a.sector == b.sector && a.hex_x == b.hex_x && a.hex_y == b.hex_y
+    same_sector(&a.sector, &b.sector) && a.hex_x == b.hex_x && a.hex_y == b.hex_y
 }
 
 /// Quick upper-bound estimate of life-support + stateroom costs we
@@ -928,62 +1659,19 @@ fn pick_to_buy(
     chosen
 }
 
-/// Pick freight lots largest-first into the available cargo. Returns
-/// `(indices_into_freight_lots, total_tons)`.
-fn pick_freight(
-    freight_lots: &[crate::trade::available_passengers::FreightLot],
-    cargo_remaining: i32,
-) -> (Vec<usize>, i32) {
-    if cargo_remaining <= 0 {
-        return (Vec::new(), 0);
-    }
-    // Sort indices by lot size descending.
-    let mut order: Vec<usize> = (0..freight_lots.len()).collect();
-    order.sort_by(|&a, &b| freight_lots[b].size.cmp(&freight_lots[a].size));
-
-    let mut chosen: Vec<usize> = Vec::new();
-    let mut tons = 0;
-    let mut left = cargo_remaining;
-    for idx in order {
-        let lot = &freight_lots[idx];
-        if lot.size <= left {
-            chosen.push(idx);
-            tons += lot.size;
-            left -= lot.size;
-            if left <= 0 {
-                break;
-            }
-        }
-    }
-    (chosen, tons)
-}
-
-/// Greedy passenger filler: high → medium → basic until staterooms run
-/// out, then low until low berths run out.
-fn pick_passengers(
-    staterooms: i32,
-    low_berths: i32,
-    available: &AvailablePassengers,
-) -> (i32, i32, i32, i32) {
-    let mut rooms = staterooms.max(0);
-    let high = available.high.max(0).min(rooms);
-    rooms -= high;
-    let medium = available.medium.max(0).min(rooms);
-    rooms -= medium;
-    // Basics share two-to-a-room: each remaining stateroom can take 2.
-    let basic_capacity = rooms.saturating_mul(2);
-    let basic = available.basic.max(0).min(basic_capacity);
-    let low = available.low.max(0).min(low_berths.max(0));
-    (high, medium, basic, low)
-}
-
 // ===== test smoke =======================================================
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sector::{Density, Sector};
+    use crate::simulator::ledger::voyage_ledger;
+    use crate::simulator::test_support::{offline_params, run_offline};
+    use crate::simulator::world_fetch::WorldCache;
+    use crate::simulator::world_source::SectorFiles;
     use crate::trade::Ship;
     use crate::trade::ZoneClassification;
+    use crate::trade::ledger::Account;
 
     fn dummy_home() -> WorldRef {
         WorldRef {
@@ -1003,6 +1691,8 @@ mod tests {
         b.name = "Different".to_string();
         b.uwp = "X000000-0".to_string();
         assert!(worldref_same_hex(&a, &b));
+        b.sector = b.sector.to_uppercase();
+        assert!(worldref_same_hex(&a, &b));
         b.hex_x = 11;
         assert!(!worldref_same_hex(&a, &b));
     }
@@ -1015,18 +1705,25 @@ mod tests {
                 broker_skill: 0,
                 steward_skill: 0,
                 leadership_skill: 0,
+                deception_skill: 0,
+                admin_skill: 0,
                 weapons: 0,
+                hull_tons: 200,
+
                 cargo_capacity: 100,
                 crew_staterooms: 4,
                 passenger_staterooms: 6,
                 low_berths: 4,
                 crew_size: 4,
                 jump_rating: 2,
+                fuel_tons: 0,
                 mortgage_per_period: 0,
                 maintenance_per_period: 0,
                 salary_per_period: 0,
+                crew: Vec::new(),
             },
             fuel_cost_per_parsec: 0,
+            fuel: None,
             crew_profit_share: 0.0,
             starting_budget: 0,
             home_world: dummy_home(),
@@ -1034,54 +1731,18 @@ mod tests {
             target_completion_date: crate::simulator::types::Date::new(100, 1105),
             illegal_goods: false,
             planetary_broker_skill: 2,
+            seed: None,
+            route: Default::default(),
+            route_weights: Default::default(),
+            cargo_plan: None,
+            accept_charters: false,
+            markets: Default::default(),
+            incidents: None,
+            condition: None,
         };
         assert!(pax_reserve_estimate(&params) > 0);
     }
 
-    #[test]
-    fn pick_passengers_high_first_then_basic_pairs() {
-        let avail = AvailablePassengers {
-            high: 2,
-            medium: 3,
-            basic: 5,
-            low: 4,
-            ..Default::default()
-        };
-        // 4 staterooms, 2 low berths.
-        let (h, m, b, l) = pick_passengers(4, 2, &avail);
-        // 2 high uses 2 rooms; 2 medium uses 2 more rooms; basics get 0
-        // because no rooms left.
-        assert_eq!(h, 2);
-        assert_eq!(m, 2);
-        assert_eq!(b, 0);
-        assert_eq!(l, 2);
-    }
-
-    #[test]
-    fn pick_freight_largest_first() {
-        use crate::trade::available_passengers::FreightLot;
-        let lots = vec![
-            FreightLot {
-                size: 20,
-                size_roll: 2,
-            },
-            FreightLot {
-                size: 50,
-                size_roll: 5,
-            },
-            FreightLot {
-                size: 10,
-                size_roll: 1,
-            },
-        ];
-        let (chosen, tons) = pick_freight(&lots, 60);
-        // Largest 50 then 10 → 60 tons; 20 doesn't fit.
-        assert_eq!(tons, 60);
-        assert_eq!(chosen.len(), 2);
-        assert!(chosen.contains(&1));
-        assert!(chosen.contains(&2));
-    }
-
     /// End-to-end smoke test against the live TravellerMap API. Ignored
     /// by default because it requires network — run with
     /// `cargo test --features backend -- --ignored simulator_smoke`.
@@ -1097,18 +1758,25 @@ mod tests {
                 broker_skill: 2,
                 steward_skill: 1,
                 leadership_skill: 1,
+                deception_skill: 0,
+                admin_skill: 0,
                 weapons: 2,
+                hull_tons: 200,
+
                 cargo_capacity: 80,
                 crew_staterooms: 4,
                 passenger_staterooms: 6,
                 low_berths: 4,
                 crew_size: 4,
                 jump_rating: 2,
+                fuel_tons: 0,
                 mortgage_per_period: 0,
                 maintenance_per_period: 30_000,
                 salary_per_period: 12_000,
+                crew: Vec::new(),
             },
             fuel_cost_per_parsec: 5_000,
+            fuel: None,
             crew_profit_share: 0.1,
             starting_budget: 1_000_000,
             home_world: WorldRef {
@@ -1123,6 +1791,14 @@ mod tests {
             target_completion_date: crate::simulator::types::Date::new(180, 1105),
             illegal_goods: false,
             planetary_broker_skill: 2,
+            seed: None,
+            route: Default::default(),
+            route_weights: Default::default(),
+            cargo_plan: None,
+            accept_charters: false,
+            markets: Default::default(),
+            incidents: None,
+            condition: None,
         };
         let mut cache = WorldCache::new();
         let mut step_count = 0;
@@ -1157,18 +1833,25 @@ mod tests {
                 broker_skill: 0,
                 steward_skill: 0,
                 leadership_skill: 0,
+                deception_skill: 0,
+                admin_skill: 0,
                 weapons: 0,
+                hull_tons: 200,
+
                 cargo_capacity: 20,
                 crew_staterooms: 1,
                 passenger_staterooms: 1,
                 low_berths: 0,
                 crew_size: 1,
                 jump_rating: 1,
+                fuel_tons: 0,
                 mortgage_per_period: 0,
                 maintenance_per_period: 30_000,
                 salary_per_period: 12_000,
+                crew: Vec::new(),
             },
             fuel_cost_per_parsec: 5_000,
+            fuel: None,
             crew_profit_share: 0.0,
             starting_budget: 50_000,
             home_world: WorldRef {
@@ -1183,6 +1866,14 @@ mod tests {
             target_completion_date: crate::simulator::types::Date::new(31, 1105),
             illegal_goods: false,
             planetary_broker_skill: 2,
+            seed: None,
+            route: Default::default(),
+            route_weights: Default::default(),
+            cargo_plan: None,
+            accept_charters: false,
+            markets: Default::default(),
+            incidents: None,
+            condition: None,
         };
         let mut cache = WorldCache::new();
         let result = run_simulation(params, &mut cache, |s| {
@@ -1207,4 +1898,745 @@ mod tests {
             "marooning should not be a normal completion"
         );
     }
+
+    /// Offline run against a generated sector: no network, and every
+    /// port of call is a hex of that sector.
+    #[tokio::test]
+    async fn simulator_runs_offline_from_sector_files() {
+        let sector = Sector::generate("Offline Test", Density::Dense);
+        let mut source = SectorFiles::from(sector.clone());
+        let mut visited = Vec::new();
+        let result = run_simulation(offline_params(&sector, None), &mut source, |s| {
+            visited.push(s.location)
+        })
+        .await
+        .expect("offline simulation should complete");
+
+        assert!(result.jumps > 0, "should have made at least one jump");
+        for location in visited {
+            assert_eq!(location.sector, sector.name);
+            let hex = sector
+                .hex(location.hex_x as u8, location.hex_y as u8)
+                .expect("every stop is an occupied hex of the sector");
+            assert_eq!(hex.name, location.name);
+        }
+    }
+
+    /// The same params and seed replay the same voyage step for step, and
+    /// an unseeded run reports a seed that replays it.
+    #[tokio::test]
+    async fn seeded_runs_replay_exactly() {
+        let sector = Sector::generate("Replay Test", Density::Dense);
+
+        let (first, first_result) = run_offline(&sector, offline_params(&sector, Some(1105))).await;
+        let (second, second_result) =
+            run_offline(&sector, offline_params(&sector, Some(1105))).await;
+        assert_eq!(first_result.seed, 1105);
+        assert_eq!(first, second);
+        assert_eq!(first_result.final_budget, second_result.final_budget);
+
+        let (other, _) = run_offline(&sector, offline_params(&sector, Some(1106))).await;
+        assert_ne!(
+            first, other,
+            "a different seed should give a different voyage"
+        );
+
+        let (fresh, fresh_result) = run_offline(&sector, offline_params(&sector, None)).await;
+        let (replay, _) =
+            run_offline(&sector, offline_params(&sector, Some(fresh_result.seed))).await;
+        assert_eq!(fresh, replay);
+    }
+    /// An occupied hex of `sector` within jump-2 of `home`.
+    fn neighbour_of(sector: &Sector, home: &WorldRef) -> WorldRef {
+        let stop = sector
+            .hexes
+            .iter()
+            .find(|h| {
+                let d = crate::util::calculate_hex_distance(
+                    home.hex_x, home.hex_y, h.x as i32, h.y as i32,
+                );
+                (1..=2).contains(&d)
+            })
+            .expect("a dense sector has a neighbour in jump range");
+        WorldRef {
+            name: stop.name.clone(),
+            uwp: stop.uwp.clone(),
+            sector: sector.name.clone(),
+            hex_x: stop.x as i32,
+            hex_y: stop.y as i32,
+            zone: stop.zone,
+        }
+    }
+
+    /// Every route strategy can drive a full voyage, and a seeded
+    /// lookahead run (which rolls estimated markets) still replays.
+    #[tokio::test]
+    async fn every_route_strategy_runs_offline() {
+        use crate::simulator::strategy::RouteStrategyConfig;
+
+        let sector = Sector::generate("Strategy Test", Density::Dense);
+        let base = offline_params(&sector, Some(7));
+        let stop_ref = neighbour_of(&sector, &base.home_world);
+
+        let configs = [
+            RouteStrategyConfig::Greedy,
+            RouteStrategyConfig::Lookahead {
+                legs: 3,
+                discount: 0.8,
+            },
+            RouteStrategyConfig::FixedItinerary {
+                stops: vec![stop_ref.clone()],
+            },
+            RouteStrategyConfig::MainRoute { min_population: 6 },
+        ];
+        for config in configs {
+            let mut params = base.clone();
+            params.route = config.clone();
+            let (steps, result) = run_offline(&sector, params.clone()).await;
+            assert!(result.jumps > 0, "{config:?} should make a jump");
+            let (replay, _) = run_offline(&sector, params).await;
+            assert_eq!(steps, replay, "{config:?} should replay");
+        }
+
+        let mut params = base.clone();
+        params.route = RouteStrategyConfig::FixedItinerary {
+            stops: vec![stop_ref.clone()],
+        };
+        let mut source = SectorFiles::from(sector.clone());
+        let mut first_jump = None;
+        run_simulation(params, &mut source, |s| {
+            if let Action::Jump { to, .. } = s.action
+                && first_jump.is_none()
+            {
+                first_jump = Some(to);
+            }
+        })
+        .await
+        .unwrap();
+        let to = first_jump.expect("the itinerary should leave home");
+        assert_eq!((to.hex_x, to.hex_y), (stop_ref.hex_x, stop_ref.hex_y));
+    }
+
+    /// Planned buying drives a whole voyage at every risk tolerance,
+    /// keeps each port's purchases inside the hold, and replays.
+    #[tokio::test]
+    async fn planned_buying_runs_offline() {
+        use crate::trade::cargo_plan::RiskTolerance;
+
+        let sector = Sector::generate("Cargo Plan Test", Density::Dense);
+        let base = offline_params(&sector, Some(11));
+        let mut bought_any = false;
+        for risk in RiskTolerance::ALL {
+            let mut params = base.clone();
+            params.cargo_plan = Some(risk);
+            let (steps, result) = run_offline(&sector, params.clone()).await;
+            assert!(result.jumps > 0, "{risk} buying should make a jump");
+            let (replay, _) = run_offline(&sector, params.clone()).await;
+            assert_eq!(steps, replay, "{risk} buying should replay");
+
+            let mut source = SectorFiles::from(sector.clone());
+            let mut tons_this_port = 0;
+            run_simulation(params, &mut source, |s| match s.action {
+                Action::BuyGood { qty, .. } => {
+                    bought_any = true;
+                    tons_this_port += qty;
+                    assert!(tons_this_port <= base.ship.cargo_capacity);
+                }
+                Action::Jump { .. } => tons_this_port = 0,
+                _ => {}
+            })
+            .await
+            .unwrap();
+        }
+        assert!(bought_any, "some port should offer a worthwhile buy");
+    }
+
+    fn to_json(step: &SimulationStep) -> String {
+        serde_json::to_string(step).unwrap()
+    }
+
+    /// Suspending a voyage, round-tripping its checkpoint through JSON and
+    /// resuming it gives the same voyage as running straight through,
+    /// and a reseeded checkpoint forks a different one.
+    #[tokio::test]
+    async fn checkpoints_resume_exactly() {
+        use crate::simulator::strategy::RouteStrategyConfig;
+
+        let sector = Sector::generate("Checkpoint Test", Density::Dense);
+        let base = offline_params(&sector, Some(2024));
+        let itinerary = RouteStrategyConfig::FixedItinerary {
+            stops: vec![neighbour_of(&sector, &base.home_world); 2],
+        };
+        for route in [RouteStrategyConfig::Greedy, itinerary] {
+            let mut params = base.clone();
+            params.route = route.clone();
+            let (straight, straight_result) = run_offline(&sector, params.clone()).await;
+
+            let mut source = SectorFiles::from(sector.clone());
+            let mut steps = Vec::new();
+            let mut voyage = Voyage::new(params).unwrap();
+            for _ in 0..2 {
+                voyage
+                    .step(&mut source, &mut |s| steps.push(to_json(&s)))
+                    .await
+                    .unwrap();
+            }
+            let saved = serde_json::to_string(&voyage.checkpoint()).unwrap();
+            let suspended_at = steps.len();
+            drop(voyage);
+
+            let checkpoint: Checkpoint = serde_json::from_str(&saved).unwrap();
+            let mut fork = Voyage::from_checkpoint(checkpoint.clone().reseeded(99));
+            let mut voyage = Voyage::from_checkpoint(checkpoint);
+            while !voyage.is_finished() {
+                voyage
+                    .step(&mut source, &mut |s| steps.push(to_json(&s)))
+                    .await
+                    .unwrap();
+            }
+            assert_eq!(steps, straight, "{route:?} should resume exactly");
+            let resumed = voyage.result();
+            assert_eq!(resumed.final_budget, straight_result.final_budget);
+            assert_eq!(resumed.end_date, straight_result.end_date);
+            assert_eq!(resumed.seed, 2024);
+
+            let mut forked = Vec::new();
+            while !fork.is_finished() {
+                fork.step(&mut source, &mut |s| forked.push(to_json(&s)))
+                    .await
+                    .unwrap();
+            }
+            assert_ne!(forked, straight[suspended_at..], "{route:?} fork");
+            assert_eq!(fork.result().seed, 99);
+        }
+    }
+
+    /// Every purchase and sale is remembered by the market it was made
+    /// in, and a voyage seeded with that memory trades differently.
+    #[tokio::test]
+    async fn trades_are_remembered_by_their_markets() {
+        let sector = Sector::generate("Market Test", Density::Dense);
+        let params = offline_params(&sector, Some(77));
+        let mut source = SectorFiles::from(sector.clone());
+        let mut steps = Vec::new();
+        let mut voyage = Voyage::new(params.clone()).unwrap();
+        while !voyage.is_finished() {
+            voyage
+                .step(&mut source, &mut |s| steps.push(s))
+                .await
+                .unwrap();
+        }
+        let traded = steps
+            .iter()
+            .filter(|s| matches!(s.action, Action::BuyGood { .. } | Action::SellGood { .. }))
+            .count();
+        assert!(traded > 0, "the voyage should trade");
+
+        let markets = voyage.checkpoint().markets;
+        let bought = steps.iter().find_map(|s| match &s.action {
+            Action::BuyGood { .. } => Some(MarketKey::new(
+                &s.location.sector,
+                s.location.hex_x,
+                s.location.hex_y,
+            )),
+            _ => None,
+        });
+        let key = bought.expect("the voyage should buy something");
+        assert!(markets.market(&key).is_some(), "{key:?} should remember");
+
+        let mut replay = params.clone();
+        replay.markets = markets;
+        let (seeded, _) = run_offline(&sector, replay).await;
+        let (fresh, _) = run_offline(&sector, params).await;
+        assert_ne!(seeded, fresh);
+    }
+
+    /// A trade the trade server records, keyed by galactic hex, reaches a
+    /// voyage through a world outside the built-in sectors when the
+    /// voyage's world source knows the sector's origin.
+    #[tokio::test]
+    async fn server_trades_reach_voyages_outside_the_builtin_sectors() {
+        use crate::sector::origins::SectorOrigins;
+
+        let sector = Sector::generate("Far Reaches", Density::Dense);
+        let mut origins = SectorOrigins::new();
+        origins.insert(&sector.name, (-40, 25));
+        let params = offline_params(&sector, Some(77));
+        let home = &params.home_world;
+        let (gx, gy) = origins
+            .to_galactic(&home.sector, home.hex_x, home.hex_y)
+            .unwrap();
+        assert!(SectorOrigins::builtin().from_galactic(gx, gy).is_none());
+
+        // Buys at the home port before the first jump out.
+        async fn opening_buys(
+            sector: &Sector,
+            origins: &SectorOrigins,
+            params: SimulationParams,
+        ) -> usize {
+            let mut source = SectorFiles::from(sector.clone()).with_origins(origins.clone());
+            let mut steps = Vec::new();
+            run_simulation(params, &mut source, |s| steps.push(s.action))
+                .await
+                .unwrap();
+            steps
+                .iter()
+                .take_while(|a| !matches!(a, Action::Jump { .. }))
+                .filter(|a| matches!(a, Action::BuyGood { .. }))
+                .count()
+        }
+        assert!(opening_buys(&sector, &origins, params.clone()).await > 0);
+
+        // The trade server buys every good out of the home market.
+        let mut bought_out = params.clone();
+        let day = params.start_date.day_number();
+        for entry in TradeTable::global().entries() {
+            bought_out.markets.record_purchase(
+                &MarketKey::at_galactic(gx, gy),
+                day,
+                entry.index,
+                100_000,
+            );
+        }
+        assert_eq!(opening_buys(&sector, &origins, bought_out).await, 0);
+    }
+
+    /// Arrivals pay berthing, and every sale and purchase pays the
+    /// world's import or export tariff.
+    #[tokio::test]
+    async fn port_charges_are_paid_on_arrival_and_trades() {
+        let sector = Sector::generate("Fees Test", Density::Dense);
+        let params = offline_params(&sector, Some(31));
+        let mut source = SectorFiles::from(sector.clone());
+        let mut steps = Vec::new();
+        let result = run_simulation(params.clone(), &mut source, |s| steps.push(s))
+            .await
+            .unwrap();
+
+        let berths = steps
+            .iter()
+            .filter(|s| matches!(s.action, Action::PayBerthing { .. }))
+            .count();
+        // Ports without facilities charge nothing, and a voyage that
+        // aborts on arrival never pays.
+        assert!(berths > 0 && berths as u32 <= result.jumps);
+
+        for pair in steps.windows(2) {
+            let world = World::from_uwp("Here", &pair[0].location.uwp, false, true).unwrap();
+            let rates = TariffRates::for_world(&world);
+            match (&pair[0].action, &pair[1].action) {
+                (
+                    Action::SellGood {
+                        qty, sell_price, ..
+                    },
+                    next,
+                ) => {
+                    let proceeds = *qty as i64 * *sell_price as i64;
+                    let expected = rates.import_on(proceeds);
+                    match next {
+                        Action::PayImportTariff { tariff, .. } => assert_eq!(*tariff, expected),
+                        _ => assert_eq!(expected, 0),
+                    }
+                }
+                (Action::BuyGood { total_cost, .. }, next) => {
+                    let expected = rates.export_on(*total_cost);
+                    match next {
+                        Action::PayExportTariff { tariff, .. } => assert_eq!(*tariff, expected),
+                        _ => assert_eq!(expected, 0),
+                    }
+                }
+                (Action::PayBerthing { fee, .. }, _) => {
+                    assert_eq!(*fee, berthing_fee(world.port, params.ship.hull_tons))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Carrying contraband draws customs checks on arrival; only a search
+    /// that finds it confiscates the hold and fines, and only a seizure
+    /// can impound the ship.
+    #[tokio::test]
+    async fn customs_seizes_contraband_only_when_found() {
+        let sector = Sector::generate("Customs Test", Density::Dense);
+        let mut inspections = 0;
+        for seed in [3, 17, 29] {
+            let mut params = offline_params(&sector, Some(seed));
+            params.illegal_goods = true;
+            let mut source = SectorFiles::from(sector.clone());
+            let mut steps = Vec::new();
+            run_simulation(params, &mut source, |s| steps.push(s))
+                .await
+                .unwrap();
+
+            for pair in steps.windows(2) {
+                match (&pair[0].action, &pair[1].action) {
+                    (Action::CustomsInspection { found, .. }, next) => {
+                        assert_eq!(*found, matches!(next, Action::ContrabandSeized { .. }));
+                        if let Action::ContrabandSeized { fine, .. } = next {
+                            assert_eq!(pair[1].budget_after, pair[0].budget_after - fine);
+                        }
+                    }
+                    (
+                        Action::ContrabandSeized {
+                            buy_cost_sunk,
+                            fine,
+                            ..
+                        },
+                        _,
+                    ) => {
+                        let world =
+                            World::from_uwp("Here", &pair[0].location.uwp, false, true).unwrap();
+                        assert_eq!(*fine, customs::contraband_fine(&world, *buy_cost_sunk));
+                    }
+                    (_, Action::ShipImpounded { .. }) => {
+                        assert!(matches!(pair[0].action, Action::ContrabandSeized { .. }));
+                    }
+                    _ => {}
+                }
+            }
+            inspections += steps
+                .iter()
+                .filter(|s| matches!(s.action, Action::CustomsInspection { .. }))
+                .count();
+        }
+        assert!(inspections > 0, "no voyage ever carried contraband");
+    }
+
+    /// Under a fuel model the tanks bound each jump, fuel is paid for at
+    /// refuelling rather than on the jump, and each port call refuels
+    /// from what its system offers.
+    #[tokio::test]
+    async fn fuel_model_bounds_jumps_and_refuels_by_port() {
+        use crate::simulator::fuel::MISJUMP_ROLL;
+
+        let sector = Sector::generate("Fuel Test", Density::Dense);
+        let mut params = offline_params(&sector, Some(12));
+        // One parsec's fuel in the tanks: every jump is a jump-1.
+        params.fuel = Some(FuelModel {
+            tankage: 20,
+            tons_per_parsec: 20,
+            ..FuelModel::default()
+        });
+        let mut source = SectorFiles::from(sector.clone());
+        let mut steps = Vec::new();
+        let result = run_simulation(params.clone(), &mut source, |s| steps.push(s))
+            .await
+            .unwrap();
+        assert!(result.jumps > 1, "should refuel and jump again");
+
+        let mut refuelled = 0;
+        for step in &steps {
+            let port = step.location.uwp.chars().next().unwrap();
+            match &step.action {
+                Action::Jump {
+                    distance,
+                    fuel_cost,
+                    ..
+                } => {
+                    assert_eq!(*distance, 1);
+                    assert_eq!(*fuel_cost, 0);
+                }
+                Action::BuyFuel { refined, .. } => {
+                    refuelled += 1;
+                    assert_eq!(*refined, matches!(port, 'A' | 'B'));
+                }
+                Action::SkimGasGiant { gas_giants, .. } => {
+                    refuelled += 1;
+                    assert!(*gas_giants > 0);
+                    assert!(!matches!(port, 'A' | 'B'));
+                }
+                Action::ScoopWater { .. } => {
+                    refuelled += 1;
+                    assert!(!matches!(port, 'A' | 'B'));
+                }
+                Action::MisjumpCheck {
+                    roll, misjumped, ..
+                } => assert_eq!(*misjumped, *roll >= MISJUMP_ROLL),
+                _ => {}
+            }
+        }
+        assert!(refuelled > 0, "the ship should refuel on the way");
+
+        let (first, _) = run_offline(&sector, params.clone()).await;
+        let (again, _) = run_offline(&sector, params).await;
+        assert_eq!(first, again, "fuel-model voyages should replay");
+    }
+
+    /// A chartered leg carries nothing of the ship's own and is paid
+    /// its weekly fee on arrival; mail never overfills the hold.
+    #[tokio::test]
+    async fn charters_carry_nothing_else_and_mail_fits() {
+        let sector = Sector::generate("Charter Test", Density::Dense);
+        let mut charters = 0;
+        for seed in [5, 11, 23, 31] {
+            let mut params = offline_params(&sector, Some(seed));
+            params.accept_charters = true;
+            let mut source = SectorFiles::from(sector.clone());
+            let mut steps = Vec::new();
+            run_simulation(params.clone(), &mut source, |s| steps.push(s))
+                .await
+                .unwrap();
+
+            let mut chartered = false;
+            for step in &steps {
+                match &step.action {
+                    Action::Charter {
+                        weeks,
+                        weekly_rate,
+                        revenue_pending,
+                    } => {
+                        charters += 1;
+                        chartered = true;
+                        assert_eq!(*weeks, CHARTER_WEEKS);
+                        assert_eq!(*weekly_rate, Charter::weekly_rate_for(&params.ship));
+                        assert_eq!(*revenue_pending, *weeks as i64 * weekly_rate);
+                    }
+                    Action::BuyGood { .. }
+                    | Action::LoadFreight { .. }
+                    | Action::LoadMail { .. }
+                    | Action::BoardPax { .. } => {
+                        assert!(!chartered, "a chartered leg loaded {:?}", step.action)
+                    }
+                    Action::Jump { .. } => chartered = false,
+                    _ => {}
+                }
+                if let Action::LoadMail { tons, .. } = step.action {
+                    assert!(tons <= params.ship.cargo_capacity);
+                    assert_eq!(tons % MAIL_CONTAINER_TONS, 0);
+                }
+            }
+        }
+        assert!(charters > 0, "no seed was offered a charter");
+    }
+
+    /// A crew loss takes someone off the roster, and the checkpoint
+    /// carries the shorter roster on; the roster's gunner fights pirates.
+    #[tokio::test]
+    async fn crew_loss_takes_a_crewmember_off_the_roster() {
+        use crate::trade::crew::{CrewMember, CrewRole, CrewStatus, Skill};
+
+        let sector = Sector::generate("Crew Test", Density::Dense);
+        let mut losses = 0;
+        for seed in 0..200 {
+            let mut params = offline_params(&sector, Some(seed));
+            params.ship.leadership_skill = 0;
+            params.ship.crew = vec![
+                CrewMember::new("Dana", CrewRole::Captain, 6_000),
+                CrewMember::new("Tomas", CrewRole::Gunner, 2_000).with_skill(Skill::Gunner, 2),
+                CrewMember::new("Ilse", CrewRole::Broker, 4_000).with_skill(Skill::Broker, 2),
+            ];
+            let mut source = SectorFiles::from(sector.clone());
+            let mut voyage = Voyage::new(params).unwrap();
+            while !voyage.is_finished() {
+                let mut steps = Vec::new();
+                voyage
+                    .step(&mut source, &mut |s| steps.push(s))
+                    .await
+                    .unwrap();
+                let crew = voyage.checkpoint().params.ship.crew;
+                for step in steps {
+                    match step.action {
+                        Action::IncidentCrewLoss { crewmember, .. } => {
+                            losses += 1;
+                            let lost = crewmember.expect("a roster names who was lost");
+                            assert!(matches!(lost.status, CrewStatus::Dead | CrewStatus::Left));
+                            assert!(crew.contains(&lost), "seed {seed}: {lost:?}");
+                        }
+                        Action::IncidentPiracy { gunner, .. } => {
+                            let tomas = crew.iter().find(|m| m.name == "Tomas").unwrap();
+                            assert_eq!(gunner, if tomas.is_active() { 2 } else { 0 });
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            if losses > 0 {
+                break;
+            }
+        }
+        assert!(losses > 0, "no seed lost a crewmember");
+    }
+
+    /// A custom incident table replaces the standard one: every incident
+    /// is one of its entries, and the ledger still closes on its costs.
+    #[tokio::test]
+    async fn custom_incident_tables_replace_the_standard_one() {
+        let sector = Sector::generate("Incident Test", Density::Dense);
+        let table = IncidentTable::from_json(
+            r#"{"entries": [{
+                "name": "Patrol boarding",
+                "text": "A naval patrol searches the ship.",
+                "effects": {"credits": {"plus": 2, "times": 1000}, "weeks": {"plus": 1}}
+            }]}"#,
+        )
+        .unwrap();
+        let mut boardings = 0;
+        for seed in [2, 9, 14] {
+            let mut params = offline_params(&sector, Some(seed));
+            params.ship.leadership_skill = 0;
+            params.incidents = Some(table.clone());
+            let mut source = SectorFiles::from(sector.clone());
+            let mut steps = Vec::new();
+            let result = run_simulation(params.clone(), &mut source, |s| steps.push(s))
+                .await
+                .unwrap();
+
+            for step in &steps {
+                match &step.action {
+                    Action::Incident {
+                        name,
+                        text,
+                        credits_lost,
+                        weeks_lost,
+                        ..
+                    } => {
+                        boardings += 1;
+                        assert_eq!(name, "Patrol boarding");
+                        assert_eq!(text, "A naval patrol searches the ship.");
+                        assert_eq!((*credits_lost, *weeks_lost), (2_000, 1));
+                    }
+                    Action::IncidentPiracy { .. }
+                    | Action::IncidentTradeScam { .. }
+                    | Action::IncidentCrewLoss { .. }
+                    | Action::IncidentAccident { .. }
+                    | Action::IncidentGovernment { .. } => {
+                        panic!("a standard incident was rolled: {:?}", step.action)
+                    }
+                    _ => {}
+                }
+            }
+            let ledger = voyage_ledger(&params, &steps);
+            assert_eq!(ledger.balance(), result.final_budget, "seed {seed}");
+        }
+        assert!(boardings > 0, "no seed was boarded");
+
+        let mut params = offline_params(&sector, None);
+        params.incidents = Some(IncidentTable::default());
+        assert!(matches!(
+            Voyage::new(params),
+            Err(ExecutorError::IncidentTable(_))
+        ));
+    }
+
+    /// Under the condition model hits stay on the ship until a port
+    /// whose yard can fix them repairs them, and an overdue ship is only
+    /// overhauled at a shipyard.
+    #[tokio::test]
+    async fn condition_model_carries_damage_to_a_yard_that_can_fix_it() {
+        use crate::simulator::condition::{ConditionModel, RepairPlan, ShipCondition, ShipSystem};
+        use crate::trade::PortCode;
+
+        let sector = Sector::generate("Condition Test", Density::Dense);
+        let table = IncidentTable::from_json(
+            r#"{"entries": [{
+                "name": "Hull strike",
+                "kind": "accident",
+                "effects": {"damage": {"plus": 1, "times": 100000}, "hits": {"plus": 2}}
+            }]}"#,
+        )
+        .unwrap();
+        let (mut damaged, mut repairs) = (0, 0);
+        for seed in [4, 8, 15] {
+            let mut params = offline_params(&sector, Some(seed));
+            params.ship.leadership_skill = 0;
+            params.incidents = Some(table.clone());
+            params.condition = Some(ConditionModel {
+                days_since_maintenance: 400,
+                ..ConditionModel::default()
+            });
+            let mut source = SectorFiles::from(sector.clone());
+            let mut steps = Vec::new();
+            let result = run_simulation(params.clone(), &mut source, |s| steps.push(s))
+                .await
+                .unwrap();
+
+            let mut carried = ShipCondition::default();
+            for step in &steps {
+                let port = PortCode::from_uwp(&step.location.uwp);
+                match &step.action {
+                    Action::IncidentAccident { repair_cost, .. } => assert_eq!(*repair_cost, 0),
+                    Action::ShipDamaged { hits, .. } => {
+                        damaged += 1;
+                        hits.iter().for_each(|&h| carried.take_hit(h));
+                    }
+                    Action::Repair {
+                        repaired, deferred, ..
+                    } => {
+                        repairs += 1;
+                        for &(system, hits) in repaired {
+                            assert!(system.repairable_at(port), "{system} fixed at {port}");
+                            assert!(hits <= carried.hits(system));
+                        }
+                        carried.repair(&RepairPlan {
+                            repaired: repaired.clone(),
+                            ..Default::default()
+                        });
+                        assert_eq!(*deferred, carried.total_hits());
+                    }
+                    Action::AnnualMaintenance { missed, .. } => {
+                        assert!(matches!(port, PortCode::A | PortCode::B));
+                        assert!(*missed >= 1);
+                    }
+                    Action::MisjumpCheck { dm, .. } => assert!(
+                        *dm >= carried.hits(ShipSystem::JDrive) as i32
+                            + carried.hits(ShipSystem::PowerPlant) as i32
+                    ),
+                    _ => {}
+                }
+            }
+            let ledger = voyage_ledger(&params, &steps);
+            assert_eq!(ledger.balance(), result.final_budget, "seed {seed}");
+
+            let (first, _) = run_offline(&sector, params.clone()).await;
+            let (again, _) = run_offline(&sector, params).await;
+            assert_eq!(first, again, "condition-model voyages should replay");
+        }
+        assert!(
+            damaged > 0 && repairs > 0,
+            "no seed was damaged and repaired"
+        );
+
+        // Without the model the same table charges the repair bill.
+        let mut params = offline_params(&sector, Some(4));
+        params.ship.leadership_skill = 0;
+        params.incidents = Some(table);
+        let mut source = SectorFiles::from(sector.clone());
+        let mut steps = Vec::new();
+        run_simulation(params, &mut source, |s| steps.push(s))
+            .await
+            .unwrap();
+        for step in &steps {
+            match &step.action {
+                Action::IncidentAccident { repair_cost, .. } => assert_eq!(*repair_cost, 100_000),
+                Action::ShipDamaged { .. }
+                | Action::Repair { .. }
+                | Action::AnnualMaintenance { .. } => {
+                    panic!("condition tracked without the model: {:?}", step.action)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Every credit the executor moves is posted to the voyage ledger,
+    /// so it closes on the final budget.
+    #[tokio::test]
+    async fn voyage_ledger_closes_on_the_final_budget() {
+        let sector = Sector::generate("Ledger Test", Density::Dense);
+        for seed in [3, 17, 29] {
+            let mut params = offline_params(&sector, Some(seed));
+            params.accept_charters = seed == 17;
+            params.fuel = (seed == 29).then(FuelModel::default);
+            let mut source = SectorFiles::from(sector.clone());
+            let mut steps = Vec::new();
+            let result = run_simulation(params.clone(), &mut source, |s| steps.push(s))
+                .await
+                .unwrap();
+
+            let ledger = voyage_ledger(&params, &steps);
+            assert_eq!(ledger.balance(), result.final_budget, "seed {seed}");
+            assert_eq!(ledger.total(Account::Capital), params.starting_budget);
+        }
+    }
 }
@@ -0,0 +1,267 @@
+//! Several ships trading on one calendar.
+//!
+//! A voyage on its own rolls every market, passenger and freight lot as
+//! if no other ship existed. A fleet runs several voyages side by side,
+//! always advancing the ship whose next port call comes first, and hands
+//! each one the same [`MarketLedger`] and [`TrafficLedger`]: goods one
+//! ship buys out are missing when a fleet-mate calls, and passengers and
+//! freight one ship loads for a destination that week are gone for the
+//! next. Running each ship alone under the same seed and comparing shows
+//! how much the ships cost one another.
+//!
+//! The result types compile with the rest of the simulator so the
+//! frontend can render them; [`Fleet`] and `run_fleet` need the `backend`
+//! feature.
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::types::{SimulationParams, SimulationResult};
+use crate::trade::market::MarketLedger;
+
+#[cfg(feature = "backend")]
+use crate::simulator::executor::{ExecutorError, Voyage};
+#[cfg(feature = "backend")]
+use crate::simulator::traffic::TrafficLedger;
+#[cfg(feature = "backend")]
+use crate::simulator::types::SimulationStep;
+#[cfg(feature = "backend")]
+use crate::simulator::world_source::WorldSource;
+
+/// A fleet request: the ships' voyages and the markets they share.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetParams {
+    /// One voyage per ship. Each ship's own `markets` is ignored in favour
+    /// of the fleet's.
+    pub ships: Vec<SimulationParams>,
+    /// Every market's memory of earlier trade, as the fleet sets out.
+    #[serde(default)]
+    pub markets: MarketLedger,
+}
+
+/// Outcome of a fleet run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FleetResult {
+    /// Each ship's result, in the order the ships were given.
+    pub ships: Vec<SimulationResult>,
+    /// The fleet as one business: see [`fleet_totals`].
+    pub fleet: SimulationResult,
+}
+
+/// The ships' results summed into one: budgets, profits, shares and jumps
+/// are added up, the end date is the last ship's, and the voyage counts
+/// as completed or home only if every ship's did. The fleet went negative
+/// or was marooned if any ship was, and the `marooned_*` details and the
+/// seed are the first such ship's. `None` for an empty fleet.
+pub fn fleet_totals(ships: &[SimulationResult]) -> Option<SimulationResult> {
+    let first = ships.first()?;
+    let stranded = ships.iter().find(|r| r.marooned).unwrap_or(first);
+    Some(SimulationResult {
+        final_budget: ships.iter().map(|r| r.final_budget).sum(),
+        gross_profit: ships.iter().map(|r| r.gross_profit).sum(),
+        crew_share: ships.iter().map(|r| r.crew_share).sum(),
+        owner_profit: ships.iter().map(|r| r.owner_profit).sum(),
+        end_date: ships
+            .iter()
+            .map(|r| r.end_date)
+            .max_by_key(|d| d.day_number())?,
+        jumps: ships.iter().map(|r| r.jumps).sum(),
+        completed_normally: ships.iter().all(|r| r.completed_normally),
+        returned_home: ships.iter().all(|r| r.returned_home),
+        went_negative: ships.iter().any(|r| r.went_negative),
+        marooned: stranded.marooned,
+        marooned_at: stranded.marooned_at.clone(),
+        marooned_on: stranded.marooned_on,
+        rescue_arrives_on: stranded.rescue_arrives_on,
+        seed: first.seed,
+    })
+}
+
+/// Several voyages in progress on one calendar, sharing their markets
+/// and route traffic.
+#[cfg(feature = "backend")]
+pub struct Fleet {
+    voyages: Vec<Voyage>,
+    markets: MarketLedger,
+    traffic: TrafficLedger,
+}
+
+#[cfg(feature = "backend")]
+impl Fleet {
+    /// A fleet whose ships are all about to make their first port call.
+    pub fn new(params: FleetParams) -> Result<Fleet, ExecutorError> {
+        if params.ships.is_empty() {
+            return Err(ExecutorError::EmptyFleet);
+        }
+        Ok(Fleet {
+            voyages: params
+                .ships
+                .into_iter()
+                .map(Voyage::new)
+                .collect::<Result<_, _>>()?,
+            markets: params.markets,
+            traffic: TrafficLedger::new(),
+        })
+    }
+
+    /// Whether every ship's voyage has ended.
+    pub fn is_finished(&self) -> bool {
+        self.voyages.iter().all(Voyage::is_finished)
+    }
+
+    /// The ship whose next port call comes first; the earlier-listed ship
+    /// on a tie. `None` once the fleet has finished.
+    pub fn next_ship(&self) -> Option<usize> {
+        self.voyages
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| !v.is_finished())
+            .min_by_key(|(i, v)| (v.current_date().day_number(), *i))
+            .map(|(i, _)| i)
+    }
+
+    /// Make the next port call of the fleet, with the shared markets and
+    /// traffic. Calls `on_step` with the ship's index and each step as it
+    /// happens.
+    pub async fn step(
+        &mut self,
+        source: &mut impl WorldSource,
+        on_step: &mut (impl FnMut(usize, SimulationStep) + Send),
+    ) -> Result<(), ExecutorError> {
+        let Some(i) = self.next_ship() else {
+            return Ok(());
+        };
+        let voyage = &mut self.voyages[i];
+        voyage.share(&mut self.markets, &mut self.traffic);
+        let stepped = voyage.step(source, &mut |step| on_step(i, step)).await;
+        voyage.share(&mut self.markets, &mut self.traffic);
+        stepped
+    }
+
+    /// Each ship's tally and the fleet's. Final once the fleet has
+    /// finished.
+    pub fn result(&self) -> FleetResult {
+        let ships: Vec<SimulationResult> = self.voyages.iter().map(Voyage::result).collect();
+        let fleet = fleet_totals(&ships).expect("a fleet has at least one ship");
+        FleetResult { ships, fleet }
+    }
+}
+
+/// Run a whole fleet. Calls `on_step` with the ship's index and each step
+/// as it happens; returns every ship's result and the fleet's.
+#[cfg(feature = "backend")]
+pub async fn run_fleet(
+    params: FleetParams,
+    source: &mut impl WorldSource,
+    mut on_step: impl FnMut(usize, SimulationStep) + Send,
+) -> Result<FleetResult, ExecutorError> {
+    let mut fleet = Fleet::new(params)?;
+    while !fleet.is_finished() {
+        fleet.step(source, &mut on_step).await?;
+    }
+    Ok(fleet.result())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::types::Date;
+
+    fn result(owner_profit: i64, end_day: u16, marooned: bool) -> SimulationResult {
+        SimulationResult {
+            final_budget: 1_000_000 + owner_profit,
+            gross_profit: owner_profit,
+            crew_share: 0,
+            owner_profit,
+            end_date: Date::new(end_day, 1105),
+            jumps: 4,
+            completed_normally: !marooned,
+            returned_home: !marooned,
+            went_negative: false,
+            marooned,
+            marooned_at: None,
+            marooned_on: marooned.then(|| Date::new(end_day, 1105)),
+            rescue_arrives_on: None,
+            seed: end_day as u64,
+        }
+    }
+
+    #[test]
+    fn fleet_totals_add_up_the_ships() {
+        assert!(fleet_totals(&[]).is_none());
+        let totals = fleet_totals(&[
+            result(50_000, 90, false),
+            result(-20_000, 120, true),
+            result(10_000, 100, false),
+        ])
+        .unwrap();
+        assert_eq!(totals.final_budget, 3_040_000);
+        assert_eq!(totals.owner_profit, 40_000);
+        assert_eq!(totals.jumps, 12);
+        assert_eq!(totals.end_date, Date::new(120, 1105));
+        assert!(!totals.returned_home);
+        assert!(totals.marooned);
+        assert_eq!(totals.marooned_on, Some(Date::new(120, 1105)));
+        assert_eq!(totals.seed, 90);
+    }
+
+    #[cfg(feature = "backend")]
+    #[tokio::test]
+    async fn fleet_mates_share_markets_and_traffic() {
+        use crate::sector::{Density, Sector};
+        use crate::simulator::executor::run_simulation;
+        use crate::simulator::test_support::offline_params;
+        use crate::simulator::world_source::SectorFiles;
+
+        let sector = Sector::generate("Fleet Test", Density::Dense);
+        let ship = offline_params(&sector, Some(42));
+
+        // The opening port call's actions, up to the jump out of home.
+        fn opening(steps: &[String]) -> Vec<String> {
+            let jump = steps.iter().position(|a| a.starts_with("Jump")).unwrap();
+            steps[..=jump].to_vec()
+        }
+
+        // A fleet of one sails exactly as the ship would alone.
+        let mut alone_steps = Vec::new();
+        let alone = run_simulation(
+            ship.clone(),
+            &mut SectorFiles::from(sector.clone()),
+            |step| alone_steps.push(format!("{:?}", step.action)),
+        )
+        .await
+        .unwrap();
+        let solo = run_fleet(
+            FleetParams {
+                ships: vec![ship.clone()],
+                markets: MarketLedger::new(),
+            },
+            &mut SectorFiles::from(sector.clone()),
+            |_, _| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(solo.ships[0].owner_profit, alone.owner_profit);
+        assert_eq!(solo.fleet.owner_profit, alone.owner_profit);
+
+        // Twins on the same seed would make the same opening alone. In a
+        // fleet the first opens as it would alone, and the second finds
+        // the market and the first leg's traffic picked over.
+        let mut steps = [Vec::new(), Vec::new()];
+        let twins = run_fleet(
+            FleetParams {
+                ships: vec![ship.clone(), ship],
+                markets: MarketLedger::new(),
+            },
+            &mut SectorFiles::from(sector),
+            |i, step| steps[i].push(format!("{:?}", step.action)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(opening(&steps[0]), opening(&alone_steps));
+        assert_ne!(opening(&steps[1]), opening(&steps[0]));
+        assert_eq!(
+            twins.fleet.owner_profit,
+            twins.ships[0].owner_profit + twins.ships[1].owner_profit
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! Fuel tankage model for the ship simulator.
+//!
+//! Without a [`FuelModel`] the executor charges the flat
+//! `SimulationParams::fuel_cost_per_parsec` per jump and every jump
+//! succeeds. With one, the ship carries a tank of jump fuel and refuels
+//! at each port call from whatever the system offers:
+//!
+//! * refined fuel bought at an A or B starport,
+//! * unrefined fuel bought at a C or D starport,
+//! * or, with fuel scoops, unrefined fuel skimmed from a gas giant or
+//!   scooped from surface water — free, but it costs a day.
+//!
+//! A jump that burns unrefined fuel risks a misjump. A fuel processor
+//! refines wilderness fuel as it's taken on.
+//!
+//! The pure pieces — the tank, the refuelling choice, the misjump roll —
+//! live here; the executor threads them through a voyage.
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::incidents::{roll_1d6, roll_2d6};
+use crate::trade::PortCode;
+
+/// Credits per ton of refined fuel at an A or B starport.
+pub const REFINED_FUEL_PRICE: i64 = 500;
+/// Credits per ton of unrefined fuel at a C or D starport.
+pub const UNREFINED_FUEL_PRICE: i64 = 100;
+/// Extra days in system to skim a gas giant or scoop water.
+pub const WILDERNESS_REFUEL_DAYS: u32 = 1;
+/// A 2d6 roll at or above this on a jump burning unrefined fuel misjumps.
+pub const MISJUMP_ROLL: i32 = 11;
+/// Repair cost per 1d6 rolled after a misjump.
+pub const MISJUMP_REPAIR_CR_PER_STEP: i64 = 10_000;
+
+/// The ship's fuel hardware and the prices it pays. Every field has a
+/// default, so a client can send `{}` to switch the model on with a
+/// 200-ton jump-2 trader's figures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FuelModel {
+    /// Jump fuel the tanks hold, in tons.
+    pub tankage: u32,
+    /// Tons burned per parsec jumped.
+    pub tons_per_parsec: u32,
+    /// Whether the ship can skim gas giants and scoop water.
+    pub scoops: bool,
+    /// Whether the ship can refine the fuel it skims or scoops.
+    pub processor: bool,
+    /// Credits per ton of refined fuel.
+    pub refined_price: i64,
+    /// Credits per ton of unrefined fuel.
+    pub unrefined_price: i64,
+}
+
+impl Default for FuelModel {
+    fn default() -> Self {
+        FuelModel {
+            tankage: 40,
+            tons_per_parsec: 20,
+            scoops: true,
+            processor: false,
+            refined_price: REFINED_FUEL_PRICE,
+            unrefined_price: UNREFINED_FUEL_PRICE,
+        }
+    }
+}
+
+impl FuelModel {
+    /// Tons a jump of `distance` parsecs burns.
+    pub fn tons_for(&self, distance: i32) -> u32 {
+        distance.max(0) as u32 * self.tons_per_parsec
+    }
+
+    /// Credit cost of a parsec's fuel at refined prices — what the route
+    /// planner weighs distance against.
+    pub fn cost_per_parsec(&self) -> i64 {
+        self.tons_per_parsec as i64 * self.refined_price
+    }
+
+    /// Where the ship would refuel at a port of class `port`. Gas giants
+    /// are only counted (via `gas_giants`, which may generate the system)
+    /// when the port sells no fuel and the ship has scoops.
+    pub fn refuel_source(
+        &self,
+        port: PortCode,
+        hydro: i32,
+        gas_giants: impl FnOnce() -> u8,
+    ) -> Option<FuelSource> {
+        match port {
+            PortCode::A | PortCode::B => return Some(FuelSource::Refined),
+            PortCode::C | PortCode::D if !self.scoops => return Some(FuelSource::Unrefined),
+            _ => {}
+        }
+        if self.scoops {
+            let giants = gas_giants();
+            if giants > 0 {
+                return Some(FuelSource::GasGiant { gas_giants: giants });
+            }
+            if hydro > 0 {
+                return Some(FuelSource::Water);
+            }
+        }
+        matches!(port, PortCode::C | PortCode::D).then_some(FuelSource::Unrefined)
+    }
+}
+
+/// Where a port call's fuel came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuelSource {
+    /// Bought refined at the starport.
+    Refined,
+    /// Bought unrefined at the starport.
+    Unrefined,
+    /// Skimmed from one of the system's gas giants.
+    GasGiant { gas_giants: u8 },
+    /// Scooped from the main world's surface water.
+    Water,
+}
+
+/// Fuel aboard. `unrefined` is the part of `tons` that is unrefined;
+/// jumps burn it first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuelTank {
+    pub tons: u32,
+    pub unrefined: u32,
+}
+
+impl FuelTank {
+    /// A tank filled to `tankage` with refined fuel.
+    pub fn full(tankage: u32) -> Self {
+        FuelTank {
+            tons: tankage,
+            unrefined: 0,
+        }
+    }
+
+    /// Add `tons` of fuel.
+    pub fn fill(&mut self, tons: u32, refined: bool) {
+        self.tons += tons;
+        if !refined {
+            self.unrefined += tons;
+        }
+    }
+
+    /// Burn `tons` for a jump, unrefined first. Returns how much of the
+    /// burn was unrefined.
+    pub fn burn(&mut self, tons: u32) -> u32 {
+        let tons = tons.min(self.tons);
+        let unrefined = tons.min(self.unrefined);
+        self.tons -= tons;
+        self.unrefined -= unrefined;
+        unrefined
+    }
+}
+
+/// Outcome of the misjump check on a jump that burned unrefined fuel
+/// or was made on damaged drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MisjumpCheck {
+    pub roll: i32,
+    /// DM from the ship's condition, added to the roll.
+    pub dm: i32,
+    pub misjumped: bool,
+    /// Weeks drifting before the ship reaches its destination.
+    pub weeks_lost: u32,
+    /// Credits to repair the drives.
+    pub repair_cost: i64,
+}
+
+/// Roll the misjump check: 2d6 + `dm`, misjumping at [`MISJUMP_ROLL`] or
+/// more. A misjump costs 1d6 weeks and 1d6 ×
+/// [`MISJUMP_REPAIR_CR_PER_STEP`].
+pub fn roll_misjump(dm: i32) -> MisjumpCheck {
+    let roll = roll_2d6();
+    if roll + dm < MISJUMP_ROLL {
+        return MisjumpCheck {
+            roll,
+            dm,
+            misjumped: false,
+            weeks_lost: 0,
+            repair_cost: 0,
+        };
+    }
+    let weeks_lost = roll_1d6() as u32;
+    let repair_cost = roll_1d6() as i64 * MISJUMP_REPAIR_CR_PER_STEP;
+    MisjumpCheck {
+        roll,
+        dm,
+        misjumped: true,
+        weeks_lost,
+        repair_cost,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::RngScope;
+
+    fn model(scoops: bool) -> FuelModel {
+        FuelModel {
+            scoops,
+            ..FuelModel::default()
+        }
+    }
+
+    #[test]
+    fn starports_sell_fuel_by_class() {
+        let no_scoops = model(false);
+        assert_eq!(
+            no_scoops.refuel_source(PortCode::A, 5, || 3),
+            Some(FuelSource::Refined)
+        );
+        assert_eq!(
+            no_scoops.refuel_source(PortCode::B, 0, || 0),
+            Some(FuelSource::Refined)
+        );
+        assert_eq!(
+            no_scoops.refuel_source(PortCode::C, 5, || 3),
+            Some(FuelSource::Unrefined)
+        );
+        assert_eq!(no_scoops.refuel_source(PortCode::E, 5, || 3), None);
+        assert_eq!(no_scoops.refuel_source(PortCode::X, 0, || 0), None);
+    }
+
+    #[test]
+    fn scoops_prefer_free_fuel_below_class_b() {
+        let scoops = model(true);
+        assert_eq!(
+            scoops.refuel_source(PortCode::D, 5, || 2),
+            Some(FuelSource::GasGiant { gas_giants: 2 })
+        );
+        assert_eq!(
+            scoops.refuel_source(PortCode::X, 5, || 0),
+            Some(FuelSource::Water)
+        );
+        // Nothing to skim or scoop: fall back to what the port sells.
+        assert_eq!(
+            scoops.refuel_source(PortCode::C, 0, || 0),
+            Some(FuelSource::Unrefined)
+        );
+        assert_eq!(scoops.refuel_source(PortCode::E, 0, || 0), None);
+    }
+
+    #[test]
+    fn refined_ports_never_generate_the_system() {
+        let scoops = model(true);
+        let source = scoops.refuel_source(PortCode::A, 0, || panic!("counted gas giants"));
+        assert_eq!(source, Some(FuelSource::Refined));
+    }
+
+    #[test]
+    fn tank_burns_unrefined_first() {
+        let mut tank = FuelTank::full(20);
+        tank.fill(20, false);
+        assert_eq!(
+            tank,
+            FuelTank {
+                tons: 40,
+                unrefined: 20
+            }
+        );
+        assert_eq!(tank.burn(30), 20);
+        assert_eq!(
+            tank,
+            FuelTank {
+                tons: 10,
+                unrefined: 0
+            }
+        );
+        assert_eq!(tank.burn(10), 0);
+        assert_eq!(tank.tons, 0);
+    }
+
+    #[test]
+    fn misjumps_only_on_high_rolls() {
+        let _guard = RngScope::new(5);
+        for _ in 0..500 {
+            let check = roll_misjump(0);
+            assert!((2..=12).contains(&check.roll));
+            assert_eq!(check.misjumped, check.roll >= MISJUMP_ROLL);
+            if check.misjumped {
+                assert!((1..=6).contains(&check.weeks_lost));
+                assert_eq!(check.repair_cost % MISJUMP_REPAIR_CR_PER_STEP, 0);
+            } else {
+                assert_eq!((check.weeks_lost, check.repair_cost), (0, 0));
+            }
+        }
+        // Damaged drives misjump on lower rolls.
+        for _ in 0..500 {
+            let check = roll_misjump(3);
+            assert_eq!(check.misjumped, check.roll + 3 >= MISJUMP_ROLL);
+        }
+    }
+}
@@ -0,0 +1,593 @@
+//! Data-driven incident table for the ship simulator.
+//!
+//! When the ship fails its avoidance roll at a port (see
+//! [`incidents`](crate::simulator::incidents)), the executor rolls 2d6
+//! plus the standard port/zone/law modifiers and looks the total up here.
+//! Each [`IncidentEntry`] covers a range of totals, can shift the total
+//! with modifiers of its own, and lists its effects — credits, repairs or
+//! hits of damage, weeks lost, cargo lost, a crewmember lost — along with
+//! the narrative text the log shows. Entries are tried in order and the first whose
+//! range covers the total is the incident; a total no entry covers
+//! passes without incident.
+//!
+//! [`IncidentTable::builtin`] is the standard table of piracy, trade
+//! scams, crew loss, accidents and government complications, read from
+//! `incident_table.json` beside this file. A referee's own table is read
+//! with [`IncidentTable::from_json`] and set on
+//! `SimulationParams::incidents`. Derelict salvage, for instance, placed
+//! ahead of the government complication it would otherwise shadow:
+//!
+//! ```json
+//! {
+//!   "name": "Derelict salvage",
+//!   "min": 12,
+//!   "modifiers": [{ "zone": "Amber", "dm": 1 }],
+//!   "text": "The ship finds a drifting hulk and strips it.",
+//!   "effects": {
+//!     "credits": { "dice": "2d6", "times": -10000 },
+//!     "weeks": { "dice": "1d3" }
+//!   }
+//! }
+//! ```
+
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::trade::{PortCode, Ship, ZoneClassification};
+use crate::util;
+
+/// The standard table, as shipped.
+const BUILTIN_TABLE: &str = include_str!("incident_table.json");
+
+/// Most dice one effect may roll.
+pub const MAX_DICE: u32 = 100;
+/// Most sides a die may have.
+pub const MAX_DIE_SIDES: u32 = 1_000;
+/// Largest scale, either way, an effect may put on its roll.
+pub const MAX_TIMES: i64 = 1_000_000_000;
+
+/// Why an incident table couldn't be read.
+#[derive(Debug, thiserror::Error)]
+pub enum IncidentTableError {
+    /// The document wasn't an incident table.
+    #[error("incident table invalid: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The table has nothing to roll on.
+    #[error("incident table has no entries")]
+    Empty,
+    /// An entry can't be rolled as written.
+    #[error("incident '{name}': {reason}")]
+    Entry { name: String, reason: String },
+}
+
+/// The incidents a ship can meet in port, tried in order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IncidentTable {
+    pub entries: Vec<IncidentEntry>,
+}
+
+/// Which log entry an incident files. The five standard kinds each have
+/// their own, with room for the effects that kind has; anything else is
+/// `other`, which reports every effect.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentKind {
+    Piracy,
+    TradeScam,
+    CrewLoss,
+    Accident,
+    Government,
+    #[default]
+    Other,
+}
+
+/// One row of the table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncidentEntry {
+    pub name: String,
+    #[serde(default)]
+    pub kind: IncidentKind,
+    /// Lowest modified total this entry covers; no floor when `None`.
+    #[serde(default)]
+    pub min: Option<i32>,
+    /// Highest modified total this entry covers; no ceiling when `None`.
+    #[serde(default)]
+    pub max: Option<i32>,
+    /// DMs on the table total for this entry alone.
+    #[serde(default)]
+    pub modifiers: Vec<IncidentModifier>,
+    #[serde(default)]
+    pub effects: IncidentEffects,
+    /// What happened, for the log.
+    #[serde(default)]
+    pub text: String,
+}
+
+/// A DM that applies when every condition it sets holds.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IncidentModifier {
+    pub port: Option<PortCode>,
+    pub zone: Option<ZoneClassification>,
+    pub law_min: Option<i32>,
+    pub law_max: Option<i32>,
+    /// Whether the ship must be in foreign-empire space, or out of it.
+    pub foreign: Option<bool>,
+    pub dm: i32,
+}
+
+/// What an incident does to the ship. Effects are rolled in field order,
+/// so a seeded voyage replays them exactly.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IncidentEffects {
+    /// Percent of the speculative cargo lost, taken good by good.
+    pub cargo_lost_pct: Option<EffectRoll>,
+    /// Credits lost to fines, thieves or ransoms. A negative amount is a
+    /// windfall.
+    pub credits: Option<EffectRoll>,
+    /// Credits of repairs to the ship.
+    pub damage: Option<EffectRoll>,
+    /// Hits of damage to the hull, drives and power plant, each landing
+    /// where a location roll puts it. Only rolled when the voyage tracks
+    /// the ship's condition, and then in place of `damage`: the hits are
+    /// paid for when a port repairs them.
+    pub hits: Option<EffectRoll>,
+    /// Weeks lost in port.
+    pub weeks: Option<EffectRoll>,
+    /// One crewmember still aboard dies or leaves the ship. Only ships
+    /// with a roster lose anyone.
+    pub crew_lost: bool,
+}
+
+/// How an effect's amount is rolled: dice, divided, plus a constant,
+/// less the ship's ratings, clamped, then scaled —
+/// `clamp(dice / divide + plus - minus, min, max) * times`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EffectRoll {
+    /// Dice rolled, e.g. `"2d6"`; none when empty.
+    pub dice: Dice,
+    /// Divide the dice by this, rounding down.
+    pub divide: i32,
+    pub plus: i32,
+    /// Ship ratings and crew skills taken off the roll.
+    pub minus: Vec<ShipRating>,
+    /// Floor on the modified roll.
+    pub min: i32,
+    /// Ceiling on the modified roll, if any.
+    pub max: Option<i32>,
+    /// Scale on the clamped roll.
+    pub times: i64,
+    /// Scale by the ship's maintenance per period as well.
+    pub times_maintenance: bool,
+}
+
+/// A rating of the ship or its crew an effect roll can subtract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShipRating {
+    Weapons,
+    Gunner,
+    Broker,
+    Steward,
+    Leadership,
+    Deception,
+    Admin,
+}
+
+/// A handful of like dice, written `"2d6"`. `"d6"` is one die and the
+/// empty string none.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Dice {
+    pub count: u32,
+    pub sides: u32,
+}
+
+impl IncidentTable {
+    /// The standard table, shared process-wide.
+    pub fn builtin() -> &'static IncidentTable {
+        static BUILTIN: OnceLock<IncidentTable> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            IncidentTable::from_json(BUILTIN_TABLE).expect("the built-in incident table is valid")
+        })
+    }
+
+    /// Read and check a table written as JSON.
+    pub fn from_json(json: &str) -> Result<IncidentTable, IncidentTableError> {
+        let table: IncidentTable = serde_json::from_str(json)?;
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Check that every entry can be rolled and reported as written.
+    pub fn validate(&self) -> Result<(), IncidentTableError> {
+        if self.entries.is_empty() {
+            return Err(IncidentTableError::Empty);
+        }
+        for entry in &self.entries {
+            let reason = if entry.min.zip(entry.max).is_some_and(|(min, max)| min > max) {
+                Some("its min is above its max".to_string())
+            } else if !entry.kind.reports(&entry.effects) {
+                Some(format!(
+                    "a {:?} incident has no room to report all its effects; make it kind \"other\"",
+                    entry.kind
+                ))
+            } else {
+                entry.effects.rolls().find_map(|roll| {
+                    if roll.divide < 1 {
+                        Some("an effect divides by less than 1".to_string())
+                    } else if roll.dice.count > MAX_DICE {
+                        Some(format!("an effect rolls more than {MAX_DICE} dice"))
+                    } else if roll.dice.sides > MAX_DIE_SIDES {
+                        Some(format!("an effect's dice have more than {MAX_DIE_SIDES} sides"))
+                    } else if roll.times.abs() > MAX_TIMES {
+                        Some(format!("an effect's times is beyond ±{MAX_TIMES}"))
+                    } else if roll.max.is_some_and(|max| max < roll.min) {
+                        Some("an effect's max is below its min".to_string())
+                    } else {
+                        None
+                    }
+                })
+            };
+            if let Some(reason) = reason {
+                return Err(IncidentTableError::Entry {
+                    name: entry.name.clone(),
+                    reason,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The entry a modified table total lands on at this port: the first
+    /// whose range covers it once the entry's own modifiers are added.
+    /// `None` when no entry does.
+    pub fn entry_for(
+        &self,
+        total: i32,
+        port: PortCode,
+        zone: ZoneClassification,
+        law: i32,
+        is_foreign: bool,
+    ) -> Option<&IncidentEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.covers(total + entry.modifier(port, zone, law, is_foreign)))
+    }
+}
+
+impl IncidentKind {
+    /// Whether this kind's log entry has room for every effect in
+    /// `effects`.
+    fn reports(self, effects: &IncidentEffects) -> bool {
+        let cargo = effects.cargo_lost_pct.is_some();
+        let credits = effects.credits.is_some();
+        let damage = effects.damage.is_some();
+        let weeks = effects.weeks.is_some();
+        let crew = effects.crew_lost;
+        match self {
+            IncidentKind::Piracy => !damage && !crew,
+            IncidentKind::TradeScam | IncidentKind::Government => !cargo && !damage && !crew,
+            IncidentKind::CrewLoss => !cargo && !credits && !damage,
+            IncidentKind::Accident => !cargo && !credits && !weeks && !crew,
+            IncidentKind::Other => true,
+        }
+    }
+}
+
+impl IncidentEntry {
+    /// Sum of this entry's modifiers that apply at this port.
+    pub fn modifier(
+        &self,
+        port: PortCode,
+        zone: ZoneClassification,
+        law: i32,
+        is_foreign: bool,
+    ) -> i32 {
+        self.modifiers
+            .iter()
+            .filter(|m| m.applies(port, zone, law, is_foreign))
+            .map(|m| m.dm)
+            .sum()
+    }
+
+    /// Whether `total` falls in this entry's range.
+    pub fn covers(&self, total: i32) -> bool {
+        self.min.is_none_or(|min| total >= min) && self.max.is_none_or(|max| total <= max)
+    }
+}
+
+impl IncidentModifier {
+    /// Whether every condition this modifier sets holds at this port.
+    pub fn applies(
+        &self,
+        port: PortCode,
+        zone: ZoneClassification,
+        law: i32,
+        is_foreign: bool,
+    ) -> bool {
+        self.port.is_none_or(|p| p == port)
+            && self.zone.is_none_or(|z| z == zone)
+            && self.law_min.is_none_or(|min| law >= min)
+            && self.law_max.is_none_or(|max| law <= max)
+            && self.foreign.is_none_or(|f| f == is_foreign)
+    }
+}
+
+impl IncidentEffects {
+    /// Every effect that is rolled, in the order they're rolled.
+    fn rolls(&self) -> impl Iterator<Item = &EffectRoll> {
+        [
+            &self.cargo_lost_pct,
+            &self.credits,
+            &self.damage,
+            &self.hits,
+            &self.weeks,
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+impl Default for EffectRoll {
+    fn default() -> Self {
+        EffectRoll {
+            dice: Dice::default(),
+            divide: 1,
+            plus: 0,
+            minus: Vec::new(),
+            min: 0,
+            max: None,
+            times: 1,
+            times_maintenance: false,
+        }
+    }
+}
+
+impl EffectRoll {
+    /// Roll the amount for `ship`. Saturates rather than overflowing on
+    /// extreme constants.
+    pub fn roll(&self, ship: &Ship) -> i64 {
+        let ratings: i32 = self.minus.iter().map(|rating| rating.value(ship)).sum();
+        let mut total = (self.dice.roll() / self.divide.max(1))
+            .saturating_add(self.plus)
+            .saturating_sub(ratings)
+            .max(self.min);
+        if let Some(max) = self.max {
+            total = total.min(max);
+        }
+        let amount = (total as i64).saturating_mul(self.times);
+        if self.times_maintenance {
+            amount.saturating_mul(ship.maintenance_per_period)
+        } else {
+            amount
+        }
+    }
+}
+
+impl ShipRating {
+    /// The ship's rating, from its roster where it has one.
+    pub fn value(self, ship: &Ship) -> i32 {
+        let value = match self {
+            ShipRating::Weapons => ship.weapons,
+            ShipRating::Gunner => ship.effective_gunner_skill(),
+            ShipRating::Broker => ship.effective_broker_skill(),
+            ShipRating::Steward => ship.effective_steward_skill(),
+            ShipRating::Leadership => ship.effective_leadership_skill(),
+            ShipRating::Deception => ship.effective_deception_skill(),
+            ShipRating::Admin => ship.effective_admin_skill(),
+        };
+        value as i32
+    }
+}
+
+impl Dice {
+    /// Roll the dice and sum them; 0 for no dice.
+    pub fn roll(self) -> i32 {
+        if self.sides == 0 {
+            return 0;
+        }
+        (0..self.count)
+            .map(|_| util::rng_random_range(1..=self.sides as i32))
+            .sum()
+    }
+}
+
+impl Display for Dice {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.count == 0 {
+            Ok(())
+        } else {
+            write!(f, "{}d{}", self.count, self.sides)
+        }
+    }
+}
+
+impl FromStr for Dice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Dice, String> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Ok(Dice::default());
+        }
+        let bad = || format!("dice '{s}' should be written like 2d6");
+        let (count, sides) = s.split_once(['d', 'D']).ok_or_else(bad)?;
+        let count = if count.is_empty() {
+            1
+        } else {
+            count.parse().map_err(|_| bad())?
+        };
+        let sides = sides.parse().map_err(|_| bad())?;
+        if sides == 0 {
+            return Err(bad());
+        }
+        Ok(Dice { count, sides })
+    }
+}
+
+impl TryFrom<String> for Dice {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Dice, String> {
+        text.parse()
+    }
+}
+
+impl From<Dice> for String {
+    fn from(dice: Dice) -> String {
+        dice.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ship() -> Ship {
+        Ship {
+            weapons: 2,
+            leadership_skill: 1,
+            maintenance_per_period: 3_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn builtin_table_has_the_five_standard_incidents() {
+        let table = IncidentTable::builtin();
+        let kinds: Vec<IncidentKind> = table.entries.iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                IncidentKind::Piracy,
+                IncidentKind::TradeScam,
+                IncidentKind::CrewLoss,
+                IncidentKind::Accident,
+                IncidentKind::Government,
+            ]
+        );
+        let at = |total| {
+            table
+                .entry_for(total, PortCode::C, ZoneClassification::Green, 5, false)
+                .map(|e| e.kind)
+        };
+        assert_eq!(at(-3), Some(IncidentKind::Piracy));
+        assert_eq!(at(6), Some(IncidentKind::TradeScam));
+        assert_eq!(at(7), Some(IncidentKind::CrewLoss));
+        assert_eq!(at(9), Some(IncidentKind::Accident));
+        assert_eq!(at(20), Some(IncidentKind::Government));
+    }
+
+    #[test]
+    fn dice_round_trip_as_text() {
+        assert_eq!("2d6".parse(), Ok(Dice { count: 2, sides: 6 }));
+        assert_eq!("d3".parse(), Ok(Dice { count: 1, sides: 3 }));
+        assert_eq!("".parse(), Ok(Dice::default()));
+        assert_eq!(Dice { count: 2, sides: 6 }.to_string(), "2d6");
+        assert!("2d0".parse::<Dice>().is_err());
+        assert!("six".parse::<Dice>().is_err());
+        assert_eq!(Dice::default().roll(), 0);
+    }
+
+    #[test]
+    fn effect_roll_subtracts_ratings_then_clamps_and_scales() {
+        let ship = ship();
+        let roll = |json: &str| {
+            serde_json::from_str::<EffectRoll>(json)
+                .unwrap()
+                .roll(&ship)
+        };
+        // 5 - weapons 2 - leadership 1 = 2, times 10.
+        assert_eq!(
+            roll(r#"{"plus": 5, "minus": ["weapons", "leadership"], "times": 10}"#),
+            20
+        );
+        // Floored at 0, whatever the ratings.
+        assert_eq!(roll(r#"{"plus": 1, "minus": ["weapons"]}"#), 0);
+        // Capped, then scaled by maintenance.
+        assert_eq!(
+            roll(r#"{"plus": 9, "max": 4, "times_maintenance": true}"#),
+            12_000
+        );
+        // A negative scale is a windfall.
+        assert_eq!(roll(r#"{"plus": 3, "times": -1000}"#), -3_000);
+        // Built without validation, extremes saturate instead of panicking.
+        assert_eq!(
+            roll(r#"{"plus": 2147483647, "times": 9223372036854775807}"#),
+            i64::MAX
+        );
+    }
+
+    #[test]
+    fn entry_modifiers_shift_the_total_for_that_entry_alone() {
+        let table = IncidentTable::from_json(
+            r#"{"entries": [
+                {"name": "Patrol boarding", "min": 12,
+                 "modifiers": [{"law_min": 10, "dm": 3}, {"zone": "Red", "foreign": true, "dm": 2}],
+                 "effects": {"weeks": {"plus": 1}}},
+                {"name": "Quiet week"}
+            ]}"#,
+        )
+        .unwrap();
+        let at = |law, zone, foreign| {
+            table
+                .entry_for(10, PortCode::B, zone, law, foreign)
+                .map(|e| e.name.as_str())
+        };
+        assert_eq!(at(5, ZoneClassification::Green, false), Some("Quiet week"));
+        assert_eq!(
+            at(10, ZoneClassification::Green, false),
+            Some("Patrol boarding")
+        );
+        assert_eq!(at(5, ZoneClassification::Red, false), Some("Quiet week"));
+        assert_eq!(
+            at(5, ZoneClassification::Red, true),
+            Some("Patrol boarding")
+        );
+    }
+
+    #[test]
+    fn tables_that_cannot_be_rolled_are_refused() {
+        let entry = |json: &str| IncidentTable::from_json(&format!(r#"{{"entries": [{json}]}}"#));
+        assert!(matches!(
+            IncidentTable::from_json(r#"{"entries": []}"#),
+            Err(IncidentTableError::Empty)
+        ));
+        assert!(matches!(
+            entry(r#"{"name": "Backwards", "min": 9, "max": 3}"#),
+            Err(IncidentTableError::Entry { .. })
+        ));
+        assert!(matches!(
+            entry(r#"{"name": "Hijacking", "kind": "accident", "effects": {"crew_lost": true}}"#),
+            Err(IncidentTableError::Entry { .. })
+        ));
+        assert!(matches!(
+            entry(r#"{"name": "Odd", "effects": {"weeks": {"dice": "2d6", "divide": 0}}}"#),
+            Err(IncidentTableError::Entry { .. })
+        ));
+        assert!(matches!(
+            entry(r#"{"name": "Odd", "effects": {"weeks": {"dice": "2x6"}}}"#),
+            Err(IncidentTableError::Json(_))
+        ));
+        for huge in [
+            r#"{"dice": "4000000000d6"}"#,
+            r#"{"dice": "2d100000"}"#,
+            r#"{"plus": 1, "times": 9223372036854775807}"#,
+        ] {
+            assert!(
+                matches!(
+                    entry(&format!(r#"{{"name": "Huge", "effects": {{"credits": {huge}}}}}"#)),
+                    Err(IncidentTableError::Entry { .. })
+                ),
+                "{huge}"
+            );
+        }
+        assert!(entry(r#"{"name": "Hijacking", "effects": {"crew_lost": true}}"#).is_ok());
+    }
+}
@@ -7,7 +7,8 @@
 //! marooning.
 //!
 //! Random helpers (`roll_2d6`, `roll_1d6`, `roll_1d3`) live here so the
-//! executor and tests share one source of truth.
+//! executor and tests share one source of truth. They draw from the
+//! worldgen thread-local RNG, so a seeded run replays them exactly.
 
 use rand::Rng;
 
@@ -15,23 +16,21 @@ use crate::simulator::economy::{DAYS_PER_WEEK, RESCUE_PARSECS_PER_WEEK};
 use crate::trade::PortCode;
 use crate::trade::ZoneClassification;
 use crate::trade::available_goods::AvailableGoodsTable;
+use crate::util;
 
 /// Roll 2d6.
 pub fn roll_2d6() -> i32 {
-    let mut rng = rand::rng();
-    rng.random_range(1..=6) + rng.random_range(1..=6)
+    util::roll_2d6()
 }
 
 /// Roll 1d6.
 pub fn roll_1d6() -> i32 {
-    let mut rng = rand::rng();
-    rng.random_range(1..=6)
+    util::roll_1d6()
 }
 
 /// Roll 1d3 (uniform `1..=3`).
 pub fn roll_1d3() -> i32 {
-    let mut rng = rand::rng();
-    rng.random_range(1..=3)
+    util::rng_random_range(1..=3)
 }
 
 /// Port-quality modifier component.
@@ -318,6 +317,8 @@ mod tests {
             quantity_roll: 0,
             buy_price_roll: None,
             sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
         }
     }
 
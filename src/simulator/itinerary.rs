@@ -0,0 +1,498 @@
+//! Multi-leg trade itinerary planner for the trade computer.
+//!
+//! [`plan_itinerary`] walks an [`Itinerary`] — an ordered list of worlds —
+//! one leg at a time, the way a captain would work the loop by hand:
+//!
+//! 1. price the cargo carried in and sell whatever beats what was paid;
+//! 2. book passengers for the next world;
+//! 3. buy speculative goods priced to sell at the next world, with
+//!    [`plan_cargo`] filling the hold left after carried cargo and
+//!    passenger baggage;
+//! 4. fill what's left with freight;
+//! 5. settle the leg through [`ShipManifest::process_trades`] and pay life
+//!    support and the jump.
+//!
+//! Cargo nobody would pay its cost for stays aboard and is offered again
+//! at the next stop; whatever is still aboard at the last stop is sold
+//! there. Each leg's market reflects the shared [`MarketLedger`] on the
+//! day the ship is expected in port. The plan is a forecast, not a
+//! booking: nothing is recorded in the ledger.
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::economy::{
+    DAYS_IN_PORT, DAYS_PER_JUMP, passenger_costs, pick_freight, pick_passengers,
+};
+use crate::simulator::types::Date;
+use crate::systems::world::World;
+use crate::trade::ZoneClassification;
+use crate::trade::available_goods::{AvailableGoodsTable, Good};
+use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::cargo_plan::{CargoLimits, RiskTolerance, SaleMarket, plan_cargo};
+use crate::trade::market::{MarketKey, MarketLedger};
+use crate::trade::port_fees::PortCharges;
+use crate::trade::ship::Ship;
+use crate::trade::ship_manifest::ShipManifest;
+use crate::trade::table::TradeTable;
+use crate::util::calculate_hex_distance;
+
+/// Errors that stop an itinerary from being planned.
+#[derive(Debug, thiserror::Error)]
+pub enum ItineraryError {
+    /// An itinerary needs somewhere to start and somewhere to go.
+    #[error("an itinerary needs at least two stops")]
+    TooFewStops,
+    /// A stop's UWP failed to parse.
+    #[error("invalid UWP '{uwp}' for {name}")]
+    InvalidUwp { name: String, uwp: String },
+    /// A stop's market couldn't be generated.
+    #[error("market generation failed: {0}")]
+    Market(String),
+}
+
+/// One world on an itinerary, as the client describes it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItineraryStop {
+    /// World name.
+    pub name: String,
+    /// World UWP (9-character code).
+    pub uwp: String,
+    /// Galactic hex coordinates (from TravellerMap). Legs with a stop
+    /// lacking coordinates are planned as zero-parsec hops.
+    pub coords: Option<(i32, i32)>,
+    /// Travel zone classification.
+    pub zone: ZoneClassification,
+}
+
+/// An ordered trade loop and the terms to plan it on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Itinerary {
+    /// Worlds in visiting order. Repeat the first stop at the end to
+    /// close the loop.
+    pub stops: Vec<ItineraryStop>,
+    /// Cash on hand for speculative purchases at the first stop. Later
+    /// stops add the profit made so far.
+    pub budget: i64,
+    /// Fuel cost per parsec jumped, in credits.
+    pub fuel_cost_per_parsec: i64,
+    /// How much expected profit to give up for a narrower spread when
+    /// choosing goods.
+    pub risk: RiskTolerance,
+}
+
+/// No stops, Cr1,000,000 to spend and Cr500 a parsec for fuel — the
+/// simulator form's fuel default.
+impl Default for Itinerary {
+    fn default() -> Self {
+        Itinerary {
+            stops: Vec::new(),
+            budget: 1_000_000,
+            fuel_cost_per_parsec: 500,
+            risk: RiskTolerance::default(),
+        }
+    }
+}
+
+/// The plan for one jump of an itinerary.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LegPlan {
+    /// Name of the world the leg starts from.
+    pub from: String,
+    /// Name of the world the leg ends at.
+    pub to: String,
+    /// Jump distance in parsecs (`0` when either world lacks coordinates).
+    pub distance: i32,
+    /// Goods to buy at `from`, with `transacted` set to the planned tons
+    /// and `sell_price` priced for sale at `to`.
+    pub purchases: Vec<Good>,
+    /// Passengers and freight on offer at `from` for this leg.
+    pub passengers: AvailablePassengers,
+    /// The ship's bookings at `from`: passengers, freight lots (indices
+    /// into `passengers.freight_lots`) and the cargo brought in, with
+    /// this port's sales marked as `transacted`.
+    pub manifest: ShipManifest,
+    /// Tons of earlier cargo kept aboard through `from`.
+    pub carried_tons: i32,
+    /// Proceeds from selling carried cargo at `from`.
+    pub sales: i64,
+    /// Fares earned on arrival at `to`.
+    pub passenger_revenue: i64,
+    /// Freight payment earned on arrival at `to`.
+    pub freight_revenue: i64,
+    /// Spent on `purchases`.
+    pub goods_cost: i64,
+    /// Stateroom, passenger, low-berth and crew life support for the jump.
+    pub life_support: i64,
+    /// Fuel for the jump.
+    pub jump_cost: i64,
+    /// Net result of this leg.
+    pub profit: i64,
+    /// Net result of the itinerary up to and including this leg.
+    pub cumulative_profit: i64,
+}
+
+/// The plan for a whole itinerary.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItineraryPlan {
+    /// One entry per jump, in order.
+    pub legs: Vec<LegPlan>,
+    /// Proceeds from selling off everything still aboard at the last stop.
+    pub final_sales: i64,
+    /// Net result of the whole itinerary, final sales included.
+    pub total_profit: i64,
+}
+
+/// A stop's world, ready for trade: trade classes, coordinates and zone set.
+fn stop_world(stop: &ItineraryStop) -> Result<World, ItineraryError> {
+    let invalid = || ItineraryError::InvalidUwp {
+        name: stop.name.clone(),
+        uwp: stop.uwp.clone(),
+    };
+    if stop.uwp.len() != 9 {
+        return Err(invalid());
+    }
+    let mut world = World::from_uwp(&stop.name, &stop.uwp, false, false).map_err(|_| invalid())?;
+    world.gen_trade_classes();
+    world.coordinates = stop.coords;
+    world.travel_zone = stop.zone;
+    Ok(world)
+}
+
+/// Price the cargo aboard for sale at `world` on `day` and mark what to
+/// sell: anything that beats its cost, or everything if `sell_all`.
+fn sell_cargo(
+    manifest: &mut ShipManifest,
+    world: &World,
+    ship: &Ship,
+    system_broker_skill: i16,
+    markets: &MarketLedger,
+    day: i64,
+    sell_all: bool,
+) {
+    if let Some((x, y)) = world.coordinates {
+        let key = MarketKey::at_galactic(x, y);
+        markets.adjust_demand(&key, day, world.get_population(), &mut manifest.trade_goods);
+    }
+    // At port the world buys and the ship supplies.
+    manifest.price_goods(
+        &Some(world.clone()),
+        system_broker_skill,
+        ship.effective_broker_skill(),
+    );
+    for good in manifest.trade_goods.goods.iter_mut() {
+        let sell_price = good.sell_price.unwrap_or(0);
+        good.transacted = if sell_all || sell_price >= good.buy_cost {
+            good.quantity
+        } else {
+            0
+        };
+    }
+}
+
+/// Plan every leg of `itinerary` for `ship`, starting at port on `start`.
+///
+/// Each stop after the first is reached one port stay and one jump after
+/// the last. Passengers, freight and goods are rolled afresh, so two plans
+/// of the same itinerary differ the way two real runs would.
+pub fn plan_itinerary(
+    itinerary: &Itinerary,
+    ship: &Ship,
+    system_broker_skill: i16,
+    illegal_goods: bool,
+    markets: &MarketLedger,
+    start: Date,
+) -> Result<ItineraryPlan, ItineraryError> {
+    if itinerary.stops.len() < 2 {
+        return Err(ItineraryError::TooFewStops);
+    }
+    let worlds = itinerary
+        .stops
+        .iter()
+        .map(stop_world)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let days_per_leg = (DAYS_IN_PORT + DAYS_PER_JUMP) as i64;
+    let mut hold = ShipManifest::default();
+    let mut legs = Vec::with_capacity(worlds.len() - 1);
+    let mut cumulative_profit = 0;
+
+    for (i, pair) in worlds.windows(2).enumerate() {
+        let (here, there) = (&pair[0], &pair[1]);
+        let day = start.day_number() + i as i64 * days_per_leg;
+        let distance = match (here.coordinates, there.coordinates) {
+            (Some((hx, hy)), Some((tx, ty))) => calculate_hex_distance(hx, hy, tx, ty),
+            _ => 0,
+        };
+
+        // Sell what's worth selling here; the rest rides on.
+        sell_cargo(
+            &mut hold,
+            here,
+            ship,
+            system_broker_skill,
+            markets,
+            day,
+            false,
+        );
+        let sales = hold.trade_goods_proceeds();
+        let carried_tons = hold.trade_goods_tonnage();
+
+        // This port's market, priced to buy here and to sell there.
+        let mut market = AvailableGoodsTable::for_world(
+            TradeTable::global(),
+            &here.get_trade_classes(),
+            here.get_population(),
+            illegal_goods,
+        )
+        .map_err(ItineraryError::Market)?;
+        if let Some((x, y)) = here.coordinates {
+            let key = MarketKey::at_galactic(x, y);
+            markets.adjust_supply(&key, day, here.get_population(), &mut market);
+        }
+        market.price_goods_to_buy(
+            &here.get_trade_classes(),
+            ship.effective_broker_skill(),
+            system_broker_skill,
+        );
+        if let Some((x, y)) = there.coordinates {
+            let key = MarketKey::at_galactic(x, y);
+            markets.adjust_demand(&key, day, there.get_population(), &mut market);
+        }
+        market.price_goods_to_sell(
+            Some(there.get_trade_classes()),
+            system_broker_skill,
+            ship.effective_broker_skill(),
+        );
+
+        // Passengers first: their baggage takes hold space.
+        let mut passengers = AvailablePassengers::default();
+        passengers.generate(
+            here.get_population(),
+            here.port,
+            here.travel_zone,
+            here.tech_level,
+            there.get_population(),
+            there.port,
+            there.travel_zone,
+            there.tech_level,
+            distance,
+            ship.effective_steward_skill() as i32,
+            ship.effective_broker_skill() as i32,
+        );
+        let (h, m, b, l) = pick_passengers(ship.passenger_staterooms, ship.low_berths, &passengers);
+        hold.high_passengers = h;
+        hold.medium_passengers = m;
+        hold.basic_passengers = b;
+        hold.low_passengers = l;
+        let committed_tons = carried_tons + hold.passenger_cargo_tons().ceil() as i32;
+
+        // Buy with what's in hand, then fill the rest of the hold with
+        // freight. A ton only has to beat freight if there is freight.
+        let sale = SaleMarket {
+            trade_classes: &there.get_trade_classes(),
+            buyer_broker_skill: system_broker_skill,
+            supplier_broker_skill: ship.effective_broker_skill(),
+            use_rolled_prices: false,
+        };
+        let limits = CargoLimits {
+            cargo_capacity: ship.cargo_capacity,
+            committed_tons,
+            budget: (itinerary.budget + cumulative_profit + sales).max(0),
+            freight_rate_per_ton: if passengers.freight_lots.is_empty() {
+                0
+            } else {
+                ShipManifest::freight_rate_per_ton(distance)
+            },
+            risk: itinerary.risk,
+        };
+        let cargo = plan_cargo(&market.goods, &sale, &limits);
+        let free_tons = (ship.cargo_capacity - committed_tons - cargo.tons).max(0);
+        let (lots, _) = pick_freight(&passengers.freight_lots, free_tons);
+        hold.freight_lot_indices = lots;
+
+        let manifest = hold.clone();
+        let passenger_revenue = hold.passenger_revenue(distance) as i64;
+        let freight_revenue = hold.freight_revenue(distance, &passengers) as i64;
+        let (stateroom, passenger_ls, low) = passenger_costs(h, m, b, l);
+        let life_support = stateroom + passenger_ls + low + ship.crew_life_support_per_jump();
+        let jump_cost = distance as i64 * itinerary.fuel_cost_per_parsec;
+
+        let before = hold.profit;
+        hold.process_trades(
+            distance,
+            &cargo.purchases,
+            &Some(passengers.clone()),
+            &PortCharges::default(),
+        );
+        let profit = hold.profit - before - life_support - jump_cost;
+        cumulative_profit += profit;
+
+        legs.push(LegPlan {
+            from: here.name.clone(),
+            to: there.name.clone(),
+            distance,
+            purchases: cargo.purchases,
+            passengers,
+            manifest,
+            carried_tons,
+            sales,
+            passenger_revenue,
+            freight_revenue,
+            goods_cost: cargo.cost,
+            life_support,
+            jump_cost,
+            profit,
+            cumulative_profit,
+        });
+    }
+
+    // Clear the hold at the last stop, whatever it fetches.
+    let last = worlds.last().expect("at least two stops");
+    let day = start.day_number() + legs.len() as i64 * days_per_leg;
+    sell_cargo(
+        &mut hold,
+        last,
+        ship,
+        system_broker_skill,
+        markets,
+        day,
+        true,
+    );
+    let final_sales = hold.trade_goods_proceeds();
+
+    Ok(ItineraryPlan {
+        legs,
+        final_sales,
+        total_profit: cumulative_profit + final_sales,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stop(name: &str, uwp: &str, coords: (i32, i32)) -> ItineraryStop {
+        ItineraryStop {
+            name: name.to_string(),
+            uwp: uwp.to_string(),
+            coords: Some(coords),
+            zone: ZoneClassification::Green,
+        }
+    }
+
+    fn ship() -> Ship {
+        Ship {
+            cargo_capacity: 80,
+            passenger_staterooms: 6,
+            low_berths: 10,
+            crew_staterooms: 4,
+            crew_size: 4,
+            ..Default::default()
+        }
+    }
+
+    fn loop_itinerary() -> Itinerary {
+        Itinerary {
+            stops: vec![
+                stop("Regina", "A788899-C", (-110, -70)),
+                stop("Efate", "A646930-D", (-108, -69)),
+                stop("Roup", "B6A2435-9", (-109, -71)),
+                stop("Regina", "A788899-C", (-110, -70)),
+            ],
+            budget: 500_000,
+            fuel_cost_per_parsec: 500,
+            risk: RiskTolerance::Balanced,
+        }
+    }
+
+    #[test]
+    fn too_few_stops_is_an_error() {
+        let mut itinerary = loop_itinerary();
+        itinerary.stops.truncate(1);
+        let result = plan_itinerary(
+            &itinerary,
+            &ship(),
+            0,
+            false,
+            &MarketLedger::new(),
+            Date::default(),
+        );
+        assert!(matches!(result, Err(ItineraryError::TooFewStops)));
+    }
+
+    #[test]
+    fn invalid_uwp_is_an_error() {
+        let mut itinerary = loop_itinerary();
+        itinerary.stops[1].uwp = "nonsense".to_string();
+        let result = plan_itinerary(
+            &itinerary,
+            &ship(),
+            0,
+            false,
+            &MarketLedger::new(),
+            Date::default(),
+        );
+        assert!(matches!(result, Err(ItineraryError::InvalidUwp { .. })));
+    }
+
+    #[test]
+    fn legs_follow_the_stops_and_add_up() {
+        let itinerary = loop_itinerary();
+        let ship = ship();
+        let plan = plan_itinerary(
+            &itinerary,
+            &ship,
+            1,
+            false,
+            &MarketLedger::new(),
+            Date::default(),
+        )
+        .unwrap();
+
+        assert_eq!(plan.legs.len(), 3);
+        let mut running = 0;
+        for (leg, pair) in plan.legs.iter().zip(itinerary.stops.windows(2)) {
+            assert_eq!(leg.from, pair[0].name);
+            assert_eq!(leg.to, pair[1].name);
+            assert!(leg.distance > 0);
+            assert_eq!(leg.jump_cost, leg.distance as i64 * 500);
+            assert!(leg.life_support >= ship.crew_life_support_per_jump());
+            assert_eq!(
+                leg.profit,
+                leg.sales + leg.passenger_revenue + leg.freight_revenue
+                    - leg.goods_cost
+                    - leg.life_support
+                    - leg.jump_cost
+            );
+            running += leg.profit;
+            assert_eq!(leg.cumulative_profit, running);
+
+            // Nothing overflows the hold.
+            let bought: i32 = leg.purchases.iter().map(|g| g.transacted).sum();
+            let freight = leg.manifest.total_freight_tons(&leg.passengers);
+            let baggage = leg.manifest.passenger_cargo_tons().ceil() as i32;
+            assert!(leg.carried_tons + baggage + bought + freight <= ship.cargo_capacity);
+        }
+        assert_eq!(plan.total_profit, running + plan.final_sales);
+    }
+
+    #[test]
+    fn unsold_cargo_rides_on_to_the_next_leg() {
+        let plan = plan_itinerary(
+            &loop_itinerary(),
+            &ship(),
+            0,
+            false,
+            &MarketLedger::new(),
+            Date::default(),
+        )
+        .unwrap();
+        for pair in plan.legs.windows(2) {
+            let bought: i32 = pair[0].purchases.iter().map(|g| g.transacted).sum();
+            let aboard = pair[0].carried_tons + bought;
+            let next = &pair[1].manifest;
+            assert_eq!(next.trade_goods.total_size(), aboard);
+            assert_eq!(next.trade_goods_tonnage(), pair[1].carried_tons);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Financial ledger for a simulated voyage.
+//!
+//! The executor moves the budget and emits an [`Action`] for every
+//! credit it spends or earns; [`voyage_ledger`] replays those steps into
+//! a [`Ledger`] so a run can be exported the same way as a trade
+//! computer session.
+//!
+//! Fares, freight, mail and charter fees are reported when they're
+//! booked but only paid once the ship jumps, so they're posted at the
+//! `Arrive` that follows the `Jump`. A ship marooned before it jumps is
+//! never paid them.
+
+use crate::simulator::types::{Action, SimulationParams, SimulationStep};
+use crate::trade::ledger::{Account, Ledger};
+
+/// The ledger for a voyage run with `params` that produced `steps`.
+///
+/// Opens with the starting budget as capital, so the closing balance is
+/// the voyage's final budget.
+pub fn voyage_ledger(params: &SimulationParams, steps: &[SimulationStep]) -> Ledger {
+    let mut ledger = Ledger::default();
+    ledger.record(
+        params.start_date,
+        &params.home_world.name,
+        Account::Capital,
+        "Starting budget",
+        params.starting_budget,
+    );
+
+    // Revenue booked at this port, and revenue for the jump under way.
+    let mut pending: Vec<(Account, String, i64)> = Vec::new();
+    let mut in_transit: Vec<(Account, String, i64)> = Vec::new();
+    for step in steps {
+        let date = step.date;
+        let world = step.location.name.as_str();
+        let mut post = |account, description: String, amount| {
+            ledger.record(date, world, account, description, amount)
+        };
+        match &step.action {
+            Action::Arrive { .. } => {
+                for (account, description, amount) in in_transit.drain(..) {
+                    post(account, description, amount);
+                }
+            }
+            Action::SellGood {
+                good,
+                qty,
+                sell_price,
+                ..
+            } => post(
+                Account::GoodsSales,
+                format!("Sold {qty}t {good}"),
+                *qty as i64 * *sell_price as i64,
+            ),
+            Action::BuyGood {
+                good,
+                qty,
+                total_cost,
+                ..
+            } => post(
+                Account::GoodsPurchases,
+                format!("Bought {qty}t {good}"),
+                -total_cost,
+            ),
+            Action::LoadFreight {
+                tons,
+                revenue_pending,
+                ..
+            } => pending.push((
+                Account::Freight,
+                format!("{tons}t freight"),
+                *revenue_pending,
+            )),
+            Action::LoadMail {
+                containers,
+                revenue_pending,
+                ..
+            } => pending.push((
+                Account::Mail,
+                format!("{containers} mail containers"),
+                *revenue_pending,
+            )),
+            Action::Charter {
+                weeks,
+                revenue_pending,
+                ..
+            } => pending.push((
+                Account::Charter,
+                format!("Charter, {weeks} weeks"),
+                *revenue_pending,
+            )),
+            Action::BoardPax {
+                high,
+                medium,
+                basic,
+                low,
+                revenue_pending,
+            } => pending.push((
+                Account::PassengerFares,
+                format!("{high} high, {medium} middle, {basic} basic, {low} low passengers"),
+                *revenue_pending,
+            )),
+            Action::PayLifeSupport {
+                stateroom_cost,
+                ls_cost,
+                low_cost,
+                crew_cost,
+            } => post(
+                Account::LifeSupport,
+                "Life support for the jump".to_string(),
+                -(stateroom_cost + ls_cost + low_cost + crew_cost),
+            ),
+            Action::Jump { to, fuel_cost, .. } => {
+                post(
+                    Account::Fuel,
+                    format!("Fuel for the jump to {}", to.name),
+                    -fuel_cost,
+                );
+                in_transit.append(&mut pending);
+            }
+            Action::PayBerthing { fee, .. } => {
+                post(Account::PortCharges, "Berthing".to_string(), -fee)
+            }
+            Action::PayImportTariff { good, tariff, .. } => post(
+                Account::PortCharges,
+                format!("Import tariff on {good}"),
+                -tariff,
+            ),
+            Action::PayExportTariff { good, tariff, .. } => post(
+                Account::PortCharges,
+                format!("Export tariff on {good}"),
+                -tariff,
+            ),
+            Action::ContrabandSeized { fine, .. } => {
+                post(Account::Customs, "Contraband fine".to_string(), -fine)
+            }
+            Action::ShipImpounded { release_fee, .. } => post(
+                Account::Customs,
+                "Impound release fee".to_string(),
+                -release_fee,
+            ),
+            Action::BuyFuel {
+                tons,
+                refined,
+                cost,
+                ..
+            } => post(
+                Account::Fuel,
+                format!(
+                    "{tons}t {} fuel",
+                    if *refined { "refined" } else { "unrefined" }
+                ),
+                -cost,
+            ),
+            Action::MisjumpCheck { repair_cost, .. } => post(
+                Account::Repairs,
+                "Misjump repairs".to_string(),
+                -repair_cost,
+            ),
+            Action::Repair { cost, .. } => {
+                post(Account::Repairs, "Shipyard repairs".to_string(), -cost)
+            }
+            Action::PayPeriodic {
+                maintenance,
+                salary,
+                mortgage,
+                ..
+            } => {
+                post(
+                    Account::Maintenance,
+                    "Maintenance".to_string(),
+                    -maintenance,
+                );
+                post(Account::Salary, "Crew salaries".to_string(), -salary);
+                post(Account::Mortgage, "Mortgage payment".to_string(), -mortgage);
+            }
+            Action::IncidentPiracy { credits_lost, .. } => {
+                post(Account::Losses, "Pirate attack".to_string(), -credits_lost)
+            }
+            Action::IncidentTradeScam { credits_lost, .. } => {
+                post(Account::Losses, "Trade scam".to_string(), -credits_lost)
+            }
+            Action::IncidentAccident { repair_cost, .. } => post(
+                Account::Repairs,
+                "Accident repairs".to_string(),
+                -repair_cost,
+            ),
+            Action::IncidentGovernment { fine_credits, .. } => post(
+                Account::Customs,
+                "Government fine".to_string(),
+                -fine_credits,
+            ),
+            Action::Incident {
+                name,
+                credits_lost,
+                repair_cost,
+                ..
+            } => {
+                post(Account::Losses, name.clone(), -credits_lost);
+                post(Account::Repairs, format!("{name} repairs"), -repair_cost);
+            }
+            _ => {}
+        }
+    }
+
+    // A voyage that ends mid-jump has still been paid for what it carried.
+    if let Some(last) = steps.last() {
+        for (account, description, amount) in in_transit {
+            ledger.record(last.date, &last.location.name, account, description, amount);
+        }
+    }
+    ledger
+}
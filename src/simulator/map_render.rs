@@ -21,47 +21,28 @@
 //! back to the galactic view in our embed. The Tile API has no SPA
 //! URL-state machinery — what you ask for is what gets rendered.
 //!
-//! Sector world-space origins are hardcoded for the most common
-//! sectors. For unknown sectors `hex_to_map_space` returns `None` and
-//! the caller falls back to a sector-named link instead of an image.
+//! Sector world-space origins come from the built-in [`SectorOrigins`]
+//! table of the most common sectors. For unknown sectors
+//! `hex_to_map_space` returns `None` and the caller falls back to a
+//! sector-named link instead of an image.
 
 use std::f64::consts::FRAC_PI_6;
 
+use crate::sector::origins::SectorOrigins;
+
 /// `cos(30°)` — horizontal stretch factor for hex columns.
 fn cos30() -> f64 {
     FRAC_PI_6.cos()
 }
 
-/// World-space `(sx, sy)` origin for sectors we hardcode. Verified against
-/// `https://travellermap.com/api/coordinates?sector=NAME`.
+/// World-space `(sx, sy)` origin for the sectors in the built-in
+/// [`SectorOrigins`] table.
 pub fn sector_world_origin(sector: &str) -> Option<(i32, i32)> {
-    let key: String = sector
-        .chars()
-        .filter(|c| !c.is_whitespace())
-        .collect::<String>()
-        .to_ascii_lowercase();
-    match key.as_str() {
-        "spinwardmarches" => Some((-4, -1)),
-        "deneb" => Some((-3, -1)),
-        "core" => Some((0, 0)),
-        "trojanreach" => Some((-4, 0)),
-        "reaversdeep" => Some((-4, 1)),
-        "gvurrdon" => Some((-4, -2)),
-        "tuglikki" => Some((-3, -2)),
-        "corridor" => Some((-2, -1)),
-        "vland" => Some((-1, -1)),
-        "lishun" => Some((-1, 0)),
-        "antares" => Some((0, -1)),
-        "ilelish" => Some((-1, 1)),
-        "fornast" => Some((1, 0)),
-        "massilia" => Some((1, 1)),
-        "diaspora" => Some((2, 1)),
-        _ => None,
-    }
+    SectorOrigins::builtin().origin(sector)
 }
 
 /// Convert a (sector, hex) into TravellerMap map-space `(x, y)`.
-/// Returns `None` if the sector isn't in the hardcoded table.
+/// Returns `None` if the sector isn't in the built-in table.
 pub fn hex_to_map_space(sector: &str, hex_x: i32, hex_y: i32) -> Option<(f64, f64)> {
     let (sx, sy) = sector_world_origin(sector)?;
     let world_x = sx * 32 + (hex_x - 1);
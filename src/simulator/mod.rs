@@ -1,13 +1,30 @@
 //! Ship simulator — automated trade-run simulation.
 
+pub mod batch;
+pub mod captains_log_instructions;
+pub mod captains_log_prompt;
+pub mod captains_log_template;
+pub mod condition;
 pub mod economy;
+pub mod fleet;
+pub mod fuel;
+pub mod incident_table;
 pub mod incidents;
+pub mod itinerary;
+pub mod ledger;
 pub mod map_render;
 pub mod protocol;
 pub mod route;
+pub mod strategy;
+pub mod traffic;
 pub mod types;
 
+#[cfg(test)]
+pub(crate) mod test_support;
+
 #[cfg(feature = "backend")]
 pub mod executor;
 #[cfg(feature = "backend")]
 pub mod world_fetch;
+#[cfg(feature = "backend")]
+pub mod world_source;
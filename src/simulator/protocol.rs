@@ -7,12 +7,27 @@
 //! 4. Server sends exactly one of [`ServerMessage::Done`] or
 //!    [`ServerMessage::Error`] and closes the connection.
 //!
+//! While a run streams, the client may send [`ClientMessage::Pause`],
+//! [`ClientMessage::Resume`], [`ClientMessage::StepOnce`] and
+//! [`ClientMessage::Cancel`]. They take effect between port calls: a
+//! paused run sends [`ServerMessage::Paused`] with a [`Checkpoint`] of its
+//! state and waits, and a cancelled one sends [`ServerMessage::Cancelled`]
+//! in place of `Done`. A stored checkpoint starts a new run with
+//! [`ClientMessage::RunFromCheckpoint`].
+//!
+//! A Monte Carlo batch follows the same shape: the client sends
+//! [`ClientMessage::RunBatch`] instead, the server streams
+//! [`ServerMessage::BatchProgress`] frames, then finishes with one
+//! [`ServerMessage::BatchDone`] or [`ServerMessage::Error`]. A
+//! [`ClientMessage::Cancel`] mid-batch stops it and ends with an `Error`.
+//!
 //! Both enums are tagged via `#[serde(tag = "type")]` to keep the wire format
 //! self-describing and forward-compatible.
 
 use serde::{Deserialize, Serialize};
 
-use crate::simulator::types::{SimulationParams, SimulationResult, SimulationStep};
+use crate::simulator::batch::{BatchParams, BatchSummary};
+use crate::simulator::types::{Checkpoint, SimulationParams, SimulationResult, SimulationStep};
 
 /// Messages sent from the simulator client to the server.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +35,27 @@ use crate::simulator::types::{SimulationParams, SimulationResult, SimulationStep
 pub enum ClientMessage {
     /// Begin a simulation with the given parameters.
     RunSimulation(SimulationParams),
+
+    /// Run the same voyage many times under different seeds and report
+    /// only the statistical summary.
+    RunBatch(BatchParams),
+
+    /// Continue a voyage from a checkpoint sent earlier in a `Paused` or
+    /// `Cancelled` frame. Streams like `RunSimulation`.
+    RunFromCheckpoint(Box<Checkpoint>),
+
+    /// Stop the running voyage after its current port call.
+    Pause,
+
+    /// Let a paused voyage run on.
+    Resume,
+
+    /// Make one more port call, then pause again.
+    StepOnce,
+
+    /// End the voyage after its current port call, without finishing it.
+    /// Mid-batch, stop the batch at once.
+    Cancel,
 }
 
 /// Messages sent from the simulator server to the client.
@@ -37,4 +73,18 @@ pub enum ServerMessage {
     /// The simulation could not run or aborted with an error before producing
     /// a result. The `message` is human-readable.
     Error { message: String },
+
+    /// The voyage is paused between port calls. Carries its state, which
+    /// can be stored and resumed later with `RunFromCheckpoint`.
+    Paused(Box<Checkpoint>),
+
+    /// The voyage was cancelled; sent instead of `Done`. Carries its state
+    /// at the point it stopped.
+    Cancelled(Box<Checkpoint>),
+
+    /// Batch progress: `completed` of `runs` voyages have finished.
+    BatchProgress { completed: u32, runs: u32 },
+
+    /// The batch finished. Carries the summary across all its voyages.
+    BatchDone(BatchSummary),
 }
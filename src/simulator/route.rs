@@ -5,37 +5,69 @@
 //! population, port quality, distance, history, and a "head home"
 //! pressure that ramps up after the trip's halfway point.
 //!
+//! It is the default of the pluggable planners in
+//! [`crate::simulator::strategy`], which reuse the scoring and the
+//! forced-home, head-home and home-exclusion rules exposed here.
+//!
 //! All scoring weights are first-cut and are meant to be tuned after
-//! end-to-end runs. They live here as `pub const` so tests can see
-//! them and a future tuner can swap values in one place.
+//! end-to-end runs. The defaults live here as `pub const` so tests can
+//! see them; a run can override any of them through [`RouteWeights`]
+//! (`SimulationParams::route_weights`).
+
+use serde::{Deserialize, Serialize};
 
+use crate::sector::origins::SectorOrigins;
 use crate::simulator::types::{Date, WorldRef};
 use crate::systems::world::World;
 use crate::trade::PortCode;
 use crate::trade::TradeClass;
 use crate::trade::available_goods::AvailableGoodsTable;
 use crate::trade::table::TradeTable;
-use crate::util::calculate_hex_distance;
 
 /// One destination world the planner is considering.
 pub struct Candidate {
-    /// The candidate world (already populated, with trade classes).
+    /// The candidate world (already populated, with trade classes and
+    /// sector-relative coordinates).
     pub world: World,
+    /// Sector the candidate lies in — not necessarily the current one
+    /// when the jump crosses a sector border.
+    pub sector: String,
     /// Distance in parsecs from the current location to this candidate.
     pub distance: i32,
     /// TravellerMap allegiance code (e.g. `"Im"`, `"ImAp"`, `"AsT4"`,
     /// `"Zh"`). `None` when the data wasn't available. Used by the route
     /// planner to apply a heavy penalty for foreign-empire space.
     pub allegiance: Option<String>,
+    /// Gas giants in the candidate's system, when the world data lists a
+    /// PBG. `None` means unknown, not none.
+    pub gas_giants: Option<u8>,
+}
+
+impl Candidate {
+    /// A `WorldRef` for this candidate, in the sector it was found in.
+    pub fn world_ref(&self) -> WorldRef {
+        let (hex_x, hex_y) = self.world.coordinates.unwrap_or((0, 0));
+        WorldRef {
+            name: self.world.name.clone(),
+            uwp: self.world.to_uwp(),
+            sector: self.sector.clone(),
+            hex_x,
+            hex_y,
+            zone: self.world.travel_zone,
+        }
+    }
 }
 
 /// Read-only context for scoring.
 ///
 /// `history` is interpreted as **most-recent first** — index `0` is the
 /// last world we visited, index `1` is the one before that, etc.
+#[derive(Clone, Copy)]
 pub struct RouteContext<'a> {
     /// Home world (for the "head home" bias and forced-home override).
     pub home: &'a WorldRef,
+    /// Where the ship is now.
+    pub current: &'a WorldRef,
     /// Current in-game date.
     pub current_date: Date,
     /// Date the run started — used to compute trip progress.
@@ -51,6 +83,21 @@ pub struct RouteContext<'a> {
     pub fuel_cost_per_parsec: i64,
     /// Recently visited worlds, **most recent first**.
     pub history: &'a [WorldRef],
+    /// Sector-origin table for distances and identity across sector
+    /// borders.
+    pub sectors: &'a SectorOrigins,
+    /// Scoring weights for this run.
+    pub weights: RouteWeights,
+}
+
+impl RouteContext<'_> {
+    /// Fraction of the trip's planned duration already elapsed; `0.0`
+    /// when the start and target dates coincide.
+    pub fn progress(&self) -> f64 {
+        let total = self.start_date.days_until(self.target_date) as f64;
+        let elapsed = self.start_date.days_until(self.current_date) as f64;
+        if total > 0.0 { elapsed / total } else { 0.0 }
+    }
 }
 
 // === Scoring weights ============================================================
@@ -95,6 +142,45 @@ pub const ROUTE_W_HOME_BIAS: f64 = 50_000.0;
 /// this when home itself is reachable.
 pub const HEAD_HOME_THRESHOLD: f64 = 0.75;
 
+/// The scoring weights as one value, so a run can tune them without
+/// recompiling. Each field defaults to the matching constant above; see
+/// those for what the weight does and its order of magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RouteWeights {
+    /// [`ROUTE_W_POP`].
+    pub population: f64,
+    /// [`ROUTE_W_PORT_A`].
+    pub port_a: f64,
+    /// [`ROUTE_W_PORT_B`].
+    pub port_b: f64,
+    /// [`ROUTE_W_DIST`].
+    pub distance: f64,
+    /// [`ROUTE_W_HISTORY`].
+    pub history: f64,
+    /// [`ROUTE_W_FOREIGN_EMPIRE`].
+    pub foreign_empire: f64,
+    /// [`ROUTE_W_HOME_BIAS`].
+    pub home_bias: f64,
+    /// [`HEAD_HOME_THRESHOLD`].
+    pub head_home_threshold: f64,
+}
+
+impl Default for RouteWeights {
+    fn default() -> Self {
+        RouteWeights {
+            population: ROUTE_W_POP,
+            port_a: ROUTE_W_PORT_A,
+            port_b: ROUTE_W_PORT_B,
+            distance: ROUTE_W_DIST,
+            history: ROUTE_W_HISTORY,
+            foreign_empire: ROUTE_W_FOREIGN_EMPIRE,
+            home_bias: ROUTE_W_HOME_BIAS,
+            head_home_threshold: HEAD_HOME_THRESHOLD,
+        }
+    }
+}
+
 /// Score a single candidate. Higher is better.
 pub fn score_candidate(
     candidate: &Candidate,
@@ -103,6 +189,7 @@ pub fn score_candidate(
 ) -> f64 {
     let candidate_classes = candidate.world.get_trade_classes();
     let trade_table = TradeTable::global();
+    let w = &ctx.weights;
 
     // 1) Trade value.
     let mut score: f64 = 0.0;
@@ -116,48 +203,39 @@ pub fn score_candidate(
     }
 
     // 2) Population bonus.
-    score += candidate.world.get_population() as f64 * ROUTE_W_POP;
+    score += candidate.world.get_population() as f64 * w.population;
 
     // 3) Port bonus.
     match candidate.world.port {
-        PortCode::A => score += ROUTE_W_PORT_A,
-        PortCode::B => score += ROUTE_W_PORT_B,
+        PortCode::A => score += w.port_a,
+        PortCode::B => score += w.port_b,
         _ => {}
     }
 
     // 4) Distance penalty.
-    score -= candidate.distance as f64 * ctx.fuel_cost_per_parsec as f64 * ROUTE_W_DIST;
+    score -= candidate.distance as f64 * ctx.fuel_cost_per_parsec as f64 * w.distance;
 
     // 5) History penalty. Match by (sector, hex_x, hex_y), not name.
     //    Decays linearly with recency: most recent → full penalty,
     //    second most recent → half, third → third, etc.
-    let cand_sector = home_sector_of(candidate);
-    if let Some((sector, hx, hy)) = cand_sector
-        && let Some(idx) = ctx
-            .history
-            .iter()
-            .position(|w| w.sector == sector && w.hex_x == hx && w.hex_y == hy)
+    if let Some(idx) = ctx
+        .history
+        .iter()
+        .position(|w| distance_to(candidate, w, ctx.sectors) == Some(0))
     {
         let recency = (idx as f64) + 1.0;
-        score -= ROUTE_W_HISTORY / recency;
+        score -= w.history / recency;
     }
 
     // 6) Home bias. Past 50% of the trip, push toward home.
-    let total = ctx.start_date.days_until(ctx.target_date) as f64;
-    let elapsed = ctx.start_date.days_until(ctx.current_date) as f64;
-    if total > 0.0 {
-        let progress = elapsed / total;
-        if progress > 0.5
-            && let (Some(cand_coords), Some(home_coords)) =
-                (candidate.world.coordinates, sector_hex_of(ctx.home))
-        {
-            let dist_home =
-                calculate_hex_distance(cand_coords.0, cand_coords.1, home_coords.0, home_coords.1)
-                    as f64;
-            // Linear ramp: 0 at progress=0.5, full at progress>=1.0.
-            let ramp = ((progress - 0.5) / 0.5).clamp(0.0, 1.0);
-            score -= dist_home * ROUTE_W_HOME_BIAS * ramp;
-        }
+    let progress = ctx.progress();
+    if progress > 0.5
+        && let Some(dist_home) = distance_to(candidate, ctx.home, ctx.sectors)
+    {
+        let dist_home = dist_home as f64;
+        // Linear ramp: 0 at progress=0.5, full at progress>=1.0.
+        let ramp = ((progress - 0.5) / 0.5).clamp(0.0, 1.0);
+        score -= dist_home * w.home_bias * ramp;
     }
 
     // 7) Foreign-empire penalty. Imperial / Non-aligned / Client-state
@@ -165,7 +243,7 @@ pub fn score_candidate(
     //    Consulate, Solomani, Hivers, K'kree, etc.) gets a near-hard
     //    block. Worlds with no allegiance data are treated as friendly.
     if !is_allegiance_friendly(candidate.allegiance.as_deref()) {
-        score -= ROUTE_W_FOREIGN_EMPIRE;
+        score -= w.foreign_empire;
     }
 
     score
@@ -204,106 +282,111 @@ pub fn pick_next<'a>(
     candidates: &'a [Candidate],
     market: &AvailableGoodsTable,
     ctx: &RouteContext,
+) -> Option<&'a Candidate> {
+    let all: Vec<&Candidate> = candidates.iter().collect();
+    pick_among(&all, market, ctx)
+}
+
+/// [`pick_next`] over a chosen subset of the candidates, for strategies
+/// that narrow the field before scoring.
+pub fn pick_among<'a>(
+    candidates: &[&'a Candidate],
+    market: &AvailableGoodsTable,
+    ctx: &RouteContext,
 ) -> Option<&'a Candidate> {
     if candidates.is_empty() {
         return None;
     }
 
-    let total = ctx.start_date.days_until(ctx.target_date) as f64;
-    let elapsed = ctx.start_date.days_until(ctx.current_date) as f64;
-    let progress = if total > 0.0 { elapsed / total } else { 0.0 };
-
-    // Exclude home from the candidate pool while we're still in the first
-    // half of the trip — otherwise the planner returns home immediately on
-    // the first or second hop because home worlds are typically high-pop
-    // A-port and score very well. Falls back to all candidates if home is
-    // somehow the only option.
-    let is_home = |c: &&Candidate| -> bool {
-        matches!(
-            home_sector_of(c),
-            Some((_, hx, hy)) if hx == ctx.home.hex_x && hy == ctx.home.hex_y
-        )
-    };
-    let candidates_for_search: Vec<&Candidate> = if progress < 0.5 {
-        let filtered: Vec<&Candidate> = candidates.iter().filter(|c| !is_home(c)).collect();
-        if filtered.is_empty() {
-            candidates.iter().collect()
-        } else {
-            filtered
-        }
-    } else {
-        candidates.iter().collect()
-    };
-
     // Forced-home override. At or past target, if home itself is reachable,
-    // take it regardless of score. v1 is in-sector, so we match purely on
-    // hex coordinates.
-    if progress >= 1.0 {
-        for c in candidates {
-            if let Some((_, hx, hy)) = home_sector_of(c)
-                && hx == ctx.home.hex_x
-                && hy == ctx.home.hex_y
-            {
-                return Some(c);
-            }
-        }
+    // take it regardless of score. Home is matched by sector and hex.
+    if let Some(home) = forced_home(candidates, ctx) {
+        return Some(home);
     }
 
     // Head-home mode. Past `HEAD_HOME_THRESHOLD` of trip progress, the
     // trade-value score (which can be in the tens of millions) drowns out
     // the home-bias penalty, so we override it entirely: pick the candidate
     // with the smallest hex distance to home, breaking ties by score.
-    if progress >= HEAD_HOME_THRESHOLD
-        && let Some(home_coords) = sector_hex_of(ctx.home)
-    {
-        return candidates
-            .iter()
-            .filter_map(|c| {
-                c.world.coordinates.map(|(x, y)| {
-                    let dh = calculate_hex_distance(x, y, home_coords.0, home_coords.1);
-                    (c, dh)
-                })
-            })
-            .min_by(|a, b| {
-                a.1.cmp(&b.1).then_with(|| {
-                    let sa = score_candidate(a.0, market, ctx);
-                    let sb = score_candidate(b.0, market, ctx);
-                    sb.partial_cmp(&sa).unwrap_or(std::cmp::Ordering::Equal)
-                })
-            })
-            .map(|(c, _)| c);
+    if ctx.progress() >= ctx.weights.head_home_threshold {
+        return closest_to(candidates.iter().copied(), ctx.home, market, ctx);
     }
 
     // Normal mode: pick highest score among non-home candidates (in the
     // first half of the trip) or all candidates (in the second half).
-    candidates_for_search.into_iter().max_by(|a, b| {
+    search_pool(candidates, ctx).into_iter().max_by(|a, b| {
         let sa = score_candidate(a, market, ctx);
         let sb = score_candidate(b, market, ctx);
         sa.partial_cmp(&sb).unwrap_or(std::cmp::Ordering::Equal)
     })
 }
 
-// ---- helpers --------------------------------------------------------------
+/// The home world among `candidates` once the trip is at or past its
+/// target date, or `None`.
+pub fn forced_home<'a>(candidates: &[&'a Candidate], ctx: &RouteContext) -> Option<&'a Candidate> {
+    if ctx.progress() < 1.0 {
+        return None;
+    }
+    candidates.iter().copied().find(|c| is_at(c, ctx.home, ctx))
+}
 
-/// Return `(sector, hex_x, hex_y)` for a candidate world, if its
-/// coordinates are populated. We don't have a sector on the `World`
-/// itself, so it comes back empty — the caller compares against the
-/// home `WorldRef.sector` separately.
-///
-/// In v1 the simulator is in-sector, so all worlds share one sector;
-/// equality on sector is implicitly satisfied. We surface only the
-/// hex pair here and let the history-match code combine sectors at the
-/// `WorldRef` level.
-fn home_sector_of(candidate: &Candidate) -> Option<(String, i32, i32)> {
-    candidate
-        .world
-        .coordinates
-        .map(|(x, y)| (String::new(), x, y))
+/// The candidates worth scoring. Home is left out while we're still in
+/// the first half of the trip — otherwise the planner returns home
+/// immediately on the first or second hop because home worlds are
+/// typically high-pop A-port and score very well. Falls back to all
+/// candidates if home is somehow the only option.
+pub fn search_pool<'a>(candidates: &[&'a Candidate], ctx: &RouteContext) -> Vec<&'a Candidate> {
+    if ctx.progress() < 0.5 {
+        let filtered: Vec<&Candidate> = candidates
+            .iter()
+            .copied()
+            .filter(|c| !is_at(c, ctx.home, ctx))
+            .collect();
+        if !filtered.is_empty() {
+            return filtered;
+        }
+    }
+    candidates.to_vec()
+}
+
+/// The candidate with the smallest hex distance to `target`, ties broken
+/// by score. Candidates whose distance can't be worked out (an unplaced
+/// sector) are only taken if nothing else is reachable.
+pub fn closest_to<'a>(
+    candidates: impl IntoIterator<Item = &'a Candidate>,
+    target: &WorldRef,
+    market: &AvailableGoodsTable,
+    ctx: &RouteContext,
+) -> Option<&'a Candidate> {
+    candidates
+        .into_iter()
+        .map(|c| {
+            let d = distance_to(c, target, ctx.sectors).unwrap_or(i32::MAX);
+            (c, d)
+        })
+        .min_by(|a, b| {
+            a.1.cmp(&b.1).then_with(|| {
+                let sa = score_candidate(a.0, market, ctx);
+                let sb = score_candidate(b.0, market, ctx);
+                sb.partial_cmp(&sa).unwrap_or(std::cmp::Ordering::Equal)
+            })
+        })
+        .map(|(c, _)| c)
+}
+
+/// Whether `candidate` is the world `target` (same sector and hex).
+pub fn is_at(candidate: &Candidate, target: &WorldRef, ctx: &RouteContext) -> bool {
+    distance_to(candidate, target, ctx.sectors) == Some(0)
 }
 
-/// Convenience — extract `(hex_x, hex_y)` from a `WorldRef`.
-fn sector_hex_of(w: &WorldRef) -> Option<(i32, i32)> {
-    Some((w.hex_x, w.hex_y))
+// ---- helpers --------------------------------------------------------------
+
+/// Parsecs from `candidate` to the world `to`, across sector borders if
+/// `sectors` places both sectors. `None` if the candidate has no
+/// coordinates or the distance can't be worked out.
+fn distance_to(candidate: &Candidate, to: &WorldRef, sectors: &SectorOrigins) -> Option<i32> {
+    let (x, y) = candidate.world.coordinates?;
+    sectors.distance((&candidate.sector, x, y), (&to.sector, to.hex_x, to.hex_y))
 }
 
 /// Local copy of the `find_max_dm` helper used in `available_goods.rs`.
@@ -355,6 +438,18 @@ mod tests {
         w
     }
 
+    /// A candidate in the current sector with no allegiance or gas giant
+    /// data.
+    fn candidate(world: World, distance: i32) -> Candidate {
+        Candidate {
+            world,
+            sector: String::new(),
+            distance,
+            allegiance: None,
+            gas_giants: None,
+        }
+    }
+
     fn mk_world_ref(name: &str, uwp: &str, x: i32, y: i32) -> WorldRef {
         WorldRef {
             name: name.to_string(),
@@ -369,12 +464,15 @@ mod tests {
     fn ctx<'a>(home: &'a WorldRef, history: &'a [WorldRef]) -> RouteContext<'a> {
         RouteContext {
             home,
+            current: home,
             current_date: Date::new(0, 1105),
             start_date: Date::new(0, 1105),
             target_date: Date::new(100, 1105),
             jump: 2,
             fuel_cost_per_parsec: 10_000,
             history,
+            sectors: SectorOrigins::builtin(),
+            weights: RouteWeights::default(),
         }
     }
 
@@ -392,16 +490,8 @@ mod tests {
         // Past target date, home should win regardless of score.
         let home_ref = mk_world_ref("Home", "A788899-A", 5, 5);
 
-        let great = Candidate {
-            world: mk_world("Great", "A999999-F", 1, 1),
-            distance: 1,
-            allegiance: None,
-        };
-        let home = Candidate {
-            world: mk_world("Home", "A788899-A", 5, 5),
-            distance: 4,
-            allegiance: None,
-        };
+        let great = candidate(mk_world("Great", "A999999-F", 1, 1), 1);
+        let home = candidate(mk_world("Home", "A788899-A", 5, 5), 4);
 
         let market = AvailableGoodsTable::default();
         let mut c = ctx(&home_ref, &[]);
@@ -417,16 +507,8 @@ mod tests {
     fn closer_preferred_all_else_equal() {
         // Two identical worlds at different distances → closer wins.
         let home_ref = mk_world_ref("Home", "A788899-A", 0, 0);
-        let near = Candidate {
-            world: mk_world("Near", "C555555-7", 1, 0),
-            distance: 1,
-            allegiance: None,
-        };
-        let far = Candidate {
-            world: mk_world("Far", "C555555-7", 3, 0),
-            distance: 3,
-            allegiance: None,
-        };
+        let near = candidate(mk_world("Near", "C555555-7", 1, 0), 1);
+        let far = candidate(mk_world("Far", "C555555-7", 3, 0), 3);
         let market = AvailableGoodsTable::default();
         let c = ctx(&home_ref, &[]);
 
@@ -439,16 +521,8 @@ mod tests {
     fn higher_port_wins_all_else_equal() {
         // Same UWP-body except port: A vs E, identical distance.
         let home_ref = mk_world_ref("Home", "A788899-A", 0, 0);
-        let porta = Candidate {
-            world: mk_world("PortA", "A555555-7", 1, 0),
-            distance: 1,
-            allegiance: None,
-        };
-        let porte = Candidate {
-            world: mk_world("PortE", "E555555-7", 0, 1),
-            distance: 1,
-            allegiance: None,
-        };
+        let porta = candidate(mk_world("PortA", "A555555-7", 1, 0), 1);
+        let porte = candidate(mk_world("PortE", "E555555-7", 0, 1), 1);
         let market = AvailableGoodsTable::default();
         let c = ctx(&home_ref, &[]);
 
@@ -463,16 +537,8 @@ mod tests {
         let visited_ref = mk_world_ref("Visited", "C555555-7", 1, 0);
 
         // Same shape candidates; only difference is whether history has it.
-        let visited_cand = Candidate {
-            world: mk_world("Visited", "C555555-7", 1, 0),
-            distance: 1,
-            allegiance: None,
-        };
-        let fresh_cand = Candidate {
-            world: mk_world("Fresh", "C555555-7", 0, 1),
-            distance: 1,
-            allegiance: None,
-        };
+        let visited_cand = candidate(mk_world("Visited", "C555555-7", 1, 0), 1);
+        let fresh_cand = candidate(mk_world("Fresh", "C555555-7", 0, 1), 1);
 
         let market = AvailableGoodsTable::default();
         let history = vec![visited_ref];
@@ -497,16 +563,9 @@ mod tests {
         // off, but should lose once we're past 50% of the trip.
         let home_ref = mk_world_ref("Home", "A788899-A", 0, 0);
 
-        let near_home = Candidate {
-            world: mk_world("Near", "C555555-7", 1, 0),
-            distance: 1,
-            allegiance: None,
-        };
-        let far_with_a = Candidate {
-            world: mk_world("FarA", "A555555-7", 8, 0),
-            distance: 1, // distance from current location, not from home
-            allegiance: None,
-        };
+        let near_home = candidate(mk_world("Near", "C555555-7", 1, 0), 1);
+        // Distance is from the current location, not from home.
+        let far_with_a = candidate(mk_world("FarA", "A555555-7", 8, 0), 1);
 
         let market = AvailableGoodsTable::default();
 
@@ -514,16 +573,8 @@ mod tests {
         let mut c_early = ctx(&home_ref, &[]);
         c_early.current_date = Date::new(0, 1105); // progress = 0
         let cands_early = [
-            Candidate {
-                world: near_home.world.clone(),
-                distance: 1,
-                allegiance: None,
-            },
-            Candidate {
-                world: far_with_a.world.clone(),
-                distance: 1,
-                allegiance: None,
-            },
+            candidate(near_home.world.clone(), 1),
+            candidate(far_with_a.world.clone(), 1),
         ];
         let early = pick_next(&cands_early, &market, &c_early).unwrap();
         assert_eq!(
@@ -536,16 +587,8 @@ mod tests {
         let mut c_late = ctx(&home_ref, &[]);
         c_late.current_date = Date::new(95, 1105); // progress ~ 0.95
         let cands_late = [
-            Candidate {
-                world: near_home.world.clone(),
-                distance: 1,
-                allegiance: None,
-            },
-            Candidate {
-                world: far_with_a.world.clone(),
-                distance: 1,
-                allegiance: None,
-            },
+            candidate(near_home.world.clone(), 1),
+            candidate(far_with_a.world.clone(), 1),
         ];
         let late = pick_next(&cands_late, &market, &c_late).unwrap();
         assert_eq!(
@@ -586,6 +629,8 @@ mod tests {
             quantity_roll: 0,
             buy_price_roll: None,
             sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
         });
 
         // Non-Agricultural: atm 0-3, hydro 0-3, pop ≥ 6 (per
@@ -612,16 +657,8 @@ mod tests {
             neutral_world.get_trade_classes()
         );
 
-        let non_ag = Candidate {
-            world: non_ag_world,
-            distance: 1,
-            allegiance: None,
-        };
-        let neutral = Candidate {
-            world: neutral_world,
-            distance: 1,
-            allegiance: None,
-        };
+        let non_ag = candidate(non_ag_world, 1);
+        let neutral = candidate(neutral_world, 1);
 
         let c = ctx(&home_ref, &[]);
 
@@ -647,16 +684,8 @@ mod tests {
         // half of the trip we should NOT pick it — otherwise the trip ends
         // immediately. Should pick the non-home option.
         let home_ref = mk_world_ref("Home", "A999999-F", 5, 5);
-        let home = Candidate {
-            world: mk_world("Home", "A999999-F", 5, 5),
-            distance: 1,
-            allegiance: None,
-        };
-        let other = Candidate {
-            world: mk_world("Other", "C555555-7", 6, 5),
-            distance: 1,
-            allegiance: None,
-        };
+        let home = candidate(mk_world("Home", "A999999-F", 5, 5), 1);
+        let other = candidate(mk_world("Other", "C555555-7", 6, 5), 1);
         let market = AvailableGoodsTable::default();
         let mut c = ctx(&home_ref, &[]);
         c.current_date = Date::new(10, 1105); // ~10% progress
@@ -671,16 +700,8 @@ mod tests {
         // Same setup as above but past 50% progress — home becomes eligible
         // and (because A-port pop 9) should win on score.
         let home_ref = mk_world_ref("Home", "A999999-F", 5, 5);
-        let home = Candidate {
-            world: mk_world("Home", "A999999-F", 5, 5),
-            distance: 1,
-            allegiance: None,
-        };
-        let other = Candidate {
-            world: mk_world("Other", "C555555-7", 6, 5),
-            distance: 1,
-            allegiance: None,
-        };
+        let home = candidate(mk_world("Home", "A999999-F", 5, 5), 1);
+        let other = candidate(mk_world("Other", "C555555-7", 6, 5), 1);
         let market = AvailableGoodsTable::default();
         let mut c = ctx(&home_ref, &[]);
         c.current_date = Date::new(60, 1105); // 60% progress
@@ -697,16 +718,8 @@ mod tests {
         // home at (0, 0). A great trade world far from home (5 hexes) vs a
         // mediocre one near home (1 hex). The mediocre one must win.
         let home_ref = mk_world_ref("Home", "A788899-A", 0, 0);
-        let great_far = Candidate {
-            world: mk_world("Great", "A999999-F", 5, 0),
-            distance: 2,
-            allegiance: None,
-        };
-        let mediocre_near = Candidate {
-            world: mk_world("Near", "E555555-5", 1, 0),
-            distance: 2,
-            allegiance: None,
-        };
+        let great_far = candidate(mk_world("Great", "A999999-F", 5, 0), 2);
+        let mediocre_near = candidate(mk_world("Near", "E555555-5", 1, 0), 2);
         let market = AvailableGoodsTable::default();
         let mut c = ctx(&home_ref, &[]);
         c.current_date = Date::new(80, 1105); // 80% progress, past 0.75
@@ -716,6 +729,29 @@ mod tests {
         assert_eq!(chosen.world.name, "Near");
     }
 
+    #[test]
+    fn head_home_measures_distance_across_sector_borders() {
+        // Home is Deneb 0110. Spinward Marches 3210 is one parsec away over
+        // the border; Deneb 0510 is four parsecs away in the same sector.
+        let mut home_ref = mk_world_ref("Home", "A788899-A", 1, 10);
+        home_ref.sector = "Deneb".to_string();
+        let over_border = Candidate {
+            sector: "Spinward Marches".to_string(),
+            ..candidate(mk_world("Border", "E555555-5", 32, 10), 2)
+        };
+        let same_sector = Candidate {
+            sector: "Deneb".to_string(),
+            ..candidate(mk_world("Inside", "A999999-F", 5, 10), 2)
+        };
+        let market = AvailableGoodsTable::default();
+        let mut c = ctx(&home_ref, &[]);
+        c.current_date = Date::new(80, 1105);
+
+        let cands = [same_sector, over_border];
+        let chosen = pick_next(&cands, &market, &c).unwrap();
+        assert_eq!(chosen.world.name, "Border");
+    }
+
     #[test]
     fn allegiance_friendliness() {
         // Friendly: Imperial variants, Non-aligned, Client states, missing/empty.
@@ -750,14 +786,12 @@ mod tests {
         // lose to a mediocre Imperial world thanks to the heavy penalty.
         let home_ref = mk_world_ref("Home", "A788899-A", 0, 0);
         let foreign_great = Candidate {
-            world: mk_world("AslanA", "A999999-F", 1, 0),
-            distance: 1,
             allegiance: Some("AsT4".to_string()),
+            ..candidate(mk_world("AslanA", "A999999-F", 1, 0), 1)
         };
         let imperial_meh = Candidate {
-            world: mk_world("ImpC", "C555555-7", 2, 0),
-            distance: 2,
             allegiance: Some("Im".to_string()),
+            ..candidate(mk_world("ImpC", "C555555-7", 2, 0), 2)
         };
         let market = AvailableGoodsTable::default();
         let c = ctx(&home_ref, &[]);
@@ -777,9 +811,8 @@ mod tests {
         // a hard block.
         let home_ref = mk_world_ref("Home", "A788899-A", 0, 0);
         let only_foreign = Candidate {
-            world: mk_world("Zhodane", "C555555-7", 1, 0),
-            distance: 1,
             allegiance: Some("Zh".to_string()),
+            ..candidate(mk_world("Zhodane", "C555555-7", 1, 0), 1)
         };
         let market = AvailableGoodsTable::default();
         let c = ctx(&home_ref, &[]);
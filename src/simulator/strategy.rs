@@ -0,0 +1,502 @@
+//! Route-planning strategies for the ship simulator.
+//!
+//! A [`RouteStrategy`] picks the next destination from the worlds in jump
+//! range. `SimulationParams::route` selects one through
+//! [`RouteStrategyConfig`], so planning styles can be compared from the
+//! same params file without recompiling:
+//!
+//! - [`Greedy`] — the one-jump scorer in [`route::pick_next`].
+//! - [`Lookahead`] — scores two- or three-jump sequences and takes the
+//!   first leg of the best one.
+//! - [`FixedItinerary`] — follows a user-supplied list of stops, then
+//!   heads home.
+//! - [`MainRoute`] — keeps to class A/B ports on populous worlds, the
+//!   stops a main trade route runs through.
+//!
+//! All of them score with the run's [`RouteWeights`] and keep the
+//! greedy planner's end-of-trip behaviour: once the trip is past the
+//! head-home threshold every strategy spirals home the same way.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::economy::{DAYS_IN_PORT, DAYS_PER_JUMP};
+use crate::simulator::route::{
+    self, Candidate, RouteContext, closest_to, is_at, score_candidate, search_pool,
+};
+use crate::simulator::types::WorldRef;
+use crate::trade::PortCode;
+use crate::trade::available_goods::AvailableGoodsTable;
+use crate::trade::table::TradeTable;
+
+#[cfg(doc)]
+use crate::simulator::route::RouteWeights;
+#[cfg(doc)]
+use crate::simulator::types::Checkpoint;
+
+/// Most jumps a [`Lookahead`] strategy will plan ahead.
+pub const MAX_LOOKAHEAD_LEGS: u32 = 3;
+
+/// Picks the next destination for the simulator.
+pub trait RouteStrategy: Send {
+    /// How many jumps ahead the strategy wants to see. For anything over
+    /// 1 the executor fills an [`Onward`] map with the worlds reachable
+    /// from each candidate, to that depth.
+    fn horizon(&self) -> u32 {
+        1
+    }
+
+    /// Pick the next destination from `candidates`, or `None` if there is
+    /// nowhere to go. `market` is the current port's market.
+    fn pick<'a>(
+        &mut self,
+        candidates: &'a [Candidate],
+        onward: &Onward,
+        market: &AvailableGoodsTable,
+        ctx: &RouteContext,
+    ) -> Option<&'a Candidate>;
+
+    /// Where a stateful strategy has got to, for a [`Checkpoint`]. 0 for
+    /// strategies that keep no state between picks.
+    fn cursor(&self) -> u32 {
+        0
+    }
+
+    /// Restore a position reported by [`RouteStrategy::cursor`].
+    fn set_cursor(&mut self, _cursor: u32) {}
+}
+
+/// Which strategy a run uses, and its settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum RouteStrategyConfig {
+    /// Best-scoring world one jump away.
+    #[default]
+    Greedy,
+    /// Best sequence of `legs` jumps, later legs scaled by `discount`.
+    Lookahead {
+        #[serde(default = "default_lookahead_legs")]
+        legs: u32,
+        #[serde(default = "default_lookahead_discount")]
+        discount: f64,
+    },
+    /// Visit `stops` in order, then head home.
+    FixedItinerary { stops: Vec<WorldRef> },
+    /// Only class A/B ports with at least `min_population`.
+    MainRoute {
+        #[serde(default = "default_main_route_population")]
+        min_population: i32,
+    },
+}
+
+fn default_lookahead_legs() -> u32 {
+    2
+}
+
+fn default_lookahead_discount() -> f64 {
+    0.8
+}
+
+fn default_main_route_population() -> i32 {
+    6
+}
+
+impl RouteStrategyConfig {
+    /// Build the configured strategy, ready for a fresh run.
+    pub fn build(&self) -> Box<dyn RouteStrategy> {
+        match self {
+            RouteStrategyConfig::Greedy => Box::new(Greedy),
+            RouteStrategyConfig::Lookahead { legs, discount } => Box::new(Lookahead {
+                legs: (*legs).clamp(1, MAX_LOOKAHEAD_LEGS),
+                discount: *discount,
+            }),
+            RouteStrategyConfig::FixedItinerary { stops } => Box::new(FixedItinerary {
+                stops: stops.clone(),
+                next: 0,
+            }),
+            RouteStrategyConfig::MainRoute { min_population } => Box::new(MainRoute {
+                min_population: *min_population,
+            }),
+        }
+    }
+}
+
+/// The worlds in jump range of each world the planner may pass through,
+/// for strategies that look more than one jump ahead.
+#[derive(Default)]
+pub struct Onward {
+    reach: HashMap<(String, i32, i32), Vec<Candidate>>,
+}
+
+impl Onward {
+    /// An empty map; enough for one-jump strategies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the worlds in jump range of `sector` hex `(hex_x, hex_y)`.
+    pub fn insert(&mut self, sector: &str, hex: (i32, i32), candidates: Vec<Candidate>) {
+        self.reach
+            .insert((sector.to_string(), hex.0, hex.1), candidates);
+    }
+
+    /// Whether the worlds beyond `sector` hex `(hex_x, hex_y)` are known.
+    pub fn contains(&self, sector: &str, hex: (i32, i32)) -> bool {
+        self.reach.contains_key(&(sector.to_string(), hex.0, hex.1))
+    }
+
+    /// The worlds in jump range of `candidate`; empty if unknown.
+    pub fn from(&self, candidate: &Candidate) -> &[Candidate] {
+        candidate
+            .world
+            .coordinates
+            .and_then(|(x, y)| self.reach.get(&(candidate.sector.clone(), x, y)))
+            .map(|v| v.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+/// [`route::pick_next`] as a strategy.
+pub struct Greedy;
+
+impl RouteStrategy for Greedy {
+    fn pick<'a>(
+        &mut self,
+        candidates: &'a [Candidate],
+        _onward: &Onward,
+        market: &AvailableGoodsTable,
+        ctx: &RouteContext,
+    ) -> Option<&'a Candidate> {
+        route::pick_next(candidates, market, ctx)
+    }
+}
+
+/// Scores every sequence of up to `legs` jumps and takes the first leg
+/// of the best. Each leg is scored as the greedy planner would score it
+/// from the world before, one port stay and jump later; legs after the
+/// first are weighted by `discount` per jump. Their markets aren't known
+/// yet, so they are rolled as if legal goods only — an estimate for
+/// ranking, not the market the ship will find.
+pub struct Lookahead {
+    legs: u32,
+    discount: f64,
+}
+
+impl Lookahead {
+    /// Best discounted score of the `legs` jumps onward from `from`.
+    fn continuation(
+        &self,
+        from: &Candidate,
+        legs: u32,
+        onward: &Onward,
+        ctx: &RouteContext,
+        markets: &mut HashMap<(String, i32, i32), AvailableGoodsTable>,
+    ) -> f64 {
+        let next = onward.from(from);
+        if legs == 0 || next.is_empty() {
+            return 0.0;
+        }
+        let here = from.world_ref();
+        let mut history = Vec::with_capacity(ctx.history.len() + 1);
+        history.push(ctx.current.clone());
+        history.extend(ctx.history.iter().cloned());
+        let leg_ctx = RouteContext {
+            current: &here,
+            current_date: ctx.current_date.add_days(DAYS_IN_PORT + DAYS_PER_JUMP),
+            history: &history,
+            ..*ctx
+        };
+
+        let key = (here.sector.clone(), here.hex_x, here.hex_y);
+        let market = markets.entry(key).or_insert_with(|| {
+            AvailableGoodsTable::for_world(
+                TradeTable::global(),
+                &from.world.get_trade_classes(),
+                from.world.get_population(),
+                false,
+            )
+            .unwrap_or_default()
+        });
+        let leg_scores: Vec<f64> = next
+            .iter()
+            .map(|c| score_candidate(c, market, &leg_ctx))
+            .collect();
+
+        next.iter()
+            .zip(leg_scores)
+            .map(|(c, score)| {
+                score + self.discount * self.continuation(c, legs - 1, onward, &leg_ctx, markets)
+            })
+            .fold(f64::NEG_INFINITY, f64::max)
+    }
+}
+
+impl RouteStrategy for Lookahead {
+    fn horizon(&self) -> u32 {
+        self.legs
+    }
+
+    fn pick<'a>(
+        &mut self,
+        candidates: &'a [Candidate],
+        onward: &Onward,
+        market: &AvailableGoodsTable,
+        ctx: &RouteContext,
+    ) -> Option<&'a Candidate> {
+        // Heading home is a one-jump decision; leave it to the greedy
+        // planner's forced-home and head-home modes.
+        if self.legs <= 1 || ctx.progress() >= ctx.weights.head_home_threshold.min(1.0) {
+            return route::pick_next(candidates, market, ctx);
+        }
+        let all: Vec<&Candidate> = candidates.iter().collect();
+        let mut markets = HashMap::new();
+        search_pool(&all, ctx)
+            .into_iter()
+            .map(|c| {
+                let value = score_candidate(c, market, ctx)
+                    + self.discount
+                        * self.continuation(c, self.legs - 1, onward, ctx, &mut markets);
+                (c, value)
+            })
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(c, _)| c)
+    }
+}
+
+/// Visits `stops` in order. When the next stop is out of range it jumps
+/// to whichever world in range is closest to it. Once every stop has
+/// been reached, or the target date has passed, it heads home.
+pub struct FixedItinerary {
+    stops: Vec<WorldRef>,
+    /// Index of the next stop to reach.
+    next: usize,
+}
+
+impl RouteStrategy for FixedItinerary {
+    fn pick<'a>(
+        &mut self,
+        candidates: &'a [Candidate],
+        _onward: &Onward,
+        market: &AvailableGoodsTable,
+        ctx: &RouteContext,
+    ) -> Option<&'a Candidate> {
+        while self
+            .stops
+            .get(self.next)
+            .is_some_and(|stop| same_hex(stop, ctx.current, ctx))
+        {
+            self.next += 1;
+        }
+        let target = match self.stops.get(self.next) {
+            Some(stop) if ctx.progress() < 1.0 => stop,
+            _ => ctx.home,
+        };
+        closest_to(candidates.iter(), target, market, ctx)
+    }
+
+    fn cursor(&self) -> u32 {
+        self.next as u32
+    }
+
+    fn set_cursor(&mut self, cursor: u32) {
+        self.next = cursor as usize;
+    }
+}
+
+/// Keeps to the worlds a main trade route runs through: class A or B
+/// starports with population of at least `min_population`. Home is
+/// always allowed. If nothing in range qualifies it falls back to the
+/// greedy pick from everything.
+pub struct MainRoute {
+    min_population: i32,
+}
+
+impl MainRoute {
+    fn on_route(&self, candidate: &Candidate, ctx: &RouteContext) -> bool {
+        is_at(candidate, ctx.home, ctx)
+            || (matches!(candidate.world.port, PortCode::A | PortCode::B)
+                && candidate.world.get_population() >= self.min_population)
+    }
+}
+
+impl RouteStrategy for MainRoute {
+    fn pick<'a>(
+        &mut self,
+        candidates: &'a [Candidate],
+        _onward: &Onward,
+        market: &AvailableGoodsTable,
+        ctx: &RouteContext,
+    ) -> Option<&'a Candidate> {
+        let on_route: Vec<&Candidate> = candidates
+            .iter()
+            .filter(|c| self.on_route(c, ctx))
+            .collect();
+        if on_route.is_empty() {
+            return route::pick_next(candidates, market, ctx);
+        }
+        route::pick_among(&on_route, market, ctx)
+    }
+}
+
+/// Whether two `WorldRef`s are the same hex, across sector-name spellings.
+fn same_hex(a: &WorldRef, b: &WorldRef, ctx: &RouteContext) -> bool {
+    ctx.sectors
+        .distance((&a.sector, a.hex_x, a.hex_y), (&b.sector, b.hex_x, b.hex_y))
+        == Some(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sector::origins::SectorOrigins;
+    use crate::simulator::route::RouteWeights;
+    use crate::simulator::types::Date;
+    use crate::systems::world::World;
+    use crate::trade::ZoneClassification;
+
+    fn candidate(name: &str, uwp: &str, x: i32, y: i32, distance: i32) -> Candidate {
+        let mut world = World::from_uwp(name, uwp, false, true).expect("from_uwp");
+        world.coordinates = Some((x, y));
+        world.gen_trade_classes();
+        Candidate {
+            world,
+            sector: "Test".to_string(),
+            distance,
+            allegiance: None,
+            gas_giants: None,
+        }
+    }
+
+    fn world_ref(name: &str, x: i32, y: i32) -> WorldRef {
+        WorldRef {
+            name: name.to_string(),
+            uwp: "A788899-A".to_string(),
+            sector: "Test".to_string(),
+            hex_x: x,
+            hex_y: y,
+            zone: ZoneClassification::Green,
+        }
+    }
+
+    fn ctx<'a>(home: &'a WorldRef, current: &'a WorldRef) -> RouteContext<'a> {
+        RouteContext {
+            home,
+            current,
+            current_date: Date::new(0, 1105),
+            start_date: Date::new(0, 1105),
+            target_date: Date::new(100, 1105),
+            jump: 1,
+            fuel_cost_per_parsec: 10_000,
+            history: &[],
+            sectors: SectorOrigins::builtin(),
+            weights: RouteWeights::default(),
+        }
+    }
+
+    #[test]
+    fn config_defaults_fill_in_from_json() {
+        let config: RouteStrategyConfig = serde_json::from_str(r#"{"kind":"Lookahead"}"#).unwrap();
+        assert_eq!(
+            config,
+            RouteStrategyConfig::Lookahead {
+                legs: 2,
+                discount: 0.8
+            }
+        );
+        let config: RouteStrategyConfig = serde_json::from_str(r#"{"kind":"MainRoute"}"#).unwrap();
+        assert_eq!(config, RouteStrategyConfig::MainRoute { min_population: 6 });
+        let weights: RouteWeights = serde_json::from_str(r#"{"population":1.0}"#).unwrap();
+        assert_eq!(weights.population, 1.0);
+        assert_eq!(weights.port_a, RouteWeights::default().port_a);
+    }
+
+    #[test]
+    fn lookahead_trades_a_good_hop_for_a_better_second_one() {
+        let home = world_ref("Home", 30, 30);
+        let here = world_ref("Here", 10, 10);
+        let mut c = ctx(&home, &here);
+        // Let population drown out the randomly rolled markets the
+        // lookahead estimates later legs with.
+        c.weights.population = 1e12;
+
+        let candidates = vec![
+            candidate("Bright", "A788900-A", 11, 10, 1),
+            candidate("Plain", "A788600-A", 10, 11, 1),
+        ];
+        let mut onward = Onward::new();
+        onward.insert(
+            "Test",
+            (11, 10),
+            vec![candidate("Dead End", "X000000-0", 12, 10, 1)],
+        );
+        onward.insert(
+            "Test",
+            (10, 11),
+            vec![candidate("Capital", "A788900-A", 10, 12, 1)],
+        );
+        let market = AvailableGoodsTable::default();
+
+        let greedy = Greedy.pick(&candidates, &onward, &market, &c).unwrap();
+        assert_eq!(greedy.world.name, "Bright");
+        let mut lookahead = RouteStrategyConfig::Lookahead {
+            legs: 2,
+            discount: 0.8,
+        }
+        .build();
+        assert_eq!(lookahead.horizon(), 2);
+        let picked = lookahead.pick(&candidates, &onward, &market, &c).unwrap();
+        assert_eq!(picked.world.name, "Plain");
+    }
+
+    #[test]
+    fn itinerary_follows_stops_then_heads_home() {
+        let home = world_ref("Home", 1, 1);
+        let stops = vec![world_ref("First", 5, 1), world_ref("Second", 5, 5)];
+        let mut itinerary = RouteStrategyConfig::FixedItinerary { stops }.build();
+        let onward = Onward::new();
+        let market = AvailableGoodsTable::default();
+        let candidates = vec![
+            candidate("East", "C555555-5", 4, 1, 1),
+            candidate("South", "C555555-5", 5, 4, 1),
+            candidate("West", "C555555-5", 2, 1, 1),
+        ];
+
+        let at = world_ref("Start", 3, 1);
+        let picked = itinerary.pick(&candidates, &onward, &market, &ctx(&home, &at));
+        assert_eq!(picked.unwrap().world.name, "East");
+
+        // Arriving at the first stop moves on to the second.
+        let at = world_ref("First", 5, 1);
+        let picked = itinerary.pick(&candidates, &onward, &market, &ctx(&home, &at));
+        assert_eq!(picked.unwrap().world.name, "South");
+
+        // With every stop made, head home.
+        let at = world_ref("Second", 5, 5);
+        let picked = itinerary.pick(&candidates, &onward, &market, &ctx(&home, &at));
+        assert_eq!(picked.unwrap().world.name, "West");
+    }
+
+    #[test]
+    fn main_route_keeps_to_good_ports() {
+        let home = world_ref("Home", 30, 30);
+        let here = world_ref("Here", 10, 10);
+        let c = ctx(&home, &here);
+        let onward = Onward::new();
+        let market = AvailableGoodsTable::default();
+        let candidates = vec![
+            candidate("Backwater", "C788900-8", 11, 10, 1),
+            candidate("Waystation", "B788700-A", 10, 11, 1),
+        ];
+
+        let greedy = Greedy.pick(&candidates, &onward, &market, &c).unwrap();
+        assert_eq!(greedy.world.name, "Backwater");
+        let mut main = RouteStrategyConfig::MainRoute { min_population: 6 }.build();
+        let picked = main.pick(&candidates, &onward, &market, &c).unwrap();
+        assert_eq!(picked.world.name, "Waystation");
+
+        // Nothing on the route in range: fall back to the greedy pick.
+        let picked = main.pick(&candidates[..1], &onward, &market, &c).unwrap();
+        assert_eq!(picked.world.name, "Backwater");
+    }
+}
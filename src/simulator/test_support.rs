@@ -0,0 +1,95 @@
+//! Fixtures shared by the simulator's unit tests.
+
+#[cfg(feature = "backend")]
+use crate::sector::Sector;
+use crate::simulator::types::{Date, SimulationParams, WorldRef};
+use crate::trade::Ship;
+
+/// Params for a 120-day voyage from `home`, starting 001-1105 with
+/// 1 MCr in a 200-ton jump-2 trader of four crew.
+pub(crate) fn voyage_from(home: WorldRef) -> SimulationParams {
+    SimulationParams {
+        ship: Ship {
+            name: "Test".into(),
+            broker_skill: 2,
+            steward_skill: 1,
+            leadership_skill: 1,
+            deception_skill: 0,
+            admin_skill: 0,
+            weapons: 2,
+            hull_tons: 200,
+
+            cargo_capacity: 80,
+            crew_staterooms: 4,
+            passenger_staterooms: 6,
+            low_berths: 4,
+            crew_size: 4,
+            jump_rating: 2,
+            fuel_tons: 0,
+            mortgage_per_period: 0,
+            maintenance_per_period: 30_000,
+            salary_per_period: 12_000,
+            crew: Vec::new(),
+        },
+        fuel_cost_per_parsec: 5_000,
+        fuel: None,
+        crew_profit_share: 0.1,
+        starting_budget: 1_000_000,
+        home_world: home,
+        start_date: Date::new(1, 1105),
+        target_completion_date: Date::new(120, 1105),
+        illegal_goods: false,
+        planetary_broker_skill: 2,
+        seed: None,
+        route: Default::default(),
+        route_weights: Default::default(),
+        cargo_plan: None,
+        accept_charters: false,
+        markets: Default::default(),
+        incidents: None,
+        condition: None,
+    }
+}
+
+/// [`voyage_from`] a class A port near the middle of `sector`, for
+/// offline runs.
+#[cfg(feature = "backend")]
+pub(crate) fn offline_params(sector: &Sector, seed: Option<u64>) -> SimulationParams {
+    let home = sector
+        .hexes
+        .iter()
+        .find(|h| h.uwp.starts_with('A') && (14..=18).contains(&h.x) && (18..=22).contains(&h.y))
+        .or_else(|| sector.hexes.iter().find(|h| h.uwp.starts_with('A')))
+        .expect("a dense sector has a class A port");
+    SimulationParams {
+        seed,
+        ..voyage_from(WorldRef {
+            name: home.name.clone(),
+            uwp: home.uwp.clone(),
+            sector: sector.name.clone(),
+            hex_x: home.x as i32,
+            hex_y: home.y as i32,
+            zone: home.zone,
+        })
+    }
+}
+
+/// Run `params` offline against `sector`, returning the step stream as
+/// JSON (for exact comparison) and the result.
+#[cfg(feature = "backend")]
+pub(crate) async fn run_offline(
+    sector: &Sector,
+    params: SimulationParams,
+) -> (Vec<String>, crate::simulator::types::SimulationResult) {
+    use crate::simulator::executor::run_simulation;
+    use crate::simulator::world_source::SectorFiles;
+
+    let mut source = SectorFiles::from(sector.clone());
+    let mut steps = Vec::new();
+    let result = run_simulation(params, &mut source, |s| {
+        steps.push(serde_json::to_string(&s).unwrap())
+    })
+    .await
+    .expect("offline simulation should complete");
+    (steps, result)
+}
@@ -0,0 +1,157 @@
+//! Passenger, freight and mail traffic shared between ships.
+//!
+//! A lone ship rolls fresh traffic on every route it loads for, and
+//! never flies the same route twice in a week, so it never sees the same
+//! week's traffic twice. A fleet can: when two ships load for the same
+//! destination within the week, the second one finds only what the first
+//! left behind. The [`TrafficLedger`] remembers, per route, the week's
+//! offer and what is still waiting to be carried.
+//!
+//! Routes are keyed by origin and destination market ([`MarketKey`]),
+//! and days are the voyage's Imperial day count, as in the
+//! [`MarketLedger`](crate::trade::market::MarketLedger).
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::economy::DAYS_PER_WEEK;
+use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::market::MarketKey;
+use crate::trade::ship_manifest::ShipManifest;
+
+/// Days a route's traffic waits before a fresh week's is rolled.
+pub const TRAFFIC_DAYS: i64 = DAYS_PER_WEEK as i64;
+
+/// What is left of a route's traffic, and the day it was first offered.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Offer {
+    day: i64,
+    left: AvailablePassengers,
+}
+
+/// Every route's traffic still waiting for a ship.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrafficLedger {
+    routes: BTreeMap<MarketKey, BTreeMap<MarketKey, Offer>>,
+}
+
+impl TrafficLedger {
+    /// An empty ledger: every route rolls fresh traffic.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether no route has traffic waiting.
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+
+    /// The traffic still waiting on `from` → `to` on `day`, if the
+    /// route was offered within the last [`TRAFFIC_DAYS`].
+    pub fn offered(
+        &self,
+        from: &MarketKey,
+        to: &MarketKey,
+        day: i64,
+    ) -> Option<&AvailablePassengers> {
+        self.routes
+            .get(from)?
+            .get(to)
+            .filter(|offer| live(offer.day, day))
+            .map(|offer| &offer.left)
+    }
+
+    /// Record what a ship left of the traffic on `from` → `to` on `day`.
+    /// A route still within its week keeps the day it was first offered,
+    /// so leftovers don't outlive the week. Routes whose week is over are
+    /// dropped.
+    pub fn record(
+        &mut self,
+        from: &MarketKey,
+        to: &MarketKey,
+        day: i64,
+        left: AvailablePassengers,
+    ) {
+        self.routes.retain(|_, routes| {
+            routes.retain(|_, offer| live(offer.day, day));
+            !routes.is_empty()
+        });
+        let routes = self.routes.entry(from.clone()).or_default();
+        let day = routes.get(to).map_or(day, |offer| offer.day);
+        routes.insert(to.clone(), Offer { day, left });
+    }
+}
+
+/// Whether traffic offered on `offered` is still waiting on `day`.
+fn live(offered: i64, day: i64) -> bool {
+    (offered..offered + TRAFFIC_DAYS).contains(&day)
+}
+
+/// The part of `offer` the `manifest` didn't take aboard: passengers and
+/// mail by count, freight by the lots it chose.
+pub fn left_after(offer: &AvailablePassengers, manifest: &ShipManifest) -> AvailablePassengers {
+    AvailablePassengers {
+        high: (offer.high - manifest.high_passengers).max(0),
+        medium: (offer.medium - manifest.medium_passengers).max(0),
+        basic: (offer.basic - manifest.basic_passengers).max(0),
+        low: (offer.low - manifest.low_passengers).max(0),
+        freight_lots: offer
+            .freight_lots
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !manifest.freight_lot_indices.contains(i))
+            .map(|(_, lot)| lot.clone())
+            .collect(),
+        mail_containers: (offer.mail_containers - manifest.mail_containers).max(0),
+        ..offer.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade::available_passengers::FreightLot;
+
+    fn lot(size: i32) -> FreightLot {
+        FreightLot { size, size_roll: 0 }
+    }
+
+    #[test]
+    fn leftovers_wait_out_the_week_they_were_offered() {
+        let (a, b) = (
+            MarketKey::new("Spinward Marches", 19, 10),
+            MarketKey::new("Spinward Marches", 20, 10),
+        );
+        let offer = AvailablePassengers {
+            high: 3,
+            low: 5,
+            freight_lots: vec![lot(10), lot(20), lot(30)],
+            mail_containers: 2,
+            ..AvailablePassengers::default()
+        };
+        let manifest = ShipManifest {
+            high_passengers: 2,
+            low_passengers: 6,
+            freight_lot_indices: vec![0, 2],
+            mail_containers: 2,
+            ..ShipManifest::default()
+        };
+        let left = left_after(&offer, &manifest);
+        assert_eq!((left.high, left.low, left.mail_containers), (1, 0, 0));
+        assert_eq!(left.freight_lots, vec![lot(20)]);
+
+        let mut ledger = TrafficLedger::new();
+        assert!(ledger.offered(&a, &b, 100).is_none());
+        ledger.record(&a, &b, 100, left.clone());
+        assert_eq!(ledger.offered(&a, &b, 103), Some(&left));
+        assert!(ledger.offered(&b, &a, 103).is_none());
+
+        // A second pick mid-week doesn't stretch the week.
+        ledger.record(&a, &b, 105, AvailablePassengers::default());
+        assert!(ledger.offered(&a, &b, 106).is_some());
+        assert!(ledger.offered(&a, &b, 107).is_none());
+        ledger.record(&b, &a, 112, AvailablePassengers::default());
+        assert!(ledger.offered(&a, &b, 105).is_none());
+    }
+}
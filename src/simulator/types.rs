@@ -6,8 +6,19 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::simulator::condition::{ConditionModel, ShipCondition, ShipSystem};
+use crate::simulator::fuel::{FuelModel, FuelTank};
+use crate::simulator::incident_table::IncidentTable;
+use crate::simulator::route::RouteWeights;
+use crate::simulator::strategy::RouteStrategyConfig;
+use crate::simulator::traffic::TrafficLedger;
+use crate::systems::world::World;
 use crate::trade::Ship;
 use crate::trade::ZoneClassification;
+use crate::trade::cargo_plan::RiskTolerance;
+use crate::trade::crew::CrewMember;
+use crate::trade::market::MarketLedger;
+use crate::trade::ship_manifest::ShipManifest;
 
 /// Imperial date: day in `0..=364` plus year. Day `365` wraps to
 /// `(0, year + 1)`.
@@ -55,12 +66,24 @@ impl Date {
         b - a
     }
 
+    /// Days since day 000 of year 0, for counting days across years.
+    pub fn day_number(self) -> i64 {
+        self.year as i64 * DAYS_PER_YEAR as i64 + self.day as i64
+    }
+
     /// Format as `"DDD-YYYY"` with the day zero-padded to three digits.
     pub fn format(self) -> String {
         format!("{:03}-{:04}", self.day, self.year)
     }
 }
 
+/// 001-1105, the simulator form's default start date.
+impl Default for Date {
+    fn default() -> Self {
+        Date::new(1, 1105)
+    }
+}
+
 /// Lightweight world identifier carried in steps and history.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WorldRef {
@@ -94,8 +117,17 @@ pub struct SimulationParams {
     /// the owner's take).
     pub ship: Ship,
 
-    /// Fuel cost in credits per parsec jumped.
+    /// Fuel cost in credits per parsec jumped. Ignored when `fuel` is set.
     pub fuel_cost_per_parsec: i64,
+    /// Model the ship's fuel tankage, refuelling and misjumps instead of
+    /// charging `fuel_cost_per_parsec`. `None` keeps the flat charge.
+    #[serde(default)]
+    pub fuel: Option<FuelModel>,
+    /// Track the ship's damage and annual maintenance from port to port,
+    /// with repairs limited by each starport's yard. `None` charges an
+    /// incident's repairs on the spot and forgets them.
+    #[serde(default)]
+    pub condition: Option<ConditionModel>,
     /// Fraction of profit shared with the crew, in `0.0..=1.0`.
     pub crew_profit_share: f32,
     /// Starting cash budget in credits.
@@ -121,6 +153,51 @@ pub struct SimulationParams {
     /// frontier rubes.
     #[serde(default = "default_planetary_broker_skill")]
     pub planetary_broker_skill: i16,
+
+    /// Seed for every roll the voyage makes: markets, passengers and
+    /// freight, incidents and the planner's choices that follow from them.
+    /// The same params with the same seed replay the same step stream
+    /// (given the same world data). `None` picks a fresh seed, which is
+    /// reported back in [`SimulationResult::seed`] so the run can be
+    /// replayed.
+    #[serde(default)]
+    pub seed: Option<u64>,
+
+    /// How the route planner picks each next world — greedy by default.
+    #[serde(default)]
+    pub route: RouteStrategyConfig,
+
+    /// Scoring weights for the route planner. Any weight left out takes
+    /// its `route::ROUTE_W_*` default.
+    #[serde(default)]
+    pub route_weights: RouteWeights,
+
+    /// Plan each purchase with [`plan_cargo`] at this risk tolerance,
+    /// weighing every good's expected sale price against its spread.
+    /// `None` keeps the greedy buyer that takes the best rolled prices
+    /// first.
+    ///
+    /// [`plan_cargo`]: crate::trade::cargo_plan::plan_cargo
+    #[serde(default)]
+    pub cargo_plan: Option<RiskTolerance>,
+
+    /// Take a whole-ship charter when one is offered at a port where the
+    /// hold is otherwise empty. A chartered leg carries no passengers,
+    /// freight, mail or speculative cargo of the ship's own.
+    #[serde(default)]
+    pub accept_charters: bool,
+
+    /// Market memory the voyage starts from, keyed on Imperial days —
+    /// the trade server's ledger, say. Empty starts every market at its
+    /// baseline.
+    #[serde(default)]
+    pub markets: MarketLedger,
+
+    /// The incident table rolled on when the ship fails to avoid trouble
+    /// in port — a referee's own, read with [`IncidentTable::from_json`].
+    /// `None` rolls on [`IncidentTable::builtin`].
+    #[serde(default)]
+    pub incidents: Option<IncidentTable>,
 }
 
 fn default_planetary_broker_skill() -> i16 {
@@ -201,6 +278,24 @@ pub enum Action {
         /// Revenue that will be paid on arrival.
         revenue_pending: i64,
     },
+    /// Loaded mail containers in the cargo hold.
+    LoadMail {
+        /// Mail containers loaded.
+        containers: i32,
+        /// Tons of hold space the containers take.
+        tons: i32,
+        /// Payment that will be made on arrival.
+        revenue_pending: i64,
+    },
+    /// Hired the whole ship out for the coming leg.
+    Charter {
+        /// Length of the charter in weeks.
+        weeks: i32,
+        /// Charter fee per week.
+        weekly_rate: i64,
+        /// Fee that will be paid on arrival.
+        revenue_pending: i64,
+    },
     /// Boarded passengers.
     BoardPax {
         /// High-passage passengers boarded.
@@ -231,9 +326,123 @@ pub enum Action {
         to: WorldRef,
         /// Distance jumped, in parsecs.
         distance: i32,
-        /// Fuel cost paid for the jump.
+        /// Fuel cost paid for the jump. Zero under a fuel model, where
+        /// fuel is paid for when it's bought.
         fuel_cost: i64,
     },
+
+    // ---- Port charges -----------------------------------------------------
+    /// Paid the starport's berthing fee on arrival.
+    PayBerthing {
+        /// Hull tonnage the fee was charged on.
+        hull_tons: i32,
+        fee: i64,
+    },
+    /// Paid the import tariff on goods just sold.
+    PayImportTariff {
+        good: String,
+        /// Sale proceeds the tariff was levied on.
+        value: i64,
+        rate_pct: i64,
+        tariff: i64,
+    },
+    /// Paid the export tariff on goods just bought.
+    PayExportTariff {
+        good: String,
+        /// Purchase cost the tariff was levied on.
+        value: i64,
+        rate_pct: i64,
+        tariff: i64,
+    },
+
+    // ---- Customs ----------------------------------------------------------
+    // Only rolled when the ship arrives carrying contraband.
+    /// Customs check on arrival. `concealment_*` are set when the ship
+    /// was searched.
+    CustomsInspection {
+        inspection_roll: i32,
+        inspection_dm: i32,
+        inspected: bool,
+        concealment_roll: Option<i32>,
+        concealment_dm: Option<i32>,
+        /// Whether the search turned up the contraband.
+        found: bool,
+    },
+    /// Customs confiscated the contraband and fined the ship.
+    ContrabandSeized {
+        /// Per-good `(name, tons)` confiscated.
+        seized: Vec<(String, i32)>,
+        /// Sum of `buy_cost * tons` — sunk; not refunded.
+        buy_cost_sunk: i64,
+        fine: i64,
+    },
+    /// The ship was impounded after a badly failed search.
+    ShipImpounded { weeks_lost: u32, release_fee: i64 },
+
+    // ---- Fuel variants -----------------------------------------------------
+    // Only emitted when `SimulationParams::fuel` is set.
+    /// Bought fuel at the starport.
+    BuyFuel {
+        tons: u32,
+        refined: bool,
+        price_per_ton: i64,
+        cost: i64,
+    },
+    /// Skimmed fuel from a gas giant, spending a day in system.
+    SkimGasGiant {
+        tons: u32,
+        /// Whether a fuel processor refined it.
+        refined: bool,
+        /// Gas giants in the system.
+        gas_giants: u8,
+    },
+    /// Scooped water from the main world, spending a day in system.
+    ScoopWater {
+        tons: u32,
+        /// Whether a fuel processor refined it.
+        refined: bool,
+    },
+    /// Misjump check for a jump that burned unrefined fuel, or was made
+    /// on damaged drives. Recorded at the destination, before the
+    /// `Arrive`.
+    MisjumpCheck {
+        roll: i32,
+        /// DM from the ship's condition, added to the roll.
+        #[serde(default)]
+        dm: i32,
+        misjumped: bool,
+        /// Weeks adrift before reaching the destination.
+        weeks_lost: u32,
+        /// Drive repairs paid on arrival.
+        repair_cost: i64,
+    },
+
+    // ---- Condition variants ------------------------------------------------
+    // Only emitted when `SimulationParams::condition` is set.
+    /// The ship took hits, from an incident or a breakdown.
+    ShipDamaged {
+        /// The incident's name, or "Breakdown".
+        cause: String,
+        /// Where each hit landed.
+        hits: Vec<ShipSystem>,
+    },
+    /// Annual maintenance done at a shipyard.
+    AnnualMaintenance {
+        weeks: u32,
+        /// Maintenance intervals since the last overhaul; above 1 means
+        /// overhauls were missed.
+        missed: u32,
+    },
+    /// Repairs made at this port.
+    Repair {
+        /// Per-system `(system, hits)` repaired.
+        repaired: Vec<(ShipSystem, u32)>,
+        cost: i64,
+        /// Days in the yard.
+        days: u32,
+        /// Hits left for a better port, or a fuller purse.
+        deferred: u32,
+    },
     /// Paid the periodic maintenance + crew salary + mortgage tick.
     PayPeriodic {
         /// Maintenance paid this tick.
@@ -262,7 +471,8 @@ pub enum Action {
     },
 
     // ---- Incident variants -------------------------------------------------
-    // The five incident kinds plus a successful-avoidance variant. All
+    // The five standard incident kinds, the catch-all for any other entry
+    // of the incident table, plus a successful-avoidance variant. All
     // share `avoidance_*` and `table_*` roll fields so the log can show
     // the saving throw inline. The frontend's renderer skips
     // `IncidentAvoided` rather than emit a row for it.
@@ -273,6 +483,9 @@ pub enum Action {
         port_mod: i32,
         zone_mod: i32,
         law_mod: i32,
+        /// DM from the ship's condition; included in `modifier_total`.
+        #[serde(default)]
+        condition_dm: i32,
         modifier_total: i32,
         avoidance_total: i32,
     },
@@ -287,6 +500,9 @@ pub enum Action {
         table_modifier_total: i32,
         table_total: i32,
         weapons: i16,
+        /// Best Gunner skill among the active crew; 0 without a roster.
+        #[serde(default)]
+        gunner: i16,
         /// Total tons removed from the manifest.
         cargo_lost_tons: i32,
         /// Per-good `(name, tons_lost)` for the log.
@@ -322,6 +538,10 @@ pub enum Action {
         table_modifier_total: i32,
         table_total: i32,
         weeks_lost: u32,
+        /// The crewmember lost, as they left the roster — dead or gone —
+        /// when the ship keeps one. Their skills and salary go with them.
+        #[serde(default)]
+        crewmember: Option<CrewMember>,
     },
     /// Mechanical accident; pure credit cost.
     IncidentAccident {
@@ -346,6 +566,31 @@ pub enum Action {
         fine_credits: i64,
         weeks_lost: u32,
     },
+    /// Any other incident from the table — one of a referee's own.
+    Incident {
+        avoidance_roll: i32,
+        leadership: i16,
+        avoidance_modifier_total: i32,
+        avoidance_total: i32,
+        table_roll: i32,
+        table_modifier_total: i32,
+        table_total: i32,
+        /// The table entry's name.
+        name: String,
+        /// The table entry's narrative text.
+        text: String,
+        /// Credits lost; negative for a windfall.
+        credits_lost: i64,
+        repair_cost: i64,
+        weeks_lost: u32,
+        cargo_lost_tons: i32,
+        /// Per-good `(name, tons_lost)` for the log.
+        cargo_lost_breakdown: Vec<(String, i32)>,
+        /// Sum of `buy_cost * tons_lost` — sunk; not refunded.
+        buy_cost_sunk: i64,
+        /// The crewmember lost, if the incident took one.
+        crewmember: Option<CrewMember>,
+    },
 
     /// Terminal: the end-of-port-stay budget check failed. The run ends
     /// here; a help message will reach the home port after `rescue_eta_days`.
@@ -388,6 +633,110 @@ pub struct SimulationResult {
     /// Date a rescue is expected to arrive (one week per 4 parsecs of the
     /// path actually travelled, rounded up).
     pub rescue_arrives_on: Option<Date>,
+    /// Seed the run used — `SimulationParams::seed` if one was given,
+    /// otherwise the one picked for it. Feed it back to replay the run.
+    #[serde(default)]
+    pub seed: u64,
+}
+
+/// The complete state of a voyage between two port calls.
+///
+/// The executor can be suspended after any port call, its state stored,
+/// and the voyage resumed later from it — or resumed several times to
+/// fork alternative futures from the same point. Resuming an unmodified
+/// checkpoint continues exactly as the uninterrupted run would have,
+/// roll for roll; [`Checkpoint::reseeded`] gives a fork its own dice.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// The voyage's parameters, unchanged from the start of the run but
+    /// for the ship's roster, which loses anyone who dies or leaves on
+    /// the way.
+    pub params: SimulationParams,
+    /// Seed of the voyage's random stream.
+    pub seed: u64,
+    /// How far into that stream the voyage has rolled, in 32-bit words.
+    pub rng_word_pos: u64,
+    /// Port calls made so far, counting the one at the home world.
+    pub port_calls: u32,
+    /// Whether the voyage has ended; a finished checkpoint only holds
+    /// the final tally.
+    pub finished: bool,
+
+    /// Current cash budget.
+    pub budget: i64,
+    /// Cargo carried into the next port.
+    pub manifest: ShipManifest,
+    /// Current date.
+    pub current_date: Date,
+
+    /// Days since periodic costs were last paid.
+    pub days_since_payment: u32,
+    /// Periodic payments made so far.
+    pub periods_paid: u32,
+    /// Mortgage paid so far, kept out of the crew's profit share.
+    pub total_mortgage_paid: i64,
+
+    /// The world the ship is at.
+    pub current_world: World,
+    /// Where that world is.
+    pub current_ref: WorldRef,
+    /// Its allegiance, `None` at the home world.
+    pub current_allegiance: Option<String>,
+    /// Its gas giants, when the world data listed them.
+    #[serde(default)]
+    pub current_gas_giants: Option<u8>,
+    /// Fuel aboard, when the voyage models fuel.
+    #[serde(default)]
+    pub fuel: FuelTank,
+    /// Damage aboard and the last overhaul, when the voyage tracks the
+    /// ship's condition.
+    #[serde(default)]
+    pub condition: ShipCondition,
+    /// Every market the voyage has traded in, starting from
+    /// `params.markets`.
+    #[serde(default)]
+    pub markets: MarketLedger,
+    /// This week's passengers, freight and mail left waiting on each
+    /// route the voyage has loaded for. Only a fleet's ships ever come
+    /// back to one.
+    #[serde(default)]
+    pub traffic: TrafficLedger,
+    /// Recently visited worlds, newest first.
+    pub history: Vec<WorldRef>,
+    /// Jumps made so far.
+    pub jumps_taken: u32,
+    /// Parsecs jumped so far, which sets how long a rescue takes.
+    pub total_parsecs_jumped: u32,
+    /// Position of a stateful route planner (the next stop of a fixed
+    /// itinerary); 0 for the others.
+    pub route_cursor: u32,
+
+    /// Whether the next port stay can roll an incident.
+    pub incident_eligible: bool,
+    /// True once the budget has gone negative.
+    pub went_negative: bool,
+    /// True once the ship is back at the home world.
+    pub returned_home: bool,
+    /// False once the voyage has aborted.
+    pub completed_normally: bool,
+    /// True once the ship is stranded for lack of funds.
+    pub marooned: bool,
+    /// World where the ship was marooned.
+    pub marooned_at: Option<WorldRef>,
+    /// Date the ship was marooned.
+    pub marooned_on: Option<Date>,
+    /// Date a rescue is expected to arrive.
+    pub rescue_arrives_on: Option<Date>,
+}
+
+impl Checkpoint {
+    /// The same point in the voyage with a fresh random stream from
+    /// `seed`, so a resumed fork rolls differently from the original.
+    pub fn reseeded(mut self, seed: u64) -> Checkpoint {
+        self.seed = seed;
+        self.rng_word_pos = 0;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -454,6 +803,13 @@ mod tests {
         assert_eq!(a.days_until(b), 10);
     }
 
+    #[test]
+    fn day_number_counts_across_years() {
+        let a = Date::new(360, 1105);
+        let b = Date::new(5, 1106);
+        assert_eq!(b.day_number() - a.day_number(), a.days_until(b));
+    }
+
     #[test]
     fn format_zero_pads_day() {
         assert_eq!(Date::new(0, 1105).format(), "000-1105");
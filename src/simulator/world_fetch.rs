@@ -1,11 +1,16 @@
 //! Server-side TravellerMap client for the simulator.
 //!
-//! In v1 the simulator stays inside one sector. To build the candidate
-//! list for a single jump we enumerate every hex within `jump` parsecs
-//! of the current location, fetch each from `https://travellermap.com/`,
-//! and turn the populated hexes into [`Candidate`]s. Empty hexes (404
+//! To build the candidate list for a single jump we enumerate every hex
+//! within `jump` parsecs of the current location — across sector borders
+//! wherever the sector-origin table places the neighbours — fetch each
+//! from `https://travellermap.com/`, and turn the populated hexes into
+//! [`Candidate`]s. [`WorldCache::load_sector_origins`] swaps the built-in
+//! origin table for TravellerMap's full universe listing. Empty hexes (404
 //! responses) are cached as `None` so a re-visit doesn't pay the
 //! network cost again.
+//!
+//! [`WorldCache`] is the network-backed [`WorldSource`]; the offline
+//! sources live in [`world_source`](crate::simulator::world_source).
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -14,16 +19,12 @@ use futures_util::future::join_all;
 use serde::Deserialize;
 use tokio::sync::Semaphore;
 
+use crate::sector::files::t5_base_codes;
+use crate::sector::origins::SectorOrigins;
 use crate::simulator::route::Candidate;
+use crate::simulator::world_source::{SourcedWorld, WorldSource, hexes_within};
 use crate::systems::world::World;
 use crate::trade::ZoneClassification;
-use crate::util::calculate_hex_distance;
-
-/// Sector-relative hex column range. TravellerMap subsectors are 8x10 each
-/// and a sector is 4x4 subsectors, giving 32 columns and 40 rows.
-const SECTOR_HEX_X_RANGE: std::ops::RangeInclusive<i32> = 1..=32;
-/// Sector-relative hex row range. See [`SECTOR_HEX_X_RANGE`].
-const SECTOR_HEX_Y_RANGE: std::ops::RangeInclusive<i32> = 1..=40;
 
 /// Max number of concurrent TravellerMap fetches. The public service
 /// resets connections aggressively when we hammer it with > ~10
@@ -59,6 +60,10 @@ struct WorldEntry {
     zone: Option<String>,
     #[serde(default)]
     allegiance: Option<String>,
+    #[serde(default, rename = "PBG")]
+    pbg: Option<String>,
+    #[serde(default)]
+    bases: Option<String>,
 }
 
 /// Wrapper for `/data/{sector}/{hex}` responses. The endpoint always
@@ -70,18 +75,13 @@ struct WorldsEnvelope {
     worlds: Vec<WorldEntry>,
 }
 
-/// One cached lookup result: a populated `World` plus its TravellerMap
-/// allegiance code (if any). The allegiance is carried alongside `World`
-/// rather than added to it so the systems-generation module stays free
-/// of simulator-specific concepts.
-type CachedWorld = (World, Option<String>);
-
 /// Cache of TravellerMap world data lookups, keyed by
 /// `(sector_name, hex_x, hex_y)`. `None` = empty hex (404 from
 /// TravellerMap), so subsequent fetches return immediately.
 pub struct WorldCache {
-    inner: HashMap<(String, i32, i32), Option<CachedWorld>>,
+    inner: HashMap<(String, i32, i32), Option<SourcedWorld>>,
     client: reqwest::Client,
+    origins: Option<SectorOrigins>,
 }
 
 impl Default for WorldCache {
@@ -105,18 +105,51 @@ impl WorldCache {
         Self {
             inner: HashMap::new(),
             client,
+            origins: None,
+        }
+    }
+
+    /// Use `origins` instead of the built-in table to cross sector borders.
+    pub fn with_origins(mut self, origins: SectorOrigins) -> Self {
+        self.origins = Some(origins);
+        self
+    }
+
+    /// Fetch TravellerMap's universe listing and use it (backed by the
+    /// built-in table) as this cache's sector-origin table. Returns the
+    /// number of sectors now known. On failure the table is left as it
+    /// was.
+    pub async fn load_sector_origins(&mut self) -> Result<usize, FetchError> {
+        let url = format!("{}/api/universe", crate::util::travellermap_base_url());
+        log::trace!("world_fetch: GET {}", url);
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(FetchError::Malformed(format!(
+                "{} returned status {}",
+                url,
+                response.status()
+            )));
         }
+        let body = response.text().await?;
+        let mut origins = SectorOrigins::from_universe_json(&body)
+            .map_err(|e| FetchError::Malformed(format!("{}: {}", url, e)))?;
+        origins.merge(SectorOrigins::builtin());
+        let count = origins.len();
+        self.origins = Some(origins);
+        Ok(count)
     }
+}
 
+impl WorldSource for WorldCache {
     /// Look up a single world. Returns `Ok(None)` for empty hexes (404).
     /// Cached on the first lookup. The returned tuple includes the
     /// world's allegiance code (e.g. `"Im"`, `"AsT4"`).
-    pub async fn fetch(
+    async fn fetch(
         &mut self,
         sector: &str,
         hex_x: i32,
         hex_y: i32,
-    ) -> Result<Option<CachedWorld>, FetchError> {
+    ) -> Result<Option<SourcedWorld>, FetchError> {
         let key = (sector.to_string(), hex_x, hex_y);
         if let Some(cached) = self.inner.get(&key) {
             return Ok(cached.clone());
@@ -127,46 +160,34 @@ impl WorldCache {
         Ok(entry)
     }
 
+    fn sector_origins(&self) -> &SectorOrigins {
+        self.origins
+            .as_ref()
+            .unwrap_or_else(|| SectorOrigins::builtin())
+    }
+
     /// Find every world within `jump` parsecs of the given hex (excluding
-    /// the hex itself). All fetches run in parallel; empty hexes and any
-    /// individual fetch failures are skipped silently — we'd rather make
-    /// progress on the run than abort because one neighbouring hex
-    /// hiccupped.
-    pub async fn candidates_within(
+    /// the hex itself), including hexes over the sector border. All
+    /// fetches run in parallel; empty hexes and any individual fetch
+    /// failures are skipped silently — we'd rather make progress on the
+    /// run than abort because one neighbouring hex hiccupped.
+    async fn candidates_within(
         &mut self,
         sector: &str,
         from_hex: (i32, i32),
         jump: i32,
     ) -> Result<Vec<Candidate>, FetchError> {
-        // Build the list of hexes worth fetching.
-        let mut targets: Vec<(i32, i32, i32)> = Vec::new();
-        for x in SECTOR_HEX_X_RANGE {
-            for y in SECTOR_HEX_Y_RANGE {
-                if (x, y) == from_hex {
-                    continue;
-                }
-                let d = calculate_hex_distance(from_hex.0, from_hex.1, x, y);
-                if d > 0 && d <= jump {
-                    targets.push((x, y, d));
-                }
-            }
-        }
-
         // Split into already-cached hits and ones that need fetching.
         let mut candidates: Vec<Candidate> = Vec::new();
-        let mut to_fetch: Vec<(i32, i32, i32)> = Vec::new();
-        for (x, y, d) in targets {
-            let key = (sector.to_string(), x, y);
+        let mut to_fetch: Vec<(String, i32, i32, i32)> = Vec::new();
+        for (target, x, y, d) in hexes_within(self.sector_origins(), sector, from_hex, jump) {
+            let key = (target, x, y);
             if let Some(cached) = self.inner.get(&key) {
-                if let Some((world, allegiance)) = cached {
-                    candidates.push(Candidate {
-                        world: world.clone(),
-                        distance: d,
-                        allegiance: allegiance.clone(),
-                    });
+                if let Some(sourced) = cached {
+                    candidates.push(sourced.clone().into_candidate(key.0, d));
                 }
             } else {
-                to_fetch.push((x, y, d));
+                to_fetch.push((key.0, x, y, d));
             }
         }
 
@@ -174,11 +195,11 @@ impl WorldCache {
         // overwhelming TravellerMap (which resets connections under
         // load).
         let client = self.client.clone();
-        let sector_owned = sector.to_string();
         let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES));
-        let futs = to_fetch.iter().map(|&(x, y, _d)| {
+        let futs = to_fetch.iter().map(|(sector, x, y, _d)| {
+            let (x, y) = (*x, *y);
             let client = client.clone();
-            let sector = sector_owned.clone();
+            let sector = sector.clone();
             let sem = semaphore.clone();
             async move {
                 let _permit = sem.acquire_owned().await.ok();
@@ -188,18 +209,13 @@ impl WorldCache {
         });
         let results = join_all(futs).await;
 
-        for ((x, y, d), (rx, ry, res)) in to_fetch.iter().zip(results) {
-            debug_assert_eq!((*x, *y), (rx, ry));
-            let key = (sector.to_string(), *x, *y);
+        for ((target, x, y, d), (rx, ry, res)) in to_fetch.into_iter().zip(results) {
+            debug_assert_eq!((x, y), (rx, ry));
+            let key = (target.clone(), x, y);
             match res {
-                Ok(Some((world, allegiance))) => {
-                    self.inner
-                        .insert(key, Some((world.clone(), allegiance.clone())));
-                    candidates.push(Candidate {
-                        world,
-                        distance: *d,
-                        allegiance,
-                    });
+                Ok(Some(sourced)) => {
+                    self.inner.insert(key, Some(sourced.clone()));
+                    candidates.push(sourced.into_candidate(target, d));
                 }
                 Ok(None) => {
                     self.inner.insert(key, None);
@@ -209,7 +225,7 @@ impl WorldCache {
                         "world_fetch: skipping hex {:02}{:02} in {} ({:?})",
                         x,
                         y,
-                        sector,
+                        target,
                         e
                     );
                     // Don't cache; transient errors might recover.
@@ -228,7 +244,7 @@ async fn fetch_one(
     sector: &str,
     hex_x: i32,
     hex_y: i32,
-) -> Result<Option<CachedWorld>, FetchError> {
+) -> Result<Option<SourcedWorld>, FetchError> {
     let hex = format!("{:02}{:02}", hex_x, hex_y);
     let encoded_sector = urlencode(sector);
     let url = format!(
@@ -273,8 +289,23 @@ async fn fetch_one(
         Some("R") => ZoneClassification::Red,
         _ => ZoneClassification::Green,
     };
+    if let Some(bases) = entry.bases.as_deref() {
+        world.set_facilities(t5_base_codes(bases));
+    }
+
+    // The PBG's last digit is the gas-giant count.
+    let gas_giants = entry
+        .pbg
+        .as_deref()
+        .and_then(|pbg| pbg.chars().nth(2))
+        .and_then(|c| c.to_digit(10))
+        .map(|n| n as u8);
 
-    Ok(Some((world, entry.allegiance)))
+    Ok(Some(SourcedWorld {
+        world,
+        allegiance: entry.allegiance,
+        gas_giants,
+    }))
 }
 
 /// Minimal URL component encoder — enough to handle spaces and other
@@ -324,7 +355,7 @@ mod tests {
         assert!(res.is_ok());
         let entry = res.unwrap();
         assert!(entry.is_some(), "Regina hex 19,10 should be present");
-        let (_, allegiance) = entry.as_ref().unwrap();
+        let allegiance = &entry.as_ref().unwrap().allegiance;
         eprintln!("allegiance: {:?}", allegiance);
         assert!(
             allegiance.as_deref().unwrap_or("").starts_with("Im"),
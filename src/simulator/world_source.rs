@@ -0,0 +1,512 @@
+//! Where the simulator gets its worlds from.
+//!
+//! The executor only needs two questions answered: "what's in this hex?"
+//! and "which worlds are within `jump` parsecs of here?". [`WorldSource`]
+//! is that interface, so a run can be pointed at live TravellerMap data,
+//! a hand-built fixture, or a local sector file without the executor
+//! knowing the difference:
+//!
+//! - [`WorldCache`](crate::simulator::world_fetch::WorldCache) — fetches
+//!   from TravellerMap over HTTP and caches the answers.
+//! - [`MemoryWorlds`] — a plain map of hexes to worlds, for tests and
+//!   callers that already have their data in hand.
+//! - [`SectorFiles`] — one or more [`Sector`] listings, loaded from T5
+//!   Second Survey / `.sec` files or generated with
+//!   [`Sector::generate`], for offline runs against homebrew sectors.
+//!
+//! The two offline sources never touch the network, so a simulation
+//! driven by them depends only on its inputs.
+//!
+//! ## Crossing sector borders
+//!
+//! Each source carries a [`SectorOrigins`] table (the built-in one unless
+//! replaced with `with_origins`). Jump ranges are measured in galactic
+//! coordinates, so a ship near the edge of a sector whose neighbours are
+//! in the table sees candidates on both sides of the border. Hexes of a
+//! sector the table doesn't know are still searched within that sector.
+//!
+//! ```rust,ignore
+//! use worldgen::simulator::world_source::SectorFiles;
+//!
+//! let mut source = SectorFiles::new();
+//! source.load("Spinward Marches", "spin.tab")?;
+//! let result = run_simulation(params, &mut source, |_| {}).await?;
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::sector::files::SectorFileError;
+use crate::sector::origins::{SectorOrigins, normalize};
+use crate::sector::{SECTOR_HEIGHT, SECTOR_WIDTH, Sector};
+use crate::simulator::route::Candidate;
+use crate::simulator::world_fetch::FetchError;
+use crate::systems::world::World;
+use crate::util::calculate_hex_distance;
+
+/// One looked-up world: a populated `World` plus the facts about its
+/// hex the simulator needs and `World` doesn't carry. They ride alongside
+/// `World` rather than on it so the systems-generation module stays free
+/// of simulator-specific concepts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcedWorld {
+    pub world: World,
+    /// TravellerMap allegiance code (e.g. `"Im"`, `"AsT4"`), if any.
+    pub allegiance: Option<String>,
+    /// Gas giants in the system, from its PBG. `None` when the source
+    /// doesn't list one.
+    pub gas_giants: Option<u8>,
+}
+
+impl SourcedWorld {
+    /// `world` with no allegiance or PBG data.
+    pub fn new(world: World) -> Self {
+        SourcedWorld {
+            world,
+            allegiance: None,
+            gas_giants: None,
+        }
+    }
+
+    /// This world as a jump candidate `distance` parsecs away in `sector`.
+    pub fn into_candidate(self, sector: String, distance: i32) -> Candidate {
+        Candidate {
+            world: self.world,
+            sector,
+            distance,
+            allegiance: self.allegiance,
+            gas_giants: self.gas_giants,
+        }
+    }
+}
+
+/// A provider of world data for the simulator.
+///
+/// Implementors only have to answer single-hex lookups; the default
+/// [`WorldSource::candidates_within`] builds the jump-range candidate
+/// list from those. Sources with a cheaper bulk path (like the parallel
+/// HTTP fetcher) override it.
+pub trait WorldSource: Send {
+    /// Look up a single hex. `Ok(None)` means the hex is empty (or not
+    /// covered by this source); `Err` means the lookup itself failed.
+    fn fetch(
+        &mut self,
+        sector: &str,
+        hex_x: i32,
+        hex_y: i32,
+    ) -> impl Future<Output = Result<Option<SourcedWorld>, FetchError>> + Send;
+
+    /// The sector-origin table used to follow jumps across sector borders.
+    fn sector_origins(&self) -> &SectorOrigins {
+        SectorOrigins::builtin()
+    }
+
+    /// Find every world within `jump` parsecs of `from_hex` in `sector`
+    /// (excluding the hex itself), in galactic column-then-row order and
+    /// including neighbouring sectors the origin table knows. Hexes whose
+    /// lookup fails are skipped rather than aborting the whole list.
+    fn candidates_within(
+        &mut self,
+        sector: &str,
+        from_hex: (i32, i32),
+        jump: i32,
+    ) -> impl Future<Output = Result<Vec<Candidate>, FetchError>> + Send {
+        async move {
+            let mut candidates = Vec::new();
+            for (target, x, y, distance) in
+                hexes_within(self.sector_origins(), sector, from_hex, jump)
+            {
+                match self.fetch(&target, x, y).await {
+                    Ok(Some(sourced)) => candidates.push(sourced.into_candidate(target, distance)),
+                    Ok(None) => {}
+                    Err(e) => log::debug!(
+                        "world_source: skipping hex {:02}{:02} in {} ({:?})",
+                        x,
+                        y,
+                        target,
+                        e
+                    ),
+                }
+            }
+            Ok(candidates)
+        }
+    }
+}
+
+/// Every hex within `jump` parsecs of `from_hex` in `sector`, excluding
+/// `from_hex` itself, as `(sector, x, y, distance)`. Neighbouring sectors
+/// are included when `origins` places both them and `sector`; otherwise
+/// the search stays inside `sector`.
+pub(crate) fn hexes_within(
+    origins: &SectorOrigins,
+    sector: &str,
+    from_hex: (i32, i32),
+    jump: i32,
+) -> Vec<(String, i32, i32, i32)> {
+    let mut targets = Vec::new();
+    if let Some((gx, gy)) = origins.to_galactic(sector, from_hex.0, from_hex.1) {
+        for x in gx - jump..=gx + jump {
+            for y in gy - jump..=gy + jump {
+                let d = calculate_hex_distance(gx, gy, x, y);
+                if d > 0
+                    && d <= jump
+                    && let Some((name, hx, hy)) = origins.from_galactic(x, y)
+                {
+                    targets.push((name, hx, hy, d));
+                }
+            }
+        }
+        return targets;
+    }
+
+    for x in 1..=SECTOR_WIDTH as i32 {
+        for y in 1..=SECTOR_HEIGHT as i32 {
+            let d = calculate_hex_distance(from_hex.0, from_hex.1, x, y);
+            if d > 0 && d <= jump {
+                targets.push((sector.to_string(), x, y, d));
+            }
+        }
+    }
+    targets
+}
+
+/// An in-memory world source: a fixed map of `(sector, x, y)` to worlds.
+/// Hexes that were never inserted are empty. Sector names match loosely,
+/// as in [`SectorOrigins`].
+#[derive(Debug, Clone, Default)]
+pub struct MemoryWorlds {
+    /// Keyed on the normalized sector name.
+    worlds: HashMap<(String, i32, i32), SourcedWorld>,
+    origins: Option<SectorOrigins>,
+}
+
+impl MemoryWorlds {
+    /// An empty source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `origins` instead of the built-in table to cross sector borders.
+    pub fn with_origins(mut self, origins: SectorOrigins) -> Self {
+        self.origins = Some(origins);
+        self
+    }
+
+    /// Place `world` at `(hex_x, hex_y)` in `sector`, replacing anything
+    /// already there. The world's coordinates are set to match.
+    pub fn insert(
+        &mut self,
+        sector: &str,
+        hex_x: i32,
+        hex_y: i32,
+        mut world: World,
+        allegiance: Option<String>,
+    ) {
+        world.coordinates = Some((hex_x, hex_y));
+        self.worlds.insert(
+            (normalize(sector), hex_x, hex_y),
+            SourcedWorld {
+                world,
+                allegiance,
+                gas_giants: None,
+            },
+        );
+    }
+
+    /// Number of worlds held.
+    pub fn len(&self) -> usize {
+        self.worlds.len()
+    }
+
+    /// Whether no worlds have been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.worlds.is_empty()
+    }
+}
+
+impl WorldSource for MemoryWorlds {
+    async fn fetch(
+        &mut self,
+        sector: &str,
+        hex_x: i32,
+        hex_y: i32,
+    ) -> Result<Option<SourcedWorld>, FetchError> {
+        Ok(self.worlds.get(&(normalize(sector), hex_x, hex_y)).cloned())
+    }
+
+    fn sector_origins(&self) -> &SectorOrigins {
+        self.origins
+            .as_ref()
+            .unwrap_or_else(|| SectorOrigins::builtin())
+    }
+}
+
+/// A world source backed by sector listings, keyed by sector name
+/// (matched loosely, as in [`SectorOrigins`]). Worlds are built from each
+/// hex's profile on lookup; sectors that weren't added are treated as
+/// empty space.
+#[derive(Debug, Clone, Default)]
+pub struct SectorFiles {
+    /// Keyed on the normalized sector name.
+    sectors: HashMap<String, Sector>,
+    origins: Option<SectorOrigins>,
+}
+
+impl SectorFiles {
+    /// A source with no sectors loaded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `origins` instead of the built-in table to cross sector borders.
+    pub fn with_origins(mut self, origins: SectorOrigins) -> Self {
+        self.origins = Some(origins);
+        self
+    }
+
+    /// Add `sector` and place it at grid position `origin`, so homebrew
+    /// sectors can sit next to each other (or next to published ones).
+    pub fn add_at(&mut self, sector: Sector, origin: (i32, i32)) {
+        self.origins
+            .get_or_insert_with(|| SectorOrigins::builtin().clone())
+            .insert(&sector.name, origin);
+        self.add(sector);
+    }
+
+    /// Add (or replace) a sector under its own name.
+    pub fn add(&mut self, sector: Sector) {
+        self.sectors.insert(normalize(&sector.name), sector);
+    }
+
+    /// Load the sector `name` from a file: `.sec` files by extension,
+    /// anything else as T5 Second Survey tab-delimited.
+    pub fn load(&mut self, name: &str, path: impl AsRef<Path>) -> Result<(), SectorFileError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let is_sec = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("sec"));
+        let sector = if is_sec {
+            Sector::from_sec(name, &text)?
+        } else {
+            Sector::from_t5_tab(name, &text)?
+        };
+        self.add(sector);
+        Ok(())
+    }
+}
+
+impl From<Sector> for SectorFiles {
+    fn from(sector: Sector) -> Self {
+        let mut source = SectorFiles::new();
+        source.add(sector);
+        source
+    }
+}
+
+impl WorldSource for SectorFiles {
+    async fn fetch(
+        &mut self,
+        sector: &str,
+        hex_x: i32,
+        hex_y: i32,
+    ) -> Result<Option<SourcedWorld>, FetchError> {
+        let (Ok(x), Ok(y)) = (u8::try_from(hex_x), u8::try_from(hex_y)) else {
+            return Ok(None);
+        };
+        let Some(hex) = self
+            .sectors
+            .get(&normalize(sector))
+            .and_then(|s| s.hex(x, y))
+        else {
+            return Ok(None);
+        };
+        let world = hex
+            .world()
+            .map_err(|e| FetchError::InvalidUwp(format!("{}: {}", hex.uwp, e)))?;
+        Ok(Some(SourcedWorld {
+            world,
+            allegiance: hex.allegiance.clone(),
+            gas_giants: Some(hex.pbg.gas_giants),
+        }))
+    }
+
+    fn sector_origins(&self) -> &SectorOrigins {
+        self.origins
+            .as_ref()
+            .unwrap_or_else(|| SectorOrigins::builtin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sector::Density;
+
+    fn world(name: &str, uwp: &str) -> World {
+        let mut world = World::from_uwp(name, uwp, false, true).unwrap();
+        world.gen_trade_classes();
+        world
+    }
+
+    #[tokio::test]
+    async fn memory_worlds_answer_lookups_and_candidates() {
+        let mut source = MemoryWorlds::new();
+        source.insert("Test", 10, 10, world("Home", "A788899-A"), None);
+        source.insert(
+            "Test",
+            11,
+            10,
+            world("Near", "B544651-8"),
+            Some("Im".into()),
+        );
+        source.insert("Test", 14, 10, world("Far", "C433432-7"), None);
+        assert_eq!(source.len(), 3);
+
+        let near = source.fetch("Test", 11, 10).await.unwrap().unwrap();
+        assert_eq!(near.world.name, "Near");
+        assert_eq!(near.world.coordinates, Some((11, 10)));
+        assert_eq!(near.allegiance.as_deref(), Some("Im"));
+        assert_eq!(near.gas_giants, None);
+        assert!(source.fetch("Test", 12, 10).await.unwrap().is_none());
+        assert!(source.fetch("Other", 11, 10).await.unwrap().is_none());
+
+        let candidates = source.candidates_within("Test", (10, 10), 2).await.unwrap();
+        let names: Vec<&str> = candidates.iter().map(|c| c.world.name.as_str()).collect();
+        assert_eq!(names, ["Near"]);
+        assert_eq!(candidates[0].distance, 1);
+    }
+
+    #[tokio::test]
+    async fn candidates_cross_sector_borders() {
+        let mut source = MemoryWorlds::new();
+        source.insert("Spinward Marches", 31, 10, world("Near", "B544651-8"), None);
+        source.insert("Deneb", 1, 10, world("Over", "C433432-7"), None);
+        source.insert("Deneb", 2, 10, world("Beyond", "A788899-A"), None);
+
+        let candidates = source
+            .candidates_within("Spinward Marches", (32, 10), 1)
+            .await
+            .unwrap();
+        let found: Vec<(&str, &str, i32)> = candidates
+            .iter()
+            .map(|c| (c.world.name.as_str(), c.sector.as_str(), c.distance))
+            .collect();
+        assert_eq!(
+            found,
+            [("Near", "Spinward Marches", 1), ("Over", "Deneb", 1)]
+        );
+
+        // Without Deneb in the table the search stops at the border.
+        let mut origins = SectorOrigins::new();
+        origins.insert("Spinward Marches", (-4, -1));
+        let mut source = source.with_origins(origins);
+        let candidates = source
+            .candidates_within("Spinward Marches", (32, 10), 2)
+            .await
+            .unwrap();
+        assert_eq!(candidates.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn homebrew_sectors_can_be_placed_side_by_side() {
+        let west = Sector::generate("Homebrew West", Density::Dense);
+        let east = Sector::generate("Homebrew East", Density::Dense);
+        let target = east
+            .hexes
+            .iter()
+            .find(|h| h.x == 1)
+            .expect("a dense sector has a world in its first column")
+            .clone();
+        let mut source = SectorFiles::new();
+        source.add_at(west, (40, 40));
+        source.add_at(east, (41, 40));
+
+        let candidates = source
+            .candidates_within("Homebrew West", (32, target.y as i32), 1)
+            .await
+            .unwrap();
+        assert!(candidates.iter().any(|c| c.sector == "Homebrew East"
+            && c.world.name == target.name
+            && c.distance == 1));
+    }
+
+    #[tokio::test]
+    async fn sector_files_serve_every_occupied_hex() {
+        let sector = Sector::generate_subsector("Source Test", 'F', Density::Dense).unwrap();
+        let expected = sector.hexes[0].clone();
+        let occupied = sector.hexes.len();
+        let mut source = SectorFiles::from(sector);
+
+        let found = source
+            .fetch("Source Test", expected.x as i32, expected.y as i32)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.world.name, expected.name);
+        assert_eq!(
+            found.world.coordinates,
+            Some((expected.x as i32, expected.y as i32))
+        );
+        assert_eq!(found.gas_giants, Some(expected.pbg.gas_giants));
+
+        // A radius spanning the whole sector reaches every occupied hex.
+        let candidates = source
+            .candidates_within("Source Test", (0, 0), 60)
+            .await
+            .unwrap();
+        assert_eq!(candidates.len(), occupied);
+    }
+
+    #[tokio::test]
+    async fn sector_names_match_loosely() {
+        // Loaded under a spelling other than the origin table's, a sector
+        // still answers the display names `hexes_within` produces.
+        let sector = Sector::generate("reavers deep", Density::Dense);
+        let hex = sector.hexes[0].clone();
+        let mut files = SectorFiles::from(sector);
+        let found = files
+            .fetch("Reaver's Deep", hex.x as i32, hex.y as i32)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.world.name, hex.name);
+        let candidates = files
+            .candidates_within("Reaver's Deep", (hex.x as i32, hex.y as i32), 2)
+            .await
+            .unwrap();
+        assert!(!candidates.is_empty());
+        assert!(candidates.iter().all(|c| c.sector == "Reaver's Deep"));
+
+        let mut memory = MemoryWorlds::new();
+        memory.insert("SPINWARD MARCHES", 32, 10, world("Edge", "B544651-8"), None);
+        let candidates = memory.candidates_within("Deneb", (1, 10), 1).await.unwrap();
+        let names: Vec<&str> = candidates.iter().map(|c| c.world.name.as_str()).collect();
+        assert_eq!(names, ["Edge"]);
+    }
+
+    #[tokio::test]
+    async fn sector_files_load_from_disk() {
+        let sector = Sector::generate_subsector("Disk Test", 'A', Density::Standard).unwrap();
+        let path = std::env::temp_dir().join(format!("world-source-{}.sec", std::process::id()));
+        std::fs::write(&path, sector.to_sec()).unwrap();
+
+        let mut source = SectorFiles::new();
+        let loaded = source.load("Disk Test", &path);
+        std::fs::remove_file(&path).unwrap();
+        loaded.unwrap();
+
+        let hex = &sector.hexes[0];
+        let found = source
+            .fetch("Disk Test", hex.x as i32, hex.y as i32)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.world.name, hex.name);
+        assert!(matches!(
+            source.load("Missing", "/nonexistent/x.sec"),
+            Err(SectorFileError::Io(_))
+        ));
+    }
+}
@@ -37,6 +37,7 @@
 
 #[cfg(feature = "frontend")]
 use reactive_stores::Store;
+use serde::{Deserialize, Serialize};
 
 use crate::systems::has_satellites::HasSatellites;
 use crate::systems::name_tables::{gen_moon_name, gen_planet_name};
@@ -60,7 +61,7 @@ const LARGE_GG_RADIUS_KM: std::ops::RangeInclusive<u32> = 60_000..=100_000;
 /// Gas giants are major planetary bodies that can host multiple satellites and
 /// play important roles in system trade and exploration. They are classified
 /// by size which affects their satellite generation characteristics.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "frontend", derive(Store))]
 pub struct GasGiant {
     /// Display name of the gas giant
@@ -83,7 +84,7 @@ pub struct GasGiant {
 ///
 /// Determines the number and types of satellites that can be generated,
 /// as well as the maximum orbital distances possible for satellites.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GasGiantSize {
     /// Smaller gas giant with fewer satellites and limited orbital ranges
     Small,
@@ -11,6 +11,7 @@
 //! - [`gas_giant`] - Gas giant generation and characteristics  
 //! - [`has_satellites`] - Satellite generation for worlds and gas giants
 //! - [`name_tables`] - Random name generation tables for worlds and features
+//! - [`persist`] - Versioned JSON / compact binary save and load of a generated system
 //! - [`system`] - Main system generation logic and coordination
 //! - [`system_tables`] - Lookup tables for system generation rules
 //! - [`world`] - Individual world generation and Universal World Profile (UWP) handling
@@ -36,6 +37,7 @@ pub mod constraint;
 pub mod gas_giant;
 pub mod has_satellites;
 pub mod name_tables;
+pub mod persist;
 pub mod system;
 pub mod system_tables;
 pub mod world;
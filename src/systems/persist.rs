@@ -0,0 +1,224 @@
+//! # System Persistence Module
+//!
+//! Saves and loads a fully generated [`System`] so it can be stored or
+//! shipped somewhere and re-rendered later without regenerating from the
+//! seed. Two encodings are provided over the same versioned envelope:
+//!
+//! - **JSON** ([`System::to_json`] / [`System::from_json`]) — human-readable,
+//!   suitable for hand inspection, diffs, and web clients.
+//! - **Compact binary** ([`System::to_bytes`] / [`System::from_bytes`]) — the
+//!   same JSON document gzip-compressed, for storage and transfer where
+//!   size matters more than readability.
+//!
+//! The round-trip is lossless: every field the renderers read (names,
+//! orbits, gas giant radii, UWPs, astro data, companion stars and nested
+//! satellites) is carried, so `sysmap::render_png` on a loaded system is
+//! byte-identical to the render of the system that was saved.
+//!
+//! ## Versioning
+//!
+//! Every document carries a `format_version` next to the `system` payload.
+//! [`SYSTEM_FORMAT_VERSION`] is bumped whenever a change to the `System`
+//! tree would make old documents decode differently; loading a document
+//! with an unknown version fails with [`PersistError::UnsupportedVersion`]
+//! rather than silently producing a different system.
+//!
+//! ```rust,ignore
+//! use worldgen::systems::system::System;
+//!
+//! let json = system.to_json()?;
+//! let restored = System::from_json(&json)?;
+//! assert_eq!(
+//!     worldgen::sysmap::render_png(&system)?,
+//!     worldgen::sysmap::render_png(&restored)?,
+//! );
+//! ```
+
+use std::io::{Read, Write};
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+
+use crate::systems::system::System;
+
+/// Current on-disk format version for saved systems. Bump this when a
+/// change to [`System`] (or anything it contains) alters how an existing
+/// document would decode.
+pub const SYSTEM_FORMAT_VERSION: u32 = 1;
+
+/// Failure modes when saving or loading a [`System`].
+#[derive(Debug, thiserror::Error)]
+pub enum PersistError {
+    /// The document wasn't valid JSON, or didn't match the `System` shape.
+    #[error("system document invalid: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Compressing or decompressing the binary form failed.
+    #[error("system binary encoding failed: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The document was written by a format version this build can't read.
+    #[error("unsupported system format version {found} (this build reads {supported})")]
+    UnsupportedVersion { found: u32, supported: u32 },
+}
+
+/// Borrowing envelope used on the write path so saving doesn't clone the
+/// whole tree.
+#[derive(Serialize)]
+struct SavedSystemRef<'a> {
+    format_version: u32,
+    system: &'a System,
+}
+
+/// Owning envelope used on the read path. The version has already been
+/// checked via [`VersionProbe`] by the time this is decoded.
+#[derive(Deserialize)]
+struct SavedSystem {
+    system: System,
+}
+
+/// Just the version field, decoded first so an unknown version is
+/// reported as such rather than as whatever shape mismatch it causes.
+#[derive(Deserialize)]
+struct VersionProbe {
+    format_version: u32,
+}
+
+impl System {
+    /// Serialize this system to a versioned JSON document.
+    pub fn to_json(&self) -> Result<String, PersistError> {
+        Ok(serde_json::to_string(&SavedSystemRef {
+            format_version: SYSTEM_FORMAT_VERSION,
+            system: self,
+        })?)
+    }
+
+    /// Load a system from a JSON document produced by [`System::to_json`].
+    pub fn from_json(json: &str) -> Result<System, PersistError> {
+        let probe: VersionProbe = serde_json::from_str(json)?;
+        if probe.format_version != SYSTEM_FORMAT_VERSION {
+            return Err(PersistError::UnsupportedVersion {
+                found: probe.format_version,
+                supported: SYSTEM_FORMAT_VERSION,
+            });
+        }
+        let saved: SavedSystem = serde_json::from_str(json)?;
+        Ok(saved.system)
+    }
+
+    /// Serialize this system to the compact binary form (gzip-compressed
+    /// versioned JSON).
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PersistError> {
+        let json = self.to_json()?;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        encoder.write_all(json.as_bytes())?;
+        Ok(encoder.finish()?)
+    }
+
+    /// Load a system from bytes produced by [`System::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<System, PersistError> {
+        let mut json = String::new();
+        GzDecoder::new(bytes).read_to_string(&mut json)?;
+        System::from_json(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::{StarSpec, build_constraints};
+    use crate::systems::system::{StarSize, StarType};
+
+    /// A three-star system with gas giants, belts and extra planets, so
+    /// the round-trip covers companions and nested satellites.
+    fn noricum() -> System {
+        let cs = build_constraints(
+            "Noricum",
+            "D8867BB-1",
+            &[
+                StarSpec::new(StarType::G, 2, StarSize::V),
+                StarSpec::new(StarType::M, 9, StarSize::V),
+                StarSpec::new(StarType::M, 6, StarSize::V),
+            ],
+            4,
+            1,
+            3,
+        )
+        .unwrap();
+        System::generate_from_constraints_seeded(17, cs).unwrap()
+    }
+
+    #[test]
+    fn json_round_trip_renders_identically() {
+        let system = noricum();
+        let json = system.to_json().unwrap();
+        let restored = System::from_json(&json).unwrap();
+
+        assert_eq!(json, restored.to_json().unwrap());
+        assert_eq!(
+            crate::sysmap::render_png(&system).unwrap(),
+            crate::sysmap::render_png(&restored).unwrap()
+        );
+        assert_eq!(
+            crate::sysmap::render_svg(&system),
+            crate::sysmap::render_svg(&restored)
+        );
+    }
+
+    #[test]
+    fn json_round_trip_keeps_companions() {
+        let system = noricum();
+        let restored = System::from_json(&system.to_json().unwrap()).unwrap();
+        assert_eq!(system.count_stars(), restored.count_stars());
+        assert_eq!(system.count_stars(), 3);
+    }
+
+    #[test]
+    fn binary_round_trip_renders_identically() {
+        let system = noricum();
+        let bytes = system.to_bytes().unwrap();
+        let json = system.to_json().unwrap();
+        assert!(
+            bytes.len() < json.len(),
+            "binary form ({} bytes) should be smaller than JSON ({} bytes)",
+            bytes.len(),
+            json.len()
+        );
+
+        let restored = System::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            crate::sysmap::render_png(&system).unwrap(),
+            crate::sysmap::render_png(&restored).unwrap()
+        );
+    }
+
+    #[test]
+    fn unknown_format_version_is_rejected() {
+        let json = noricum().to_json().unwrap().replacen(
+            &format!("\"format_version\":{SYSTEM_FORMAT_VERSION}"),
+            "\"format_version\":999",
+            1,
+        );
+        assert!(matches!(
+            System::from_json(&json),
+            Err(PersistError::UnsupportedVersion {
+                found: 999,
+                supported: SYSTEM_FORMAT_VERSION
+            })
+        ));
+    }
+
+    #[test]
+    fn garbage_is_a_json_error() {
+        assert!(matches!(
+            System::from_json("not json"),
+            Err(PersistError::Json(_))
+        ));
+        assert!(matches!(
+            System::from_bytes(b"not gzip"),
+            Err(PersistError::Io(_))
+        ));
+    }
+}
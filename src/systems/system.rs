@@ -35,6 +35,7 @@
 use log::{debug, error, warn};
 #[cfg(feature = "frontend")]
 use reactive_stores::Store;
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 use crate::systems::constraint::{Constraint, ConstraintError, PartialUwp, SystemConstraints};
@@ -144,7 +145,7 @@ pub struct SystemOverrides {
 /// - Gas giants (with their own satellite systems)
 /// - Secondary/tertiary star markers
 /// - Blocked orbits (intentionally empty for realism)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "frontend", derive(Store))]
 pub struct System {
     pub name: String,
@@ -174,7 +175,7 @@ pub struct System {
 /// - **G**: Yellow stars like Sol (5,200-6,000K), stable main sequence
 /// - **K**: Orange stars, cooler (3,700-5,200K), long-lived
 /// - **M**: Red dwarfs, coolest (2,400-3,700K), most common, very long-lived
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "frontend", derive(Store))]
 pub enum StarType {
     O,
@@ -207,7 +208,7 @@ pub type StarSubType = u8;
 /// - **V**: Main sequence (dwarfs), stable hydrogen burning
 /// - **VI**: Subdwarfs, metal-poor, lower luminosity
 /// - **D**: White dwarfs, stellar remnants, very compact zones
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "frontend", derive(Store))]
 pub enum StarSize {
     Ia,
@@ -233,7 +234,7 @@ pub enum StarSize {
 /// - **Primary**: Contact binary or very close orbit
 /// - **Far**: Distant orbit, independent zone system
 /// - **System(n)**: Orbits within primary's zone system at position n
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "frontend", derive(Store))]
 pub enum StarOrbit {
     Primary,
@@ -253,7 +254,7 @@ pub enum StarOrbit {
 /// - Sol: G2V (G-type, subtype 2, main sequence)
 /// - Rigel: B8Ia (B-type, subtype 8, supergiant)
 /// - Proxima Centauri: M5.5V (M-type, subtype 5-6, main sequence)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "frontend", derive(Store))]
 pub struct Star {
     pub star_type: StarType,
@@ -273,7 +274,7 @@ pub struct Star {
 /// - **World**: Rocky planets with full UWP characteristics
 /// - **GasGiant**: Gas giants with satellite systems
 /// - **Blocked**: Intentionally empty orbits for realism
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "frontend", derive(Store))]
 pub enum OrbitContent {
     // This orbit contains the secondary star system of the primary.
@@ -323,6 +324,21 @@ impl System {
         count
     }
 
+    /// Gas giants anywhere in this system, including around companion stars.
+    pub fn count_gas_giants(&self) -> u8 {
+        let here = self
+            .orbit_slots
+            .iter()
+            .filter(|slot| matches!(slot, Some(OrbitContent::GasGiant(_))))
+            .count() as u8;
+        let companions = [&self.secondary, &self.tertiary]
+            .into_iter()
+            .flatten()
+            .map(|companion| companion.count_gas_giants())
+            .sum::<u8>();
+        here + companions
+    }
+
     pub fn set_max_orbits(&mut self, max_orbits: usize) {
         self.orbit_slots.resize(max_orbits + 1, None);
     }
@@ -1633,6 +1649,32 @@ fn gen_stars(world_mod: i32, companions_possible: bool, overrides: &SystemOverri
     system
 }
 
+/// Roll just a system's star roster — primary first, then up to two
+/// companions — using the same count, type and size tables as
+/// [`gen_stars`], but without laying out any orbits. This is what a
+/// sector map's "Stellar" column records; feeding the result back as
+/// `Star` constraints pins the same stars when the system is expanded.
+pub(crate) fn roll_stellar(world_mod: i32) -> Vec<Star> {
+    let num_stars = gen_num_stars();
+    let primary_type_roll = roll_2d6();
+    let primary_size_roll = roll_2d6();
+    let star_type = gen_primary_star_type(primary_type_roll + world_mod);
+    let subtype = roll_10() as StarSubType;
+    let mut stars = vec![Star {
+        star_type,
+        subtype,
+        size: gen_primary_star_size(primary_size_roll, star_type, subtype),
+    }];
+    for _ in 1..num_stars {
+        stars.push(Star {
+            star_type: gen_companion_star_type(roll_2d6() + primary_type_roll),
+            subtype: roll_10() as StarSubType,
+            size: gen_companion_star_size(roll_2d6() + primary_size_roll),
+        });
+    }
+    stars
+}
+
 /// Pull the actionable star and gas-giant overrides out of a
 /// `SystemConstraints`. Star constraints map to entries in `stars` in
 /// declaration order (first becomes the primary override). Gas-giant
@@ -1835,6 +1877,28 @@ mod tests {
         assert_eq!(arabic_to_roman(40), "XL");
     }
 
+    #[test]
+    fn test_count_gas_giants_includes_companions() {
+        let mut primary = System::new(StarType::G, 2, StarSize::V, StarOrbit::Primary, 6);
+        primary.orbit_slots[1] = Some(OrbitContent::GasGiant(GasGiant::new(
+            GasGiantSize::Large,
+            1,
+        )));
+        primary.orbit_slots[4] = Some(OrbitContent::GasGiant(GasGiant::new(
+            GasGiantSize::Small,
+            4,
+        )));
+        primary.orbit_slots[5] = Some(OrbitContent::Secondary);
+        let mut secondary = System::new(StarType::M, 5, StarSize::V, StarOrbit::System(5), 3);
+        secondary.orbit_slots[2] = Some(OrbitContent::GasGiant(GasGiant::new(
+            GasGiantSize::Small,
+            2,
+        )));
+        primary.secondary = Some(Box::new(secondary));
+
+        assert_eq!(primary.count_gas_giants(), 3);
+    }
+
     #[test_log::test]
     fn test_generate_system() {
         let main_uwp = "A788899-A";
@@ -153,6 +153,11 @@ impl World {
         self.law_level
     }
 
+    /// Returns the government type of the world
+    pub fn get_government(&self) -> i32 {
+        self.government
+    }
+
     /// Sets the starport code for the world
     ///
     /// # Arguments
@@ -487,6 +492,12 @@ impl World {
         }
     }
 
+    /// Replaces the world's trade classifications, e.g. with the trade
+    /// codes a sector file lists for it.
+    pub fn set_trade_classes(&mut self, trade_classes: Vec<TradeClass>) {
+        self.trade_classes = trade_classes;
+    }
+
     /// Sets the facilities present on the world
     ///
     /// # Arguments
@@ -497,6 +508,11 @@ impl World {
         self.facilities = facilities;
     }
 
+    /// Returns the facilities present on the world
+    pub fn facilities(&self) -> &[Facility] {
+        &self.facilities
+    }
+
     /// Returns a formatted string of all facilities on the world
     pub fn facilities_string(&self) -> String {
         self.facilities
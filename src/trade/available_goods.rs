@@ -81,6 +81,7 @@ use log::debug;
 
 use crate::trade::TradeClass;
 use crate::trade::table::{Availability, TradeTable, TradeTableEntry};
+use crate::util::WorldgenRng;
 
 /// A trade good available for purchase at a specific world
 ///
@@ -139,6 +140,22 @@ pub struct Good {
     pub buy_price_roll: Option<i32>,
     /// Raw 3d6 roll for sell price calculation (if applicable)
     pub sell_price_roll: Option<i32>,
+    /// DM on the buy roll from the selling world's memory of recent trade
+    /// (see [`crate::trade::market`]); 0 at a market's baseline.
+    #[serde(default)]
+    pub buy_market_dm: i16,
+    /// DM on the sell roll from the buying world's memory of recent
+    /// trade; 0 at a market's baseline.
+    #[serde(default)]
+    pub sell_market_dm: i16,
+}
+
+impl Good {
+    /// Whether this good is contraband: trade table entries 61–66, only
+    /// offered when illegal goods are enabled.
+    pub fn is_illegal(&self) -> bool {
+        self.source_index > 60
+    }
 }
 
 impl Display for Good {
@@ -270,7 +287,7 @@ impl AvailableGoodsTable {
         }
 
         // Add random goods based on population
-        let mut rng = rand::rng();
+        let mut rng = WorldgenRng;
         let max_tens = if illegal_ok { 6 } else { 5 };
 
         for _ in 0..population {
@@ -373,6 +390,8 @@ impl AvailableGoodsTable {
             quantity_roll: raw_roll,
             buy_price_roll: None, // Will be set when pricing
             sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
         };
 
         self.goods.push(good);
@@ -385,7 +404,7 @@ impl AvailableGoodsTable {
         entry: TradeTableEntry,
         world_population: i32,
     ) -> Result<(), String> {
-        let mut rng = rand::rng();
+        let mut rng = WorldgenRng;
         self.gen_entry_rng(entry, &mut rng, world_population)
     }
 
@@ -584,7 +603,7 @@ impl AvailableGoodsTable {
         buyer_broker_skill: i16,
         supplier_broker_skill: i16,
     ) {
-        let mut rng = rand::rng();
+        let mut rng = WorldgenRng;
         for good in &mut self.goods {
             // Roll 3d6 and save it
             let roll = match good.buy_price_roll {
@@ -611,7 +630,8 @@ impl AvailableGoodsTable {
             // Calculate the modified roll
             let modified_roll = roll as i16 + buyer_broker_skill - supplier_broker_skill
                 + purchase_origin_dm
-                - sale_origin_dm;
+                - sale_origin_dm
+                + good.buy_market_dm;
 
             // Determine the price multiplier based on the modified roll
             let price_multiplier = match modified_roll {
@@ -647,10 +667,11 @@ impl AvailableGoodsTable {
             };
 
             good.buy_cost_comment = format!(
-                "(roll) {} + (broker) {} + (trade mod) {} = {} which gives a multiplier of {}",
+                "(roll) {} + (broker) {} + (trade mod) {}{} = {} which gives a multiplier of {}",
                 roll,
                 buyer_broker_skill - supplier_broker_skill,
                 purchase_origin_dm - sale_origin_dm,
+                market_comment(good.buy_market_dm),
                 modified_roll,
                 price_multiplier
             );
@@ -708,7 +729,7 @@ impl AvailableGoodsTable {
         buyer_broker_skill: i16,
         supplier_broker_skill: i16,
     ) {
-        let rng = rand::rng();
+        let rng = WorldgenRng;
         self.price_goods_to_sell_rng(
             possible_destination_trade_classes,
             buyer_broker_skill,
@@ -763,6 +784,32 @@ impl AvailableGoodsTable {
 }
 
 impl Good {
+    /// Trade table entry this good was generated from.
+    fn table_entry(&self) -> &'static crate::trade::table::TradeTableEntry {
+        TradeTable::global()
+            .get(self.source_index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Failed to get trade table entry for index {}",
+                    &self.source_index
+                )
+            })
+    }
+
+    /// Total DM on the 3d6 sale roll for this good at a world with
+    /// `trade_classes`: broker skills, trade-class DMs and market memory.
+    pub fn sell_dm(
+        &self,
+        trade_classes: &[crate::trade::TradeClass],
+        seller_broker_skill: i16,
+        buyer_broker_skill: i16,
+    ) -> i16 {
+        let entry = self.table_entry();
+        seller_broker_skill - buyer_broker_skill - find_max_dm(&entry.purchase_dm, trade_classes)
+            + find_max_dm(&entry.sale_dm, trade_classes)
+            + self.sell_market_dm
+    }
+
     /// Price this good for selling at a destination
     /// - If destination trade classes are provided, computes a sell_price and comment
     /// - If None, clears sell_price
@@ -789,61 +836,21 @@ impl Good {
                 }
             };
 
-            let entry = TradeTable::global()
-                .get(self.source_index)
-                .unwrap_or_else(|| {
-                    panic!(
-                        "Failed to get trade table entry for index {}",
-                        &self.source_index
-                    )
-                });
-
+            let entry = self.table_entry();
             let purchase_origin_dm = find_max_dm(&entry.purchase_dm, trade_classes);
             let sale_origin_dm = find_max_dm(&entry.sale_dm, trade_classes);
 
             // Calculate the modified roll (mirror price_goods_to_sell)
             let modified_roll =
-                roll as i16 + seller_broker_skill - buyer_broker_skill - purchase_origin_dm
-                    + sale_origin_dm;
-
-            // Determine the price multiplier based on the modified roll
-            let price_multiplier = match modified_roll {
-                i16::MIN..=-3 => 0.1,
-                -2 => 0.2,
-                -1 => 0.3,
-                0 => 0.4,
-                1 => 0.45,
-                2 => 0.5,
-                3 => 0.55,
-                4 => 0.60,
-                5 => 0.65,
-                6 => 0.70,
-                7 => 0.75,
-                8 => 0.80,
-                9 => 0.85,
-                10 => 0.9,
-                11 => 1.0,
-                12 => 1.05,
-                13 => 1.10,
-                14 => 1.15,
-                15 => 1.20,
-                16 => 1.25,
-                17 => 1.30,
-                18 => 1.40,
-                19 => 1.50,
-                20 => 1.60,
-                21 => 1.75,
-                22 => 2.0,
-                23 => 2.5,
-                24 => 3.0,
-                25.. => 4.0,
-            };
+                roll as i16 + self.sell_dm(trade_classes, seller_broker_skill, buyer_broker_skill);
+            let price_multiplier = sell_multiplier(modified_roll);
 
             self.sell_price_comment = format!(
-                "(roll) {} + (broker) {} + (trade mod) {} = {} which gives a multiplier of {}",
+                "(roll) {} + (broker) {} + (trade mod) {}{} = {} which gives a multiplier of {}",
                 roll,
                 seller_broker_skill - buyer_broker_skill,
                 sale_origin_dm - purchase_origin_dm,
+                market_comment(self.sell_market_dm),
                 modified_roll,
                 price_multiplier
             );
@@ -860,6 +867,51 @@ impl Good {
     }
 }
 
+/// Sell-price multiplier for a modified 3d6 sale roll.
+pub fn sell_multiplier(modified_roll: i16) -> f64 {
+    match modified_roll {
+        i16::MIN..=-3 => 0.1,
+        -2 => 0.2,
+        -1 => 0.3,
+        0 => 0.4,
+        1 => 0.45,
+        2 => 0.5,
+        3 => 0.55,
+        4 => 0.60,
+        5 => 0.65,
+        6 => 0.70,
+        7 => 0.75,
+        8 => 0.80,
+        9 => 0.85,
+        10 => 0.9,
+        11 => 1.0,
+        12 => 1.05,
+        13 => 1.10,
+        14 => 1.15,
+        15 => 1.20,
+        16 => 1.25,
+        17 => 1.30,
+        18 => 1.40,
+        19 => 1.50,
+        20 => 1.60,
+        21 => 1.75,
+        22 => 2.0,
+        23 => 2.5,
+        24 => 3.0,
+        25.. => 4.0,
+    }
+}
+
+/// The market-memory term of a price comment, or nothing at a market's
+/// baseline.
+fn market_comment(dm: i16) -> String {
+    if dm == 0 {
+        String::new()
+    } else {
+        format!(" + (market) {dm}")
+    }
+}
+
 /// Calculate max DM for a set of world trade classes
 ///
 /// Find all relevant DMs given the world trade classes adn the map
@@ -1063,6 +1115,8 @@ mod tests {
             quantity_roll: 10,
             buy_price_roll: None,
             sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
         };
 
         // Check the display output
@@ -1135,6 +1189,8 @@ mod tests {
             quantity_roll: 10,
             buy_price_roll: Some(10),
             sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
         };
 
         let good2 = Good {
@@ -1150,6 +1206,8 @@ mod tests {
             quantity_roll: 10,
             buy_price_roll: Some(10),
             sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
         };
 
         let good3 = Good {
@@ -1165,6 +1223,8 @@ mod tests {
             quantity_roll: 10,
             buy_price_roll: Some(10),
             sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
         };
 
         // Add goods in random order
@@ -1,7 +1,10 @@
 //! # Available Passengers Module
 //!
 //! This module handles the generation of available passengers and freight
-//! for interstellar travel between worlds in the Traveller universe.
+//! for interstellar travel between worlds in the Traveller universe, and
+//! the mail the route offers.
+use crate::systems::world::World;
+use crate::trade::contracts::{MAIL_TARGET, mail_dm};
 use crate::trade::{PortCode, ZoneClassification};
 use crate::util::{roll_1d6, roll_2d6};
 use serde::{Deserialize, Serialize};
@@ -70,6 +73,16 @@ pub struct AvailablePassengers {
     pub incidental_cargo_check_roll: Option<i32>,
     /// Rolls for size of individual incidental cargo lots
     pub incidental_cargo_size_rolls: Vec<i32>,
+
+    /// Number of mail containers offered
+    #[serde(default)]
+    pub mail_containers: i32,
+    /// Raw 2d6 roll for mail availability (saved for recalculation)
+    #[serde(default)]
+    pub mail_roll: Option<i32>,
+    /// 1d6 roll for the number of mail containers (saved for recalculation)
+    #[serde(default)]
+    pub mail_containers_roll: Option<i32>,
 }
 
 impl AvailablePassengers {
@@ -148,6 +161,128 @@ impl AvailablePassengers {
         self.minor_cargo_check_roll = None;
         self.incidental_cargo_roll = None;
         self.incidental_cargo_check_roll = None;
+        self.mail_roll = None;
+        self.mail_containers_roll = None;
+    }
+
+    /// Generates the mail offered for a route between two worlds
+    ///
+    /// Rolls on the mail table (see [`crate::trade::contracts`]): 2d6 plus
+    /// the mail DM must reach 12, and then the ship is offered 1d6
+    /// containers.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - The world the mail is carried from; its bases count
+    /// * `destination` - The world the mail is carried to
+    /// * `distance_parsecs` - Distance between worlds in parsecs
+    /// * `weapons` - Number of weapon turrets on the ship
+    pub fn generate_mail(
+        &mut self,
+        origin: &World,
+        destination: &World,
+        distance_parsecs: i32,
+        weapons: i16,
+    ) {
+        let traffic = Self::traffic_dm(
+            origin.get_population(),
+            origin.port,
+            origin.travel_zone,
+            origin.tech_level,
+            destination.get_population(),
+            destination.port,
+            destination.travel_zone,
+            destination.tech_level,
+            distance_parsecs,
+        );
+        let dm = mail_dm(
+            traffic,
+            weapons,
+            origin.facilities(),
+            origin.tech_level - destination.tech_level,
+        );
+        let roll = *self.mail_roll.get_or_insert_with(roll_2d6);
+        self.mail_containers = if roll + dm >= MAIL_TARGET {
+            *self.mail_containers_roll.get_or_insert_with(roll_1d6)
+        } else {
+            0
+        };
+    }
+
+    /// Freight traffic DM for a route: the population, starport, tech
+    /// level, zone and distance modifiers from the freight tables.
+    ///
+    /// # Arguments
+    ///
+    /// * `origin_population` - Population level of the origin world
+    /// * `origin_port` - Starport quality of the origin world
+    /// * `origin_zone` - Travel zone classification of the origin world
+    /// * `origin_tech_level` - Technology level of the origin world
+    /// * `destination_population` - Population level of the destination world
+    /// * `destination_port` - Starport quality of the destination world
+    /// * `destination_zone` - Travel zone classification of the destination world
+    /// * `destination_tech_level` - Technology level of the destination world
+    /// * `distance_parsecs` - Distance between worlds in parsecs
+    #[allow(clippy::too_many_arguments)]
+    pub fn traffic_dm(
+        origin_population: i32,
+        origin_port: PortCode,
+        origin_zone: ZoneClassification,
+        origin_tech_level: i32,
+        destination_population: i32,
+        destination_port: PortCode,
+        destination_zone: ZoneClassification,
+        destination_tech_level: i32,
+        distance_parsecs: i32,
+    ) -> i32 {
+        let mut dm = 0;
+
+        // Population modifiers
+        for pop in [origin_population, destination_population] {
+            if pop <= 1 {
+                dm -= 4;
+            } else if pop >= 8 {
+                dm += 4;
+            } else if pop >= 6 {
+                dm += 2;
+            }
+        }
+
+        // Starport modifiers
+        for port in [origin_port, destination_port] {
+            match port {
+                PortCode::A => dm += 2,
+                PortCode::B => dm += 1,
+                PortCode::E => dm -= 1,
+                PortCode::X => dm -= 3,
+                _ => {}
+            }
+        }
+
+        // Tech level modifiers
+        for tech_level in [origin_tech_level, destination_tech_level] {
+            if tech_level <= 6 {
+                dm -= 1;
+            } else if tech_level >= 9 {
+                dm += 2;
+            }
+        }
+
+        // Zone modifiers
+        for zone in [origin_zone, destination_zone] {
+            match zone {
+                ZoneClassification::Green => continue,
+                ZoneClassification::Amber => dm -= 2,
+                ZoneClassification::Red => dm -= 6,
+            }
+        }
+
+        // Distance modifier
+        if distance_parsecs > 1 {
+            dm -= distance_parsecs - 1;
+        }
+
+        dm
     }
 
     /// Generates passengers for all passenger classes
@@ -389,50 +524,18 @@ impl AvailablePassengers {
             _ => {}
         }
 
-        // Population modifiers
-        for pop in [origin_population, destination_population] {
-            if pop <= 1 {
-                num_lots -= 4;
-            } else if pop >= 8 {
-                num_lots += 4;
-            } else if pop >= 6 {
-                num_lots += 2;
-            }
-        }
-
-        // Starport modifiers
-        for port in [origin_port, destination_port] {
-            match port {
-                PortCode::A => num_lots += 2,
-                PortCode::B => num_lots += 1,
-                PortCode::E => num_lots -= 1,
-                PortCode::X => num_lots -= 3,
-                _ => {}
-            }
-        }
-
-        // Tech level modifiers
-        for tech_level in [origin_tech_level, destination_tech_level] {
-            if tech_level <= 6 {
-                num_lots -= 1;
-            } else if tech_level >= 9 {
-                num_lots += 2;
-            }
-        }
-
-        // Zone modifiers
-        for zone in [origin_zone, destination_zone] {
-            match zone {
-                ZoneClassification::Green => continue,
-                ZoneClassification::Amber => num_lots -= 2,
-                ZoneClassification::Red => num_lots -= 6,
-            }
-        }
-
-        // Distance modifier
-        if distance_parsecs > 1 {
-            num_lots -= distance_parsecs - 1;
-        }
+        // Route traffic modifiers
+        num_lots += Self::traffic_dm(
+            origin_population,
+            origin_port,
+            origin_zone,
+            origin_tech_level,
+            destination_population,
+            destination_port,
+            destination_zone,
+            destination_tech_level,
+            distance_parsecs,
+        );
 
         // Generate additional size rolls if needed
         let num_new = num_lots - size_rolls.len() as i32;
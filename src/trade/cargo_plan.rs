@@ -0,0 +1,441 @@
+//! # Cargo Plan Module
+//!
+//! Picks which speculative goods to buy for the next leg. A good's sale
+//! price at the destination is a 3d6 roll away, so each good is judged on
+//! its [`SaleOutlook`] — the mean and spread of that roll's prices —
+//! rather than a single estimate.
+//!
+//! [`plan_cargo`] fills the hold left over after freight and passengers,
+//! within the budget, to maximise
+//!
+//! ```text
+//! expected profit − penalty × standard deviation of profit
+//! ```
+//!
+//! where the penalty comes from the chosen [`RiskTolerance`]. Every ton of
+//! a good sells at the same price, so a big lot of one good is riskier
+//! than the same tonnage spread over several. A ton only earns its place
+//! if it beats the freight it displaces.
+//!
+//! The trade computer offers the plan as a suggested manifest; the
+//! simulator can use it for its purchases instead of buying the best
+//! rolled prices.
+use serde::{Deserialize, Serialize};
+
+use crate::trade::TradeClass;
+use crate::trade::available_goods::{AvailableGoodsTable, Good, sell_multiplier};
+
+/// Ways to roll each total on 3d6, from 3 up to 18.
+const THREE_D6_WAYS: [u32; 16] = [1, 3, 6, 10, 15, 21, 25, 27, 27, 25, 21, 15, 10, 6, 3, 1];
+
+/// Hold tons are handed out in steps of at most this share of the hold,
+/// which keeps planning quick for big freighters.
+const STEPS_PER_HOLD: i32 = 200;
+
+/// How much expected profit a trader will give up to narrow the spread of
+/// outcomes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RiskTolerance {
+    /// Maximise expected profit, whatever the spread.
+    Bold,
+    /// Give up half a standard deviation.
+    #[default]
+    Balanced,
+    /// Give up a full standard deviation.
+    Cautious,
+}
+
+impl RiskTolerance {
+    /// Every tolerance, boldest first.
+    pub const ALL: [RiskTolerance; 3] = [
+        RiskTolerance::Bold,
+        RiskTolerance::Balanced,
+        RiskTolerance::Cautious,
+    ];
+
+    /// Standard deviations of profit charged against its expectation.
+    pub fn penalty(self) -> f64 {
+        match self {
+            RiskTolerance::Bold => 0.0,
+            RiskTolerance::Balanced => 0.5,
+            RiskTolerance::Cautious => 1.0,
+        }
+    }
+}
+
+impl std::fmt::Display for RiskTolerance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RiskTolerance::Bold => write!(f, "Bold"),
+            RiskTolerance::Balanced => write!(f, "Balanced"),
+            RiskTolerance::Cautious => write!(f, "Cautious"),
+        }
+    }
+}
+
+/// Mean and standard deviation of a good's per-ton sale price.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SaleOutlook {
+    pub mean: f64,
+    pub std_dev: f64,
+}
+
+impl SaleOutlook {
+    /// A price that is already known, such as one rolled earlier.
+    pub fn known(price: i32) -> Self {
+        SaleOutlook {
+            mean: price as f64,
+            std_dev: 0.0,
+        }
+    }
+
+    /// Prices of the sale roll for a good of `base_cost` with a total DM
+    /// of `sell_dm`, over every 3d6 outcome.
+    pub fn estimate(base_cost: i32, sell_dm: i16) -> Self {
+        let mut mean = 0.0;
+        let mut mean_sq = 0.0;
+        for (roll, ways) in (3i16..).zip(THREE_D6_WAYS) {
+            let price = (base_cost as f64 * sell_multiplier(roll + sell_dm)).round();
+            let p = ways as f64 / 216.0;
+            mean += price * p;
+            mean_sq += price * price * p;
+        }
+        SaleOutlook {
+            mean,
+            std_dev: (mean_sq - mean * mean).max(0.0).sqrt(),
+        }
+    }
+}
+
+/// Where the cargo will be sold, and how much is known about its prices.
+#[derive(Debug, Clone, Copy)]
+pub struct SaleMarket<'a> {
+    /// Trade classes of the destination.
+    pub trade_classes: &'a [TradeClass],
+    /// Broker skills as passed to
+    /// [`AvailableGoodsTable::price_goods_to_sell`].
+    pub buyer_broker_skill: i16,
+    pub supplier_broker_skill: i16,
+    /// Trust a good's already-rolled sale price rather than estimating
+    /// it. Only sound when that roll is the one the sale will use.
+    pub use_rolled_prices: bool,
+}
+
+impl SaleMarket<'_> {
+    /// The outlook for selling `good` here.
+    pub fn outlook(&self, good: &Good) -> SaleOutlook {
+        match (
+            self.use_rolled_prices,
+            good.sell_price_roll,
+            good.sell_price,
+        ) {
+            (true, Some(_), Some(price)) => SaleOutlook::known(price),
+            _ => SaleOutlook::estimate(
+                good.base_cost,
+                good.sell_dm(
+                    self.trade_classes,
+                    self.buyer_broker_skill,
+                    self.supplier_broker_skill,
+                ),
+            ),
+        }
+    }
+}
+
+/// What the hold and purse allow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CargoLimits {
+    pub cargo_capacity: i32,
+    /// Tons already spoken for: booked freight, passengers' baggage and
+    /// goods kept aboard.
+    pub committed_tons: i32,
+    /// Credits available for purchases.
+    pub budget: i64,
+    /// What a spare ton would earn carrying freight, or 0 when there is
+    /// no freight to carry.
+    pub freight_rate_per_ton: i32,
+    pub risk: RiskTolerance,
+}
+
+impl CargoLimits {
+    /// Tons free for speculative cargo.
+    pub fn free_tons(&self) -> i32 {
+        (self.cargo_capacity - self.committed_tons).max(0)
+    }
+}
+
+/// A suggested purchase.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CargoPlan {
+    /// Copies of the market's goods with `transacted` set to the tons to
+    /// buy, ready for [`crate::trade::ship_manifest::ShipManifest::process_trades`].
+    pub purchases: Vec<Good>,
+    pub tons: i32,
+    pub cost: i64,
+    /// Expected sale proceeds less purchase cost.
+    pub expected_profit: f64,
+    /// Standard deviation of that profit.
+    pub std_dev: f64,
+}
+
+impl CargoPlan {
+    /// Mark this plan's purchases on `market`, clearing any others.
+    pub fn apply(&self, market: &mut AvailableGoodsTable) {
+        market.zero_transacted();
+        for good in &self.purchases {
+            if let Some(g) = market
+                .goods
+                .iter_mut()
+                .find(|g| g.source_index == good.source_index)
+            {
+                g.transacted = good.transacted;
+            }
+        }
+    }
+}
+
+/// A good worth buying, and how much of it is planned so far.
+struct Candidate<'a> {
+    good: &'a Good,
+    outlook: SaleOutlook,
+    /// Expected profit per ton over carrying freight instead.
+    margin: f64,
+    tons: i32,
+}
+
+/// Plan the purchase from `goods` for sale at `market` that best trades
+/// expected profit against risk within `limits`.
+///
+/// Tons go out a step at a time to whichever good adds the most
+/// risk-adjusted profit for the hold space or credits it uses, whichever
+/// is scarcer, until no good adds any.
+pub fn plan_cargo(goods: &[Good], market: &SaleMarket, limits: &CargoLimits) -> CargoPlan {
+    let hold = limits.free_tons();
+    if hold <= 0 || limits.budget <= 0 {
+        return CargoPlan::default();
+    }
+    let mut candidates: Vec<Candidate> = goods
+        .iter()
+        .filter(|g| g.quantity > 0 && g.buy_cost > 0)
+        .filter_map(|good| {
+            let outlook = market.outlook(good);
+            let margin = outlook.mean - good.buy_cost as f64 - limits.freight_rate_per_ton as f64;
+            (margin > 0.0).then_some(Candidate {
+                good,
+                outlook,
+                margin,
+                tons: 0,
+            })
+        })
+        .collect();
+
+    let penalty = limits.risk.penalty();
+    let step = (hold / STEPS_PER_HOLD).max(1);
+    let mut free = hold;
+    let mut budget = limits.budget;
+    // Variance of total profit: tons² × price variance, summed over goods.
+    let mut variance = 0.0;
+    loop {
+        let mut best: Option<(usize, i32, f64, f64)> = None;
+        for (i, c) in candidates.iter().enumerate() {
+            let affordable = (budget / c.good.buy_cost as i64).min(i32::MAX as i64) as i32;
+            let take = step.min(c.good.quantity - c.tons).min(free).min(affordable);
+            if take <= 0 {
+                continue;
+            }
+            let tons = (c.tons + take) as f64;
+            let added = (tons * tons - (c.tons as f64).powi(2)) * c.outlook.std_dev.powi(2);
+            let gain =
+                take as f64 * c.margin - penalty * ((variance + added).sqrt() - variance.sqrt());
+            if gain <= 0.0 {
+                continue;
+            }
+            let used = (take as f64 / hold as f64)
+                .max(take as f64 * c.good.buy_cost as f64 / limits.budget as f64);
+            let value = gain / used;
+            if best.is_none_or(|(_, _, _, v)| value > v) {
+                best = Some((i, take, added, value));
+            }
+        }
+        let Some((i, take, added, _)) = best else {
+            break;
+        };
+        let c = &mut candidates[i];
+        c.tons += take;
+        free -= take;
+        budget -= take as i64 * c.good.buy_cost as i64;
+        variance += added;
+    }
+
+    let mut plan = CargoPlan::default();
+    for c in candidates.into_iter().filter(|c| c.tons > 0) {
+        let cost = c.tons as i64 * c.good.buy_cost as i64;
+        plan.tons += c.tons;
+        plan.cost += cost;
+        plan.expected_profit += c.tons as f64 * c.outlook.mean - cost as f64;
+        let mut good = c.good.clone();
+        good.transacted = c.tons;
+        plan.purchases.push(good);
+    }
+    plan.std_dev = variance.sqrt();
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade::table::TradeTable;
+
+    fn good(index: i16, quantity: i32, buy_cost: i32) -> Good {
+        let entry = TradeTable::global().get(index).unwrap();
+        Good {
+            name: entry.name.clone(),
+            quantity,
+            transacted: 0,
+            base_cost: entry.base_cost,
+            buy_cost,
+            buy_cost_comment: String::new(),
+            sell_price: None,
+            sell_price_comment: String::new(),
+            source_index: index,
+            quantity_roll: quantity,
+            buy_price_roll: None,
+            sell_price_roll: None,
+            buy_market_dm: 0,
+            sell_market_dm: 0,
+        }
+    }
+
+    fn market(classes: &[TradeClass]) -> SaleMarket<'_> {
+        SaleMarket {
+            trade_classes: classes,
+            buyer_broker_skill: 0,
+            supplier_broker_skill: 0,
+            use_rolled_prices: false,
+        }
+    }
+
+    fn limits(cargo_capacity: i32, budget: i64, risk: RiskTolerance) -> CargoLimits {
+        CargoLimits {
+            cargo_capacity,
+            committed_tons: 0,
+            budget,
+            freight_rate_per_ton: 0,
+            risk,
+        }
+    }
+
+    #[test]
+    fn outlook_matches_the_roll_table() {
+        // An unmodified 3d6 averages 10.5; the multipliers around it are
+        // 0.9 and 1.0, so the mean sits between them.
+        let outlook = SaleOutlook::estimate(1000, 0);
+        assert!(outlook.mean > 850.0 && outlook.mean < 1000.0, "{outlook:?}");
+        assert!(outlook.std_dev > 100.0);
+        assert!(SaleOutlook::estimate(1000, 4).mean > outlook.mean);
+        assert_eq!(SaleOutlook::known(700).std_dev, 0.0);
+    }
+
+    #[test]
+    fn rolled_prices_are_trusted_only_when_asked() {
+        let mut g = good(11, 10, 1000);
+        g.sell_price_roll = Some(18);
+        g.sell_price = Some(12_345);
+        let mut m = market(&[]);
+        assert_ne!(m.outlook(&g).mean, 12_345.0);
+        m.use_rolled_prices = true;
+        assert_eq!(m.outlook(&g), SaleOutlook::known(12_345));
+    }
+
+    #[test]
+    fn plan_respects_hold_budget_and_commitments() {
+        let goods = vec![good(11, 50, 5_000), good(12, 50, 4_000)];
+        let mut l = limits(60, 10_000_000, RiskTolerance::Bold);
+        l.committed_tons = 25;
+        let plan = plan_cargo(&goods, &market(&[]), &l);
+        assert!(plan.tons <= 35, "{plan:?}");
+
+        let plan = plan_cargo(
+            &goods,
+            &market(&[]),
+            &limits(100, 60_000, RiskTolerance::Bold),
+        );
+        assert!(plan.cost <= 60_000, "{plan:?}");
+        for p in &plan.purchases {
+            let g = goods
+                .iter()
+                .find(|g| g.source_index == p.source_index)
+                .unwrap();
+            assert!(p.transacted <= g.quantity);
+        }
+    }
+
+    #[test]
+    fn only_goods_that_beat_freight_are_bought() {
+        // Bought well under their average sale price, both goods pay.
+        let goods = vec![good(11, 20, 1), good(12, 20, 1)];
+        let plan = plan_cargo(
+            &goods,
+            &market(&[]),
+            &limits(40, 1_000_000, RiskTolerance::Bold),
+        );
+        assert_eq!(plan.tons, 40);
+        assert!(plan.expected_profit > 0.0);
+
+        // Bought at several times base, neither does.
+        let goods = vec![good(11, 20, 100_000), good(12, 20, 100_000)];
+        let plan = plan_cargo(
+            &goods,
+            &market(&[]),
+            &limits(40, 10_000_000, RiskTolerance::Bold),
+        );
+        assert!(plan.purchases.is_empty());
+
+        // A freight rate above the margin crowds the goods out.
+        let goods = vec![good(11, 20, 10_000)];
+        let mut l = limits(40, 10_000_000, RiskTolerance::Bold);
+        let outlook = market(&[]).outlook(&goods[0]);
+        l.freight_rate_per_ton = (outlook.mean - 10_000.0).ceil() as i32 + 1;
+        assert!(plan_cargo(&goods, &market(&[]), &l).purchases.is_empty());
+    }
+
+    #[test]
+    fn caution_spreads_and_shrinks_the_bet() {
+        let classes = [TradeClass::Industrial];
+        let goods: Vec<Good> = (11..=16)
+            .map(|i| {
+                let g = good(i, 100, 1);
+                let buy = (market(&classes).outlook(&g).mean * 0.8) as i32;
+                good(i, 100, buy)
+            })
+            .collect();
+        let bold = plan_cargo(
+            &goods,
+            &market(&classes),
+            &limits(100, 100_000_000, RiskTolerance::Bold),
+        );
+        let cautious = plan_cargo(
+            &goods,
+            &market(&classes),
+            &limits(100, 100_000_000, RiskTolerance::Cautious),
+        );
+        assert!(bold.expected_profit >= cautious.expected_profit);
+        assert!(bold.std_dev >= cautious.std_dev);
+        assert!(cautious.purchases.len() >= bold.purchases.len());
+    }
+
+    #[test]
+    fn apply_marks_the_market() {
+        let mut table = AvailableGoodsTable::new();
+        table.goods = vec![good(11, 20, 1), good(12, 20, 1)];
+        table.goods[1].transacted = 5;
+        let plan = plan_cargo(
+            &table.goods[..1],
+            &market(&[]),
+            &limits(10, 1_000_000, RiskTolerance::Bold),
+        );
+        plan.apply(&mut table);
+        assert_eq!(table.goods[0].transacted, 10);
+        assert_eq!(table.goods[1].transacted, 0);
+    }
+}
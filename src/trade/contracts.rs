@@ -0,0 +1,167 @@
+//! # Mail and Charter Contracts
+//!
+//! Revenue a ship can earn besides passengers, freight and speculation:
+//! carrying the mail under contract, and hiring the whole ship out by the
+//! week.
+//!
+//! Mail follows the Mongoose mail table. A ship is offered mail on a 2D
+//! roll of 12+ after DMs for the route's freight traffic, the ship's
+//! armament, the naval and scout bases at the origin and the gap in tech
+//! level between the two worlds; it is then offered D6 containers. Each
+//! 5-ton container pays a flat Cr25,000 whatever the distance.
+//!
+//! A charter hires the whole ship — hold, staterooms and low berths — for
+//! a number of weeks at a rate set by what the ship can carry. The rates
+//! are the classic two-week charter rates halved to a week.
+
+use serde::{Deserialize, Serialize};
+
+use crate::systems::world::Facility;
+use crate::trade::Ship;
+
+/// Tons of hold space one mail container takes.
+pub const MAIL_CONTAINER_TONS: i32 = 5;
+
+/// Payment in credits for carrying one mail container, at any distance.
+pub const MAIL_PAYMENT_PER_CONTAINER: i32 = 25_000;
+
+/// Modified 2D roll at or above which the ship is offered mail.
+pub const MAIL_TARGET: i32 = 12;
+
+/// Weekly charter rate per ton of cargo capacity.
+pub const CHARTER_PER_CARGO_TON_WEEK: i64 = 450;
+
+/// Weekly charter rate per passenger stateroom.
+pub const CHARTER_PER_STATEROOM_WEEK: i64 = 4_500;
+
+/// Weekly charter rate per low berth.
+pub const CHARTER_PER_LOW_BERTH_WEEK: i64 = 450;
+
+/// Banded DM from a route's freight traffic DM, as the mail table uses it.
+///
+/// `traffic_dm` is the sum of the population, starport, tech level, zone
+/// and distance modifiers from the freight tables — see
+/// [`AvailablePassengers::traffic_dm`](crate::trade::available_passengers::AvailablePassengers::traffic_dm).
+pub fn traffic_band(traffic_dm: i32) -> i32 {
+    match traffic_dm {
+        i32::MIN..=-10 => -2,
+        -9..=-5 => -1,
+        -4..=4 => 0,
+        5..=9 => 1,
+        10..=i32::MAX => 2,
+    }
+}
+
+/// Total DM on the mail roll.
+///
+/// # Arguments
+///
+/// * `traffic_dm` - The route's freight traffic DM
+/// * `weapons` - Number of weapon turrets; an armed ship gets +2
+/// * `origin_bases` - Bases at the origin world; +1 each for naval and scout
+/// * `tech_level_difference` - Tech level gap between the two worlds; -1 per
+///   full two levels
+///
+/// # Examples
+///
+/// ```
+/// use worldgen::systems::world::Facility;
+/// use worldgen::trade::contracts::mail_dm;
+///
+/// // Busy route, armed ship, naval base at origin, worlds 3 TLs apart.
+/// assert_eq!(mail_dm(6, 1, &[Facility::Naval], 3), 1 + 2 + 1 - 1);
+/// ```
+pub fn mail_dm(
+    traffic_dm: i32,
+    weapons: i16,
+    origin_bases: &[Facility],
+    tech_level_difference: i32,
+) -> i32 {
+    let mut dm = traffic_band(traffic_dm);
+    if weapons > 0 {
+        dm += 2;
+    }
+    if origin_bases.contains(&Facility::Naval) {
+        dm += 1;
+    }
+    if origin_bases.contains(&Facility::Scout) {
+        dm += 1;
+    }
+    dm - tech_level_difference.abs() / 2
+}
+
+/// A whole-ship charter: the ship is hired out for `weeks` at
+/// `weekly_rate` credits a week.
+///
+/// The rate is fixed when the charter is agreed, so a later refit
+/// doesn't change what the charterer owes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Charter {
+    /// Length of the charter in weeks
+    pub weeks: i32,
+    /// Credits paid per week
+    pub weekly_rate: i64,
+}
+
+impl Charter {
+    /// Weekly rate for chartering `ship`, from its cargo capacity,
+    /// passenger staterooms and low berths.
+    pub fn weekly_rate_for(ship: &Ship) -> i64 {
+        ship.cargo_capacity.max(0) as i64 * CHARTER_PER_CARGO_TON_WEEK
+            + ship.passenger_staterooms.max(0) as i64 * CHARTER_PER_STATEROOM_WEEK
+            + ship.low_berths.max(0) as i64 * CHARTER_PER_LOW_BERTH_WEEK
+    }
+
+    /// A charter of `ship` for `weeks`, or `None` for no weeks.
+    pub fn for_ship(ship: &Ship, weeks: i32) -> Option<Charter> {
+        (weeks > 0).then(|| Charter {
+            weeks,
+            weekly_rate: Self::weekly_rate_for(ship),
+        })
+    }
+
+    /// Total credits the charter pays.
+    pub fn revenue(&self) -> i64 {
+        self.weeks.max(0) as i64 * self.weekly_rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traffic_band_edges() {
+        assert_eq!(traffic_band(-10), -2);
+        assert_eq!(traffic_band(-9), -1);
+        assert_eq!(traffic_band(-5), -1);
+        assert_eq!(traffic_band(-4), 0);
+        assert_eq!(traffic_band(4), 0);
+        assert_eq!(traffic_band(5), 1);
+        assert_eq!(traffic_band(9), 1);
+        assert_eq!(traffic_band(10), 2);
+    }
+
+    #[test]
+    fn mail_dm_counts_arms_bases_and_tech_gap() {
+        assert_eq!(mail_dm(0, 0, &[], 0), 0);
+        assert_eq!(mail_dm(0, 2, &[], 0), 2);
+        assert_eq!(mail_dm(0, 0, &[Facility::Naval, Facility::Scout], 0), 2);
+        // Military bases don't help, and the gap counts either way round.
+        assert_eq!(mail_dm(0, 0, &[Facility::Military], -5), -2);
+    }
+
+    #[test]
+    fn charter_rate_scales_with_capacity() {
+        let ship = Ship {
+            cargo_capacity: 82,
+            passenger_staterooms: 6,
+            low_berths: 20,
+            ..Default::default()
+        };
+        let charter = Charter::for_ship(&ship, 2).unwrap();
+        assert_eq!(charter.weekly_rate, 82 * 450 + 6 * 4_500 + 20 * 450);
+        assert_eq!(charter.revenue(), 2 * charter.weekly_rate);
+        assert_eq!(Charter::for_ship(&ship, 0), None);
+    }
+}
@@ -0,0 +1,292 @@
+//! # Crew roster
+//!
+//! The individual crew aboard a [`Ship`]: each crewmember's name, role,
+//! skills, salary and whether they're still serving. When a ship carries
+//! a roster, its working skills and payroll come from the crew on it —
+//! see [`Ship::effective_broker_skill`] and friends — and losing a
+//! crewmember loses what they brought aboard.
+//!
+//! [`Ship`]: crate::trade::Ship
+//! [`Ship::effective_broker_skill`]: crate::trade::Ship::effective_broker_skill
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// A crewmember's job aboard.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CrewRole {
+    Captain,
+    Pilot,
+    Astrogator,
+    Engineer,
+    Medic,
+    Steward,
+    Gunner,
+    Broker,
+    /// Any other hand aboard.
+    #[default]
+    Crew,
+}
+
+impl CrewRole {
+    /// Every role, in the order the roster offers them.
+    pub const ALL: [CrewRole; 9] = [
+        CrewRole::Captain,
+        CrewRole::Pilot,
+        CrewRole::Astrogator,
+        CrewRole::Engineer,
+        CrewRole::Medic,
+        CrewRole::Steward,
+        CrewRole::Gunner,
+        CrewRole::Broker,
+        CrewRole::Crew,
+    ];
+}
+
+impl Display for CrewRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// A skill a crewmember can bring to the ship.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Skill {
+    Admin,
+    Astrogation,
+    Broker,
+    Deception,
+    Engineer,
+    Gunner,
+    Leadership,
+    Medic,
+    Pilot,
+    Steward,
+}
+
+impl Skill {
+    /// Every skill, alphabetically.
+    pub const ALL: [Skill; 10] = [
+        Skill::Admin,
+        Skill::Astrogation,
+        Skill::Broker,
+        Skill::Deception,
+        Skill::Engineer,
+        Skill::Gunner,
+        Skill::Leadership,
+        Skill::Medic,
+        Skill::Pilot,
+        Skill::Steward,
+    ];
+}
+
+impl Display for Skill {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl FromStr for Skill {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Skill, String> {
+        Skill::ALL
+            .into_iter()
+            .find(|skill| skill.to_string().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| format!("unknown skill '{}'", s.trim()))
+    }
+}
+
+/// Whether a crewmember is still serving.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CrewStatus {
+    /// Aboard and at work.
+    #[default]
+    Active,
+    /// Aboard and drawing pay, but unfit to use their skills.
+    Injured,
+    /// Killed in service.
+    Dead,
+    /// Left the ship.
+    Left,
+}
+
+impl CrewStatus {
+    /// Every status, serving first.
+    pub const ALL: [CrewStatus; 4] = [
+        CrewStatus::Active,
+        CrewStatus::Injured,
+        CrewStatus::Dead,
+        CrewStatus::Left,
+    ];
+}
+
+impl Display for CrewStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CrewStatus::Left => write!(f, "Left the ship"),
+            _ => write!(f, "{self:?}"),
+        }
+    }
+}
+
+/// One member of a ship's crew.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CrewMember {
+    pub name: String,
+    pub role: CrewRole,
+    /// Skills and their levels. A skill that isn't listed is 0.
+    pub skills: Vec<(Skill, i16)>,
+    /// Salary per 28-day period.
+    pub salary: i64,
+    pub status: CrewStatus,
+}
+
+impl CrewMember {
+    /// An active crewmember with no skills yet.
+    pub fn new(name: impl Into<String>, role: CrewRole, salary: i64) -> CrewMember {
+        CrewMember {
+            name: name.into(),
+            role,
+            salary,
+            ..Default::default()
+        }
+    }
+
+    /// This crewmember with `skill` at `level`.
+    pub fn with_skill(mut self, skill: Skill, level: i16) -> CrewMember {
+        match self.skills.iter_mut().find(|(s, _)| *s == skill) {
+            Some(entry) => entry.1 = level,
+            None => self.skills.push((skill, level)),
+        }
+        self
+    }
+
+    /// Their level in `skill`, 0 if they don't have it.
+    pub fn skill(&self, skill: Skill) -> i16 {
+        self.skills
+            .iter()
+            .find(|(s, _)| *s == skill)
+            .map_or(0, |(_, level)| *level)
+    }
+
+    /// Their skills as text, e.g. `"Broker 2, Pilot 1"`; the inverse of
+    /// [`parse_skills`].
+    pub fn skills_text(&self) -> String {
+        self.skills
+            .iter()
+            .map(|(skill, level)| format!("{skill} {level}"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Whether they're still aboard — and on the payroll.
+    pub fn is_aboard(&self) -> bool {
+        matches!(self.status, CrewStatus::Active | CrewStatus::Injured)
+    }
+
+    /// Whether they're aboard and fit to work.
+    pub fn is_active(&self) -> bool {
+        self.status == CrewStatus::Active
+    }
+}
+
+/// The best level in `skill` among the active crew; 0 if nobody fit to
+/// work has it.
+///
+/// # Examples
+///
+/// ```
+/// use worldgen::trade::crew::{best_skill, CrewMember, CrewRole, CrewStatus, Skill};
+///
+/// let mut crew = vec![
+///     CrewMember::new("Dana", CrewRole::Captain, 0).with_skill(Skill::Broker, 1),
+///     CrewMember::new("Ilse", CrewRole::Broker, 1_000).with_skill(Skill::Broker, 3),
+/// ];
+/// assert_eq!(best_skill(&crew, Skill::Broker), 3);
+///
+/// crew[1].status = CrewStatus::Injured;
+/// assert_eq!(best_skill(&crew, Skill::Broker), 1);
+/// ```
+pub fn best_skill(crew: &[CrewMember], skill: Skill) -> i16 {
+    crew.iter()
+        .filter(|m| m.is_active())
+        .map(|m| m.skill(skill))
+        .max()
+        .unwrap_or(0)
+        .max(0)
+}
+
+/// Parse a skill list written as `"Broker 2, Pilot 1"`. A skill named
+/// without a level is level 0.
+///
+/// # Examples
+///
+/// ```
+/// use worldgen::trade::crew::{parse_skills, Skill};
+///
+/// assert_eq!(
+///     parse_skills("broker 2, Pilot").unwrap(),
+///     vec![(Skill::Broker, 2), (Skill::Pilot, 0)]
+/// );
+/// assert!(parse_skills("Vacc Suit 1").is_err());
+/// ```
+pub fn parse_skills(text: &str) -> Result<Vec<(Skill, i16)>, String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, level) = entry
+                .rsplit_once(char::is_whitespace)
+                .and_then(|(name, level)| Some((name, level.parse::<i16>().ok()?)))
+                .unwrap_or((entry, 0));
+            Ok((name.parse::<Skill>()?, level))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_skill_replaces_an_existing_level() {
+        let member = CrewMember::new("Tomas", CrewRole::Gunner, 1_000)
+            .with_skill(Skill::Gunner, 1)
+            .with_skill(Skill::Gunner, 2);
+        assert_eq!(member.skills, vec![(Skill::Gunner, 2)]);
+        assert_eq!(member.skill(Skill::Pilot), 0);
+    }
+
+    #[test]
+    fn skills_text_round_trips() {
+        let member = CrewMember::new("Ro", CrewRole::Pilot, 6_000)
+            .with_skill(Skill::Pilot, 2)
+            .with_skill(Skill::Leadership, 1);
+        assert_eq!(member.skills_text(), "Pilot 2, Leadership 1");
+        assert_eq!(parse_skills(&member.skills_text()).unwrap(), member.skills);
+        assert_eq!(parse_skills("  ").unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn only_the_active_crew_work() {
+        let mut crew =
+            vec![CrewMember::new("Ava", CrewRole::Steward, 2_000).with_skill(Skill::Steward, 2)];
+        crew[0].status = CrewStatus::Left;
+        assert!(!crew[0].is_aboard());
+        assert_eq!(best_skill(&crew, Skill::Steward), 0);
+        assert_eq!(best_skill(&[], Skill::Steward), 0);
+    }
+
+    #[test]
+    fn crew_member_deserializes_with_missing_fields_as_default() {
+        let member: CrewMember =
+            serde_json::from_str(r#"{"name":"Ro","skills":[["Pilot",2]]}"#).unwrap();
+        assert_eq!(member.role, CrewRole::Crew);
+        assert_eq!(member.status, CrewStatus::Active);
+        assert_eq!(member.skill(Skill::Pilot), 2);
+    }
+}
@@ -0,0 +1,241 @@
+//! # Customs Module
+//!
+//! Customs inspections of ships arriving with contraband — the illegal
+//! goods (trade table entries 61–66) a market only offers when illegal
+//! goods are enabled. Three rolls decide what happens on arrival:
+//!
+//! 1. **Inspection**: 2d6 + half the law level + a zone DM − the ship's
+//!    Admin skill; the ship is searched on [`INSPECTION_TARGET`] or more.
+//! 2. **Concealment**: a searched ship rolls 2d6 + Deception − half the
+//!    law level; below [`CONCEAL_TARGET`] the contraband is found and
+//!    confiscated, and the ship is fined a share of its value that rises
+//!    with the law level.
+//! 3. **Impound**: a concealment total of [`IMPOUND_AT_OR_BELOW`] or less
+//!    also sees the ship impounded for 1d6 weeks and 1d6 ×
+//!    [`IMPOUND_CR_PER_STEP`] in release fees.
+//!
+//! The simulator rolls [`inspect`]; the trade computer shows the odds
+//! from [`CustomsRisk::estimate`].
+use serde::{Deserialize, Serialize};
+
+use crate::systems::world::World;
+use crate::trade::Ship;
+use crate::trade::ZoneClassification;
+use crate::util::{roll_1d6, roll_2d6};
+
+/// An inspection total at or above this gets the ship searched.
+pub const INSPECTION_TARGET: i32 = 10;
+/// A concealment total below this means the contraband is found.
+pub const CONCEAL_TARGET: i32 = 8;
+/// A concealment total at or below this also gets the ship impounded.
+pub const IMPOUND_AT_OR_BELOW: i32 = 3;
+/// Fine, as a percentage of the contraband's value, per law level.
+pub const FINE_PCT_PER_LAW_LEVEL: i64 = 10;
+/// Release fee per 1d6 rolled for an impounded ship.
+pub const IMPOUND_CR_PER_STEP: i64 = 10_000;
+
+/// Inspection-roll DM for the destination's travel zone.
+fn zone_dm(zone: ZoneClassification) -> i32 {
+    match zone {
+        ZoneClassification::Green => 0,
+        ZoneClassification::Amber => 1,
+        ZoneClassification::Red => 2,
+    }
+}
+
+/// Total DM on the inspection roll at `world` for a ship whose Admin
+/// skill is `admin`.
+pub fn inspection_dm(world: &World, admin: i16) -> i32 {
+    world.get_law_level().max(0) / 2 + zone_dm(world.travel_zone) - admin as i32
+}
+
+/// Total DM on the concealment roll at `world` for a ship whose Deception
+/// skill is `deception`.
+pub fn concealment_dm(world: &World, deception: i16) -> i32 {
+    deception as i32 - world.get_law_level().max(0) / 2
+}
+
+/// Fine for contraband worth `value` at `world`.
+pub fn contraband_fine(world: &World, value: i64) -> i64 {
+    value.max(0) * world.get_law_level().max(0) as i64 * FINE_PCT_PER_LAW_LEVEL / 100
+}
+
+/// The rolls and outcome of one arrival's customs check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Inspection {
+    pub inspection_roll: i32,
+    pub inspection_dm: i32,
+    pub inspected: bool,
+    /// Concealment roll and DM, when the ship was searched.
+    pub concealment: Option<(i32, i32)>,
+    pub found: bool,
+    pub impounded: bool,
+}
+
+/// Roll customs for `ship` arriving at `world`.
+pub fn inspect(world: &World, ship: &Ship) -> Inspection {
+    let inspection_roll = roll_2d6();
+    let inspection_dm = inspection_dm(world, ship.effective_admin_skill());
+    let inspected = inspection_roll + inspection_dm >= INSPECTION_TARGET;
+    if !inspected {
+        return Inspection {
+            inspection_roll,
+            inspection_dm,
+            inspected,
+            concealment: None,
+            found: false,
+            impounded: false,
+        };
+    }
+    let roll = roll_2d6();
+    let dm = concealment_dm(world, ship.effective_deception_skill());
+    Inspection {
+        inspection_roll,
+        inspection_dm,
+        inspected,
+        concealment: Some((roll, dm)),
+        found: roll + dm < CONCEAL_TARGET,
+        impounded: roll + dm <= IMPOUND_AT_OR_BELOW,
+    }
+}
+
+/// Weeks held and release fee for an impounded ship.
+pub fn roll_impound() -> (u32, i64) {
+    let weeks = roll_1d6() as u32;
+    let fee = roll_1d6() as i64 * IMPOUND_CR_PER_STEP;
+    (weeks, fee)
+}
+
+/// Chance that 2d6 + `dm` reaches `target`.
+fn chance_at_least(target: i32, dm: i32) -> f64 {
+    let hits = (1..=6)
+        .flat_map(|a| (1..=6).map(move |b| a + b))
+        .filter(|roll| roll + dm >= target)
+        .count();
+    hits as f64 / 36.0
+}
+
+/// The odds of carrying contraband into a world, for display.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CustomsRisk {
+    /// Chance the ship is searched.
+    pub inspection_chance: f64,
+    /// Chance the contraband is found and seized.
+    pub seizure_chance: f64,
+    /// Chance the ship is also impounded.
+    pub impound_chance: f64,
+    /// Expected credits lost: seized cargo, fines and release fees.
+    pub expected_loss: f64,
+}
+
+impl CustomsRisk {
+    /// Odds for `ship` arriving at `world` with contraband worth
+    /// `contraband_value`. No contraband, no risk.
+    pub fn estimate(world: &World, ship: &Ship, contraband_value: i64) -> Self {
+        if contraband_value <= 0 {
+            return CustomsRisk::default();
+        }
+        let inspection_chance = chance_at_least(
+            INSPECTION_TARGET,
+            inspection_dm(world, ship.effective_admin_skill()),
+        );
+        let conceal = concealment_dm(world, ship.effective_deception_skill());
+        let found = 1.0 - chance_at_least(CONCEAL_TARGET, conceal);
+        let impounded = 1.0 - chance_at_least(IMPOUND_AT_OR_BELOW + 1, conceal);
+        let seizure_chance = inspection_chance * found;
+        let impound_chance = inspection_chance * impounded;
+        // A 1d6 release fee averages 3.5 steps.
+        let expected_fee = 3.5 * IMPOUND_CR_PER_STEP as f64;
+        let seizure_cost = (contraband_value + contraband_fine(world, contraband_value)) as f64;
+        CustomsRisk {
+            inspection_chance,
+            seizure_chance,
+            impound_chance,
+            expected_loss: seizure_chance * seizure_cost + impound_chance * expected_fee,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::RngScope;
+
+    fn world(uwp: &str, zone: ZoneClassification) -> World {
+        let mut world = World::from_uwp("Test", uwp, false, true).unwrap();
+        world.travel_zone = zone;
+        world
+    }
+
+    #[test]
+    fn inspection_odds_rise_with_law_and_zone() {
+        let ship = Ship::default();
+        let lax = CustomsRisk::estimate(&world("C565300-8", ZoneClassification::Green), &ship, 1);
+        let strict =
+            CustomsRisk::estimate(&world("C56539A-8", ZoneClassification::Green), &ship, 1);
+        let red = CustomsRisk::estimate(&world("C56539A-8", ZoneClassification::Red), &ship, 1);
+        assert!(lax.inspection_chance < strict.inspection_chance);
+        assert!(strict.inspection_chance < red.inspection_chance);
+        assert!(lax.seizure_chance < strict.seizure_chance);
+    }
+
+    #[test]
+    fn skills_lower_the_odds() {
+        let w = world("A788899-A", ZoneClassification::Amber);
+        let green_crew = CustomsRisk::estimate(&w, &Ship::default(), 100_000);
+        let old_hands = CustomsRisk::estimate(
+            &w,
+            &Ship {
+                admin_skill: 2,
+                deception_skill: 3,
+                ..Ship::default()
+            },
+            100_000,
+        );
+        assert!(old_hands.inspection_chance < green_crew.inspection_chance);
+        assert!(old_hands.seizure_chance < green_crew.seizure_chance);
+        assert!(old_hands.expected_loss < green_crew.expected_loss);
+    }
+
+    #[test]
+    fn no_contraband_no_risk() {
+        let w = world("A788899-A", ZoneClassification::Red);
+        assert_eq!(
+            CustomsRisk::estimate(&w, &Ship::default(), 0),
+            CustomsRisk::default()
+        );
+    }
+
+    #[test]
+    fn fines_scale_with_law_level() {
+        assert_eq!(
+            contraband_fine(&world("A788899-A", ZoneClassification::Green), 10_000),
+            9_000
+        );
+        assert_eq!(
+            contraband_fine(&world("C565300-8", ZoneClassification::Green), 10_000),
+            0
+        );
+    }
+
+    #[test]
+    fn inspect_is_consistent_with_its_rolls() {
+        let w = world("A788899-A", ZoneClassification::Amber);
+        let ship = Ship::default();
+        let _guard = RngScope::new(3);
+        for _ in 0..200 {
+            let check = inspect(&w, &ship);
+            assert_eq!(
+                check.inspected,
+                check.inspection_roll + check.inspection_dm >= INSPECTION_TARGET
+            );
+            match check.concealment {
+                Some((roll, dm)) => {
+                    assert_eq!(check.found, roll + dm < CONCEAL_TARGET);
+                    assert_eq!(check.impounded, roll + dm <= IMPOUND_AT_OR_BELOW);
+                }
+                None => assert!(!check.found && !check.impounded),
+            }
+        }
+    }
+}
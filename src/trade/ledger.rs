@@ -0,0 +1,444 @@
+//! # Ledger Module
+//!
+//! A running record of every credit a ship earns or spends: goods bought
+//! and sold, passenger fares, freight, mail and charter payments, port
+//! charges, life support, fuel, and the periodic maintenance, salary and
+//! mortgage payments.
+//!
+//! The ledger is kept double-entry style. Each [`LedgerEntry`] moves
+//! credits between the ship's cash and one named [`Account`]: income
+//! debits cash and credits the income account, an expense debits the
+//! expense account and credits cash. The running cash balance is then
+//! just the sum of the entries, and the per-account totals always add
+//! up to it.
+//!
+//! The trade computer keeps a ledger per ship on its
+//! [`TradeState`](crate::comms::TradeState); the ship simulator builds one
+//! from a voyage's steps. Either exports to CSV for a spreadsheet or to
+//! JSON.
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::simulator::types::Date;
+use crate::trade::Ship;
+use crate::trade::available_goods::Good;
+use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::port_fees::PortCharges;
+use crate::trade::ship_manifest::ShipManifest;
+
+/// Income and expense accounts the ledger posts against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Account {
+    /// Credits the ship started with
+    Capital,
+    /// Speculative goods sold
+    GoodsSales,
+    /// Speculative goods bought
+    GoodsPurchases,
+    /// Passenger fares of every class
+    PassengerFares,
+    /// Freight carried for hire
+    Freight,
+    /// Mail contract payments
+    Mail,
+    /// Whole-ship charter fees
+    Charter,
+    /// Berthing fees and customs tariffs
+    PortCharges,
+    /// Stateroom, passenger, low berth and crew life support
+    LifeSupport,
+    /// Fuel bought or paid for per jump
+    Fuel,
+    /// Ship maintenance
+    Maintenance,
+    /// Crew salaries
+    Salary,
+    /// Mortgage payments
+    Mortgage,
+    /// Fines, seizures and impound fees
+    Customs,
+    /// Repairs after accidents and misjumps
+    Repairs,
+    /// Credits lost to pirates and scams
+    Losses,
+}
+
+impl Account {
+    /// The cash account every entry posts against.
+    pub const CASH: &'static str = "Cash";
+
+    /// Display name, as used in the CSV export.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Account::Capital => "Capital",
+            Account::GoodsSales => "Goods Sales",
+            Account::GoodsPurchases => "Goods Purchases",
+            Account::PassengerFares => "Passenger Fares",
+            Account::Freight => "Freight",
+            Account::Mail => "Mail",
+            Account::Charter => "Charter",
+            Account::PortCharges => "Port Charges",
+            Account::LifeSupport => "Life Support",
+            Account::Fuel => "Fuel",
+            Account::Maintenance => "Maintenance",
+            Account::Salary => "Salary",
+            Account::Mortgage => "Mortgage",
+            Account::Customs => "Customs",
+            Account::Repairs => "Repairs",
+            Account::Losses => "Losses",
+        }
+    }
+}
+
+impl Display for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// One posting: `amount` credits between cash and `account`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Imperial date of the transaction
+    pub date: Date,
+    /// World the transaction happened at
+    pub world: String,
+    /// Income or expense account
+    pub account: Account,
+    /// What the credits were for
+    pub description: String,
+    /// Credits into cash if positive, out of cash if negative
+    pub amount: i64,
+}
+
+impl LedgerEntry {
+    /// Account debited: cash for income, the account for an expense.
+    pub fn debit(&self) -> &'static str {
+        if self.amount >= 0 {
+            Account::CASH
+        } else {
+            self.account.name()
+        }
+    }
+
+    /// Account credited: the account for income, cash for an expense.
+    pub fn credit(&self) -> &'static str {
+        if self.amount >= 0 {
+            self.account.name()
+        } else {
+            Account::CASH
+        }
+    }
+}
+
+/// A ship's financial ledger, oldest entry first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+}
+
+impl Ledger {
+    /// Post `amount` credits against `account`. Zero amounts aren't
+    /// recorded.
+    pub fn record(
+        &mut self,
+        date: Date,
+        world: &str,
+        account: Account,
+        description: impl Into<String>,
+        amount: i64,
+    ) {
+        if amount == 0 {
+            return;
+        }
+        self.entries.push(LedgerEntry {
+            date,
+            world: world.to_string(),
+            account,
+            description: description.into(),
+            amount,
+        });
+    }
+
+    /// Cash balance: the sum of every entry.
+    pub fn balance(&self) -> i64 {
+        self.entries.iter().map(|e| e.amount).sum()
+    }
+
+    /// Net credits posted against `account`.
+    pub fn total(&self, account: Account) -> i64 {
+        self.entries
+            .iter()
+            .filter(|e| e.account == account)
+            .map(|e| e.amount)
+            .sum()
+    }
+
+    /// Record the settlement of a trade-computer leg at `world`: goods
+    /// sold from `manifest`, `buy_goods` bought, fares, freight, mail and
+    /// charter for a `distance`-parsec jump, and the port's `charges`.
+    ///
+    /// Posts exactly what [`ShipManifest::process_trades`] adds to the
+    /// manifest's profit, so call it before that clears the manifest.
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_trades(
+        &mut self,
+        date: Date,
+        world: &str,
+        distance: i32,
+        manifest: &ShipManifest,
+        buy_goods: &[Good],
+        available_passengers: &Option<AvailablePassengers>,
+        charges: &PortCharges,
+    ) {
+        for good in manifest
+            .trade_goods
+            .goods
+            .iter()
+            .filter(|g| g.transacted > 0)
+        {
+            if let Some(price) = good.sell_price {
+                self.record(
+                    date,
+                    world,
+                    Account::GoodsSales,
+                    format!("Sold {}t {}", good.transacted, good.name),
+                    good.transacted as i64 * price as i64,
+                );
+            }
+        }
+        for good in buy_goods.iter().filter(|g| g.transacted > 0) {
+            self.record(
+                date,
+                world,
+                Account::GoodsPurchases,
+                format!("Bought {}t {}", good.transacted, good.name),
+                -(good.transacted as i64 * good.buy_cost as i64),
+            );
+        }
+        self.record(
+            date,
+            world,
+            Account::PassengerFares,
+            format!(
+                "{} high, {} middle, {} basic, {} low passengers",
+                manifest.high_passengers,
+                manifest.medium_passengers,
+                manifest.basic_passengers,
+                manifest.low_passengers
+            ),
+            manifest.passenger_revenue(distance) as i64,
+        );
+        if let Some(passengers) = available_passengers {
+            self.record(
+                date,
+                world,
+                Account::Freight,
+                format!("{}t freight", manifest.total_freight_tons(passengers)),
+                manifest.freight_revenue(distance, passengers) as i64,
+            );
+        }
+        self.record(
+            date,
+            world,
+            Account::Mail,
+            format!("{} mail containers", manifest.mail_containers),
+            manifest.mail_revenue() as i64,
+        );
+        if let Some(charter) = manifest.charter {
+            self.record(
+                date,
+                world,
+                Account::Charter,
+                format!("Charter, {} weeks", charter.weeks),
+                charter.revenue(),
+            );
+        }
+        self.record(
+            date,
+            world,
+            Account::PortCharges,
+            "Berthing",
+            -charges.berthing,
+        );
+        self.record(
+            date,
+            world,
+            Account::PortCharges,
+            "Customs tariffs",
+            -(charges.import_tariff + charges.export_tariff),
+        );
+    }
+
+    /// Record one 28-day period of `ship`'s fixed costs: maintenance,
+    /// salary and mortgage, as [`Ship::monthly_expenses`] totals them.
+    pub fn record_periodic(&mut self, date: Date, world: &str, ship: &Ship) {
+        self.record(
+            date,
+            world,
+            Account::Maintenance,
+            "Maintenance",
+            -ship.maintenance_per_period,
+        );
+        self.record(
+            date,
+            world,
+            Account::Salary,
+            "Crew salaries",
+            -ship.effective_salary_per_period(),
+        );
+        self.record(
+            date,
+            world,
+            Account::Mortgage,
+            "Mortgage payment",
+            -ship.mortgage_per_period,
+        );
+    }
+
+    /// The ledger as CSV, one row per entry with the running cash
+    /// balance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use worldgen::simulator::types::Date;
+    /// use worldgen::trade::ledger::{Account, Ledger};
+    ///
+    /// let mut ledger = Ledger::default();
+    /// ledger.record(Date::new(10, 1105), "Regina", Account::Freight, "12t freight", 12_000);
+    /// ledger.record(Date::new(10, 1105), "Regina", Account::PortCharges, "Berthing", -1_000);
+    ///
+    /// let csv = ledger.to_csv();
+    /// let rows: Vec<&str> = csv.lines().collect();
+    /// assert_eq!(rows[0], "date,world,debit,credit,description,amount,balance");
+    /// assert_eq!(rows[1], "010-1105,Regina,Cash,Freight,12t freight,12000,12000");
+    /// assert_eq!(rows[2], "010-1105,Regina,Port Charges,Cash,Berthing,1000,11000");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("date,world,debit,credit,description,amount,balance\n");
+        let mut balance = 0;
+        for entry in &self.entries {
+            balance += entry.amount;
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{}\n",
+                entry.date.format(),
+                csv_field(&entry.world),
+                entry.debit(),
+                entry.credit(),
+                csv_field(&entry.description),
+                entry.amount.abs(),
+                balance
+            ));
+        }
+        csv
+    }
+
+    /// The ledger as pretty-printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Quote a CSV field if it holds a comma, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn good(name: &str, transacted: i32, buy_cost: i32, sell_price: Option<i32>) -> Good {
+        Good {
+            name: name.to_string(),
+            quantity: transacted,
+            transacted,
+            buy_cost,
+            sell_price,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn record_trades_matches_process_trades_profit() {
+        let mut manifest = ShipManifest {
+            high_passengers: 2,
+            low_passengers: 3,
+            mail_containers: 1,
+            ..Default::default()
+        };
+        manifest
+            .trade_goods
+            .add_good(good("Radioactives", 5, 900_000, Some(1_100_000)));
+        let buy = vec![good("Wood", 10, 1_000, None)];
+        let charges = PortCharges {
+            berthing: 500,
+            import_tariff: 1_000,
+            export_tariff: 250,
+        };
+
+        let mut ledger = Ledger::default();
+        ledger.record_trades(
+            Date::default(),
+            "Efate",
+            2,
+            &manifest,
+            &buy,
+            &None,
+            &charges,
+        );
+        manifest.process_trades(2, &buy, &None, &charges);
+
+        assert_eq!(ledger.balance(), manifest.profit);
+        assert_eq!(ledger.total(Account::GoodsSales), 5_500_000);
+        assert_eq!(ledger.total(Account::GoodsPurchases), -10_000);
+        assert_eq!(ledger.total(Account::PortCharges), -1_750);
+    }
+
+    #[test]
+    fn record_periodic_splits_monthly_expenses() {
+        let ship = Ship {
+            mortgage_per_period: 100_000,
+            maintenance_per_period: 30_000,
+            salary_per_period: 50_000,
+            ..Default::default()
+        };
+        let mut ledger = Ledger::default();
+        ledger.record_periodic(Date::default(), "Efate", &ship);
+        assert_eq!(ledger.entries.len(), 3);
+        assert_eq!(ledger.balance(), -ship.monthly_expenses());
+        assert_eq!(ledger.entries[0].debit(), "Maintenance");
+        assert_eq!(ledger.entries[0].credit(), Account::CASH);
+    }
+
+    #[test]
+    fn csv_quotes_awkward_fields_and_json_round_trips() {
+        let mut ledger = Ledger::default();
+        ledger.record(
+            Date::default(),
+            "Hell, Gateway",
+            Account::Losses,
+            "Scammed by a \"broker\"",
+            -2_000,
+        );
+        ledger.record(Date::default(), "Nowhere", Account::Fuel, "Nothing", 0);
+
+        let csv = ledger.to_csv();
+        assert_eq!(
+            csv.lines().nth(1),
+            Some(
+                "001-1105,\"Hell, Gateway\",Losses,Cash,\"Scammed by a \"\"broker\"\"\",2000,-2000"
+            )
+        );
+        assert_eq!(csv.lines().count(), 2);
+
+        let json = ledger.to_json().unwrap();
+        assert_eq!(serde_json::from_str::<Ledger>(&json).unwrap(), ledger);
+    }
+}
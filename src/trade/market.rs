@@ -0,0 +1,383 @@
+//! # Market Module
+//!
+//! Memory of what ships have done to each world's market. A market is
+//! otherwise rolled fresh on every visit by
+//! [`AvailableGoodsTable::for_world`], so a ship that dumps 200 tons of
+//! electronics on a world would see the same price the next week. The
+//! [`MarketLedger`] remembers, per world and per good, the net tons ships
+//! have sold into or bought out of the market:
+//!
+//! - **Supply**: goods bought out are missing from the next visit's
+//!   listing; goods sold in are added to it.
+//! - **Prices**: the net tons, scaled by the world's population, become a
+//!   DM on the buy and sell rolls ([`Good::buy_market_dm`] and
+//!   [`Good::sell_market_dm`]). A glut makes a good cheaper to buy and
+//!   worth less to sell; a shortage does the opposite.
+//! - **Recovery**: the net tons halve every
+//!   [`RECOVERY_HALF_LIFE_DAYS`], so a market drifts back to its baseline.
+//!
+//! Worlds are keyed by galactic hex where a sector-origin table places
+//! them, else by sector and hex ([`MarketKey`]), so the trade server,
+//! which knows its worlds' galactic hexes, and the simulator, which knows
+//! their sectors, key the same world alike. Days are whatever
+//! day count the caller keeps, as long as it keeps one consistently: the
+//! simulator and the trade server both count Imperial days, from the
+//! voyage's date and each ship's trading date.
+//!
+//! [`AvailableGoodsTable::for_world`]: crate::trade::available_goods::AvailableGoodsTable::for_world
+//! [`Good::buy_market_dm`]: crate::trade::available_goods::Good::buy_market_dm
+//! [`Good::sell_market_dm`]: crate::trade::available_goods::Good::sell_market_dm
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::sector::origins::{SectorOrigins, normalize};
+use crate::trade::available_goods::AvailableGoodsTable;
+
+/// Days for a market's memory of a trade to fall by half.
+pub const RECOVERY_HALF_LIFE_DAYS: f64 = 14.0;
+/// Net tons per point of population that shift a good's price by one DM.
+pub const TONS_PER_DM_PER_POP: f64 = 10.0;
+/// Largest DM a market's memory can put on a price roll, either way.
+pub const MAX_MARKET_DM: i16 = 4;
+
+/// Identifies a world's market: by galactic hex when its sector's origin
+/// is known, else by sector and hex. Sector names match loosely, as in
+/// [`SectorOrigins`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct MarketKey(String);
+
+impl MarketKey {
+    /// The market at `hex_x`, `hex_y` in `sector`, placed by the built-in
+    /// sector origins.
+    pub fn new(sector: &str, hex_x: i32, hex_y: i32) -> Self {
+        MarketKey::placed(SectorOrigins::builtin(), sector, hex_x, hex_y)
+    }
+
+    /// The market at `hex_x`, `hex_y` in `sector`. Keys the same as
+    /// [`MarketKey::at_galactic`] when `origins` places the sector, so a
+    /// world outside the built-in sectors still matches the trade server's
+    /// key as long as the simulator's world source knows its origin.
+    pub fn placed(origins: &SectorOrigins, sector: &str, hex_x: i32, hex_y: i32) -> Self {
+        match origins.to_galactic(sector, hex_x, hex_y) {
+            Some((gx, gy)) => MarketKey::at_galactic(gx, gy),
+            None => MarketKey(format!("{}/{:02}{:02}", normalize(sector), hex_x, hex_y)),
+        }
+    }
+
+    /// The market at galactic hex `(gx, gy)`.
+    pub fn at_galactic(gx: i32, gy: i32) -> Self {
+        MarketKey(format!("{gx},{gy}"))
+    }
+}
+
+/// One world's memory of trade.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorldMarket {
+    /// Day `net_tons` was last brought up to date.
+    pub day: i64,
+    /// Per good (by trade table index), net tons sold into (+) or bought
+    /// out of (−) the market as of `day`. Whole tons, so a stored ledger
+    /// reads back exactly.
+    #[serde(with = "index_keys")]
+    pub net_tons: BTreeMap<i16, i32>,
+}
+
+/// Good indices as map keys, written as strings. JSON writes them that
+/// way anyway; Firestore reads every map key back as a string, so the
+/// indices must parse from one.
+mod index_keys {
+    use std::collections::BTreeMap;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        map: &BTreeMap<i16, i32>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        map.iter()
+            .map(|(index, tons)| (index.to_string(), *tons))
+            .collect::<BTreeMap<String, i32>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<i16, i32>, D::Error> {
+        BTreeMap::<String, i32>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(index, tons)| {
+                index
+                    .parse()
+                    .map(|index| (index, tons))
+                    .map_err(|_| D::Error::custom(format!("bad good index {index:?}")))
+            })
+            .collect()
+    }
+}
+
+impl WorldMarket {
+    /// Let the market recover up to `day`. Earlier days leave it alone.
+    fn recover_to(&mut self, day: i64) {
+        if day <= self.day {
+            return;
+        }
+        let factor = recovery(day - self.day);
+        for tons in self.net_tons.values_mut() {
+            *tons = (*tons as f64 * factor).round() as i32;
+        }
+        self.net_tons.retain(|_, tons| *tons != 0);
+        self.day = day;
+    }
+
+    /// Net tons of good `index` as of `day`.
+    fn net_tons_on(&self, day: i64, index: i16) -> f64 {
+        self.net_tons
+            .get(&index)
+            .map_or(0.0, |&tons| tons as f64 * recovery((day - self.day).max(0)))
+    }
+}
+
+/// Share of a market's memory left after `days`.
+fn recovery(days: i64) -> f64 {
+    0.5f64.powf(days as f64 / RECOVERY_HALF_LIFE_DAYS)
+}
+
+/// Every world's market memory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MarketLedger {
+    markets: BTreeMap<MarketKey, WorldMarket>,
+}
+
+impl MarketLedger {
+    /// An empty ledger: every market at its baseline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of worlds with a memory of trade.
+    pub fn len(&self) -> usize {
+        self.markets.len()
+    }
+
+    /// Whether every market is at its baseline.
+    pub fn is_empty(&self) -> bool {
+        self.markets.is_empty()
+    }
+
+    /// The memory of the market at `key`, if it has one.
+    pub fn market(&self, key: &MarketKey) -> Option<&WorldMarket> {
+        self.markets.get(key)
+    }
+
+    /// Record `tons` of good `index` sold to the market at `key` on `day`.
+    pub fn record_sale(&mut self, key: &MarketKey, day: i64, index: i16, tons: i32) {
+        self.record(key, day, index, tons);
+    }
+
+    /// Record `tons` of good `index` bought from the market at `key` on
+    /// `day`.
+    pub fn record_purchase(&mut self, key: &MarketKey, day: i64, index: i16, tons: i32) {
+        self.record(key, day, index, -tons);
+    }
+
+    fn record(&mut self, key: &MarketKey, day: i64, index: i16, tons: i32) {
+        if tons == 0 {
+            return;
+        }
+        let market = self
+            .markets
+            .entry(key.clone())
+            .or_insert_with(|| WorldMarket {
+                day,
+                net_tons: BTreeMap::new(),
+            });
+        market.recover_to(day);
+        *market.net_tons.entry(index).or_default() += tons;
+    }
+
+    /// Net tons of good `index` sold into (+) or bought out of (−) the
+    /// market at `key`, as it stands on `day`.
+    pub fn net_tons(&self, key: &MarketKey, day: i64, index: i16) -> f64 {
+        self.markets
+            .get(key)
+            .map_or(0.0, |market| market.net_tons_on(day, index))
+    }
+
+    /// Price DM from the net tons of good `index` at `key` on `day`, on a
+    /// world of `population`: positive after a glut, negative after a
+    /// shortage.
+    pub fn glut_dm(&self, key: &MarketKey, day: i64, index: i16, population: i32) -> i16 {
+        let depth = TONS_PER_DM_PER_POP * population.max(1) as f64;
+        let dm = (self.net_tons(key, day, index) / depth).round() as i16;
+        dm.clamp(-MAX_MARKET_DM, MAX_MARKET_DM)
+    }
+
+    /// Apply the market's memory to a freshly generated `table` for the
+    /// world at `key`: goods bought out are removed from the listing and
+    /// goods sold in added, and each good's buy price takes its
+    /// [`glut_dm`](Self::glut_dm). Call before pricing goods to buy.
+    pub fn adjust_supply(
+        &self,
+        key: &MarketKey,
+        day: i64,
+        population: i32,
+        table: &mut AvailableGoodsTable,
+    ) {
+        for good in &mut table.goods {
+            let tons = self.net_tons(key, day, good.source_index);
+            good.quantity = (good.quantity as f64 + tons).round().max(0.0) as i32;
+            good.buy_market_dm = self.glut_dm(key, day, good.source_index, population);
+        }
+        table.goods.retain(|g| g.quantity > 0);
+    }
+
+    /// Set the sell-price DM of each good in `table` for selling it to
+    /// the world at `key`. Call before pricing goods to sell there.
+    pub fn adjust_demand(
+        &self,
+        key: &MarketKey,
+        day: i64,
+        population: i32,
+        table: &mut AvailableGoodsTable,
+    ) {
+        for good in &mut table.goods {
+            good.sell_market_dm = -self.glut_dm(key, day, good.source_index, population);
+        }
+    }
+
+    /// Let every market recover up to `day`, forgetting those back at
+    /// their baseline.
+    pub fn settle(&mut self, day: i64) {
+        for market in self.markets.values_mut() {
+            market.recover_to(day);
+        }
+        self.markets.retain(|_, market| !market.net_tons.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade::available_goods::Good;
+    use crate::trade::table::TradeTable;
+
+    /// A pop-5 market listing only good `index`, `tons` of it.
+    fn table_with(index: i16, tons: i32) -> AvailableGoodsTable {
+        let entry = TradeTable::global().get(index).unwrap();
+        let mut table = AvailableGoodsTable::new();
+        table.goods.push(Good {
+            name: entry.name.clone(),
+            quantity: tons,
+            base_cost: entry.base_cost,
+            buy_cost: entry.base_cost,
+            source_index: index,
+            ..Default::default()
+        });
+        table
+    }
+
+    #[test]
+    fn keys_match_across_sector_spellings_and_galactic_hexes() {
+        assert_eq!(
+            MarketKey::new("Spinward Marches", 19, 10),
+            MarketKey::new("spinwardmarches", 19, 10)
+        );
+        // Regina, Spinward Marches 1910.
+        let (gx, gy) = SectorOrigins::builtin()
+            .to_galactic("Spinward Marches", 19, 10)
+            .unwrap();
+        assert_eq!(
+            MarketKey::at_galactic(gx, gy),
+            MarketKey::new("Spinward Marches", 19, 10)
+        );
+    }
+
+    #[test]
+    fn keys_match_outside_the_builtin_sectors_given_their_origin() {
+        let mut origins = SectorOrigins::new();
+        origins.insert("Far Reaches", (-40, 25));
+        let (gx, gy) = origins.to_galactic("Far Reaches", 5, 7).unwrap();
+        assert!(SectorOrigins::builtin().from_galactic(gx, gy).is_none());
+        assert_eq!(
+            MarketKey::placed(&origins, "far reaches", 5, 7),
+            MarketKey::at_galactic(gx, gy)
+        );
+        // Without the origin the hex can only be keyed by name.
+        assert_ne!(
+            MarketKey::new("Far Reaches", 5, 7),
+            MarketKey::at_galactic(gx, gy)
+        );
+    }
+
+    #[test]
+    fn a_glut_lowers_prices_and_recovers() {
+        let key = MarketKey::new("Test", 1, 1);
+        let mut ledger = MarketLedger::new();
+        ledger.record_sale(&key, 0, 31, 200);
+
+        assert_eq!(ledger.glut_dm(&key, 0, 31, 5), 4);
+        assert!(ledger.glut_dm(&key, 14, 31, 5) < 4);
+        assert!((ledger.net_tons(&key, 14, 31) - 100.0).abs() < 1e-9);
+        assert_eq!(ledger.glut_dm(&key, 365, 31, 5), 0);
+        // Other goods and other worlds are untouched.
+        assert_eq!(ledger.glut_dm(&key, 0, 32, 5), 0);
+        assert_eq!(ledger.glut_dm(&MarketKey::new("Test", 1, 2), 0, 31, 5), 0);
+
+        ledger.settle(365);
+        assert!(ledger.is_empty());
+    }
+
+    #[test]
+    fn supply_and_prices_follow_the_ledger() {
+        let key = MarketKey::new("Test", 1, 1);
+        let mut ledger = MarketLedger::new();
+        ledger.record_purchase(&key, 0, 31, 30);
+
+        let mut table = table_with(31, 50);
+        ledger.adjust_supply(&key, 0, 3, &mut table);
+        assert_eq!(table.goods[0].quantity, 20);
+        assert_eq!(table.goods[0].buy_market_dm, -1);
+        ledger.adjust_demand(&key, 0, 3, &mut table);
+        assert_eq!(table.goods[0].sell_market_dm, 1);
+
+        // Buying the listing out empties it.
+        ledger.record_purchase(&key, 0, 31, 20);
+        let mut table = table_with(31, 50);
+        ledger.adjust_supply(&key, 0, 3, &mut table);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn market_dm_moves_the_price() {
+        let key = MarketKey::new("Test", 1, 1);
+        let mut ledger = MarketLedger::new();
+        ledger.record_sale(&key, 0, 31, 200);
+
+        let mut baseline = table_with(31, 10);
+        baseline.goods[0].buy_price_roll = Some(10);
+        baseline.goods[0].sell_price_roll = Some(10);
+        let mut glutted = baseline.clone();
+        ledger.adjust_supply(&key, 0, 5, &mut glutted);
+        ledger.adjust_demand(&key, 0, 5, &mut glutted);
+
+        for table in [&mut baseline, &mut glutted] {
+            table.price_goods_to_buy(&[], 0, 0);
+            table.price_goods_to_sell(Some(vec![]), 0, 0);
+        }
+        assert!(glutted.goods[0].buy_cost < baseline.goods[0].buy_cost);
+        assert!(glutted.goods[0].sell_price < baseline.goods[0].sell_price);
+    }
+
+    #[test]
+    fn ledger_round_trips_through_json() {
+        let mut ledger = MarketLedger::new();
+        ledger.record_sale(&MarketKey::new("Test", 1, 1), 3, 31, 40);
+        ledger.record_purchase(&MarketKey::new("Test", 2, 1), 5, 14, 10);
+        let json = serde_json::to_string(&ledger).unwrap();
+        assert!(json.contains(r#""31":40"#), "{json}");
+        assert_eq!(serde_json::from_str::<MarketLedger>(&json).unwrap(), ledger);
+    }
+}
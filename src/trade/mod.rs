@@ -8,8 +8,16 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 pub mod available_goods;
 pub mod available_passengers;
+pub mod cargo_plan;
+pub mod contracts;
+pub mod crew;
+pub mod customs;
+pub mod ledger;
+pub mod market;
+pub mod port_fees;
 pub mod ship;
 pub mod ship_manifest;
+pub mod ship_template;
 pub mod table;
 
 pub use ship::Ship;
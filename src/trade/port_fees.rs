@@ -0,0 +1,180 @@
+//! # Port Fees Module
+//!
+//! Charges a ship pays to use a world's starport and trade there: a
+//! berthing fee for landing, scaled by starport class and hull size, and
+//! customs tariffs on speculative cargo — an import tariff on goods sold
+//! to the world and an export tariff on goods bought from it. Tariff
+//! rates follow the world's law level and government.
+//!
+//! Both the trade computer (via [`ShipManifest::process_trades`]) and the
+//! ship simulator assess these through [`PortCharges`].
+//!
+//! [`ShipManifest::process_trades`]: crate::trade::ship_manifest::ShipManifest::process_trades
+use serde::{Deserialize, Serialize};
+
+use crate::systems::world::World;
+use crate::trade::PortCode;
+use crate::trade::Ship;
+
+/// Smallest hull berthing is charged for, in tons.
+pub const MIN_BERTHING_HULL_TONS: i32 = 100;
+
+/// Berthing fee per 100 tons of hull (or part thereof) at a starport.
+///
+/// Frontier (E) ports and worlds without a starport have no facilities
+/// to charge for.
+pub fn berthing_per_100_tons(port: PortCode) -> i64 {
+    match port {
+        PortCode::A => 1_000,
+        PortCode::B => 500,
+        PortCode::C => 200,
+        PortCode::D => 100,
+        _ => 0,
+    }
+}
+
+/// Berthing fee for a `hull_tons` ship at a port of class `port`.
+pub fn berthing_fee(port: PortCode, hull_tons: i32) -> i64 {
+    let hull = hull_tons.max(MIN_BERTHING_HULL_TONS) as i64;
+    berthing_per_100_tons(port) * ((hull + 99) / 100)
+}
+
+/// Customs tariff rates at a world, in whole percent of cargo value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TariffRates {
+    /// Charged on the proceeds of goods sold to the world.
+    pub import_pct: i64,
+    /// Charged on the cost of goods bought from the world.
+    pub export_pct: i64,
+}
+
+impl TariffRates {
+    /// Rates at `world`. Stricter law raises the import tariff; the
+    /// government adds a surcharge, from nothing under open-market
+    /// governments to the most under dictatorships. A world with no
+    /// government has no customs at all. Exports pay half the import
+    /// rate.
+    pub fn for_world(world: &World) -> Self {
+        let government = world.get_government();
+        if government <= 0 {
+            return TariffRates::default();
+        }
+        let law_pct = match world.get_law_level() {
+            ..=2 => 0,
+            3..=5 => 2,
+            6..=8 => 5,
+            _ => 10,
+        };
+        let government_pct = match government {
+            1 | 2 | 4 => 0,
+            3 | 5 | 8 | 9 | 12 => 2,
+            _ => 5,
+        };
+        let import_pct = law_pct + government_pct;
+        TariffRates {
+            import_pct,
+            export_pct: import_pct / 2,
+        }
+    }
+
+    /// Import tariff on goods sold for `proceeds`.
+    pub fn import_on(&self, proceeds: i64) -> i64 {
+        proceeds.max(0) * self.import_pct / 100
+    }
+
+    /// Export tariff on goods bought for `cost`.
+    pub fn export_on(&self, cost: i64) -> i64 {
+        cost.max(0) * self.export_pct / 100
+    }
+}
+
+/// Everything a port call costs beyond the cargo itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortCharges {
+    /// Berthing fee for the stay.
+    pub berthing: i64,
+    /// Tariff on goods sold here.
+    pub import_tariff: i64,
+    /// Tariff on goods bought here.
+    pub export_tariff: i64,
+}
+
+impl PortCharges {
+    /// Charges for `ship` berthing at `world`, selling goods there for
+    /// `sale_proceeds` and buying goods for `purchase_cost`.
+    pub fn assess(world: &World, ship: &Ship, sale_proceeds: i64, purchase_cost: i64) -> Self {
+        let tariffs = TariffRates::for_world(world);
+        PortCharges {
+            berthing: berthing_fee(world.port, ship.hull_tons),
+            import_tariff: tariffs.import_on(sale_proceeds),
+            export_tariff: tariffs.export_on(purchase_cost),
+        }
+    }
+
+    /// Sum of all charges.
+    pub fn total(&self) -> i64 {
+        self.berthing + self.import_tariff + self.export_tariff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world(uwp: &str) -> World {
+        World::from_uwp("Test", uwp, false, true).unwrap()
+    }
+
+    #[test]
+    fn berthing_scales_with_port_and_hull() {
+        assert_eq!(berthing_fee(PortCode::A, 200), 2_000);
+        assert_eq!(berthing_fee(PortCode::A, 250), 3_000);
+        assert_eq!(berthing_fee(PortCode::C, 400), 800);
+        assert_eq!(berthing_fee(PortCode::D, 100), 100);
+        assert_eq!(berthing_fee(PortCode::E, 1_000), 0);
+        assert_eq!(berthing_fee(PortCode::X, 1_000), 0);
+    }
+
+    #[test]
+    fn unset_hull_pays_the_minimum() {
+        assert_eq!(berthing_fee(PortCode::B, 0), berthing_fee(PortCode::B, 100));
+        assert_eq!(berthing_fee(PortCode::B, 0), 500);
+    }
+
+    #[test]
+    fn tariffs_follow_law_and_government() {
+        // Government 9 (impersonal bureaucracy), law 9.
+        let strict = TariffRates::for_world(&world("A788899-A"));
+        assert_eq!(
+            strict,
+            TariffRates {
+                import_pct: 12,
+                export_pct: 6
+            }
+        );
+        // Government 4 (representative democracy), law 2.
+        let open = TariffRates::for_world(&world("B564542-8"));
+        assert_eq!(open, TariffRates::default());
+        // No government, however strict the law code reads.
+        let lawless = TariffRates::for_world(&world("C56400A-8"));
+        assert_eq!(lawless, TariffRates::default());
+    }
+
+    #[test]
+    fn assess_applies_each_charge() {
+        let ship = Ship {
+            hull_tons: 200,
+            ..Default::default()
+        };
+        let charges = PortCharges::assess(&world("A788899-A"), &ship, 100_000, 50_000);
+        assert_eq!(
+            charges,
+            PortCharges {
+                berthing: 2_000,
+                import_tariff: 12_000,
+                export_tariff: 3_000,
+            }
+        );
+        assert_eq!(charges.total(), 17_000);
+    }
+}
@@ -13,6 +13,9 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::trade::crew::{CrewMember, Skill, best_skill};
+use crate::trade::ship_template::ShipTemplate;
+
 /// Cost in credits to rent a stateroom for one jump.
 ///
 /// One stateroom houses either one high-passage passenger, one
@@ -45,10 +48,17 @@ pub const CREW_LIFE_SUPPORT_PER_MEMBER: i64 = 1_000;
 /// simulator UI exposes as "System broker skill"). Never refer to this
 /// field as the "Player Broker skill".
 ///
-/// `leadership_skill` and `weapons` are only consumed by the simulator;
-/// the trade computer doesn't surface them in its UI. They still belong
-/// on `Ship` so that a single record fully describes the vessel for both
-/// tools.
+/// `leadership_skill` is only consumed by the simulator; the trade
+/// computer doesn't surface it in its UI. It still belongs on `Ship` so
+/// that a single record fully describes the vessel for both tools.
+/// `weapons` drives the simulator's piracy resolution and, in both tools,
+/// the odds of being offered mail.
+///
+/// `crew` is an optional roster of individual crewmembers. When it's
+/// empty the flat `crew_size`, `salary_per_period` and skill fields
+/// describe the crew; once it lists anyone, the `effective_*` methods
+/// derive those values from the crew still aboard instead, and every
+/// consumer reads them through those methods.
 #[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 #[serde(default)]
 pub struct Ship {
@@ -58,6 +68,9 @@ pub struct Ship {
     pub name: String,
 
     // -- Capacity --------------------------------------------------------
+    /// Hull displacement in tons. Scales berthing fees; an unset (`0`)
+    /// hull is charged as the smallest starship hull.
+    pub hull_tons: i32,
     /// Cargo hold capacity in tons.
     pub cargo_capacity: i32,
     /// Number of staterooms allocated to passengers (high / medium /
@@ -84,6 +97,16 @@ pub struct Ship {
     /// system to reduce avoidance failures and shorten layovers; not
     /// surfaced in the trade-computer UI.
     pub leadership_skill: i16,
+    /// Deception skill. Hides contraband from customs searches in the
+    /// simulator and the trade computer's customs-risk estimate.
+    pub deception_skill: i16,
+    /// Admin skill. Keeps the ship's papers in order, making customs
+    /// less inclined to search it.
+    pub admin_skill: i16,
+    /// The individual crew, when the ship keeps a roster. Overrides
+    /// `crew_size`, `salary_per_period` and the skills above; see the
+    /// `effective_*` methods.
+    pub crew: Vec<CrewMember>,
 
     // -- Hardware --------------------------------------------------------
     /// Ship's J-rating: the maximum jump distance it can make in one
@@ -92,8 +115,13 @@ pub struct Ship {
     /// origin/destination and is computed elsewhere.
     pub jump_rating: i16,
     /// Number of weapon turrets. Used by the simulator's piracy
-    /// resolution; not surfaced in the trade-computer UI.
+    /// resolution and by the mail table: an armed ship is more likely to
+    /// be offered mail.
     pub weapons: i16,
+    /// Fuel tankage in tons, as the design carries it. The simulator's
+    /// fuel model is sized from it when a template is applied; `0` means
+    /// the tankage wasn't given.
+    pub fuel_tons: i32,
 
     // -- Periodic costs (per 28-day period) ------------------------------
     /// Mortgage payment per 28-day period. Paid alongside maintenance
@@ -107,21 +135,119 @@ pub struct Ship {
 }
 
 impl Ship {
+    /// A ship built to `template`, named after its class, with no
+    /// skilled crew.
+    pub fn from_template(template: &ShipTemplate) -> Ship {
+        let mut ship = Ship {
+            name: template.name.to_string(),
+            ..Default::default()
+        };
+        ship.apply_template(template);
+        ship
+    }
+
+    /// Refit this ship to `template`: its capacity, crew complement,
+    /// hardware (fuel tankage included) and periodic costs are replaced
+    /// by the design's, while the name and skills are kept.
+    pub fn apply_template(&mut self, template: &ShipTemplate) {
+        self.hull_tons = template.hull_tons;
+        self.cargo_capacity = template.cargo_capacity;
+        self.passenger_staterooms = template.passenger_staterooms;
+        self.low_berths = template.low_berths;
+        self.crew_staterooms = template.crew_staterooms;
+        self.crew_size = template.crew_size;
+        self.jump_rating = template.jump_rating;
+        self.weapons = template.weapons;
+        self.fuel_tons = template.fuel_tons;
+        self.mortgage_per_period = template.mortgage_per_period();
+        self.maintenance_per_period = template.maintenance_per_period();
+        self.salary_per_period = template.salary_per_period;
+    }
+
+    /// Crew aboard: the roster's crew still aboard, injured included,
+    /// or `crew_size` without a roster.
+    pub fn effective_crew_size(&self) -> i32 {
+        if self.crew.is_empty() {
+            self.crew_size
+        } else {
+            self.crew.iter().filter(|m| m.is_aboard()).count() as i32
+        }
+    }
+
+    /// Salary per 28-day period: the pay of the roster's crew still
+    /// aboard, or `salary_per_period` without a roster.
+    pub fn effective_salary_per_period(&self) -> i64 {
+        if self.crew.is_empty() {
+            self.salary_per_period
+        } else {
+            self.crew
+                .iter()
+                .filter(|m| m.is_aboard())
+                .map(|m| m.salary)
+                .sum()
+        }
+    }
+
+    /// The best Broker skill among the active crew, or `broker_skill`
+    /// without a roster.
+    pub fn effective_broker_skill(&self) -> i16 {
+        self.roster_skill(Skill::Broker, self.broker_skill)
+    }
+
+    /// The best Steward skill among the active crew, or `steward_skill`
+    /// without a roster.
+    pub fn effective_steward_skill(&self) -> i16 {
+        self.roster_skill(Skill::Steward, self.steward_skill)
+    }
+
+    /// The best Leadership skill among the active crew, or
+    /// `leadership_skill` without a roster.
+    pub fn effective_leadership_skill(&self) -> i16 {
+        self.roster_skill(Skill::Leadership, self.leadership_skill)
+    }
+
+    /// The best Deception skill among the active crew, or
+    /// `deception_skill` without a roster.
+    pub fn effective_deception_skill(&self) -> i16 {
+        self.roster_skill(Skill::Deception, self.deception_skill)
+    }
+
+    /// The best Admin skill among the active crew, or `admin_skill`
+    /// without a roster.
+    pub fn effective_admin_skill(&self) -> i16 {
+        self.roster_skill(Skill::Admin, self.admin_skill)
+    }
+
+    /// The best Gunner skill among the active crew. A ship without a
+    /// roster has no gunner to speak of.
+    pub fn effective_gunner_skill(&self) -> i16 {
+        self.roster_skill(Skill::Gunner, 0)
+    }
+
+    /// The roster's best `skill`, or `flat` when there's no roster.
+    fn roster_skill(&self, skill: Skill, flat: i16) -> i16 {
+        if self.crew.is_empty() {
+            flat
+        } else {
+            best_skill(&self.crew, skill)
+        }
+    }
+
     /// Total credits paid every 28-day period: mortgage + maintenance +
     /// salary. Used by the trade computer's "Apply monthly expenses"
     /// button and by the simulator's periodic tick.
     pub fn monthly_expenses(&self) -> i64 {
-        self.mortgage_per_period + self.maintenance_per_period + self.salary_per_period
+        self.mortgage_per_period + self.maintenance_per_period + self.effective_salary_per_period()
     }
 
     /// Per-jump life-support cost for the crew:
-    /// `crew_staterooms * STATEROOM_COST + crew_size * CREW_LIFE_SUPPORT_PER_MEMBER`.
+    /// `crew_staterooms * STATEROOM_COST + crew aboard * CREW_LIFE_SUPPORT_PER_MEMBER`.
     ///
     /// Replaces the standalone `simulator::economy::crew_cost(crew_staterooms, crew_size)`.
     /// Negative values for either field are clamped to zero.
     pub fn crew_life_support_per_jump(&self) -> i64 {
         let rooms = self.crew_staterooms.max(0) as i64;
-        let crew = self.crew_size.max(0) as i64;
+        let crew = self.effective_crew_size().max(0) as i64;
         rooms * STATEROOM_COST + crew * CREW_LIFE_SUPPORT_PER_MEMBER
     }
 }
@@ -168,6 +294,71 @@ mod tests {
         assert_eq!(ship.crew_life_support_per_jump(), 0);
     }
 
+    #[test]
+    fn roster_overrides_the_flat_crew_fields() {
+        use crate::trade::crew::{CrewMember, CrewRole, CrewStatus};
+
+        let mut ship = Ship {
+            crew_size: 3,
+            salary_per_period: 9_000,
+            broker_skill: 1,
+            leadership_skill: 2,
+            ..Default::default()
+        };
+        assert_eq!(ship.effective_broker_skill(), 1);
+        assert_eq!(ship.effective_gunner_skill(), 0);
+
+        ship.crew = vec![
+            CrewMember::new("Hale", CrewRole::Captain, 0).with_skill(Skill::Leadership, 1),
+            CrewMember::new("Mori", CrewRole::Broker, 4_000).with_skill(Skill::Broker, 3),
+            CrewMember::new("Okoro", CrewRole::Gunner, 1_000).with_skill(Skill::Gunner, 2),
+        ];
+        assert_eq!(ship.effective_crew_size(), 3);
+        assert_eq!(ship.effective_salary_per_period(), 5_000);
+        assert_eq!(ship.effective_broker_skill(), 3);
+        assert_eq!(ship.effective_leadership_skill(), 1);
+        assert_eq!(ship.effective_steward_skill(), 0);
+        assert_eq!(ship.effective_gunner_skill(), 2);
+
+        // Injured crew stay on the payroll but don't work; the dead don't.
+        ship.crew[1].status = CrewStatus::Injured;
+        ship.crew[2].status = CrewStatus::Dead;
+        assert_eq!(ship.effective_crew_size(), 2);
+        assert_eq!(ship.effective_salary_per_period(), 4_000);
+        assert_eq!(ship.effective_broker_skill(), 0);
+        assert_eq!(ship.effective_gunner_skill(), 0);
+        assert_eq!(ship.monthly_expenses(), 4_000);
+    }
+
+    #[test]
+    fn from_template_takes_the_design_and_its_costs() {
+        let template = ShipTemplate::find("free_trader").unwrap();
+        let ship = Ship::from_template(template);
+        assert_eq!(ship.name, "Type-A Free Trader");
+        assert_eq!(ship.hull_tons, 200);
+        assert_eq!(ship.cargo_capacity, 82);
+        assert_eq!(ship.jump_rating, 1);
+        assert_eq!(ship.mortgage_per_period, 154_500);
+        assert_eq!(ship.maintenance_per_period, 3_090);
+        assert_eq!(ship.broker_skill, 0);
+    }
+
+    #[test]
+    fn apply_template_keeps_name_and_skills() {
+        let mut ship = Ship {
+            name: "Beowulf".to_string(),
+            broker_skill: 2,
+            cargo_capacity: 10,
+            ..Default::default()
+        };
+        ship.apply_template(ShipTemplate::find("far_trader").unwrap());
+        assert_eq!(ship.name, "Beowulf");
+        assert_eq!(ship.broker_skill, 2);
+        assert_eq!(ship.cargo_capacity, 64);
+        assert_eq!(ship.jump_rating, 2);
+        assert_eq!(ship.fuel_tons, 41);
+    }
+
     #[test]
     fn ship_default_is_zeroed() {
         let ship = Ship::default();
@@ -181,6 +372,7 @@ mod tests {
     fn ship_round_trips_through_serde() {
         let ship = Ship {
             name: "Beowulf".to_string(),
+            hull_tons: 200,
             cargo_capacity: 82,
             passenger_staterooms: 6,
             low_berths: 4,
@@ -189,11 +381,18 @@ mod tests {
             broker_skill: 2,
             steward_skill: 1,
             leadership_skill: 1,
+            deception_skill: 0,
+            admin_skill: 2,
             jump_rating: 1,
             weapons: 1,
+            fuel_tons: 22,
             mortgage_per_period: 187_654,
             maintenance_per_period: 5_433,
             salary_per_period: 12_000,
+            crew: vec![
+                CrewMember::new("Ilse", crate::trade::crew::CrewRole::Engineer, 4_000)
+                    .with_skill(Skill::Engineer, 2),
+            ],
         };
         let json = serde_json::to_string(&ship).unwrap();
         let back: Ship = serde_json::from_str(&json).unwrap();
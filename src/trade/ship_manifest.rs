@@ -3,8 +3,9 @@
 //! This module defines the ship manifest structure and revenue calculation
 //! functionality for passenger and freight transport in the Traveller universe.
 //!
-//! The manifest tracks different classes of passengers, freight lots, and trade goods,
-//! and calculates revenue based on distance traveled and passenger/freight types.
+//! The manifest tracks different classes of passengers, freight lots, mail,
+//! any charter the ship is under, and trade goods, and calculates revenue
+//! based on distance traveled and passenger/freight types.
 use serde::{Deserialize, Serialize};
 
 #[allow(unused_imports)]
@@ -13,6 +14,8 @@ use log::{debug, error};
 use crate::systems::world::World;
 use crate::trade::available_goods::{AvailableGoodsTable, Good};
 use crate::trade::available_passengers::AvailablePassengers;
+use crate::trade::contracts::{Charter, MAIL_CONTAINER_TONS, MAIL_PAYMENT_PER_CONTAINER};
+use crate::trade::port_fees::PortCharges;
 
 /// Represents a ship's manifest of passengers, freight, and trade goods
 ///
@@ -31,6 +34,12 @@ pub struct ShipManifest {
     pub low_passengers: i32,
     /// Indices of freight lots from available freight being carried
     pub freight_lot_indices: Vec<usize>,
+    /// Number of mail containers being carried
+    #[serde(default)]
+    pub mail_containers: i32,
+    /// Whole-ship charter for this leg, if the ship has been hired out
+    #[serde(default)]
+    pub charter: Option<Charter>,
     /// Trade goods purchased for speculation
     pub trade_goods: AvailableGoodsTable,
     /// Accumulated profit across processed trades (in credits)
@@ -177,6 +186,22 @@ impl ShipManifest {
         FREIGHT_COST[distance_index] * self.total_freight_tons(available_passengers)
     }
 
+    /// Revenue from the mail containers carried, in credits. Mail pays
+    /// the same at any distance.
+    pub fn mail_revenue(&self) -> i32 {
+        self.mail_containers * MAIL_PAYMENT_PER_CONTAINER
+    }
+
+    /// Tons of hold space taken by the mail containers carried.
+    pub fn mail_tons(&self) -> i32 {
+        self.mail_containers * MAIL_CONTAINER_TONS
+    }
+
+    /// Revenue from the charter, if the ship is chartered, in credits.
+    pub fn charter_revenue(&self) -> i64 {
+        self.charter.map_or(0, |charter| charter.revenue())
+    }
+
     /// Per-ton freight rate at a given jump distance, in credits.
     /// `distance` is clamped to `1..=6` to match the published table.
     pub fn freight_rate_per_ton(distance: i32) -> i32 {
@@ -194,11 +219,12 @@ impl ShipManifest {
     }
 
     /// Total tons of cargo space consumed by the manifest:
-    /// trade goods + freight + passenger personal cargo. Returned as
-    /// `f64` because passenger cargo is fractional.
+    /// trade goods + freight + mail + passenger personal cargo. Returned
+    /// as `f64` because passenger cargo is fractional.
     pub fn total_cargo_used_tons(&self, available_passengers: &AvailablePassengers) -> f64 {
         self.trade_goods_tonnage() as f64
             + self.total_freight_tons(available_passengers) as f64
+            + self.mail_tons() as f64
             + self.passenger_cargo_tons()
     }
 
@@ -244,6 +270,8 @@ impl ShipManifest {
     ///     quantity_roll: 10,
     ///     buy_price_roll: None,
     ///     sell_price_roll: None,
+    ///     buy_market_dm: 0,
+    ///     sell_market_dm: 0,
     /// };
     ///
     /// // Add a good with quantity 5
@@ -298,23 +326,27 @@ impl ShipManifest {
         self.trade_goods.zero_transacted();
     }
 
-    /// Reset passengers and freight selections, preserving trade goods and sell plans
+    /// Reset passengers, freight, mail and charter selections, preserving trade goods and sell plans
     pub fn reset_passengers_and_freight(&mut self) {
         self.high_passengers = 0;
         self.medium_passengers = 0;
         self.basic_passengers = 0;
         self.low_passengers = 0;
         self.freight_lot_indices.clear();
+        self.mail_containers = 0;
+        self.charter = None;
         self.zero_transacted();
     }
 
-    /// Process trades: add current Total to profit and clear passenger/freight counts and sell plans
+    /// Process trades: add current Total, less the port's `charges`, to profit and clear
+    /// passenger/freight/mail counts, the charter and sell plans
     /// Does NOT clear trade_goods quantities (tons) or list; only resets sell_plan to 0 and passenger/freight
     pub fn process_trades(
         &mut self,
         distance: i32,
         buy_goods: &[Good],
         available_passengers: &Option<AvailablePassengers>,
+        charges: &PortCharges,
     ) {
         // Compute current totals
         let passenger_revenue = self.passenger_revenue(distance) as i64;
@@ -323,6 +355,7 @@ impl ShipManifest {
         } else {
             0
         };
+        let contract_revenue = self.mail_revenue() as i64 + self.charter_revenue();
         let goods_profit = self.trade_goods_proceeds()
             - buy_goods
                 .iter()
@@ -351,8 +384,9 @@ impl ShipManifest {
         // Clear the sell plan.
         self.zero_transacted();
 
-        // Compute total revenue
-        let total = passenger_revenue + freight_revenue + goods_profit;
+        // Compute total revenue, net of berthing and tariffs.
+        let total =
+            passenger_revenue + freight_revenue + contract_revenue + goods_profit - charges.total();
 
         // Add to accumulated profit
         self.profit += total;
@@ -381,6 +415,35 @@ mod tests {
         assert!((m.passenger_cargo_tons() - 3.45).abs() < 1e-9);
     }
 
+    #[test]
+    fn process_trades_deducts_port_charges() {
+        let mut m = ShipManifest::default();
+        let charges = PortCharges {
+            berthing: 1_000,
+            import_tariff: 250,
+            export_tariff: 50,
+        };
+        m.process_trades(1, &[], &None, &charges);
+        assert_eq!(m.profit, -1_300);
+    }
+
+    #[test]
+    fn process_trades_pays_mail_and_charter_then_clears_them() {
+        let mut m = ShipManifest {
+            mail_containers: 3,
+            charter: Some(Charter {
+                weeks: 2,
+                weekly_rate: 10_000,
+            }),
+            ..ShipManifest::default()
+        };
+        assert_eq!(m.mail_tons(), 15);
+        m.process_trades(1, &[], &None, &PortCharges::default());
+        assert_eq!(m.profit, 3 * 25_000 + 20_000);
+        assert_eq!(m.mail_containers, 0);
+        assert_eq!(m.charter, None);
+    }
+
     #[test]
     fn total_cargo_used_includes_passenger_cargo() {
         let m = ShipManifest {
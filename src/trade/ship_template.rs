@@ -0,0 +1,214 @@
+//! # Ship templates
+//!
+//! A catalog of canonical ship designs to start a [`Ship`] from instead of
+//! entering every field by hand: the Type-A Free Trader, Type-A2 Far
+//! Trader, Type-R Subsidized Merchant, Type-S Scout/Courier and Type-M
+//! Subsidized Liner.
+//!
+//! Each template carries the design's tonnage, hold, staterooms, low
+//! berths, crew, jump and maneuver ratings, fuel tankage and purchase
+//! price. The periodic costs follow from the price the standard way: a
+//! mortgage of 1/240th of the price per 28-day period, and maintenance
+//! of 0.1% of the price a year, paid in twelve parts.
+//!
+//! [`Ship`]: crate::trade::Ship
+
+/// Purchase price divided by this is the mortgage payment per period.
+pub const MORTGAGE_DIVISOR: i64 = 240;
+
+/// Purchase price divided by this is the maintenance cost per period.
+pub const MAINTENANCE_DIVISOR: i64 = 12_000;
+
+/// A canonical ship design.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShipTemplate {
+    /// Stable key, e.g. `"free_trader"`
+    pub id: &'static str,
+    /// Display name, e.g. `"Type-A Free Trader"`
+    pub name: &'static str,
+    /// Hull displacement in tons
+    pub hull_tons: i32,
+    /// Cargo hold in tons
+    pub cargo_capacity: i32,
+    /// Staterooms for the crew
+    pub crew_staterooms: i32,
+    /// Staterooms for passengers
+    pub passenger_staterooms: i32,
+    /// Low berths
+    pub low_berths: i32,
+    /// Standard crew complement
+    pub crew_size: i32,
+    /// Jump rating in parsecs
+    pub jump_rating: i16,
+    /// Maneuver rating in G
+    pub maneuver_rating: i16,
+    /// Fuel tankage in tons
+    pub fuel_tons: i32,
+    /// Turrets fitted
+    pub weapons: i16,
+    /// Purchase price in credits
+    pub purchase_price: i64,
+    /// Salary of the standard crew per 28-day period
+    pub salary_per_period: i64,
+}
+
+/// The built-in catalog, smallest hull first.
+pub const SHIP_TEMPLATES: &[ShipTemplate] = &[
+    ShipTemplate {
+        id: "scout",
+        name: "Type-S Scout/Courier",
+        hull_tons: 100,
+        cargo_capacity: 11,
+        crew_staterooms: 1,
+        passenger_staterooms: 3,
+        low_berths: 0,
+        crew_size: 1,
+        jump_rating: 2,
+        maneuver_rating: 2,
+        fuel_tons: 23,
+        weapons: 1,
+        purchase_price: 29_430_000,
+        // Pilot.
+        salary_per_period: 6_000,
+    },
+    ShipTemplate {
+        id: "free_trader",
+        name: "Type-A Free Trader",
+        hull_tons: 200,
+        cargo_capacity: 82,
+        crew_staterooms: 4,
+        passenger_staterooms: 6,
+        low_berths: 20,
+        crew_size: 4,
+        jump_rating: 1,
+        maneuver_rating: 1,
+        fuel_tons: 22,
+        weapons: 0,
+        purchase_price: 37_080_000,
+        // Pilot, engineer, medic, steward.
+        salary_per_period: 15_000,
+    },
+    ShipTemplate {
+        id: "far_trader",
+        name: "Type-A2 Far Trader",
+        hull_tons: 200,
+        cargo_capacity: 64,
+        crew_staterooms: 4,
+        passenger_staterooms: 6,
+        low_berths: 6,
+        crew_size: 4,
+        jump_rating: 2,
+        maneuver_rating: 1,
+        fuel_tons: 41,
+        weapons: 1,
+        purchase_price: 50_100_000,
+        // Pilot, astrogator, engineer, steward.
+        salary_per_period: 17_000,
+    },
+    ShipTemplate {
+        id: "subsidized_merchant",
+        name: "Type-R Subsidized Merchant",
+        hull_tons: 400,
+        cargo_capacity: 200,
+        crew_staterooms: 6,
+        passenger_staterooms: 7,
+        low_berths: 9,
+        crew_size: 6,
+        jump_rating: 1,
+        maneuver_rating: 1,
+        fuel_tons: 46,
+        weapons: 2,
+        purchase_price: 101_970_000,
+        // Pilot, astrogator, two engineers, medic, steward.
+        salary_per_period: 24_000,
+    },
+    ShipTemplate {
+        id: "subsidized_liner",
+        name: "Type-M Subsidized Liner",
+        hull_tons: 600,
+        cargo_capacity: 129,
+        crew_staterooms: 9,
+        passenger_staterooms: 21,
+        low_berths: 20,
+        crew_size: 9,
+        jump_rating: 3,
+        maneuver_rating: 1,
+        fuel_tons: 190,
+        weapons: 2,
+        purchase_price: 240_000_000,
+        // Pilot, astrogator, three engineers, medic, three stewards.
+        salary_per_period: 32_000,
+    },
+];
+
+impl ShipTemplate {
+    /// The catalog template with key `id`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use worldgen::trade::ship_template::ShipTemplate;
+    ///
+    /// let far_trader = ShipTemplate::find("far_trader").unwrap();
+    /// assert_eq!(far_trader.jump_rating, 2);
+    /// assert!(ShipTemplate::find("battlecruiser").is_none());
+    /// ```
+    pub fn find(id: &str) -> Option<&'static ShipTemplate> {
+        SHIP_TEMPLATES.iter().find(|t| t.id == id)
+    }
+
+    /// Mortgage payment per 28-day period.
+    pub fn mortgage_per_period(&self) -> i64 {
+        self.purchase_price / MORTGAGE_DIVISOR
+    }
+
+    /// Maintenance cost per 28-day period.
+    pub fn maintenance_per_period(&self) -> i64 {
+        self.purchase_price / MAINTENANCE_DIVISOR
+    }
+
+    /// Tons of fuel a one-parsec jump burns: a tenth of the hull.
+    pub fn fuel_per_parsec(&self) -> i32 {
+        self.hull_tons / 10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_ids_are_unique_and_found() {
+        for template in SHIP_TEMPLATES {
+            assert_eq!(ShipTemplate::find(template.id), Some(template));
+        }
+        for name in [
+            "Type-A Free Trader",
+            "Type-A2 Far Trader",
+            "Type-R Subsidized Merchant",
+            "Type-S Scout/Courier",
+            "Type-M Subsidized Liner",
+        ] {
+            assert!(SHIP_TEMPLATES.iter().any(|t| t.name == name), "{name}");
+        }
+    }
+
+    #[test]
+    fn every_design_carries_fuel_for_its_jump() {
+        for template in SHIP_TEMPLATES {
+            assert!(
+                template.fuel_tons >= template.fuel_per_parsec() * template.jump_rating as i32,
+                "{}",
+                template.name
+            );
+            assert!(template.crew_staterooms >= (template.crew_size + 1) / 2);
+        }
+    }
+
+    #[test]
+    fn periodic_costs_follow_the_price() {
+        let free_trader = ShipTemplate::find("free_trader").unwrap();
+        assert_eq!(free_trader.mortgage_per_period(), 154_500);
+        assert_eq!(free_trader.maintenance_per_period(), 3_090);
+    }
+}
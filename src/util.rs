@@ -71,6 +71,92 @@ pub fn rng_choose<T>(slice: &[T]) -> Option<&T> {
         }
     })
 }
+/// A `rand::Rng` that draws from the worldgen thread-local seeded RNG if
+/// one is installed, otherwise from the system RNG — the same fallback
+/// the dice helpers use. Pass it anywhere an `impl Rng` is wanted so the
+/// call honours an enclosing [`RngScope`] or [`ParkedRng::enter`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WorldgenRng;
+
+impl rand::RngCore for WorldgenRng {
+    fn next_u32(&mut self) -> u32 {
+        WORLDGEN_RNG.with(|cell| match cell.borrow_mut().as_mut() {
+            Some(rng) => rng.next_u32(),
+            None => rand::rng().next_u32(),
+        })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        WORLDGEN_RNG.with(|cell| match cell.borrow_mut().as_mut() {
+            Some(rng) => rng.next_u64(),
+            None => rand::rng().next_u64(),
+        })
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        WORLDGEN_RNG.with(|cell| match cell.borrow_mut().as_mut() {
+            Some(rng) => rng.fill_bytes(dst),
+            None => rand::rng().fill_bytes(dst),
+        })
+    }
+}
+
+/// Seeded RNG state owned by a long-running async task.
+///
+/// [`RngScope`] can't be held across an `.await`: the task may resume on
+/// another thread, leaving the seeded state behind on the old one. A
+/// `ParkedRng` keeps the state in the task instead and installs it as the
+/// thread-local only for the synchronous stretches between awaits, via
+/// [`ParkedRng::enter`]. Drop the returned guard before awaiting.
+#[derive(Debug, Clone)]
+pub struct ParkedRng {
+    rng: Option<ChaCha8Rng>,
+}
+
+impl ParkedRng {
+    pub fn new(seed: u64) -> Self {
+        ParkedRng {
+            rng: Some(ChaCha8Rng::seed_from_u64(seed)),
+        }
+    }
+
+    /// The stream for `seed`, already advanced `word_pos` 32-bit words —
+    /// picks up where a [`ParkedRng::word_pos`] reading left off.
+    pub fn resume(seed: u64, word_pos: u64) -> Self {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_word_pos(word_pos as u128);
+        ParkedRng { rng: Some(rng) }
+    }
+
+    /// How far the parked stream has advanced, in 32-bit words.
+    pub fn word_pos(&self) -> u64 {
+        self.rng.as_ref().map_or(0, |rng| rng.get_word_pos() as u64)
+    }
+
+    /// Install the parked state as the worldgen thread-local until the
+    /// guard drops, at which point the advanced state is parked again and
+    /// the thread's previous RNG restored.
+    pub fn enter(&mut self) -> ParkedRngGuard<'_> {
+        let rng = self.rng.take();
+        let prev = WORLDGEN_RNG.with(|cell| std::mem::replace(&mut *cell.borrow_mut(), rng));
+        ParkedRngGuard { parked: self, prev }
+    }
+}
+
+/// Guard returned by [`ParkedRng::enter`].
+pub struct ParkedRngGuard<'a> {
+    parked: &'a mut ParkedRng,
+    prev: Option<ChaCha8Rng>,
+}
+
+impl Drop for ParkedRngGuard<'_> {
+    fn drop(&mut self) {
+        let prev = self.prev.take();
+        self.parked.rng =
+            WORLDGEN_RNG.with(|cell| std::mem::replace(&mut *cell.borrow_mut(), prev));
+    }
+}
+
 /// Converts Arabic numerals to Roman numerals.
 ///
 /// Used primarily for displaying orbital positions and other small numbers
@@ -539,3 +625,79 @@ mod travellermap_url_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod rng_tests {
+    use super::*;
+
+    #[test]
+    fn worldgen_rng_follows_the_scope() {
+        let draw = || {
+            let mut rng = WorldgenRng;
+            (0..8)
+                .map(|_| rng.random_range(0..1000))
+                .collect::<Vec<i32>>()
+        };
+        let a = {
+            let _scope = RngScope::new(9);
+            draw()
+        };
+        let b = {
+            let _scope = RngScope::new(9);
+            draw()
+        };
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn parked_rng_resumes_where_it_left_off() {
+        let straight: Vec<i32> = {
+            let _scope = RngScope::new(5);
+            (0..6).map(|_| roll_2d6()).collect()
+        };
+
+        let mut parked = ParkedRng::new(5);
+        let mut resumed = Vec::new();
+        for _ in 0..3 {
+            let _guard = parked.enter();
+            resumed.push(roll_2d6());
+            resumed.push(roll_2d6());
+        }
+        assert_eq!(straight, resumed);
+
+        // Entering restores whatever was installed before.
+        let _outer = RngScope::new(77);
+        let expected = {
+            let _probe = RngScope::new(77);
+            roll_1d6()
+        };
+        drop(parked.enter());
+        assert_eq!(roll_1d6(), expected);
+    }
+
+    #[test]
+    fn parked_rng_resumes_from_a_word_position() {
+        use rand::RngCore;
+
+        let mut parked = ParkedRng::new(11);
+        let draw = |parked: &mut ParkedRng, n: usize| {
+            let _guard = parked.enter();
+            let mut rng = WorldgenRng;
+            (0..n)
+                .map(|i| {
+                    if i % 3 == 0 {
+                        rng.next_u32() as u64
+                    } else {
+                        rng.next_u64()
+                    }
+                })
+                .collect::<Vec<u64>>()
+        };
+        // Stop at an odd word count so the resume lands mid-block.
+        draw(&mut parked, 7);
+        let pos = parked.word_pos();
+        let mut resumed = ParkedRng::resume(11, pos);
+        assert_eq!(resumed.word_pos(), pos);
+        assert_eq!(draw(&mut parked, 40), draw(&mut resumed, 40));
+    }
+}
@@ -11,6 +11,7 @@
 use rand::Rng;
 use rand::SeedableRng;
 use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
 
 use super::Uwp;
 use super::climate::{self, HumidityField};
@@ -18,7 +19,7 @@ use super::grid::{Grid, xy_to_sphere};
 use super::noise::ElevationField;
 use super::raster::{apply_continentality, continentality};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize)]
 pub enum Biome {
     #[default]
     Unassigned,
@@ -6,12 +6,13 @@
 
 use rand::Rng;
 use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
 
 use super::Uwp;
 use super::biome::Biome;
 use super::grid::Grid;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum Feature {
     /// `starport` flags the single most important city per world; rendered red.
     City {
@@ -21,7 +22,7 @@ pub enum Feature {
     PolarIce,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
 pub enum CityTier {
     /// 10M+ inhabitants. Double-ring + dot.
     Megacity,
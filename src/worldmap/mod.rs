@@ -28,6 +28,7 @@ pub mod tectonics;
 
 use rand::{Rng, SeedableRng};
 use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
 
 /// Parsed UWP. `digits` holds the base-16 numerics for size, atmo,
 /// hydro, pop, gov, law, tech (indices 1..=7). Index 0 is also kept
@@ -215,6 +216,66 @@ pub fn generate(uwp: &str, seed: u64, name: Option<&str>) -> Result<WorldMap, Ma
     Ok(map)
 }
 
+/// Structured per-hex digest of a [`WorldMap`] — the data behind the
+/// rendered image, for clients that want to draw their own view (or
+/// query terrain) instead of consuming the PNG. Serializes to JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct MapSummary {
+    /// Canonical UWP string the map was generated from.
+    pub uwp: String,
+    pub seed: u64,
+    pub name: Option<String>,
+    /// Elevation threshold below which a hex is ocean.
+    pub sea_level: f64,
+    /// One entry per grid hex, in `Grid::hexes` order.
+    pub hexes: Vec<HexSummary>,
+}
+
+/// One hex of a [`MapSummary`].
+#[derive(Debug, Clone, Serialize)]
+pub struct HexSummary {
+    /// Icosahedron face (0..20) the hex belongs to.
+    pub face: usize,
+    /// Hex center on the unfolded sheet (first unfolded position for the
+    /// seam face, which is drawn twice).
+    pub center: (f64, f64),
+    /// Latitude in degrees, +90 at the north pole.
+    pub latitude_deg: f64,
+    pub elevation: f64,
+    pub temperature: f64,
+    pub humidity: f64,
+    pub biome: biome::Biome,
+    pub features: Vec<features::Feature>,
+}
+
+impl WorldMap {
+    /// Build the per-hex [`MapSummary`] for this map. Pure — reads only
+    /// the already-generated grid, so it's as deterministic as the map.
+    pub fn summary(&self) -> MapSummary {
+        MapSummary {
+            uwp: self.uwp.to_string(),
+            seed: self.seed,
+            name: self.name.clone(),
+            sea_level: self.sea_level,
+            hexes: self
+                .grid
+                .hexes
+                .iter()
+                .map(|h| HexSummary {
+                    face: h.face_idx,
+                    center: h.centers_2d.first().copied().unwrap_or_default(),
+                    latitude_deg: grid::latitude(&h.sphere_pos).to_degrees(),
+                    elevation: h.elevation,
+                    temperature: h.temperature,
+                    humidity: h.humidity,
+                    biome: h.biome,
+                    features: h.features.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
 pub fn render_svg(map: &WorldMap) -> String {
     render::render_svg(map)
 }
@@ -324,6 +385,21 @@ mod tests {
         assert!(water_count > 0 && water_count < map.grid.hexes.len());
     }
 
+    #[test]
+    fn summary_has_one_entry_per_hex() {
+        let map = generate("A788899-A", 0xDEADBEEF, Some("Regina")).unwrap();
+        let summary = map.summary();
+        assert_eq!(summary.hexes.len(), map.grid.hexes.len());
+        assert_eq!(summary.uwp, "A788899-A");
+        assert_eq!(summary.name.as_deref(), Some("Regina"));
+        assert!(
+            summary
+                .hexes
+                .iter()
+                .all(|h| (-90.0..=90.0).contains(&h.latitude_deg))
+        );
+    }
+
     #[test]
     fn svg_output_is_nonempty_and_well_formed() {
         let map = generate("A788899-A", 0xCAFEBABE, None).unwrap();
@@ -143,6 +143,115 @@ async fn get_system_svg_with_bad_uwp_returns_422() {
     assert!(head.contains("Access-Control-Allow-Origin: *"));
 }
 
+#[tokio::test]
+async fn get_system_json_is_the_system_the_png_drew() {
+    // The JSON endpoint shares the query contract and seed derivation with
+    // /api/system, so re-rendering the returned system must reproduce the
+    // PNG endpoint's bytes exactly.
+    let addr = spawn_http_server().await;
+    let query = "sector=Trojan+Reach&hex=2018&name=Noricum&uwp=D8867BB-1&pbg=804&stellar=G2+V+M9+V+M6+V&worlds=14";
+    let json_req = format!(
+        "GET /api/system_json?{query} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    );
+    let png_req = format!(
+        "GET /api/system?{query} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    );
+    let (head, body) = split_response(&send_request(addr, &json_req).await);
+    let (_, png) = split_response(&send_request(addr, &png_req).await);
+
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "head:\n{head}");
+    assert!(head.contains("Content-Type: application/json"));
+    assert!(head.contains("Access-Control-Allow-Origin: *"));
+
+    let json = String::from_utf8(body).expect("JSON body is UTF-8");
+    let system = worldgen::systems::system::System::from_json(&json).expect("valid system JSON");
+    assert_eq!(system.count_stars(), 3);
+    assert_eq!(
+        worldgen::sysmap::render_png_scaled(&system, 2.0).unwrap(),
+        png,
+        "system JSON doesn't describe the system /api/system rendered"
+    );
+}
+
+#[tokio::test]
+async fn cli_system_png_matches_api_system_by_default() {
+    // Neither side names a scale, so both must fall back to the same
+    // default and hand back the same bytes for the same query.
+    let addr = spawn_http_server().await;
+    let req = format!(
+        "GET /api/system?sector=Trojan+Reach&hex=2018&name=Noricum&uwp=D8867BB-1&pbg=804&stellar=G2+V+M9+V+M6+V&worlds=14 \
+         HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    );
+    let (head, png) = split_response(&send_request(addr, &req).await);
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "head:\n{head}");
+
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_worldgen"))
+        .args(["system", "--sector", "Trojan Reach", "--hex", "2018"])
+        .args(["--name", "Noricum", "--uwp", "D8867BB-1", "--pbg", "804"])
+        .args(["--stellar", "G2 V M9 V M6 V", "--worlds", "14"])
+        .args(["--format", "png", "--out", "-"])
+        .output()
+        .expect("run worldgen");
+    assert!(
+        out.status.success(),
+        "stderr:\n{}",
+        String::from_utf8_lossy(&out.stderr)
+    );
+    assert_eq!(out.stdout, png, "CLI and /api/system disagree");
+}
+
+#[tokio::test]
+async fn get_system_json_matches_expanded_sector_hex() {
+    // A generated sector hex, queried by its listing columns, must come
+    // back as exactly the system `Sector::expand` builds for it.
+    use worldgen::sector::{Density, Sector};
+
+    let sector = Sector::generate_subsector("Spinward Marches", 'C', Density::Standard).unwrap();
+    let hex = &sector.hexes[0];
+    let expanded = sector.expand(hex).unwrap();
+
+    let encode = |s: &str| -> String {
+        s.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' => (b as char).to_string(),
+                _ => format!("%{b:02X}"),
+            })
+            .collect()
+    };
+    let addr = spawn_http_server().await;
+    let query = format!(
+        "sector=Spinward+Marches&hex={}&name={}&uwp={}&pbg={}&stellar={}",
+        hex.hex(),
+        encode(&hex.name),
+        hex.uwp,
+        hex.pbg,
+        encode(&hex.stellar),
+    );
+    let req = format!(
+        "GET /api/system_json?{query} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    );
+    let (head, body) = split_response(&send_request(addr, &req).await);
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "head:\n{head}");
+    assert_eq!(
+        String::from_utf8(body).unwrap(),
+        expanded.to_json().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn get_system_json_with_bad_uwp_returns_422() {
+    let addr = spawn_http_server().await;
+    let req = format!(
+        "GET /api/system_json?sector=x&hex=0000&name=x&uwp=NOT-A-UWP-WAY-TOO-LONG \
+         HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    );
+    let (head, _) = split_response(&send_request(addr, &req).await);
+    assert!(
+        head.starts_with("HTTP/1.1 422 Unprocessable Entity\r\n"),
+        "head:\n{head}"
+    );
+}
+
 #[tokio::test]
 async fn get_system_is_byte_identical_across_calls() {
     let addr = spawn_http_server().await;
@@ -405,3 +514,41 @@ async fn get_world_with_scale_below_1_returns_400() {
         "head:\n{head}"
     );
 }
+
+#[tokio::test]
+async fn get_world_json_returns_per_hex_summary() {
+    let addr = spawn_http_server().await;
+    let req = format!(
+        "GET /api/world_json?{NORICUM_WORLD_QUERY} HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    );
+    let buf = send_request(addr, &req).await;
+    let (head, body) = split_response(&buf);
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"), "head:\n{head}");
+    assert!(head.contains("Content-Type: application/json"));
+    assert!(head.contains("Access-Control-Allow-Origin: *"));
+
+    let v: serde_json::Value = serde_json::from_slice(&body).expect("valid JSON");
+    assert_eq!(v["uwp"], "D8867BB-1");
+    assert_eq!(v["name"], "Noricum");
+    let hexes = v["hexes"].as_array().expect("hexes array");
+    assert!(!hexes.is_empty());
+    assert!(hexes[0]["biome"].is_string());
+
+    // Deterministic across calls, like the PNG.
+    let (_, body2) = split_response(&send_request(addr, &req).await);
+    assert_eq!(body, body2, "world JSON determinism contract broken");
+}
+
+#[tokio::test]
+async fn get_world_json_with_missing_required_param_returns_400() {
+    let addr = spawn_http_server().await;
+    let req = format!(
+        "GET /api/world_json?sector=x&name=x&uwp=A788899-A HTTP/1.1\r\nHost: {addr}\r\nConnection: close\r\n\r\n"
+    );
+    let (head, body) = split_response(&send_request(addr, &req).await);
+    assert!(
+        head.starts_with("HTTP/1.1 400 Bad Request\r\n"),
+        "head:\n{head}"
+    );
+    assert!(String::from_utf8_lossy(&body).contains("hex"));
+}